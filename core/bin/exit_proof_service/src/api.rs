@@ -0,0 +1,91 @@
+//! REST API of the exit proof service.
+//!
+//! `GET /exit_proof/{address}/{token}` returns the exit proof for the account and token,
+//! where token is either an ERC-20 token address or symbol, or the id of the token or NFT.
+//! Cached proofs are returned right away, while the requests for the new ones are queued
+//! and rejected once the queue is full.
+
+// Built-in uses
+use std::net::SocketAddr;
+// External uses
+use actix_web::{web, App, HttpResponse, HttpServer, ResponseError};
+use tokio::sync::{Mutex, Semaphore};
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::{Address, TokenLike};
+// Local uses
+use crate::{get_cached_exit_proof, get_exit_proof, ExitProofError};
+
+impl ResponseError for ExitProofError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            Self::AccountNotFound(_) | Self::TokenNotFound(_) | Self::NFTNotFound(_) => {
+                actix_web::http::StatusCode::NOT_FOUND
+            }
+            Self::Internal(_) => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AppState {
+    connection_pool: ConnectionPool,
+    /// Limits the amount of requests awaiting for the proof generation.
+    generation_queue: Semaphore,
+    /// Proof generation consumes a lot of memory, so only one proof is generated at a time.
+    generation_lock: Mutex<()>,
+}
+
+#[actix_web::get("/exit_proof/{address}/{token}")]
+async fn exit_proof(
+    data: web::Data<AppState>,
+    path: web::Path<(Address, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (address, token) = path.into_inner();
+    let token = TokenLike::parse(&token);
+
+    let log_error = |err: ExitProofError| {
+        vlog::warn!("Failed to get exit proof for {:?}: {}", address, err);
+        err
+    };
+
+    let cached_proof = get_cached_exit_proof(&data.connection_pool, address, token.clone())
+        .await
+        .map_err(log_error)?;
+    if let Some(exit_proof) = cached_proof {
+        return Ok(HttpResponse::Ok().json(exit_proof));
+    }
+
+    let _permit = data.generation_queue.try_acquire().map_err(|_| {
+        actix_web::error::ErrorServiceUnavailable(
+            "Too many exit proofs are being generated, try again later",
+        )
+    })?;
+    // The proof might have been generated while the request was waiting for the lock,
+    // in that case it's loaded from the cache.
+    let _guard = data.generation_lock.lock().await;
+    let exit_proof = get_exit_proof(&data.connection_pool, address, token)
+        .await
+        .map_err(log_error)?;
+    Ok(HttpResponse::Ok().json(exit_proof))
+}
+
+/// Runs the REST API server until it's stopped. At most `max_queued_proofs` requests
+/// can wait for the proof generation at a time.
+pub async fn run_server(
+    connection_pool: ConnectionPool,
+    bind_address: SocketAddr,
+    max_queued_proofs: usize,
+) -> std::io::Result<()> {
+    let state = web::Data::new(AppState {
+        connection_pool,
+        generation_queue: Semaphore::new(max_queued_proofs),
+        generation_lock: Mutex::new(()),
+    });
+
+    vlog::info!("Exit proof service is listening on {}", bind_address);
+    HttpServer::new(move || App::new().app_data(state.clone()).service(exit_proof))
+        .bind(bind_address)?
+        .run()
+        .await
+}
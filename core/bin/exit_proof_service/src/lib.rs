@@ -0,0 +1,390 @@
+//! Exit proof service generates the proofs required to withdraw funds from the zkSync contract
+//! in the exodus mode, so that the users can self-serve their exits if the operator disappears.
+//!
+//! Proofs are generated for the state of the last verified block, which should be present
+//! in the database (it can be restored from L1 using the `data-restore` module).
+//! Generated proofs are cached in the database, since the generation takes minutes.
+
+// Built-in uses
+use std::time::Instant;
+// External uses
+use ethabi::Token;
+use num::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+// Workspace uses
+use zksync_contracts::zksync_contract;
+use zksync_crypto::{params::MIN_NFT_TOKEN_ID, proof::EncodedSingleProof};
+use zksync_prover_utils::exit_proof::{create_exit_proof_fungible, create_exit_proof_nft};
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{block::Block, AccountId, Address, BlockNumber, TokenId, TokenLike, H256, NFT};
+use zksync_utils::BigUintSerdeWrapper;
+
+pub mod api;
+
+/// Errors occurred while preparing the exit proof.
+#[derive(Debug, Error)]
+pub enum ExitProofError {
+    #[error("Account with address {0:?} is not found")]
+    AccountNotFound(Address),
+    #[error(
+        "Token {0:?} is not found. If you're addressing an ERC-20 token by its symbol, \
+         it may not be available after data restore. Try using token address in that case"
+    )]
+    TokenNotFound(TokenLike),
+    #[error("NFT with id {0} is not found")]
+    NFTNotFound(TokenId),
+    #[error("Internal error: {0}")]
+    Internal(#[from] anyhow::Error),
+}
+
+/// Last verified block in the format of the `StoredBlockInfo` structure of the zkSync contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockInfo {
+    pub block_number: BlockNumber,
+    pub priority_operations: u64,
+    pub pending_onchain_operations_hash: H256,
+    pub timestamp: u64,
+    pub state_hash: H256,
+    pub commitment: H256,
+}
+
+impl StoredBlockInfo {
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            block_number: block.block_number,
+            priority_operations: block.number_of_processed_prior_ops(),
+            pending_onchain_operations_hash: block.get_onchain_operations_block_info().1,
+            timestamp: block.timestamp,
+            state_hash: block.get_eth_encoded_root(),
+            commitment: block.block_commitment,
+        }
+    }
+
+    fn to_token(&self) -> Token {
+        Token::Tuple(vec![
+            Token::Uint((*self.block_number).into()),
+            Token::Uint(self.priority_operations.into()),
+            Token::FixedBytes(self.pending_onchain_operations_hash.as_bytes().to_vec()),
+            Token::Uint(self.timestamp.into()),
+            Token::FixedBytes(self.state_hash.as_bytes().to_vec()),
+            Token::FixedBytes(self.commitment.as_bytes().to_vec()),
+        ])
+    }
+}
+
+/// Arguments of the `performExodus` function of the zkSync contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProofData {
+    pub stored_block_info: StoredBlockInfo,
+    pub owner: Address,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub amount: BigUintSerdeWrapper,
+    pub nft_creator_id: AccountId,
+    pub nft_creator_address: Address,
+    pub nft_serial_id: u32,
+    pub nft_content_hash: H256,
+    pub proof: EncodedSingleProof,
+    pub token_address: Address,
+}
+
+impl ExitProofData {
+    /// Encodes the call of the `performExodus` function of the zkSync contract.
+    pub fn perform_exodus_calldata(&self) -> anyhow::Result<Vec<u8>> {
+        let amount = self
+            .amount
+            .0
+            .to_u128()
+            .ok_or_else(|| anyhow::format_err!("Exit amount doesn't fit into uint128"))?;
+        let args = [
+            self.stored_block_info.to_token(),
+            Token::Address(self.owner),
+            Token::Uint((*self.account_id).into()),
+            Token::Uint((*self.token_id).into()),
+            Token::Uint(amount.into()),
+            Token::Uint((*self.nft_creator_id).into()),
+            Token::Address(self.nft_creator_address),
+            Token::Uint(self.nft_serial_id.into()),
+            Token::FixedBytes(self.nft_content_hash.as_bytes().to_vec()),
+            Token::Array(self.proof.proof.iter().copied().map(Token::Uint).collect()),
+        ];
+        let calldata = zksync_contract()
+            .function("performExodus")?
+            .encode_input(&args)?;
+        Ok(calldata)
+    }
+}
+
+/// Exit proof along with the calldata of the exit transaction to be sent to the zkSync contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitProof {
+    #[serde(flatten)]
+    pub data: ExitProofData,
+    /// Hex-encoded call of the `performExodus` function.
+    pub calldata: String,
+}
+
+impl ExitProof {
+    pub fn new(data: ExitProofData) -> anyhow::Result<Self> {
+        let calldata = format!("0x{}", hex::encode(data.perform_exodus_calldata()?));
+        Ok(Self { data, calldata })
+    }
+}
+
+#[derive(Debug)]
+struct NFTInfo {
+    creator_id: AccountId,
+    creator_address: Address,
+    serial_id: u32,
+    content_hash: H256,
+}
+
+impl NFTInfo {
+    async fn load(
+        storage: &mut StorageProcessor<'_>,
+        nft: Option<&NFT>,
+    ) -> Result<Self, ExitProofError> {
+        let info = match nft {
+            Some(nft) => Self {
+                creator_id: nft.creator_id,
+                creator_address: nft.creator_address,
+                serial_id: nft.serial_id,
+                content_hash: nft.content_hash,
+            },
+            None => {
+                // The placeholder creator address should be the address
+                // of the account with id 0
+                let creator_address = storage
+                    .chain()
+                    .account_schema()
+                    .account_address_by_id(AccountId(0))
+                    .await?
+                    .ok_or_else(|| anyhow::format_err!("Account with id 0 does not exist"))?;
+
+                Self {
+                    creator_id: AccountId(0),
+                    creator_address,
+                    serial_id: 0,
+                    content_hash: Default::default(),
+                }
+            }
+        };
+        Ok(info)
+    }
+}
+
+async fn load_token_and_account(
+    storage: &mut StorageProcessor<'_>,
+    owner: Address,
+    token: TokenLike,
+) -> Result<(zksync_types::Token, AccountId), ExitProofError> {
+    let token = storage
+        .tokens_schema()
+        .get_token(token.clone())
+        .await?
+        .ok_or(ExitProofError::TokenNotFound(token))?;
+    let account_id = storage
+        .chain()
+        .account_schema()
+        .account_id_by_address(owner)
+        .await?
+        .ok_or(ExitProofError::AccountNotFound(owner))?;
+    Ok((token, account_id))
+}
+
+async fn load_cached_proof(
+    storage: &mut StorageProcessor<'_>,
+    account_id: AccountId,
+    token_id: TokenId,
+    block_number: BlockNumber,
+) -> Result<Option<ExitProof>, ExitProofError> {
+    let cached = match storage
+        .prover_schema()
+        .load_exit_proof(account_id, token_id, block_number)
+        .await?
+    {
+        Some(cached) => cached,
+        None => return Ok(None),
+    };
+    vlog::info!(
+        "Exit proof for account {} and token {} is loaded from the cache",
+        *account_id,
+        *token_id
+    );
+    let exit_proof = serde_json::from_value(cached.proof_data).map_err(anyhow::Error::from)?;
+    Ok(Some(exit_proof))
+}
+
+/// Loads the exit proof for the account and token (or NFT) from the cache,
+/// returns `None` if it's not generated for the last verified block yet.
+pub async fn get_cached_exit_proof(
+    connection_pool: &ConnectionPool,
+    owner: Address,
+    token: TokenLike,
+) -> Result<Option<ExitProof>, ExitProofError> {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .map_err(anyhow::Error::from)?;
+    let (token, account_id) = load_token_and_account(&mut storage, owner, token).await?;
+
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    load_cached_proof(&mut storage, account_id, token.id, last_verified_block).await
+}
+
+/// Loads the exit proof for the account and token (or NFT) from the cache,
+/// or generates it for the state of the last verified block.
+pub async fn get_exit_proof(
+    connection_pool: &ConnectionPool,
+    owner: Address,
+    token: TokenLike,
+) -> Result<ExitProof, ExitProofError> {
+    let mut storage = connection_pool
+        .access_storage()
+        .await
+        .map_err(anyhow::Error::from)?;
+    let (token, account_id) = load_token_and_account(&mut storage, owner, token).await?;
+
+    let timer = Instant::now();
+    // The cache is checked for the block of the loaded state, so that the proof is both
+    // looked up and stored for the same block, even if a new one was verified meanwhile.
+    let (block_number, accounts) = storage.chain().state_schema().load_verified_state().await?;
+    if let Some(exit_proof) =
+        load_cached_proof(&mut storage, account_id, token.id, block_number).await?
+    {
+        return Ok(exit_proof);
+    }
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| anyhow::format_err!("Block {} is not stored", *block_number))?;
+    let nft = if token.id.0 < MIN_NFT_TOKEN_ID {
+        None
+    } else {
+        let nft = storage
+            .tokens_schema()
+            .get_nft(token.id)
+            .await?
+            .ok_or(ExitProofError::NFTNotFound(token.id))?;
+        Some(nft)
+    };
+    let nft_info = NFTInfo::load(&mut storage, nft.as_ref()).await?;
+    vlog::info!("Restored state from db: {} s", timer.elapsed().as_secs());
+    // Generation takes a while, so the connection is returned to the pool meanwhile.
+    drop(storage);
+
+    let token_id = token.id;
+    let (proof, amount) = tokio::task::spawn_blocking(move || match nft {
+        Some(nft) => create_exit_proof_nft(
+            accounts,
+            account_id,
+            owner,
+            token_id,
+            nft.creator_id,
+            nft.serial_id,
+            nft.content_hash,
+        ),
+        None => create_exit_proof_fungible(accounts, account_id, owner, token_id),
+    })
+    .await
+    .map_err(anyhow::Error::from)??;
+
+    let exit_proof = ExitProof::new(ExitProofData {
+        stored_block_info: StoredBlockInfo::from_block(&block),
+        owner,
+        account_id,
+        token_id,
+        amount: amount.into(),
+        nft_creator_id: nft_info.creator_id,
+        nft_creator_address: nft_info.creator_address,
+        nft_serial_id: nft_info.serial_id,
+        nft_content_hash: nft_info.content_hash,
+        proof,
+        token_address: token.address,
+    })?;
+
+    let proof_data = serde_json::to_value(&exit_proof).map_err(anyhow::Error::from)?;
+    connection_pool
+        .access_storage()
+        .await
+        .map_err(anyhow::Error::from)?
+        .prover_schema()
+        .store_exit_proof(account_id, token_id, block_number, proof_data)
+        .await?;
+
+    Ok(exit_proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perform_exodus_calldata() {
+        let data = ExitProofData {
+            stored_block_info: StoredBlockInfo {
+                block_number: BlockNumber(10),
+                priority_operations: 2,
+                pending_onchain_operations_hash: H256::repeat_byte(1),
+                timestamp: 1_000,
+                state_hash: H256::repeat_byte(2),
+                commitment: H256::repeat_byte(3),
+            },
+            owner: Address::repeat_byte(4),
+            account_id: AccountId(5),
+            token_id: TokenId(0),
+            amount: num::BigUint::from(100u32).into(),
+            nft_creator_id: AccountId(0),
+            nft_creator_address: Address::repeat_byte(6),
+            nft_serial_id: 0,
+            nft_content_hash: H256::zero(),
+            proof: EncodedSingleProof::default(),
+            token_address: Address::zero(),
+        };
+
+        let calldata = data.perform_exodus_calldata().unwrap();
+        let contract = zksync_contract();
+        let function = contract.function("performExodus").unwrap();
+        assert_eq!(calldata[..4], function.short_signature());
+
+        let args = function.decode_input(&calldata[4..]).unwrap();
+        assert_eq!(args[0], data.stored_block_info.to_token());
+        assert_eq!(args[1], Token::Address(data.owner));
+        assert_eq!(args[4], Token::Uint(100u32.into()));
+    }
+
+    #[test]
+    fn exit_proof_amount_overflow() {
+        let data = ExitProofData {
+            stored_block_info: StoredBlockInfo {
+                block_number: BlockNumber(1),
+                priority_operations: 0,
+                pending_onchain_operations_hash: H256::zero(),
+                timestamp: 0,
+                state_hash: H256::zero(),
+                commitment: H256::zero(),
+            },
+            owner: Address::zero(),
+            account_id: AccountId(1),
+            token_id: TokenId(0),
+            amount: (num::BigUint::from(u128::MAX) + 1u32).into(),
+            nft_creator_id: AccountId(0),
+            nft_creator_address: Address::zero(),
+            nft_serial_id: 0,
+            nft_content_hash: H256::zero(),
+            proof: EncodedSingleProof::default(),
+            token_address: Address::zero(),
+        };
+        assert!(ExitProof::new(data).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! Generate exit proof for exodus mode given account and token
+//! correct verified state should be present in the db (could be restored using `data-restore` module)
+
+use std::net::SocketAddr;
+use structopt::StructOpt;
+use zksync_exit_proof_service::{api::run_server, get_exit_proof};
+use zksync_storage::ConnectionPool;
+use zksync_types::{Address, TokenLike};
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "zkSync exit proof service",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+enum Opt {
+    /// Generates the exit proof and prints it
+    Generate {
+        /// Account address
+        #[structopt(long)]
+        address: Address,
+
+        /// Token to withdraw - "ETH", address of the ERC20 token or the id of the token or NFT
+        #[structopt(long)]
+        token: String,
+    },
+    /// Runs the REST API serving the exit proofs
+    Server {
+        /// Address to listen on
+        #[structopt(long, default_value = "0.0.0.0:3040")]
+        bind: SocketAddr,
+
+        /// Maximum amount of requests waiting for the proof generation,
+        /// the requests above the limit are rejected
+        #[structopt(long, default_value = "10")]
+        max_queued_proofs: usize,
+    },
+}
+
+#[actix_web::main]
+async fn main() -> anyhow::Result<()> {
+    vlog::init();
+
+    match Opt::from_args() {
+        Opt::Generate { address, token } => {
+            let connection_pool = ConnectionPool::new(Some(1));
+            let exit_proof =
+                get_exit_proof(&connection_pool, address, TokenLike::parse(&token)).await?;
+
+            println!("\n\n");
+            println!("==========================");
+            println!("Generating proof completed");
+            println!(
+                "Below you can see the input data for the exit transaction on zkSync contract"
+            );
+            println!("Look up the manuals of your desired smart wallet in order to know how to sign and send this transaction to the Ethereum");
+            println!("The `calldata` field contains the ready-to-send data of the transaction");
+            println!("==========================");
+
+            println!("Exit transaction inputs:");
+
+            println!("{}", serde_json::to_string_pretty(&exit_proof)?);
+        }
+        Opt::Server {
+            bind,
+            max_queued_proofs,
+        } => {
+            let connection_pool = ConnectionPool::new(Some(2));
+            run_server(connection_pool, bind, max_queued_proofs).await?;
+        }
+    }
+    Ok(())
+}
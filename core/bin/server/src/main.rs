@@ -6,19 +6,24 @@ use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
+use zksync_api::core_api_client::CoreApiClient;
 use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_client::EthereumGateway;
 use zksync_forced_exit_requests::run_forced_exit_requests_actors;
 use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
+use zksync_notifier::webhooks::run_webhook_notifier;
 use zksync_witness_generator::run_prover_server;
 
 use tokio::task::JoinHandle;
 use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig};
 use zksync_config::{
-    configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
+    configs::api::{
+        AdminApiConfig, CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config,
+    },
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, WebhooksConfig,
+    ZkSyncConfig,
 };
 use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
 use zksync_mempool::run_mempool_tx_handler;
@@ -37,6 +42,7 @@ pub enum ServerCommand {
 pub enum Component {
     // Api components
     RestApi,
+    AdminApi,
     Web3Api,
     RpcApi,
     RpcWebSocketApi,
@@ -47,6 +53,7 @@ pub enum Component {
     Core,
     WitnessGenerator,
     ForcedExit,
+    Webhooks,
 
     // Additional components
     Prometheus,
@@ -60,12 +67,14 @@ impl FromStr for Component {
     fn from_str(s: &str) -> Result<Component, String> {
         match s {
             "rest-api" => Ok(Component::RestApi),
+            "admin-api" => Ok(Component::AdminApi),
             "web3-api" => Ok(Component::Web3Api),
             "rpc-api" => Ok(Component::RpcApi),
             "rpc-websocket-api" => Ok(Component::RpcWebSocketApi),
             "eth-sender" => Ok(Component::EthSender),
             "witness-generator" => Ok(Component::WitnessGenerator),
             "forced-exit" => Ok(Component::ForcedExit),
+            "webhooks" => Ok(Component::Webhooks),
             "prometheus" => Ok(Component::Prometheus),
             "fetchers" => Ok(Component::Fetchers),
             "core" => Ok(Component::Core),
@@ -145,12 +154,10 @@ async fn main() -> anyhow::Result<()> {
     // It's a `ServerCommand::Launch`, perform the usual routine.
     vlog::info!("Running the zkSync server");
 
-    run_server(&opt.components).await;
-
-    Ok(())
+    run_server(&opt.components).await
 }
 
-async fn run_server(components: &ComponentsToRun) {
+async fn run_server(components: &ComponentsToRun) -> anyhow::Result<()> {
     let connection_pool = ConnectionPool::new(None);
     let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
@@ -175,7 +182,10 @@ async fn run_server(components: &ComponentsToRun) {
     if components.0.iter().any(|c| {
         matches!(
             c,
-            Component::RpcWebSocketApi | Component::RpcApi | Component::RestApi
+            Component::RpcWebSocketApi
+                | Component::RpcApi
+                | Component::RestApi
+                | Component::AdminApi
         )
     }) {
         // Create gateway
@@ -200,6 +210,9 @@ async fn run_server(components: &ComponentsToRun) {
 
         let contracts_config = ContractsConfig::from_env();
         let common_config = CommonApiConfig::from_env();
+        common_config
+            .validate_fee_quote_secret()
+            .map_err(anyhow::Error::msg)?;
         let chain_config = ChainConfig::from_env();
         let fee_ticker_config = TickerConfig::from_env();
         let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
@@ -208,8 +221,17 @@ async fn run_server(components: &ComponentsToRun) {
             ticker_info,
             fee_ticker_config,
             chain_config.max_blocks_to_aggregate(),
+            chain_config
+                .state_keeper
+                .block_chunk_sizes
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default(),
             read_only_connection_pool.clone(),
-        );
+            connection_pool.clone(),
+        )
+        .expect("failed to create the fee ticker");
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -218,6 +240,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
@@ -228,6 +251,7 @@ async fn run_server(components: &ComponentsToRun) {
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                CoreApiClient::new(PrivateApiConfig::from_env().url),
             ));
         }
 
@@ -238,6 +262,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
@@ -247,6 +272,15 @@ async fn run_server(components: &ComponentsToRun) {
                 &common_config,
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                CoreApiClient::new(PrivateApiConfig::from_env().url),
+            ));
+        }
+
+        if components.0.contains(&Component::AdminApi) {
+            tasks.push(zksync_api::api_server::admin_server::start_admin_server(
+                connection_pool.clone(),
+                AdminApiConfig::from_env(),
+                ticker.clone(),
             ));
         }
 
@@ -257,6 +291,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes,
+                chain_config.mempool,
             ));
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
@@ -310,6 +345,13 @@ async fn run_server(components: &ComponentsToRun) {
         tasks.append(&mut run_forced_exit(connection_pool.clone()));
     }
 
+    if components.0.contains(&Component::Webhooks) {
+        tasks.push(run_webhook_notifier(
+            connection_pool.clone(),
+            WebhooksConfig::from_env(),
+        ));
+    }
+
     if components.0.contains(&Component::RejectedTaskCleaner) {
         let config = DBConfig::from_env();
         tasks.push(run_rejected_tx_cleaner(&config, connection_pool));
@@ -332,6 +374,7 @@ async fn run_server(components: &ComponentsToRun) {
             vlog::warn!("Stop signal received, shutting down");
         }
     };
+    Ok(())
 }
 
 pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -348,6 +391,7 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
         connection_pool.clone(),
         mempool_tx_request_receiver,
         chain_config.state_keeper.block_chunk_sizes,
+        chain_config.mempool,
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -384,7 +428,11 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     let ticker_config = TickerConfig::from_env();
-    run_updaters(connection_pool, &ticker_config)
+    // Ethereum node is only queried by the DEX price source.
+    let eth_gateway = ticker_config
+        .uses_dex_price_source()
+        .then(create_eth_gateway);
+    run_updaters(connection_pool, eth_gateway, &ticker_config)
 }
 
 pub fn create_eth_gateway() -> EthereumGateway {
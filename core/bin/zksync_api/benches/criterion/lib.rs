@@ -18,6 +18,7 @@ fn generate_transactions(number: usize) -> BatchFeeRequest {
     BatchFeeRequest {
         transactions,
         token_like: TokenLike::Id(TokenId(2)), // id of wBTC on localhost
+        quote_sender: None,
     }
 }
 
@@ -35,6 +36,7 @@ fn get_txs_fee(client: Client, url: String) {
         tx_type: ApiTxFeeTypes::Withdraw,
         address: Address::random(),
         token_like: TokenLike::Id(TokenId(2)), // id of wBTC on localhost
+        quote_sender: None,
     };
 
     let res = client
@@ -0,0 +1,325 @@
+//! Admin server is used by the operator to manage the fee token policies:
+//! to explicitly allow or deny tokens for paying fees and to set the fee discounts.
+//!
+//! All the endpoints require the JWT bearer token signed with the admin API secret.
+
+// Built-in uses
+use std::thread;
+// External uses
+use actix_web::{dev::ServiceRequest, web, App, HttpResponse, HttpServer};
+use actix_web_httpauth::{extractors::bearer::BearerAuth, middleware::HttpAuthentication};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_config::configs::api::AdminApiConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    tokens::{FeeTokenListing, MAX_FEE_DISCOUNT_PERCENT},
+    TokenId, TokenLike,
+};
+use zksync_utils::{
+    http_auth::AuthTokenValidator,
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+};
+// Local uses
+use crate::fee_ticker::FeeTicker;
+
+#[derive(Clone)]
+struct AppState {
+    secret_auth: String,
+    connection_pool: ConnectionPool,
+    fee_ticker: FeeTicker,
+}
+
+impl AppState {
+    async fn access_storage(&self) -> actix_web::Result<zksync_storage::StorageProcessor<'_>> {
+        self.connection_pool.access_storage().await.map_err(|e| {
+            vlog::warn!("Failed to access storage: {}", e);
+            actix_web::error::ErrorInternalServerError(e)
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeTokenPolicyRequest {
+    token: TokenLike,
+    /// Explicit listing of the token, `None` to keep the default fee token validation.
+    listing: Option<FeeTokenListing>,
+    #[serde(default)]
+    discount_percent: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RemovePolicyResponse {
+    removed: bool,
+}
+
+async fn fee_token_policies(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let policies = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .load_fee_token_policies()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(policies))
+}
+
+async fn set_fee_token_policy(
+    data: web::Data<AppState>,
+    r: web::Json<FeeTokenPolicyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = r.into_inner();
+    if request.discount_percent > MAX_FEE_DISCOUNT_PERCENT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "discount can't exceed {}%",
+            MAX_FEE_DISCOUNT_PERCENT
+        )));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let token = storage
+        .tokens_schema()
+        .get_token(request.token)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("unknown token"))?;
+
+    let policy = storage
+        .tokens_schema()
+        .store_fee_token_policy(token.id, request.listing, request.discount_percent)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    data.fee_ticker.invalidate_fee_token_policies().await;
+
+    vlog::info!("Fee token policy is updated: {:?}", policy);
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+async fn remove_fee_token_policy(
+    data: web::Data<AppState>,
+    token_id: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(token_id.into_inner());
+    let removed = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .remove_fee_token_policy(token_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    data.fee_ticker.invalidate_fee_token_policies().await;
+
+    if removed {
+        vlog::info!("Fee token policy is removed for the token {}", token_id);
+    }
+    Ok(HttpResponse::Ok().json(RemovePolicyResponse { removed }))
+}
+
+async fn validate_auth_token(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let secret_auth = req
+        .app_data::<web::Data<AppState>>()
+        .expect("failed get AppState upon receipt of the authentication token")
+        .secret_auth
+        .clone();
+    AuthTokenValidator::new(&secret_auth)
+        .validator(req, credentials)
+        .await
+}
+
+fn fee_token_policies_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/fee_tokens/policies", web::get().to(fee_token_policies))
+        .route("/fee_tokens/policies", web::post().to(set_fee_token_policy))
+        .route(
+            "/fee_tokens/policies/{token_id}",
+            web::delete().to(remove_fee_token_policy),
+        );
+}
+
+pub fn start_admin_server(
+    connection_pool: ConnectionPool,
+    config: AdminApiConfig,
+    fee_ticker: FeeTicker,
+) -> JoinHandle<()> {
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    thread::Builder::new()
+        .name("admin_server".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_sender.clone());
+            let actix_runtime = actix_rt::System::new();
+
+            actix_runtime.block_on(async move {
+                let bind_addr = config.bind_addr();
+                HttpServer::new(move || {
+                    let app_state = AppState {
+                        secret_auth: config.secret_auth.clone(),
+                        connection_pool: connection_pool.clone(),
+                        fee_ticker: fee_ticker.clone(),
+                    };
+
+                    App::new()
+                        .wrap(HttpAuthentication::bearer(validate_auth_token))
+                        .app_data(web::Data::new(app_state))
+                        .configure(fee_token_policies_routes)
+                })
+                .workers(1)
+                .bind(&bind_addr)
+                .expect("failed to bind")
+                .run()
+                .await
+            })
+        })
+        .expect("failed to start admin server");
+
+    handler
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use actix_web::http::StatusCode;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use zksync_types::tokens::FeeTokenPolicy;
+    use zksync_utils::http_auth::PayloadAuthToken;
+
+    use super::*;
+    use crate::api_server::rest::v02::test_utils::{dummy_fee_ticker, TestServerConfig};
+
+    const SECRET_AUTH: &str = "sample";
+
+    fn auth_token(secret: &str) -> String {
+        let exp = UNIX_EPOCH.elapsed().unwrap() + Duration::from_secs(3600);
+        encode(
+            &Header::default(),
+            &PayloadAuthToken::new(exp.as_secs() as usize),
+            &EncodingKey::from_secret(secret.as_ref()),
+        )
+        .unwrap()
+    }
+
+    fn start_server(cfg: &TestServerConfig) -> actix_test::TestServer {
+        let app_state = AppState {
+            secret_auth: SECRET_AUTH.to_owned(),
+            connection_pool: cfg.pool.clone(),
+            fee_ticker: dummy_fee_ticker(&[], None),
+        };
+        actix_test::start(move || {
+            App::new()
+                .wrap(HttpAuthentication::bearer(validate_auth_token))
+                .app_data(web::Data::new(app_state.clone()))
+                .configure(fee_token_policies_routes)
+        })
+    }
+
+    /// Checks that the requests without the valid JWT are rejected.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn admin_server_auth() {
+        let cfg = TestServerConfig::default();
+        let server = start_server(&cfg);
+
+        let response = server.get("/fee_tokens/policies").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .get("/fee_tokens/policies")
+            .bearer_auth(auth_token("wrong secret"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .get("/fee_tokens/policies")
+            .bearer_auth(auth_token(SECRET_AUTH))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        server.stop().await;
+    }
+
+    /// Checks that the fee token policy can be set, loaded and removed.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn fee_token_policy_round_trip() {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await.unwrap();
+        let server = start_server(&cfg);
+        let token = auth_token(SECRET_AUTH);
+
+        // The discount is capped.
+        let response = server
+            .post("/fee_tokens/policies")
+            .bearer_auth(&token)
+            .send_json(&FeeTokenPolicyRequest {
+                token: TokenLike::Id(TokenId(0)),
+                listing: Some(FeeTokenListing::Allow),
+                discount_percent: 100,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let mut response = server
+            .post("/fee_tokens/policies")
+            .bearer_auth(&token)
+            .send_json(&FeeTokenPolicyRequest {
+                token: TokenLike::Id(TokenId(0)),
+                listing: Some(FeeTokenListing::Allow),
+                discount_percent: 20,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let policy: FeeTokenPolicy = response.json().await.unwrap();
+        assert_eq!(policy.token_id, TokenId(0));
+        assert_eq!(policy.listing, Some(FeeTokenListing::Allow));
+        assert_eq!(policy.discount_percent, 20);
+
+        let mut response = server
+            .get("/fee_tokens/policies")
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+        let policies: Vec<FeeTokenPolicy> = response.json().await.unwrap();
+        assert_eq!(policies, vec![policy]);
+
+        for expected_removed in [true, false] {
+            let mut response = server
+                .delete("/fee_tokens/policies/0")
+                .bearer_auth(&token)
+                .send()
+                .await
+                .unwrap();
+            let response: RemovePolicyResponse = response.json().await.unwrap();
+            assert_eq!(response.removed, expected_removed);
+        }
+
+        let mut response = server
+            .get("/fee_tokens/policies")
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+        let policies: Vec<FeeTokenPolicy> = response.json().await.unwrap();
+        assert!(policies.is_empty());
+
+        server.stop().await;
+    }
+}
@@ -1,12 +1,17 @@
-use super::ExecutedOps;
+use super::{BlockStatusUpdate, ExecutedOps, TokenPriceUpdate};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use futures::{channel::mpsc, SinkExt};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
+use zksync_api_types::v02::block::BlockStatus;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::ExecutedOperations,
     block::PendingBlock,
-    BlockNumber,
+    tx::TxHash,
+    BlockNumber, TokenId,
 };
 
 /// Simple awaiter for the database futures, which will add a log entry upon DB failure
@@ -23,8 +28,12 @@ macro_rules! await_db {
     };
 }
 
+/// Token prices are updated by the ticker once in a few minutes, so there is no need
+/// to poll them every miniblock.
+const TOKEN_PRICES_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Event fetcher is an actor which polls the database from time to time in order to see
-/// whether new blocks were committed or verified.
+/// whether new blocks were committed or verified, and whether the token prices have changed.
 ///
 /// Once tha new data is available, it is sent to the `OperationNotifier`, which broadcasts it
 /// to the subscribers.
@@ -34,11 +43,19 @@ pub struct EventFetcher {
     db_pool: ConnectionPool,
 
     last_committed_block: BlockNumber,
+    last_committed_confirmed_block: BlockNumber,
     last_verified_block: BlockNumber,
     pending_block: Option<PendingBlock>,
+    last_replacement_id: i64,
+    last_token_prices_update: DateTime<Utc>,
+    last_token_prices_poll: Instant,
+    token_prices: HashMap<TokenId, BigDecimal>,
 
     operations_sender: mpsc::Sender<AggregatedOperation>,
     txs_sender: mpsc::Sender<ExecutedOps>,
+    replacements_sender: mpsc::Sender<Vec<(TxHash, TxHash)>>,
+    block_status_sender: mpsc::Sender<BlockStatusUpdate>,
+    token_prices_sender: mpsc::Sender<Vec<TokenPriceUpdate>>,
 }
 
 impl EventFetcher {
@@ -47,25 +64,41 @@ impl EventFetcher {
         miniblock_interval: Duration,
         operations_sender: mpsc::Sender<AggregatedOperation>,
         txs_sender: mpsc::Sender<ExecutedOps>,
+        replacements_sender: mpsc::Sender<Vec<(TxHash, TxHash)>>,
+        block_status_sender: mpsc::Sender<BlockStatusUpdate>,
+        token_prices_sender: mpsc::Sender<Vec<TokenPriceUpdate>>,
     ) -> anyhow::Result<Self> {
         let mut fetcher = EventFetcher {
             miniblock_interval,
             db_pool,
 
             last_committed_block: BlockNumber(0),
+            last_committed_confirmed_block: BlockNumber(0),
             last_verified_block: BlockNumber(0),
             pending_block: None,
+            last_replacement_id: 0,
+            // Only the price changes occurred after the start are reported.
+            last_token_prices_update: Utc::now(),
+            last_token_prices_poll: Instant::now(),
+            token_prices: HashMap::new(),
 
             operations_sender,
             txs_sender,
+            replacements_sender,
+            block_status_sender,
+            token_prices_sender,
         };
 
         let pending_block = fetcher.load_pending_block().await?;
         let last_committed_block = fetcher.last_committed_block().await?;
+        let last_committed_confirmed_block = fetcher.last_committed_confirmed_block().await?;
         let last_verified_block = fetcher.last_verified_block().await?;
+        let last_replacement_id = fetcher.last_replacement_id().await?;
 
         fetcher.last_committed_block = last_committed_block;
+        fetcher.last_committed_confirmed_block = last_committed_confirmed_block;
         fetcher.last_verified_block = last_verified_block;
+        fetcher.last_replacement_id = last_replacement_id;
         if let Some(block) = pending_block {
             // We only want to set this field if the pending block is actually the latest block (ahead of last committed one).
             if block.number > fetcher.last_committed_block {
@@ -82,7 +115,20 @@ impl EventFetcher {
         loop {
             interval.tick().await;
 
-            // 1. Update last verified block.
+            // 1. Update last block with the confirmed commit.
+            let last_committed_confirmed_block =
+                await_db!(self.last_committed_confirmed_block(), continue);
+            if last_committed_confirmed_block > self.last_committed_confirmed_block {
+                self.send_block_statuses(
+                    self.last_committed_confirmed_block,
+                    last_committed_confirmed_block,
+                    BlockStatus::Committed,
+                )
+                .await;
+                self.last_committed_confirmed_block = last_committed_confirmed_block;
+            }
+
+            // 2. Update last verified block.
             let last_verified_block = await_db!(self.last_verified_block(), continue);
             if last_verified_block > self.last_verified_block {
                 self.send_operations(
@@ -91,10 +137,16 @@ impl EventFetcher {
                     AggregatedActionType::ExecuteBlocks,
                 )
                 .await;
+                self.send_block_statuses(
+                    self.last_verified_block,
+                    last_verified_block,
+                    BlockStatus::Finalized,
+                )
+                .await;
                 self.last_verified_block = last_verified_block;
             }
 
-            // 2. Update last committed block.
+            // 3. Update last committed block.
             let last_committed_block = await_db!(self.last_committed_block(), continue);
             if last_committed_block > self.last_committed_block {
                 self.send_operations(
@@ -106,7 +158,7 @@ impl EventFetcher {
                 self.last_committed_block = last_committed_block;
             }
 
-            // 3. Update pending block (it may contain new executed txs).
+            // 4. Update pending block (it may contain new executed txs).
             let pending_block = await_db!(self.load_pending_block(), continue);
             if let Some(pending_block) = pending_block {
                 // We're only interested in the pending blocks **newer** than the last committed blocks;
@@ -114,6 +166,33 @@ impl EventFetcher {
                     self.txs_sender.send(executed_ops).await.unwrap_or_default();
                 }
             }
+
+            // 5. Load transactions replaced in the mempool.
+            let replacements = await_db!(self.load_replacements(), continue);
+            if let Some((last_id, _, _)) = replacements.last() {
+                self.last_replacement_id = *last_id;
+                let replacements = replacements
+                    .into_iter()
+                    .map(|(_, hash, replaced_by)| (hash, replaced_by))
+                    .collect();
+                self.replacements_sender
+                    .send(replacements)
+                    .await
+                    .unwrap_or_default();
+            }
+
+            // 6. Load changed token prices.
+            if self.last_token_prices_poll.elapsed() < TOKEN_PRICES_POLL_INTERVAL {
+                continue;
+            }
+            self.last_token_prices_poll = Instant::now();
+            let token_prices = await_db!(self.load_token_price_updates(), continue);
+            if !token_prices.is_empty() {
+                self.token_prices_sender
+                    .send(token_prices)
+                    .await
+                    .unwrap_or_default();
+            }
         }
     }
 
@@ -188,6 +267,24 @@ impl EventFetcher {
         metrics::histogram!("api.event_fetcher.send_operations", start.elapsed());
     }
 
+    async fn send_block_statuses(
+        &mut self,
+        current_last_block: BlockNumber,
+        new_last_block: BlockNumber,
+        status: BlockStatus,
+    ) {
+        for block_idx in (*current_last_block + 1)..=*new_last_block {
+            let update = BlockStatusUpdate {
+                block_number: BlockNumber(block_idx),
+                status,
+            };
+            self.block_status_sender
+                .send(update)
+                .await
+                .unwrap_or_default();
+        }
+    }
+
     async fn load_pending_block(&mut self) -> anyhow::Result<Option<PendingBlock>> {
         let start = Instant::now();
         let mut storage = self
@@ -219,6 +316,27 @@ impl EventFetcher {
         Ok(last_block)
     }
 
+    async fn last_committed_confirmed_block(&mut self) -> anyhow::Result<BlockNumber> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .expect("Can't get access to the storage");
+
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await?;
+
+        metrics::histogram!(
+            "api.event_fetcher.last_committed_confirmed_block",
+            start.elapsed()
+        );
+        Ok(last_block)
+    }
+
     async fn last_verified_block(&mut self) -> anyhow::Result<BlockNumber> {
         let start = Instant::now();
         let mut storage = self
@@ -237,6 +355,74 @@ impl EventFetcher {
         Ok(last_block)
     }
 
+    async fn last_replacement_id(&mut self) -> anyhow::Result<i64> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .expect("Can't get access to the storage");
+
+        let last_id = storage
+            .chain()
+            .mempool_schema()
+            .get_last_replacement_id()
+            .await?;
+        Ok(last_id)
+    }
+
+    async fn load_replacements(&mut self) -> anyhow::Result<Vec<(i64, TxHash, TxHash)>> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .expect("Can't get access to the storage");
+
+        let replacements = storage
+            .chain()
+            .mempool_schema()
+            .load_replacements(self.last_replacement_id)
+            .await?;
+
+        metrics::histogram!("api.event_fetcher.load_replacements", start.elapsed());
+        Ok(replacements)
+    }
+
+    /// Loads the token prices updated since the previous call and returns the changed ones.
+    /// Ticker updates the prices periodically even if they stay the same.
+    async fn load_token_price_updates(&mut self) -> anyhow::Result<Vec<TokenPriceUpdate>> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .expect("Can't get access to the storage");
+
+        let prices = storage
+            .tokens_schema()
+            .load_ticker_prices_updated_after(self.last_token_prices_update)
+            .await?;
+
+        let mut updates = Vec::new();
+        for price in prices {
+            self.last_token_prices_update = self.last_token_prices_update.max(price.last_updated);
+            let token_id = TokenId(price.token_id as u32);
+            if self.token_prices.get(&token_id) != Some(&price.usd_price) {
+                self.token_prices.insert(token_id, price.usd_price.clone());
+                updates.push(TokenPriceUpdate {
+                    token_id,
+                    usd_price: price.usd_price,
+                });
+            }
+        }
+
+        metrics::histogram!(
+            "api.event_fetcher.load_token_price_updates",
+            start.elapsed()
+        );
+        Ok(updates)
+    }
+
     async fn load_aggregated_operation(
         &mut self,
         block_number: BlockNumber,
@@ -1,15 +1,21 @@
 use super::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp};
+use bigdecimal::BigDecimal;
 use futures::{channel::mpsc, select, stream::StreamExt};
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
     SubscriptionId,
 };
 use std::time::Duration;
+use zksync_api_types::v02::{
+    block::{BlockInfo, BlockStatus},
+    token::TokenPrice,
+    transaction::Transaction,
+};
 use zksync_config::configs::api::CommonApiConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::tx::TxHash;
-use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, ActionType, Address};
+use zksync_types::{BlockNumber, TokenId, TokenLike};
 
 use self::{event_fetcher::EventFetcher, operation_notifier::OperationNotifier};
 
@@ -26,6 +32,21 @@ pub struct ExecutedOps {
     pub block_number: BlockNumber,
 }
 
+/// Block which has become available in the REST API with the new status,
+/// i.e. its commit or execute operation was confirmed on Ethereum.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockStatusUpdate {
+    pub block_number: BlockNumber,
+    pub status: BlockStatus,
+}
+
+/// New price of the token in USD.
+#[derive(Debug, Clone)]
+pub struct TokenPriceUpdate {
+    pub token_id: TokenId,
+    pub usd_price: BigDecimal,
+}
+
 pub enum EventSubscribeRequest {
     Transaction {
         hash: TxHash,
@@ -42,6 +63,23 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    Blocks {
+        action: ActionType,
+        subscriber: Subscriber<BlockInfo>,
+    },
+    PendingTxs {
+        address: Address,
+        subscriber: Subscriber<Transaction>,
+    },
+    NFTs {
+        address: Address,
+        action: ActionType,
+        subscriber: Subscriber<Transaction>,
+    },
+    TokenPrice {
+        token: TokenLike,
+        subscriber: Subscriber<TokenPrice>,
+    },
 }
 
 pub enum EventNotifierRequest {
@@ -64,6 +102,9 @@ pub fn start_sub_notifier(
 ) -> tokio::task::JoinHandle<()> {
     let (new_block_sender, mut new_block_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
     let (new_txs_sender, mut new_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (replaced_txs_sender, mut replaced_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (block_status_sender, mut block_status_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (token_prices_sender, mut token_prices_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
 
     let mut notifier = OperationNotifier::new(
         api_requests_caches_size,
@@ -77,6 +118,9 @@ pub fn start_sub_notifier(
             miniblock_interval,
             new_block_sender,
             new_txs_sender,
+            replaced_txs_sender,
+            block_status_sender,
+            token_prices_sender,
         )
         .await
         .expect("Unable to create event fetcher");
@@ -100,6 +144,29 @@ pub fn start_sub_notifier(
                             .unwrap_or_default();
                     }
                 },
+                replaced_txs = replaced_txs_receiver.next() => {
+                    if let Some(replaced_txs) = replaced_txs {
+                        notifier.handle_replaced_txs(replaced_txs)
+                            .map_err(|e| vlog::warn!("Failed to handle replaced txs: {}",e))
+                            .unwrap_or_default();
+                    }
+                },
+                block_status = block_status_receiver.next() => {
+                    if let Some(block_status) = block_status {
+                        notifier.handle_block_status_update(block_status)
+                            .await
+                            .map_err(|e| vlog::warn!("Failed to handle block status update: {}",e))
+                            .unwrap_or_default();
+                    }
+                },
+                token_prices = token_prices_receiver.next() => {
+                    if let Some(token_prices) = token_prices {
+                        notifier.handle_token_prices(token_prices)
+                            .await
+                            .map_err(|e| vlog::warn!("Failed to handle token prices: {}",e))
+                            .unwrap_or_default();
+                    }
+                },
                 new_sub = subscription_stream.next() => {
                     if let Some(new_sub) = new_sub {
                         notifier.handle_notify_req(new_sub)
@@ -1,25 +1,67 @@
-use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+use crate::api_server::{
+    rest::v02::block::block_info_from_details,
+    rpc_server::types::{BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
 };
+use jsonrpc_core::Error as RpcError;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::{Duration, Instant};
+use zksync_api_types::v02::{
+    block::{BlockInfo as ApiBlockInfo, BlockStatus},
+    token::TokenPrice,
+    transaction::Transaction,
+};
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_storage::ConnectionPool;
 use zksync_types::aggregated_operations::AggregatedOperation;
 use zksync_types::tx::TxHash;
-use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
+use zksync_types::{BlockNumber, TokenId, TokenLike, ZkSyncTx};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{
+        AccountAddress, NewBlocks, SubStorage, ACCOUNT_SUB_PREFIX, BLOCK_SUB_PREFIX,
+        ETHOP_SUB_PREFIX, NFT_SUB_PREFIX, PENDING_TX_SUB_PREFIX, TOKEN_PRICE_SUB_PREFIX,
+        TX_SUB_PREFIX,
+    },
+    BlockStatusUpdate, EventNotifierRequest, EventSubscribeRequest, ExecutedOps, TokenPriceUpdate,
 };
 
+/// Currency of the prices sent to the token price subscribers.
+const TOKEN_PRICE_CURRENCY: &str = "usd";
+
+/// Unwraps the result of the subscription preparation, or rejects the subscriber
+/// with the error, so that the client doesn't wait for the events that will never come.
+macro_rules! try_or_reject {
+    ($e:expr, $sub:expr) => {
+        match $e {
+            Ok(res) => res,
+            Err(err) => {
+                let err = anyhow::Error::from(err);
+                $sub.reject(RpcError::invalid_params(err.to_string()))
+                    .unwrap_or_default();
+                return Err(err);
+            }
+        }
+    };
+}
+
 pub struct OperationNotifier {
     state: NotifierState,
 
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+
+    // Subscriptions below stay active until the client unsubscribes.
+    block_subs: SubStorage<NewBlocks, ApiBlockInfo>,
+    // Subscriptions of the accounts are keyed by the address, so that the accounts
+    // which don't exist yet can be subscribed to as well.
+    /// Transactions are only executed in the pending block, so only the commit action is used.
+    pending_tx_subs: SubStorage<AccountAddress, Transaction>,
+    nft_subs: SubStorage<AccountAddress, Transaction>,
+    /// Prices aren't tied to the blocks processing, so only the commit action is used.
+    token_price_subs: SubStorage<TokenId, TokenPrice>,
 }
 
 impl OperationNotifier {
@@ -30,9 +72,13 @@ impl OperationNotifier {
     ) -> Self {
         Self {
             state: NotifierState::new(cache_capacity, db_pool, token_cache_invalidate_period),
-            tx_subs: SubStorage::new(),
-            prior_op_subs: SubStorage::new(),
-            account_subs: SubStorage::new(),
+            tx_subs: SubStorage::new(TX_SUB_PREFIX),
+            prior_op_subs: SubStorage::new(ETHOP_SUB_PREFIX),
+            account_subs: SubStorage::new(ACCOUNT_SUB_PREFIX),
+            block_subs: SubStorage::new(BLOCK_SUB_PREFIX),
+            pending_tx_subs: SubStorage::new(PENDING_TX_SUB_PREFIX),
+            nft_subs: SubStorage::new(NFT_SUB_PREFIX),
+            token_price_subs: SubStorage::new(TOKEN_PRICE_SUB_PREFIX),
         }
     }
 
@@ -64,6 +110,21 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::Blocks { action, subscriber } => {
+                    self.add_blocks_sub(action, subscriber)
+                }
+                EventSubscribeRequest::PendingTxs {
+                    address,
+                    subscriber,
+                } => self.add_pending_txs_sub(address, subscriber),
+                EventSubscribeRequest::NFTs {
+                    address,
+                    action,
+                    subscriber,
+                } => self.add_nft_sub(address, action, subscriber),
+                EventSubscribeRequest::TokenPrice { token, subscriber } => {
+                    self.add_token_price_sub(token, subscriber).await
+                }
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -91,6 +152,7 @@ impl OperationNotifier {
                 action,
                 block.block_number,
             );
+            self.handle_nft_operations(&block.block_transactions, action, block.block_number);
 
             let updated_accounts: Vec<AccountId> = block
                 .block_transactions
@@ -143,6 +205,8 @@ impl OperationNotifier {
                             committed: true,
                             verified: action == ActionType::VERIFY,
                         }),
+                        replaced_by: None,
+                        cancelled: false,
                     };
                     self.tx_subs.notify(hash, action, resp);
                 }
@@ -163,11 +227,126 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.handle_executed_operations", start.elapsed());
     }
 
+    /// Processes NFT mints and transfers in the committed or finalized block,
+    /// notifying the subscribers of the involved accounts.
+    fn handle_nft_operations(
+        &mut self,
+        ops: &[ExecutedOperations],
+        action: ActionType,
+        block_number: BlockNumber,
+    ) {
+        let start = Instant::now();
+        for op in ops.iter().filter(|op| is_nft_mint_or_transfer(op)) {
+            self.broadcast_operation(op, action, block_number, SubKind::NFT);
+        }
+        metrics::histogram!("api.notifier.handle_nft_operations", start.elapsed());
+    }
+
+    /// Sends the operation to the subscribers of every account it affects.
+    fn broadcast_operation(
+        &mut self,
+        op: &ExecutedOperations,
+        action: ActionType,
+        block_number: BlockNumber,
+        kind: SubKind,
+    ) {
+        let subs = match kind {
+            SubKind::PendingTx => &mut self.pending_tx_subs,
+            SubKind::NFT => &mut self.nft_subs,
+        };
+        let status = block_status(action);
+        for address in affected_addresses(op) {
+            let address = AccountAddress(address);
+            if !subs.subscriber_exists(address, action) {
+                continue;
+            }
+            if let Some(tx) = Transaction::from_executed_operation(op, block_number, status) {
+                subs.broadcast(address, action, tx);
+            }
+        }
+    }
+
+    /// Processes the block which commit or execution was confirmed on Ethereum,
+    /// notifying the blocks subscribers.
+    pub async fn handle_block_status_update(
+        &mut self,
+        update: BlockStatusUpdate,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let action = match update.status {
+            BlockStatus::Committed => ActionType::COMMIT,
+            BlockStatus::Finalized => ActionType::VERIFY,
+        };
+        if !self.block_subs.subscriber_exists(NewBlocks, action) {
+            return Ok(());
+        }
+
+        match self.state.get_block_details(update.block_number).await? {
+            Some(details) => {
+                self.block_subs
+                    .broadcast(NewBlocks, action, block_info_from_details(details));
+            }
+            None => vlog::warn!(
+                "Block status is updated but block is not stored in DB, block: {}",
+                *update.block_number
+            ),
+        }
+
+        metrics::histogram!("api.notifier.handle_block_status_update", start.elapsed());
+        Ok(())
+    }
+
+    /// Processes changed token prices, notifying the subscribers.
+    pub async fn handle_token_prices(
+        &mut self,
+        updates: Vec<TokenPriceUpdate>,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        for update in updates {
+            if !self
+                .token_price_subs
+                .subscriber_exists(update.token_id, ActionType::COMMIT)
+            {
+                continue;
+            }
+            let token = match self.state.get_token(update.token_id).await? {
+                Some(token) => token,
+                None => {
+                    vlog::warn!(
+                        "Token price is updated but token is not stored in DB, id: {}",
+                        *update.token_id
+                    );
+                    continue;
+                }
+            };
+            let price = TokenPrice {
+                token_id: token.id,
+                token_symbol: token.symbol,
+                price_in: TOKEN_PRICE_CURRENCY.to_string(),
+                decimals: token.decimals,
+                price: update.usd_price,
+            };
+            self.token_price_subs
+                .broadcast(update.token_id, ActionType::COMMIT, price);
+        }
+        metrics::histogram!("api.notifier.handle_token_prices", start.elapsed());
+        Ok(())
+    }
+
     /// More convenient alias for `handle_executed_operations`.
+    /// Also notifies the subscribers of the pending transactions.
     pub fn handle_new_executed_batch(
         &mut self,
         exec_batch: ExecutedOps,
     ) -> Result<(), anyhow::Error> {
+        for op in &exec_batch.operations {
+            self.broadcast_operation(
+                op,
+                ActionType::COMMIT,
+                exec_batch.block_number,
+                SubKind::PendingTx,
+            );
+        }
         self.handle_executed_operations(
             exec_batch.operations,
             ActionType::COMMIT,
@@ -176,11 +355,31 @@ impl OperationNotifier {
         Ok(())
     }
 
+    /// Processes transactions replaced in the mempool, notifying the subscribers.
+    /// Replaced transaction will never be executed, so both commit and verify subscribers are notified.
+    pub fn handle_replaced_txs(
+        &mut self,
+        replacements: Vec<(TxHash, TxHash)>,
+    ) -> Result<(), anyhow::Error> {
+        let start = Instant::now();
+        for (hash, replaced_by) in replacements {
+            let resp = TransactionInfoResp::replaced(replaced_by);
+            self.tx_subs.notify(hash, ActionType::COMMIT, resp.clone());
+            self.tx_subs.notify(hash, ActionType::VERIFY, resp);
+        }
+        metrics::histogram!("api.notifier.handle_replaced_txs", start.elapsed());
+        Ok(())
+    }
+
     /// Removes provided subscription from the list.
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.block_subs.remove(sub_id.clone())?;
+        self.pending_tx_subs.remove(sub_id.clone())?;
+        self.nft_subs.remove(sub_id.clone())?;
+        self.token_price_subs.remove(sub_id)?;
         Ok(())
     }
 
@@ -196,17 +395,21 @@ impl OperationNotifier {
             .prior_op_subs
             .generate_sub_id(PriorityOpId(serial_id), action);
 
-        let executed_op = self
-            .state
-            .get_executed_priority_operation(serial_id as u32)
-            .await?;
+        let executed_op = try_or_reject!(
+            self.state
+                .get_executed_priority_operation(serial_id as u32)
+                .await,
+            sub
+        );
         if let Some(executed_op) = executed_op {
             // There may be no block, if transaction was executed in the pending block only.
-            if let Some(block_info) = self
-                .state
-                .get_block_info(BlockNumber(executed_op.block_number as u32))
-                .await?
-            {
+            let block_info = try_or_reject!(
+                self.state
+                    .get_block_info(BlockNumber(executed_op.block_number as u32))
+                    .await,
+                sub
+            );
+            if let Some(block_info) = block_info {
                 match action {
                     ActionType::COMMIT => {
                         let resp = ETHOpInfoResp {
@@ -246,7 +449,7 @@ impl OperationNotifier {
         let start = Instant::now();
         let sub_id = self.tx_subs.generate_sub_id(hash, action);
 
-        let tx_receipt = self.state.get_tx_receipt(&hash).await?;
+        let tx_receipt = try_or_reject!(self.state.get_tx_receipt(&hash).await, sub);
 
         if let Some(receipt) = tx_receipt {
             let tx_info_resp = TransactionInfoResp {
@@ -258,6 +461,8 @@ impl OperationNotifier {
                     committed: receipt.success,
                     verified: receipt.verified,
                 }),
+                replaced_by: None,
+                cancelled: false,
             };
             match action {
                 ActionType::COMMIT => {
@@ -273,6 +478,20 @@ impl OperationNotifier {
             }
         }
 
+        // Transaction can't be executed anymore if it was replaced in the mempool.
+        let replaced_by = try_or_reject!(self.state.get_tx_replacement(&hash).await, sub);
+        if let Some(replaced_by) = replaced_by {
+            self.tx_subs
+                .respond_once(sub_id, sub, TransactionInfoResp::replaced(replaced_by))?;
+            return Ok(());
+        }
+        // The same goes for the transactions cancelled by the account owner.
+        if try_or_reject!(self.state.is_tx_cancelled(&hash).await, sub) {
+            self.tx_subs
+                .respond_once(sub_id, sub, TransactionInfoResp::cancelled())?;
+            return Ok(());
+        }
+
         self.tx_subs.insert_new(sub_id, sub, hash, action)?;
         metrics::histogram!("api.notifier.add_transaction_sub", start.elapsed());
         Ok(())
@@ -286,7 +505,8 @@ impl OperationNotifier {
         sub: Subscriber<ResponseAccountState>,
     ) -> Result<(), anyhow::Error> {
         let start = Instant::now();
-        let (account_id, _account_state) = self.state.get_account_info(address, action).await?;
+        let (account_id, _account_state) =
+            try_or_reject!(self.state.get_account_info(address, action).await, sub);
 
         let sub_id = self.account_subs.generate_sub_id(account_id, action);
 
@@ -295,4 +515,243 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add new blocks subscription.
+    fn add_blocks_sub(
+        &mut self,
+        action: ActionType,
+        sub: Subscriber<ApiBlockInfo>,
+    ) -> Result<(), anyhow::Error> {
+        let sub_id = self.block_subs.generate_sub_id(NewBlocks, action);
+        self.block_subs.insert_new(sub_id, sub, NewBlocks, action)
+    }
+
+    /// Add pending transactions subscription.
+    fn add_pending_txs_sub(
+        &mut self,
+        address: Address,
+        sub: Subscriber<Transaction>,
+    ) -> Result<(), anyhow::Error> {
+        let address = AccountAddress(address);
+        let sub_id = self
+            .pending_tx_subs
+            .generate_sub_id(address, ActionType::COMMIT);
+        self.pending_tx_subs
+            .insert_new(sub_id, sub, address, ActionType::COMMIT)
+    }
+
+    /// Add NFT mints and transfers subscription.
+    fn add_nft_sub(
+        &mut self,
+        address: Address,
+        action: ActionType,
+        sub: Subscriber<Transaction>,
+    ) -> Result<(), anyhow::Error> {
+        let address = AccountAddress(address);
+        let sub_id = self.nft_subs.generate_sub_id(address, action);
+        self.nft_subs.insert_new(sub_id, sub, address, action)
+    }
+
+    /// Add token price subscription.
+    async fn add_token_price_sub(
+        &mut self,
+        token: TokenLike,
+        sub: Subscriber<TokenPrice>,
+    ) -> Result<(), anyhow::Error> {
+        let start = Instant::now();
+        let token = try_or_reject!(self.state.get_token(token.clone()).await, sub)
+            .ok_or_else(|| anyhow::format_err!("Token {} is not found", token));
+        let token_id = try_or_reject!(token, sub).id;
+
+        let sub_id = self
+            .token_price_subs
+            .generate_sub_id(token_id, ActionType::COMMIT);
+        self.token_price_subs
+            .insert_new(sub_id, sub, token_id, ActionType::COMMIT)?;
+        metrics::histogram!("api.notifier.add_token_price_sub", start.elapsed());
+        Ok(())
+    }
+}
+
+/// Subscriptions receiving the executed operations of the accounts.
+#[derive(Debug, Clone, Copy)]
+enum SubKind {
+    PendingTx,
+    NFT,
+}
+
+fn block_status(action: ActionType) -> BlockStatus {
+    match action {
+        ActionType::COMMIT => BlockStatus::Committed,
+        ActionType::VERIFY => BlockStatus::Finalized,
+    }
+}
+
+/// Returns the addresses of the accounts affected by the operation, the same ones the operation
+/// is shown in the transactions history of. Failed transactions are included as well.
+///
+/// Signers of the swap orders are only known by their ids, so the recipients of the orders are used.
+fn affected_addresses(op: &ExecutedOperations) -> Vec<Address> {
+    let mut addresses = match op {
+        ExecutedOperations::Tx(exec_tx) => match &exec_tx.signed_tx.tx {
+            ZkSyncTx::Transfer(tx) => vec![tx.from, tx.to],
+            ZkSyncTx::Withdraw(tx) => vec![tx.from, tx.to],
+            ZkSyncTx::Close(tx) => vec![tx.account],
+            ZkSyncTx::ChangePubKey(tx) => vec![tx.account],
+            ZkSyncTx::ForcedExit(tx) => vec![tx.target],
+            ZkSyncTx::Swap(tx) => vec![
+                tx.submitter_address,
+                tx.orders.0.recipient_address,
+                tx.orders.1.recipient_address,
+            ],
+            ZkSyncTx::MintNFT(tx) => vec![tx.creator_address, tx.recipient],
+            ZkSyncTx::WithdrawNFT(tx) => vec![tx.from, tx.to],
+        },
+        ExecutedOperations::PriorityOp(exec_prior_op) => {
+            exec_prior_op.priority_op.data.affected_accounts()
+        }
+    };
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+/// Checks whether the operation is a successful NFT mint or transfer.
+fn is_nft_mint_or_transfer(op: &ExecutedOperations) -> bool {
+    match op {
+        ExecutedOperations::Tx(exec_tx) if exec_tx.success => match &exec_tx.signed_tx.tx {
+            ZkSyncTx::MintNFT(_) => true,
+            ZkSyncTx::Transfer(tx) => tx.token.0 >= MIN_NFT_TOKEN_ID,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use num::BigUint;
+    use zksync_types::{
+        block::{ExecutedPriorityOp, ExecutedTx},
+        tx::{TimeRange, Transfer, Withdraw},
+        Deposit, DepositOp, MintNFT, Nonce, PriorityOp, ZkSyncOp, ZkSyncPriorityOp,
+    };
+
+    fn executed_tx(tx: ZkSyncTx, success: bool) -> ExecutedOperations {
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: tx.into(),
+            success,
+            op: None,
+            fail_reason: None,
+            block_index: None,
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    fn transfer(from: Address, to: Address, token: TokenId) -> ZkSyncTx {
+        ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(1),
+            from,
+            to,
+            token,
+            BigUint::from(1u32),
+            BigUint::from(0u32),
+            Nonce(0),
+            TimeRange::default(),
+            None,
+        )))
+    }
+
+    fn mint_nft(creator: Address, recipient: Address) -> ZkSyncTx {
+        ZkSyncTx::MintNFT(Box::new(MintNFT::new(
+            AccountId(1),
+            creator,
+            Default::default(),
+            recipient,
+            BigUint::from(0u32),
+            TokenId(0),
+            Nonce(0),
+            None,
+        )))
+    }
+
+    #[test]
+    fn affected_addresses_of_operations() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+
+        let op = executed_tx(transfer(from, to, TokenId(0)), true);
+        assert_eq!(affected_addresses(&op), vec![from, to]);
+        // The failed transaction is sent to the accounts as well.
+        let op = executed_tx(transfer(to, from, TokenId(0)), false);
+        assert_eq!(affected_addresses(&op), vec![from, to]);
+        // Accounts are not duplicated.
+        let op = executed_tx(transfer(from, from, TokenId(0)), true);
+        assert_eq!(affected_addresses(&op), vec![from]);
+
+        let withdraw = Withdraw::new(
+            AccountId(1),
+            from,
+            to,
+            TokenId(0),
+            BigUint::from(1u32),
+            BigUint::from(0u32),
+            Nonce(0),
+            TimeRange::default(),
+            None,
+        );
+        let op = executed_tx(ZkSyncTx::Withdraw(Box::new(withdraw)), true);
+        assert_eq!(affected_addresses(&op), vec![from, to]);
+
+        let deposit = Deposit {
+            from,
+            token: TokenId(0),
+            amount: BigUint::from(1u32),
+            to,
+        };
+        let op = ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id: 0,
+                data: ZkSyncPriorityOp::Deposit(deposit.clone()),
+                deadline_block: 0,
+                eth_hash: Default::default(),
+                eth_block: 0,
+                eth_block_index: None,
+            },
+            op: ZkSyncOp::Deposit(Box::new(DepositOp {
+                priority_op: deposit,
+                account_id: AccountId(2),
+            })),
+            block_index: 0,
+            created_at: Utc::now(),
+        }));
+        assert_eq!(affected_addresses(&op), vec![from, to]);
+    }
+
+    #[test]
+    fn nft_mint_or_transfer() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+
+        assert!(is_nft_mint_or_transfer(&executed_tx(
+            mint_nft(from, to),
+            true
+        )));
+        assert!(is_nft_mint_or_transfer(&executed_tx(
+            transfer(from, to, TokenId(MIN_NFT_TOKEN_ID)),
+            true
+        )));
+        // Transfers of the fungible tokens and the failed transactions are skipped.
+        assert!(!is_nft_mint_or_transfer(&executed_tx(
+            transfer(from, to, TokenId(MIN_NFT_TOKEN_ID - 1)),
+            true
+        )));
+        assert!(!is_nft_mint_or_transfer(&executed_tx(
+            mint_nft(from, to),
+            false
+        )));
+    }
 }
@@ -1,20 +1,23 @@
 use crate::api_server::rpc_server::types::{BlockInfo, ResponseAccountState};
+use crate::utils::block_details_cache::BlockDetailsCache;
 use lru_cache::LruCache;
 use std::time::{Duration, Instant};
+use zksync_storage::chain::block::records::StorageBlockDetails;
 use zksync_storage::chain::operations::records::StoredExecutedPriorityOperation;
 use zksync_storage::chain::operations_ext::records::TxReceiptResponse;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::tx::TxHash;
-use zksync_types::BlockNumber;
 use zksync_types::{AccountId, ActionType, Address};
+use zksync_types::{BlockNumber, Token, TokenLike};
 
 pub struct NotifierState {
     pub(super) cache_of_executed_priority_operations:
         LruCache<u32, StoredExecutedPriorityOperation>,
     pub(super) cache_of_transaction_receipts: LruCache<Vec<u8>, TxReceiptResponse>,
     pub(super) cache_of_blocks_info: LruCache<BlockNumber, BlockInfo>,
+    pub(super) block_details_cache: BlockDetailsCache,
     pub(super) tokens_cache: TokenDBCache,
     pub(super) db_pool: ConnectionPool,
 }
@@ -29,6 +32,7 @@ impl NotifierState {
             cache_of_executed_priority_operations: LruCache::new(cache_capacity),
             cache_of_transaction_receipts: LruCache::new(cache_capacity),
             cache_of_blocks_info: LruCache::new(cache_capacity),
+            block_details_cache: BlockDetailsCache::new(cache_capacity),
             tokens_cache: TokenDBCache::new(token_cache_invalidate_period),
             db_pool,
         }
@@ -66,6 +70,35 @@ impl NotifierState {
         Ok(res)
     }
 
+    pub async fn get_tx_replacement(
+        &mut self,
+        hash: &TxHash,
+    ) -> Result<Option<TxHash>, anyhow::Error> {
+        let start = Instant::now();
+        let mut storage = self.db_pool.access_storage().await?;
+        let replaced_by = storage
+            .chain()
+            .mempool_schema()
+            .get_replacement(*hash)
+            .await?;
+
+        metrics::histogram!("api.notifier.get_tx_replacement", start.elapsed());
+        Ok(replaced_by)
+    }
+
+    pub async fn is_tx_cancelled(&mut self, hash: &TxHash) -> Result<bool, anyhow::Error> {
+        let start = Instant::now();
+        let mut storage = self.db_pool.access_storage().await?;
+        let cancelled = storage
+            .chain()
+            .mempool_schema()
+            .is_tx_cancelled(*hash)
+            .await?;
+
+        metrics::histogram!("api.notifier.is_tx_cancelled", start.elapsed());
+        Ok(cancelled)
+    }
+
     pub async fn get_block_info(
         &mut self,
         block_number: BlockNumber,
@@ -123,6 +156,22 @@ impl NotifierState {
         Ok(Some(res))
     }
 
+    /// Returns the block in the format of the REST API. Block is only available once
+    /// its commit is confirmed on Ethereum.
+    pub async fn get_block_details(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<StorageBlockDetails>, anyhow::Error> {
+        let start = Instant::now();
+        let details = self
+            .block_details_cache
+            .get(&self.db_pool, block_number)
+            .await?;
+
+        metrics::histogram!("api.notifier.get_block_details", start.elapsed());
+        Ok(details)
+    }
+
     pub async fn get_executed_priority_operation(
         &mut self,
         serial_id: u32,
@@ -192,6 +241,11 @@ impl NotifierState {
         Ok((account_id, account_state))
     }
 
+    pub async fn get_token(&self, token: impl Into<TokenLike>) -> anyhow::Result<Option<Token>> {
+        let mut storage = self.db_pool.access_storage().await?;
+        self.tokens_cache.get_token(&mut storage, token).await
+    }
+
     pub async fn get_account_state(
         &self,
         id: AccountId,
@@ -1,7 +1,7 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
-use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
+use std::{cmp::Ord, collections::BTreeMap, fmt, str::FromStr};
+use zksync_types::{ActionType, Address};
 
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -9,44 +9,69 @@ use jsonrpc_pubsub::{
 };
 
 const MAX_LISTENERS_PER_ENTITY: usize = 2048;
-const TX_SUB_PREFIX: &str = "txsub";
-const ETHOP_SUB_PREFIX: &str = "eosub";
-const ACCOUNT_SUB_PREFIX: &str = "acsub";
+pub const TX_SUB_PREFIX: &str = "txsub";
+pub const ETHOP_SUB_PREFIX: &str = "eosub";
+pub const ACCOUNT_SUB_PREFIX: &str = "acsub";
+pub const BLOCK_SUB_PREFIX: &str = "blsub";
+pub const PENDING_TX_SUB_PREFIX: &str = "ptsub";
+pub const NFT_SUB_PREFIX: &str = "nfsub";
+pub const TOKEN_PRICE_SUB_PREFIX: &str = "tpsub";
 
-pub trait ActionId {
-    fn sub_type() -> &'static str;
+/// Identifier of the subscriptions to all the new blocks, which aren't bound to any entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NewBlocks;
+
+impl fmt::Display for NewBlocks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("blocks")
+    }
 }
 
-impl ActionId for PriorityOpId {
-    fn sub_type() -> &'static str {
-        ETHOP_SUB_PREFIX
+impl FromStr for NewBlocks {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(Self),
+            _ => Err(anyhow::format_err!("Unknown blocks subscription: {}", s)),
+        }
     }
 }
 
-impl ActionId for TxHash {
-    fn sub_type() -> &'static str {
-        TX_SUB_PREFIX
+/// Address of the account the subscription is bound to. Unlike `Address`, it's formatted
+/// as the full hex string, so that it can be parsed back from the subscription id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AccountAddress(pub Address);
+
+impl fmt::Display for AccountAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
     }
 }
 
-impl ActionId for AccountId {
-    fn sub_type() -> &'static str {
-        ACCOUNT_SUB_PREFIX
+impl FromStr for AccountAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
     }
 }
 
 #[derive(Debug)]
 pub struct SubStorage<ID, RESP> {
+    /// Prefix of the subscription ids, which tells the type of the subscription.
+    sub_type: &'static str,
     storage: BTreeMap<(ID, ActionType), Vec<SubscriptionSender<RESP>>>,
 }
 
 impl<ID, RESP> SubStorage<ID, RESP>
 where
-    ID: Ord + Clone + ToString + FromStr + ActionId + std::fmt::Debug,
+    ID: Ord + Clone + ToString + FromStr + std::fmt::Debug,
     RESP: serde::Serialize + Clone + std::fmt::Debug,
 {
-    pub fn new() -> Self {
+    pub fn new(sub_type: &'static str) -> Self {
         Self {
+            sub_type,
             storage: BTreeMap::default(),
         }
     }
@@ -60,7 +85,7 @@ where
     pub fn generate_sub_id(&mut self, action_id: ID, action_type: ActionType) -> SubscriptionId {
         SubscriptionId::String(format!(
             "{}/{}/{}/{}",
-            ID::sub_type(),
+            self.sub_type,
             action_id.to_string(),
             action_type.to_string(),
             zksync_crypto::rand::random::<u64>()
@@ -75,7 +100,7 @@ where
         let sub_action_id = id_split.next().ok_or_else(incorrect_id_err)?;
         let sub_action_type = id_split.next().ok_or_else(incorrect_id_err)?;
 
-        if sub_type != ID::sub_type() {
+        if sub_type != self.sub_type {
             // Not our type, do nothing.
             return Ok(None);
         }
@@ -144,6 +169,24 @@ where
         }
     }
 
+    /// Sends the event to the subscribers without removing them, unlike `notify`.
+    /// Subscribers which have disconnected are removed.
+    pub fn broadcast(&mut self, action_id: ID, action_type: ActionType, event: RESP) {
+        let key = (action_id, action_type);
+        if let Some(subs) = self.storage.get_mut(&key) {
+            subs.retain(|sub| match sub.sink.notify(Ok(event.clone())) {
+                Ok(()) => true,
+                Err(e) => {
+                    vlog::warn!("{}", e.to_string());
+                    !e.is_disconnected()
+                }
+            });
+            if subs.is_empty() {
+                self.storage.remove(&key);
+            }
+        }
+    }
+
     pub fn respond_once(
         &mut self,
         sub_id: SubscriptionId,
@@ -158,3 +201,67 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_address_roundtrip() {
+        let address = AccountAddress(Address::random());
+        assert_eq!(
+            address.to_string().parse::<AccountAddress>().unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn broadcast() {
+        let mut subs: SubStorage<AccountAddress, u64> = SubStorage::new(PENDING_TX_SUB_PREFIX);
+        let address = AccountAddress(Address::random());
+        let other_address = AccountAddress(Address::random());
+
+        let (first_sub, _first_id, mut first_events) = Subscriber::new_test("test");
+        let first_sub_id = subs.generate_sub_id(address, ActionType::COMMIT);
+        subs.insert_new(first_sub_id, first_sub, address, ActionType::COMMIT)
+            .unwrap();
+        let (second_sub, _second_id, second_events) = Subscriber::new_test("test");
+        let second_sub_id = subs.generate_sub_id(address, ActionType::COMMIT);
+        subs.insert_new(second_sub_id, second_sub, address, ActionType::COMMIT)
+            .unwrap();
+
+        // Unlike `notify`, subscribers stay subscribed after receiving the event.
+        subs.broadcast(address, ActionType::COMMIT, 1);
+        subs.broadcast(address, ActionType::COMMIT, 2);
+        assert!(first_events
+            .try_next()
+            .unwrap()
+            .unwrap()
+            .contains("\"result\":1"));
+        assert!(first_events
+            .try_next()
+            .unwrap()
+            .unwrap()
+            .contains("\"result\":2"));
+        assert!(subs.subscriber_exists(address, ActionType::COMMIT));
+
+        // Events of the other accounts and actions are not received.
+        subs.broadcast(other_address, ActionType::COMMIT, 3);
+        subs.broadcast(address, ActionType::VERIFY, 4);
+        assert!(first_events.try_next().is_err());
+
+        // Disconnected subscribers are removed.
+        drop(second_events);
+        subs.broadcast(address, ActionType::COMMIT, 5);
+        assert!(first_events
+            .try_next()
+            .unwrap()
+            .unwrap()
+            .contains("\"result\":5"));
+        assert_eq!(subs.storage[&(address, ActionType::COMMIT)].len(), 1);
+
+        drop(first_events);
+        subs.broadcast(address, ActionType::COMMIT, 6);
+        assert!(!subs.subscriber_exists(address, ActionType::COMMIT));
+    }
+}
@@ -1,9 +1,11 @@
 //! API server handles endpoints for interaction with node.
 //!
+//! `mod admin_server` - api is used by the operator to manage the fee token policies.
 //! `mod rest` - api is used for block explorer.
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
 
+pub mod admin_server;
 mod event_notify;
 pub mod forced_exit_checker;
 mod helpers;
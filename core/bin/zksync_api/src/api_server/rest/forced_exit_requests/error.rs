@@ -105,6 +105,8 @@ impl SumbitErrorCode {
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
             SubmitError::Toggle2FA(_) => Self::Other,
+            SubmitError::CancelTx(_) => Self::Other,
+            SubmitError::ExodusMode => Self::Other,
             SubmitError::PriceError(_) => Self::Other,
         }
     }
@@ -13,6 +13,7 @@ use crate::signature_checker::VerifySignatureRequest;
 use super::tx_sender::TxSender;
 
 use crate::api_server::rest::network_status::SharedNetworkStatus;
+use crate::core_api_client::CoreApiClient;
 use crate::fee_ticker::FeeTicker;
 use tokio::task::JoinHandle;
 use zksync_config::ZkSyncConfig;
@@ -23,6 +24,7 @@ mod helpers;
 pub mod network_status;
 mod v01;
 pub mod v02;
+mod webhooks;
 
 async fn start_server(
     api_v01: ApiV01,
@@ -45,6 +47,9 @@ async fn start_server(
             api_v01.config.contracts.forced_exit_addr,
         );
 
+        let webhooks_api_scope =
+            webhooks::api_scope(api_v01.connection_pool.clone(), &api_v01.config.webhooks);
+
         let api_v02_scope = {
             let tx_sender = TxSender::new(
                 api_v01.connection_pool.clone(),
@@ -52,6 +57,7 @@ async fn start_server(
                 fee_ticker.clone(),
                 &api_v01.config.api.common,
                 mempool_tx_sender.clone(),
+                CoreApiClient::new(api_v01.config.api.private.url.clone()),
             );
             v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
         };
@@ -66,6 +72,7 @@ async fn start_server(
             )
             .service(api_v01.into_scope())
             .service(forced_exit_requests_api_scope)
+            .service(webhooks_api_scope)
             .service(api_v02_scope)
             // Endpoint needed for js isReachable
             .route(
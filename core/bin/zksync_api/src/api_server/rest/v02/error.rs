@@ -39,6 +39,8 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    CancelTxError = 609,
+    ExodusMode = 610,
     Other = 60_000,
 }
 
@@ -200,6 +202,8 @@ impl ApiError for SubmitError {
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
+            Self::CancelTx(_) => ErrorCode::CancelTxError,
+            Self::ExodusMode => ErrorCode::ExodusMode,
             Self::Other(_) => ErrorCode::Other,
             Self::PriceError(_) => ErrorCode::InternalError,
         }
@@ -11,6 +11,7 @@ use actix_web::{
 
 // Workspace uses
 use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_types::TxFeeTypes;
 
 // Local uses
 use super::{error::Error, response::ApiResult};
@@ -46,16 +47,32 @@ async fn get_tx_fee(
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
     // TODO implement subsidies for v02 api ZKS-888
-    let res = data
+    let tx_type: TxFeeTypes = body.tx_type.into();
+    let fee = api_try!(data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_from_ticker_in_wei(tx_type, body.token_like.clone(), body.address)
         .await
-        .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+        .map_err(Error::from))
+    .normal_fee;
+    let quote = match body.quote_sender {
+        Some(sender) => api_try!(data
+            .tx_sender
+            .quote_fee(
+                sender,
+                body.token_like,
+                vec![(tx_type, body.address)],
+                &fee.total_fee
+            )
+            .await
+            .map_err(Error::from)),
+        None => None,
+    };
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tx_fee");
-    res
+    ApiResult::Ok(ApiFee {
+        quote,
+        ..fee.into()
+    })
 }
 
 async fn get_batch_fee(
@@ -72,21 +89,31 @@ async fn get_batch_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
-    let txs = body
+    let txs: Vec<_> = body
         .transactions
         .into_iter()
         .map(|tx| (tx.tx_type.into(), tx.address))
         .collect();
-    let res = data
+    let fee = api_try!(data
         .tx_sender
         .ticker
-        .get_batch_from_ticker_in_wei(body.token_like, txs)
+        .get_batch_from_ticker_in_wei(body.token_like.clone(), txs.clone())
         .await
-        .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+        .map_err(Error::from))
+    .normal_fee;
+    let quote = match body.quote_sender {
+        Some(sender) => api_try!(data
+            .tx_sender
+            .quote_fee(sender, body.token_like, txs, &fee.total_fee)
+            .await
+            .map_err(Error::from)),
+        None => None,
+    };
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_batch_fee");
-    res
+    ApiResult::Ok(ApiFee {
+        quote,
+        ..fee.into()
+    })
 }
 
 pub fn api_scope(tx_sender: TxSender) -> Scope {
@@ -107,6 +134,7 @@ mod tests {
         },
         SharedData,
     };
+    use crate::core_api_client::CoreApiClient;
     use crate::fee_ticker::validator::cache::TokenInMemoryCache;
     use chrono::Utc;
     use futures::channel::mpsc;
@@ -171,6 +199,7 @@ mod tests {
                     dummy_fee_ticker(&prices, Some(cache.clone())),
                     &cfg.config.api.common,
                     mempool_tx_request_sender.clone(),
+                    CoreApiClient::new(cfg.config.api.private.url.clone()),
                 ))
             },
             Some(shared_data),
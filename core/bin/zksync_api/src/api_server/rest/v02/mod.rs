@@ -13,7 +13,7 @@ use zksync_types::network::Network;
 use crate::api_server::tx_sender::TxSender;
 
 mod account;
-mod block;
+pub(crate) mod block;
 mod config;
 pub mod error;
 mod fee;
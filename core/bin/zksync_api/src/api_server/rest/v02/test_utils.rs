@@ -48,9 +48,9 @@ use zksync_utils::{big_decimal_to_ratio, scaled_u64_to_ratio, UnsignedRatioSeria
 // Local uses
 use crate::fee_ticker::{
     tests::TestToken,
-    ticker_info::BlocksInFutureAggregatedOperations,
+    ticker_info::{BlocksInFutureAggregatedOperations, CongestionInfo},
     validator::{cache::TokenInMemoryCache, FeeTokenValidator},
-    {FeeTicker, FeeTickerInfo, GasOperationsCost, PriceError, TickerConfig},
+    {FeeTicker, FeeTickerInfo, GasOperationsCost, PriceError, SurgeConfig, TickerConfig},
 };
 use crate::signature_checker::{VerifiedTx, VerifySignatureRequest};
 use std::any::Any;
@@ -499,6 +499,7 @@ impl TestServerConfig {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -585,6 +586,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -626,6 +628,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -854,6 +857,10 @@ impl FeeTickerInfo for DummyFeeTickerInfo {
         Ok(None)
     }
 
+    async fn congestion_info(&self, _blocks_window: u32) -> anyhow::Result<CongestionInfo> {
+        Ok(CongestionInfo::default())
+    }
+
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         if let Some(price) = self.prices.get(&token) {
             Ok(TokenPrice {
@@ -912,6 +919,7 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        surge: SurgeConfig::default(),
     }
 }
 pub fn dummy_fee_ticker(
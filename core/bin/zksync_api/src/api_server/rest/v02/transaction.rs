@@ -5,15 +5,16 @@ use std::time::Instant;
 // External uses
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
 
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        ApiTxBatch, CancelTx, CancelTxResponse, IncomingTx, IncomingTxBatch, L1Receipt,
+        L1Transaction, L2Receipt, Receipt, SimulateTxsRequest, SimulateTxsResponse,
+        SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData,
+        TxHashSerializeWrapper, TxInBlockStatus,
     },
     TxWithSignature,
 };
@@ -21,7 +22,10 @@ use zksync_types::{tx::TxHash, EthBlockId};
 
 // Local uses
 use super::{error::Error, response::ApiResult};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use crate::api_server::{
+    rpc_server::types::{RequestMetadata, CLOUDFLARE_CONNECTING_IP_HEADER},
+    tx_sender::{SubmitError, TxSender},
+};
 
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
@@ -68,7 +72,22 @@ impl ApiTransactionData {
                 id: op.serial_id,
             })))
         }
-        // 3. No operation found, return nothing.
+        // 3. Check whether the transaction was cancelled by the account owner.
+        else if storage
+            .chain()
+            .mempool_schema()
+            .is_tx_cancelled(tx_hash)
+            .await
+            .map_err(Error::storage)?
+        {
+            Ok(Some(Receipt::L2(L2Receipt {
+                tx_hash,
+                rollup_block: None,
+                status: TxInBlockStatus::Cancelled,
+                fail_reason: None,
+            })))
+        }
+        // 4. No operation found, return nothing.
         else {
             Ok(None)
         }
@@ -160,14 +179,41 @@ async fn tx_data(
     res
 }
 
+/// Extracts the IP of the request origin the same way the JSON-RPC server does.
+/// Requests without the Cloudflare header are attributed to the peer address of the connection.
+fn request_metadata(req: &HttpRequest) -> Option<RequestMetadata> {
+    let forwarded_ip = req
+        .headers()
+        .get(CLOUDFLARE_CONNECTING_IP_HEADER)
+        .and_then(|header| {
+            header
+                .to_str()
+                .map_err(|err| {
+                    vlog::warn!("Failed to parse CF-Connecting-IP header. Reason: {}", err)
+                })
+                .ok()
+        });
+    let ip = match forwarded_ip {
+        Some(ip) => ip.to_owned(),
+        None => req.peer_addr()?.ip().to_string(),
+    };
+    Some(RequestMetadata { ip })
+}
+
 async fn submit_tx(
     data: web::Data<ApiTransactionData>,
-    Json(body): Json<TxWithSignature>,
+    req: HttpRequest,
+    Json(body): Json<IncomingTx>,
 ) -> ApiResult<TxHashSerializeWrapper> {
     let start = Instant::now();
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, None)
+        .submit_tx(
+            body.tx,
+            body.signature,
+            body.fee_quote,
+            request_metadata(&req),
+        )
         .await;
 
     if let Err(err) = &tx_hash {
@@ -187,12 +233,18 @@ async fn submit_tx(
 
 async fn submit_batch(
     data: web::Data<ApiTransactionData>,
+    req: HttpRequest,
     Json(body): Json<IncomingTxBatch>,
 ) -> ApiResult<SubmitBatchResponse> {
     let start = Instant::now();
     let response = data
         .tx_sender
-        .submit_txs_batch(body.txs, body.signature, None)
+        .submit_txs_batch(
+            body.txs,
+            body.signature,
+            body.fee_quote,
+            request_metadata(&req),
+        )
         .await;
 
     if let Err(err) = &response {
@@ -225,6 +277,37 @@ async fn toggle_2fa(
     response.into()
 }
 
+async fn cancel_tx(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<TxHash>,
+    Json(body): Json<CancelTx>,
+) -> ApiResult<CancelTxResponse> {
+    let start = Instant::now();
+    let response = data
+        .tx_sender
+        .cancel_tx(*tx_hash, body)
+        .await
+        .map_err(Error::from);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cancel_tx");
+    response.into()
+}
+
+async fn simulate_txs(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<SimulateTxsRequest>,
+) -> ApiResult<SimulateTxsResponse> {
+    let start = Instant::now();
+    let response = data
+        .tx_sender
+        .simulate_txs(body.txs)
+        .await
+        .map_err(Error::from);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "simulate_txs");
+    response.into()
+}
+
 async fn get_batch(
     data: web::Data<ApiTransactionData>,
     batch_hash: web::Path<TxHash>,
@@ -242,10 +325,12 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .app_data(web::Data::new(data))
         .route("", web::post().to(submit_tx))
         .route("{tx_hash}", web::get().to(tx_status))
+        .route("{tx_hash}", web::delete().to(cancel_tx))
         .route("{tx_hash}/data", web::get().to(tx_data))
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
         .route("/toggle2FA", web::post().to(toggle_2fa))
+        .route("/simulate", web::post().to(simulate_txs))
 }
 
 #[cfg(test)]
@@ -258,7 +343,9 @@ mod tests {
         },
         SharedData,
     };
+    use crate::core_api_client::CoreApiClient;
     use crate::fee_ticker::validator::cache::TokenInMemoryCache;
+    use crate::tx_error::CancelTxError;
     use chrono::Utc;
     use futures::{channel::mpsc, StreamExt};
     use num::{rational::Ratio, BigUint};
@@ -266,15 +353,17 @@ mod tests {
     use std::str::FromStr;
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
+        fee::ApiFeeQuote,
         transaction::{L2Receipt, TxHashSerializeWrapper},
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
+    use zksync_test_account::ZkSyncAccount;
     use zksync_types::{
         tokens::{Token, TokenMarketVolume},
         tx::{
-            EthBatchSignData, EthBatchSignatures, PackedEthSignature, TxEthSignature,
-            TxEthSignatureVariant,
+            error::TxAddError, EthBatchSignData, EthBatchSignatures, PackedEthSignature,
+            TxEthSignature, TxEthSignatureVariant, TxSignature,
         },
         Address, BlockNumber, SignedZkSyncTx, TokenId, TokenKind, TokenLike,
     };
@@ -285,15 +374,21 @@ mod tests {
         let task = tokio::spawn(async move {
             while let Some(tx) = mempool_tx_request_receiver.next().await {
                 match tx {
-                    MempoolTransactionRequest::NewTx(_, resp) => {
+                    MempoolTransactionRequest::NewTx(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
                     MempoolTransactionRequest::NewPriorityOps(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
-                    MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
+                    MempoolTransactionRequest::RemovePriorityOps(_, resp) => {
+                        resp.send(Ok(Vec::new())).unwrap_or_default()
+                    }
+                    MempoolTransactionRequest::NewTxsBatch(_, _, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
+                    MempoolTransactionRequest::CancelTx(tx_hash, resp) => {
+                        resp.send(Ok(Some(vec![tx_hash]))).unwrap_or_default()
+                    }
                 }
             }
         });
@@ -352,6 +447,7 @@ mod tests {
                     dummy_fee_ticker(&prices, Some(cache.clone())),
                     &cfg.config.api.common,
                     sender.clone(),
+                    CoreApiClient::new(cfg.config.api.private.url.clone()),
                 ))
             },
             Some(shared_data),
@@ -423,7 +519,7 @@ mod tests {
             storage
                 .chain()
                 .mempool_schema()
-                .insert_batch(&txs, Vec::new())
+                .insert_batch(&txs, Vec::new(), None)
                 .await?;
         };
 
@@ -469,11 +565,14 @@ mod tests {
             storage
                 .chain()
                 .mempool_schema()
-                .insert_tx(&SignedZkSyncTx {
-                    tx,
-                    eth_sign_data: None,
-                    created_at: Utc::now(),
-                })
+                .insert_tx(
+                    &SignedZkSyncTx {
+                        tx,
+                        eth_sign_data: None,
+                        created_at: Utc::now(),
+                    },
+                    None,
+                )
                 .await?;
 
             tx_hash
@@ -497,8 +596,164 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert!(tx_data.is_none());
 
+        // Cancel request must be signed by the key of the transaction account.
+        let cancel_signature = |tx_hash: TxHash| {
+            TxSignature::sign_musig(
+                &ZkSyncAccount::rand().private_key,
+                &CancelTx::get_zksync_sign_message(&tx_hash),
+            )
+        };
+        let response = client
+            .cancel_tx(pending_tx_hash, cancel_signature(pending_tx_hash))
+            .await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(
+            error,
+            Error::from(SubmitError::CancelTx(CancelTxError::IncorrectSignature))
+        );
+
+        // Already executed transaction can't be cancelled.
+        let response = client.cancel_tx(tx_hash, cancel_signature(tx_hash)).await?;
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(
+            error,
+            Error::from(SubmitError::CancelTx(CancelTxError::NotInMempool))
+        );
+
         server.stop().await;
         task.abort();
         Ok(())
     }
+
+    /// Checks that the transaction paying less than the required fee is accepted
+    /// only along with the valid quote issued for its sender.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn submit_tx_with_fee_quote() -> anyhow::Result<()> {
+        let (sender, task) = submit_txs_loopback();
+
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let mut tokens = HashMap::new();
+        tokens.insert(
+            TokenLike::Id(TokenId(0)),
+            Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20),
+        );
+        let mut market = HashMap::new();
+        market.insert(
+            TokenId(0),
+            TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(400u32)),
+                last_updated: Utc::now(),
+            },
+        );
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market);
+        let prices = vec![(TokenLike::Id(TokenId(0)), 10500_u64.into())];
+
+        let mut tx_sender = TxSender::new(
+            cfg.pool.clone(),
+            dummy_sign_verifier(),
+            dummy_fee_ticker(&prices, Some(cache)),
+            &cfg.config.api.common,
+            sender,
+            CoreApiClient::new(cfg.config.api.private.url.clone()),
+        );
+        tx_sender.fee_quote_lifetime = Some(chrono::Duration::minutes(1));
+        tx_sender.fee_quote_secret = "0123456789abcdef0123456789abcdef".to_owned();
+
+        // Transfer paying the fee of 1 wei, which is definitely too low.
+        let tx = TestServerConfig::gen_zk_txs(1_u64).txs[1].0.clone();
+        let (tx_type, token, to, fee) = tx.get_fee_info().unwrap();
+        let submit = |fee_quote: Option<ApiFeeQuote>| {
+            tx_sender.submit_tx(
+                tx.clone(),
+                TxEthSignatureVariant::Single(None),
+                fee_quote,
+                None,
+            )
+        };
+        let is_fee_too_low = |result: Result<TxHash, SubmitError>| {
+            matches!(result, Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow)))
+        };
+
+        assert!(is_fee_too_low(submit(None).await));
+
+        let quote = tx_sender
+            .quote_fee(tx.account(), token.clone(), vec![(tx_type, to)], &fee)
+            .await?
+            .unwrap();
+        assert_eq!(submit(Some(quote.clone())).await?, tx.hash());
+        // The quote can't be used again.
+        assert!(is_fee_too_low(submit(Some(quote.clone())).await));
+
+        // The signature doesn't match the quote.
+        let quote = tx_sender
+            .quote_fee(tx.account(), token.clone(), vec![(tx_type, to)], &fee)
+            .await?
+            .unwrap();
+        let mut tampered = quote.clone();
+        tampered.total_fee = BigUint::from(0u32);
+        assert!(is_fee_too_low(submit(Some(tampered)).await));
+        let mut tampered = quote;
+        tampered.id += 1;
+        assert!(is_fee_too_low(submit(Some(tampered)).await));
+
+        // The quote was issued for another sender or transaction.
+        let other_sender = tx_sender
+            .quote_fee(Address::random(), token.clone(), vec![(tx_type, to)], &fee)
+            .await?;
+        assert!(is_fee_too_low(submit(other_sender).await));
+        let other_tx = tx_sender
+            .quote_fee(
+                tx.account(),
+                token.clone(),
+                vec![(tx_type, Address::random())],
+                &fee,
+            )
+            .await?;
+        assert!(is_fee_too_low(submit(other_tx).await));
+
+        // The quote has expired.
+        let mut expired_sender = tx_sender.clone();
+        expired_sender.fee_quote_lifetime = Some(chrono::Duration::minutes(-1));
+        let expired = expired_sender
+            .quote_fee(tx.account(), token, vec![(tx_type, to)], &fee)
+            .await?;
+        assert!(is_fee_too_low(submit(expired).await));
+
+        task.abort();
+        Ok(())
+    }
+
+    /// Checks that the IP of the submitter is taken from the Cloudflare header, as in the JSON-RPC server,
+    /// and from the peer address of the connection if there is no header.
+    #[test]
+    fn request_metadata_from_cloudflare_header() {
+        let peer_addr = "10.0.0.1:3000".parse().unwrap();
+        let req = actix_web::test::TestRequest::default()
+            .insert_header((CLOUDFLARE_CONNECTING_IP_HEADER, "100.100.100.100"))
+            .peer_addr(peer_addr)
+            .to_http_request();
+        assert_eq!(
+            request_metadata(&req).map(|metadata| metadata.ip),
+            Some("100.100.100.100".to_owned())
+        );
+
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr(peer_addr)
+            .to_http_request();
+        assert_eq!(
+            request_metadata(&req).map(|metadata| metadata.ip),
+            Some("10.0.0.1".to_owned())
+        );
+
+        let req = actix_web::test::TestRequest::default().to_http_request();
+        assert!(request_metadata(&req).is_none());
+    }
 }
@@ -0,0 +1,14 @@
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_config::WebhooksConfig;
+use zksync_storage::ConnectionPool;
+
+// Local uses
+
+mod v01;
+
+pub(crate) fn api_scope(connection_pool: ConnectionPool, config: &WebhooksConfig) -> Scope {
+    web::scope("/api/webhooks").service(v01::api_scope(connection_pool, config))
+}
@@ -0,0 +1,280 @@
+//! Webhooks registration part of API implementation.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use chrono::{DateTime, Utc};
+
+// Workspace uses
+pub use zksync_api_client::rest::webhooks::{
+    ListWebhooksRequest, RegisterWebhookRequest, RegisteredWebhook, RemoveWebhookRequest,
+    RemoveWebhookResponse, WebhookInfo,
+};
+use zksync_config::WebhooksConfig;
+use zksync_crypto::rand::{thread_rng, Rng};
+use zksync_notifier::webhooks::validate_webhook_url;
+use zksync_storage::ConnectionPool;
+use zksync_types::{tx::PackedEthSignature, Address};
+
+// Local uses
+use crate::api_server::rest::forced_exit_requests::{error::ApiError, JsonResult};
+
+/// Shared data between `/api/webhooks/v0.1/` endpoints.
+pub struct ApiWebhooksData {
+    pub(crate) connection_pool: ConnectionPool,
+
+    pub(crate) max_webhooks_per_address: u32,
+    pub(crate) request_lifetime: Duration,
+}
+
+impl ApiWebhooksData {
+    fn new(connection_pool: ConnectionPool, config: &WebhooksConfig) -> Self {
+        Self {
+            connection_pool,
+            max_webhooks_per_address: config.max_webhooks_per_address,
+            request_lifetime: config.request_lifetime(),
+        }
+    }
+}
+
+/// Checks that the request is signed by the owner of the address and isn't stale.
+/// The timestamp prevents the intercepted requests from being replayed later.
+fn verify_request(
+    address: Address,
+    timestamp: DateTime<Utc>,
+    signature: &PackedEthSignature,
+    message: String,
+    request_lifetime: Duration,
+) -> Result<(), ApiError> {
+    let age = Utc::now().signed_duration_since(timestamp);
+    let lifetime = chrono::Duration::from_std(request_lifetime).map_err(ApiError::internal)?;
+    if age > lifetime || -age > lifetime {
+        return Err(ApiError::bad_request("Request timestamp is too old"));
+    }
+
+    let signer = signature
+        .signature_recover_signer(message.as_bytes())
+        .map_err(|_| ApiError::bad_request("Invalid signature"))?;
+    if signer != address {
+        return Err(ApiError::bad_request(
+            "Request is not signed by the owner of the address",
+        ));
+    }
+    Ok(())
+}
+
+async fn validate_url(url: &str) -> Result<(), ApiError> {
+    validate_webhook_url(url)
+        .await
+        .map_err(ApiError::bad_request)?;
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    hex::encode(thread_rng().gen::<[u8; 32]>())
+}
+
+pub async fn register_webhook(
+    data: web::Data<ApiWebhooksData>,
+    params: web::Json<RegisterWebhookRequest>,
+) -> JsonResult<RegisteredWebhook> {
+    let start = Instant::now();
+
+    verify_request(
+        params.address,
+        params.timestamp,
+        &params.signature,
+        params.get_ethereum_sign_message(),
+        data.request_lifetime,
+    )?;
+    validate_url(&params.url).await?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+    let webhook = storage
+        .webhooks_schema()
+        .register_webhook(
+            params.address,
+            &params.url,
+            &generate_secret(),
+            data.max_webhooks_per_address,
+        )
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::bad_request("Maximum number of webhooks per address exceeded"))?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "register_webhook");
+    Ok(Json(RegisteredWebhook {
+        id: webhook.id,
+        address: webhook.address,
+        url: webhook.url,
+        secret: webhook.secret,
+        created_at: webhook.created_at,
+    }))
+}
+
+pub async fn remove_webhook(
+    data: web::Data<ApiWebhooksData>,
+    params: web::Json<RemoveWebhookRequest>,
+) -> JsonResult<RemoveWebhookResponse> {
+    let start = Instant::now();
+
+    verify_request(
+        params.address,
+        params.timestamp,
+        &params.signature,
+        params.get_ethereum_sign_message(),
+        data.request_lifetime,
+    )?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+    let removed = storage
+        .webhooks_schema()
+        .remove_webhook(params.id, params.address)
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "remove_webhook");
+    Ok(Json(RemoveWebhookResponse { removed }))
+}
+
+pub async fn list_webhooks(
+    data: web::Data<ApiWebhooksData>,
+    params: web::Json<ListWebhooksRequest>,
+) -> JsonResult<Vec<WebhookInfo>> {
+    let start = Instant::now();
+
+    verify_request(
+        params.address,
+        params.timestamp,
+        &params.signature,
+        params.get_ethereum_sign_message(),
+        data.request_lifetime,
+    )?;
+
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?;
+    let webhooks = storage
+        .webhooks_schema()
+        .load_webhooks(params.address)
+        .await
+        .map_err(warn_err)
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(|webhook| WebhookInfo {
+            id: webhook.id,
+            address: webhook.address,
+            url: webhook.url,
+            created_at: webhook.created_at,
+        })
+        .collect();
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "list_webhooks");
+    Ok(Json(webhooks))
+}
+
+pub fn api_scope(connection_pool: ConnectionPool, config: &WebhooksConfig) -> Scope {
+    let data = ApiWebhooksData::new(connection_pool, config);
+    let scope = web::scope("v0.1").app_data(web::Data::new(data));
+    if config.enabled {
+        scope
+            .route("/register", web::post().to(register_webhook))
+            .route("/remove", web::post().to(remove_webhook))
+            .route("/list", web::post().to(list_webhooks))
+    } else {
+        scope
+    }
+}
+
+fn warn_err<T: std::fmt::Display>(err: T) -> T {
+    vlog::warn!("Internal Server Error: '{}';", err);
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::H256;
+
+    use super::*;
+
+    fn signed_request(private_key: H256, timestamp: DateTime<Utc>) -> ListWebhooksRequest {
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let mut request = ListWebhooksRequest {
+            address,
+            timestamp,
+            // Placeholder, replaced with the signature of the request message below.
+            signature: PackedEthSignature::sign(&private_key, &[]).unwrap(),
+        };
+        request.signature =
+            PackedEthSignature::sign(&private_key, request.get_ethereum_sign_message().as_bytes())
+                .unwrap();
+        request
+    }
+
+    #[test]
+    fn test_verify_request() {
+        let lifetime = Duration::from_secs(300);
+        let check = |request: &ListWebhooksRequest| {
+            verify_request(
+                request.address,
+                request.timestamp,
+                &request.signature,
+                request.get_ethereum_sign_message(),
+                lifetime,
+            )
+        };
+
+        let request = signed_request(H256::repeat_byte(0x11), Utc::now());
+        assert!(check(&request).is_ok());
+
+        // Stale request.
+        let stale = signed_request(
+            H256::repeat_byte(0x11),
+            Utc::now() - chrono::Duration::seconds(600),
+        );
+        assert!(check(&stale).is_err());
+
+        // Request signed by another key.
+        let mut foreign = signed_request(H256::repeat_byte(0x22), Utc::now());
+        foreign.address = request.address;
+        assert!(check(&foreign).is_err());
+
+        // Signature doesn't match the message.
+        let mut tampered = request;
+        tampered.timestamp = tampered.timestamp + chrono::Duration::seconds(1);
+        assert!(check(&tampered).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_url() {
+        assert!(validate_url("https://93.184.216.34/hooks/zksync")
+            .await
+            .is_ok());
+        // Webhooks can't point to the server's network.
+        assert!(validate_url("http://127.0.0.1:8080").await.is_err());
+        assert!(validate_url("http://10.0.0.1/hooks").await.is_err());
+        assert!(validate_url("ftp://example.com").await.is_err());
+        assert!(validate_url("not a url").await.is_err());
+        assert!(validate_url("unix:/run/hook.sock").await.is_err());
+    }
+}
@@ -22,6 +22,9 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    CancelTx = 305,
+    ExodusMode = 306,
+    TxReplacement = 307,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -41,6 +44,10 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::PendingTxsLimitExceeded => Self::OperationsLimitReached,
+            TxAddError::ReplacementFeeTooLow => Self::FeeTooLow,
+            TxAddError::ReplacedTxAlreadyProposed => Self::TxReplacement,
+            TxAddError::BatchTxReplacement => Self::TxReplacement,
         }
     }
 }
@@ -82,6 +89,16 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::CancelTx(inner) => Self {
+                code: RpcErrorCodes::CancelTx.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::ExodusMode => Self {
+                code: RpcErrorCodes::ExodusMode.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::InappropriateFeeToken => Self {
                 code: RpcErrorCodes::InappropriateFeeToken.into(),
                 message: inner.to_string(),
@@ -31,7 +31,7 @@ pub mod types;
 pub use self::rpc_trait::Rpc;
 use self::types::*;
 use super::tx_sender::TxSender;
-use crate::fee_ticker::FeeTicker;
+use crate::{core_api_client::CoreApiClient, fee_ticker::FeeTicker};
 use ip_insert_middleware::IpInsertMiddleWare;
 use zksync_mempool::MempoolTransactionRequest;
 
@@ -54,6 +54,7 @@ impl RpcApp {
         config: &CommonApiConfig,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        core_api_client: CoreApiClient,
     ) -> Self {
         let api_requests_caches_size = config.caches_size;
 
@@ -63,6 +64,7 @@ impl RpcApp {
             ticker,
             config,
             mempool_tx_sender,
+            core_api_client,
         );
 
         RpcApp {
@@ -178,6 +180,48 @@ impl RpcApp {
         Ok(res)
     }
 
+    async fn get_tx_replacement(&self, tx_hash: TxHash) -> Result<Option<TxHash>> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let replaced_by = storage
+            .chain()
+            .mempool_schema()
+            .get_replacement(tx_hash)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: {}",
+                    err,
+                    tx_hash.to_string()
+                );
+                Error::internal_error()
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_tx_replacement");
+        Ok(replaced_by)
+    }
+
+    async fn is_tx_cancelled(&self, tx_hash: TxHash) -> Result<bool> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let cancelled = storage
+            .chain()
+            .mempool_schema()
+            .is_tx_cancelled(tx_hash)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: {}",
+                    err,
+                    tx_hash.to_string()
+                );
+                Error::internal_error()
+            })?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "is_tx_cancelled");
+        Ok(cancelled)
+    }
+
     async fn get_account_state(&self, address: Address) -> Result<AccountStateInfo> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
@@ -263,6 +307,7 @@ pub fn start_rpc_server(
     common_api_config: &CommonApiConfig,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    core_api_client: CoreApiClient,
 ) -> JoinHandle<()> {
     let addr = config.http_bind_addr();
     let rpc_app = RpcApp::new(
@@ -272,6 +317,7 @@ pub fn start_rpc_server(
         common_api_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        core_api_client,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
@@ -6,9 +6,11 @@ use jsonrpc_core::{Error, Result};
 // Workspace uses
 use zksync_api_types::{
     v02::{
-        fee::ApiTxFeeTypes,
+        fee::{ApiFeeQuote, ApiTxFeeTypes, FeeWithQuote},
         token::ApiNFT,
-        transaction::{Toggle2FA, Toggle2FAResponse},
+        transaction::{
+            CancelTx, CancelTxResponse, SimulateTxsResponse, Toggle2FA, Toggle2FAResponse,
+        },
     },
     TxWithSignature,
 };
@@ -114,6 +116,16 @@ impl RpcApp {
     pub async fn _impl_tx_info(self, tx_hash: TxHash) -> Result<TransactionInfoResp> {
         let start = Instant::now();
         let stored_receipt = self.get_tx_receipt(tx_hash).await?;
+        let replaced_by = if stored_receipt.is_none() {
+            self.get_tx_replacement(tx_hash).await?
+        } else {
+            None
+        };
+        let cancelled = if stored_receipt.is_none() && replaced_by.is_none() {
+            self.is_tx_cancelled(tx_hash).await?
+        } else {
+            false
+        };
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "tx_info");
         Ok(if let Some(stored_receipt) = stored_receipt {
             TransactionInfoResp {
@@ -125,13 +137,21 @@ impl RpcApp {
                     committed: true,
                     verified: stored_receipt.verified,
                 }),
+                replaced_by: None,
+                cancelled: false,
             }
+        } else if let Some(replaced_by) = replaced_by {
+            TransactionInfoResp::replaced(replaced_by)
+        } else if cancelled {
+            TransactionInfoResp::cancelled()
         } else {
             TransactionInfoResp {
                 executed: false,
                 success: None,
                 fail_reason: None,
                 block: None,
+                replaced_by: None,
+                cancelled: false,
             }
         })
     }
@@ -142,6 +162,7 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash> {
         let start = Instant::now();
@@ -152,6 +173,7 @@ impl RpcApp {
                 *tx,
                 *signature,
                 fast_processing,
+                fee_quote,
                 extracted_request_metadata,
             )
             .await;
@@ -173,13 +195,14 @@ impl RpcApp {
         self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<Vec<TxHash>> {
         let start = Instant::now();
 
         let result = self
             .tx_sender
-            .submit_txs_batch(txs, eth_signatures, extracted_request_metadata)
+            .submit_txs_batch(txs, eth_signatures, fee_quote, extracted_request_metadata)
             .await
             .map(|response| {
                 response
@@ -317,6 +340,43 @@ impl RpcApp {
         Ok(fee)
     }
 
+    /// Same as `get_tx_fee`, but also issues the quote guaranteeing the fee
+    /// for the transaction sent by `sender`. Subsidies are not applied to the quoted fees.
+    pub async fn _impl_get_tx_fee_quote(
+        self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token: TokenLike,
+        sender: Address,
+    ) -> Result<FeeWithQuote> {
+        let start = Instant::now();
+        let token_allowed = self
+            .tx_sender
+            .ticker
+            .token_allowed_for_fees(token.clone())
+            .await
+            .map_err(SubmitError::Internal)?;
+        if !token_allowed {
+            return Err(SubmitError::InappropriateFeeToken.into());
+        }
+
+        let tx_type: TxFeeTypes = tx_type.into();
+        let fee = self
+            .tx_sender
+            .ticker
+            .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
+            .await
+            .map_err(SubmitError::Internal)?
+            .normal_fee;
+        let quote = self
+            .tx_sender
+            .quote_fee(sender, token, vec![(tx_type, address)], &fee.total_fee)
+            .await?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_tx_fee_quote");
+        Ok(FeeWithQuote { fee, quote })
+    }
+
     pub async fn _impl_get_txs_batch_fee_in_wei(
         self,
         tx_types: Vec<ApiTxFeeTypes>,
@@ -434,6 +494,30 @@ impl RpcApp {
         response
     }
 
+    pub async fn _impl_cancel_tx(
+        self,
+        tx_hash: TxHash,
+        cancel_tx: CancelTx,
+    ) -> Result<CancelTxResponse> {
+        let start = Instant::now();
+        let response = self
+            .tx_sender
+            .cancel_tx(tx_hash, cancel_tx)
+            .await
+            .map_err(Error::from);
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "cancel_tx");
+        response
+    }
+
+    pub async fn _impl_simulate_txs(self, txs: Vec<ZkSyncTx>) -> Result<SimulateTxsResponse> {
+        let start = Instant::now();
+        let response = self.tx_sender.simulate_txs(txs).await.map_err(Error::from);
+
+        metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "simulate_txs");
+        response
+    }
+
     pub async fn _impl_get_nft_id_by_tx_hash(self, tx_hash: TxHash) -> Result<Option<TokenId>> {
         let start = Instant::now();
 
@@ -7,9 +7,11 @@ use jsonrpc_derive::rpc;
 // Workspace uses
 use zksync_api_types::{
     v02::{
-        fee::ApiTxFeeTypes,
+        fee::{ApiFeeQuote, ApiTxFeeTypes, FeeWithQuote},
         token::ApiNFT,
-        transaction::{Toggle2FA, Toggle2FAResponse},
+        transaction::{
+            CancelTx, CancelTxResponse, SimulateTxsResponse, Toggle2FA, Toggle2FAResponse,
+        },
     },
     TxWithSignature,
 };
@@ -48,6 +50,7 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash>;
 
@@ -56,6 +59,7 @@ pub trait Rpc {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<Vec<TxHash>>;
 
@@ -76,6 +80,15 @@ pub trait Rpc {
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<Fee>;
 
+    #[rpc(name = "get_tx_fee_quote", returns = "FeeWithQuote")]
+    fn get_tx_fee_quote(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token_like: TokenLike,
+        sender: Address,
+    ) -> BoxFutureResult<FeeWithQuote>;
+
     // _addresses argument is left for the backward compatibility.
     #[rpc(name = "get_txs_batch_fee_in_wei", returns = "TotalFee")]
     fn get_txs_batch_fee_in_wei(
@@ -110,6 +123,12 @@ pub trait Rpc {
 
     #[rpc(name = "get_nft_id_by_tx_hash", returns = "Option<TokenId>")]
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>>;
+
+    #[rpc(name = "cancel_tx", returns = "CancelTxResponse")]
+    fn cancel_tx(&self, tx_hash: TxHash, cancel_tx: CancelTx) -> BoxFutureResult<CancelTxResponse>;
+
+    #[rpc(name = "simulate_txs", returns = "SimulateTxsResponse")]
+    fn simulate_txs(&self, txs: Vec<ZkSyncTx>) -> BoxFutureResult<SimulateTxsResponse>;
 }
 
 impl Rpc for RpcApp {
@@ -131,9 +150,10 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<ApiFeeQuote>,
         meta: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash> {
-        spawn!(self._impl_tx_submit(tx, signature, fast_processing, meta))
+        spawn!(self._impl_tx_submit(tx, signature, fast_processing, fee_quote, meta))
     }
 
     // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
@@ -141,9 +161,10 @@ impl Rpc for RpcApp {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        fee_quote: Option<ApiFeeQuote>,
         meta: Option<RequestMetadata>,
     ) -> BoxFutureResult<Vec<TxHash>> {
-        spawn!(self._impl_submit_txs_batch(txs, eth_signatures, meta))
+        spawn!(self._impl_submit_txs_batch(txs, eth_signatures, fee_quote, meta))
     }
 
     fn contract_address(&self) -> BoxFutureResult<ContractAddressResp> {
@@ -165,6 +186,16 @@ impl Rpc for RpcApp {
         spawn!(self._impl_get_tx_fee(tx_type, address, token_like, meta))
     }
 
+    fn get_tx_fee_quote(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token_like: TokenLike,
+        sender: Address,
+    ) -> BoxFutureResult<FeeWithQuote> {
+        spawn!(self._impl_get_tx_fee_quote(tx_type, address, token_like, sender))
+    }
+
     // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
     fn get_txs_batch_fee_in_wei(
         &self,
@@ -210,4 +241,12 @@ impl Rpc for RpcApp {
     fn get_nft_id_by_tx_hash(&self, tx_hash: TxHash) -> BoxFutureResult<Option<TokenId>> {
         spawn!(self._impl_get_nft_id_by_tx_hash(tx_hash))
     }
+
+    fn cancel_tx(&self, tx_hash: TxHash, cancel_tx: CancelTx) -> BoxFutureResult<CancelTxResponse> {
+        spawn!(self._impl_cancel_tx(tx_hash, cancel_tx))
+    }
+
+    fn simulate_txs(&self, txs: Vec<ZkSyncTx>) -> BoxFutureResult<SimulateTxsResponse> {
+        spawn!(self._impl_simulate_txs(txs))
+    }
 }
@@ -12,7 +12,7 @@ use zksync_api_types::v02::{
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
+use zksync_types::{tx::TxHash, Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
 use zksync_utils::BigUintSerdeWrapper;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -110,6 +110,38 @@ pub struct TransactionInfoResp {
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
     pub block: Option<BlockInfo>,
+    /// Hash of the transaction which replaced this one in the mempool, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<TxHash>,
+    /// Whether the transaction was cancelled by the account owner while awaiting in the mempool.
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+impl TransactionInfoResp {
+    /// Info of the transaction which was replaced in the mempool and thus will never be executed.
+    pub fn replaced(replaced_by: TxHash) -> Self {
+        Self {
+            executed: false,
+            success: Some(false),
+            fail_reason: Some(format!("Replaced by {}", replaced_by.to_string())),
+            block: None,
+            replaced_by: Some(replaced_by),
+            cancelled: false,
+        }
+    }
+
+    /// Info of the transaction which was cancelled in the mempool and thus will never be executed.
+    pub fn cancelled() -> Self {
+        Self {
+            executed: false,
+            success: Some(false),
+            fail_reason: Some("Cancelled by the account owner".to_string()),
+            block: None,
+            replaced_by: None,
+            cancelled: true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -126,6 +158,9 @@ pub struct ContractAddressResp {
     pub gov_contract: String,
 }
 
+/// Header with the IP of the request origin. It's inserted by Cloudflare and users can never set it by themselves.
+pub const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+
 /// The metadata of the JSON-RPC call retrieved from the HTTP request of the call
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestMetadata {
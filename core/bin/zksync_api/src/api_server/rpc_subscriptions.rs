@@ -11,12 +11,14 @@ use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
 use jsonrpc_ws_server::RequestContext;
 use tokio::task::JoinHandle;
 // Workspace uses
+use zksync_api_types::v02::{block::BlockInfo, token::TokenPrice, transaction::Transaction};
 use zksync_config::configs::api::{CommonApiConfig, JsonRpcConfig};
 use zksync_mempool::MempoolTransactionRequest;
 use zksync_storage::ConnectionPool;
-use zksync_types::{tx::TxHash, ActionType, Address};
+use zksync_types::{tx::TxHash, ActionType, Address, TokenLike};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 // Local uses
+use crate::core_api_client::CoreApiClient;
 use crate::fee_ticker::FeeTicker;
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
@@ -82,6 +84,95 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    /// Notifies about every new block once it's committed or finalized (depending on the action type).
+    #[pubsub(
+        subscription = "blocks",
+        subscribe,
+        name = "blocks_subscribe",
+        alias("blocks_sub")
+    )]
+    fn subscribe_blocks(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<BlockInfo>,
+        action_type: ActionType,
+    );
+    #[pubsub(subscription = "blocks", unsubscribe, name = "blocks_unsubscribe")]
+    fn unsubscribe_blocks(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+
+    /// Notifies about every transaction of the account executed in the pending block.
+    #[pubsub(
+        subscription = "pending_txs",
+        subscribe,
+        name = "pending_txs_subscribe",
+        alias("pending_txs_sub")
+    )]
+    fn subscribe_pending_txs(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<Transaction>,
+        addr: Address,
+    );
+    #[pubsub(
+        subscription = "pending_txs",
+        unsubscribe,
+        name = "pending_txs_unsubscribe"
+    )]
+    fn unsubscribe_pending_txs(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+
+    /// Notifies about every NFT mint or transfer involving the account.
+    #[pubsub(
+        subscription = "nft",
+        subscribe,
+        name = "nft_subscribe",
+        alias("nft_sub")
+    )]
+    fn subscribe_nft(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<Transaction>,
+        addr: Address,
+        action_type: ActionType,
+    );
+    #[pubsub(subscription = "nft", unsubscribe, name = "nft_unsubscribe")]
+    fn unsubscribe_nft(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+
+    /// Notifies about every change of the token price in USD.
+    #[pubsub(
+        subscription = "token_price",
+        subscribe,
+        name = "token_price_subscribe",
+        alias("token_price_sub")
+    )]
+    fn subscribe_token_price(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<TokenPrice>,
+        token: TokenLike,
+    );
+    #[pubsub(
+        subscription = "token_price",
+        unsubscribe,
+        name = "token_price_unsubscribe"
+    )]
+    fn unsubscribe_token_price(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -170,6 +261,109 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_blocks(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<BlockInfo>,
+        action: ActionType,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(EventSubscribeRequest::Blocks {
+                action,
+                subscriber,
+            }))
+            .unwrap_or_default();
+    }
+    fn unsubscribe_blocks(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
+
+    fn subscribe_pending_txs(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<Transaction>,
+        address: Address,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::PendingTxs {
+                    address,
+                    subscriber,
+                },
+            ))
+            .unwrap_or_default();
+    }
+    fn unsubscribe_pending_txs(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
+
+    fn subscribe_nft(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<Transaction>,
+        address: Address,
+        action: ActionType,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(EventSubscribeRequest::NFTs {
+                address,
+                action,
+                subscriber,
+            }))
+            .unwrap_or_default();
+    }
+    fn unsubscribe_nft(&self, _meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
+
+    fn subscribe_token_price(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<TokenPrice>,
+        token: TokenLike,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::TokenPrice { token, subscriber },
+            ))
+            .unwrap_or_default();
+    }
+    fn unsubscribe_token_price(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -187,6 +381,7 @@ pub fn start_ws_server(
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    core_api_client: CoreApiClient,
 ) -> JoinHandle<()> {
     let addr = config.ws_bind_addr();
 
@@ -207,6 +402,7 @@ pub fn start_ws_server(
         common_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        core_api_client,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
@@ -10,25 +10,32 @@ use std::{
 
 // External uses
 use bigdecimal::BigDecimal;
-use chrono::{Duration, Utc};
+use chrono::{Duration, TimeZone, Utc};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
 };
+use hmac::{Hmac, Mac, NewMac};
 use itertools::izip;
 use num::rational::Ratio;
 use num::{bigint::ToBigInt, BigUint, Zero};
+use sha2::Sha256;
 use thiserror::Error;
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    v02::fee::ApiFeeQuote,
+    v02::transaction::{
+        CancelTx, CancelTxResponse, SimulateTxsResponse, SimulatedFee, SubmitBatchResponse,
+        Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper,
+    },
     TxWithSignature,
 };
 use zksync_storage::misc::records::Subsidy;
 use zksync_storage::{chain::account::records::EthAccountType, ConnectionPool};
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
+    fee::FeeQuote,
     tx::{
         EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
         TxEthSignatureVariant, TxHash,
@@ -42,13 +49,14 @@ use zksync_utils::{
 // Local uses
 use crate::{
     api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    core_api_client::CoreApiClient,
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
         BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
         VerifySignatureRequest,
     },
-    tx_error::Toggle2FAError,
-    utils::block_details_cache::BlockDetailsCache,
+    tx_error::{CancelTxError, Toggle2FAError},
+    utils::{block_details_cache::BlockDetailsCache, exodus_mode_cache::ExodusModeCache},
 };
 use zksync_config::configs::api::CommonApiConfig;
 use zksync_mempool::MempoolTransactionRequest;
@@ -64,12 +72,14 @@ pub struct TxSender {
     pub mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     pub sign_verify_requests: mpsc::Sender<VerifySignatureRequest>,
     pub ticker: FeeTicker,
+    pub core_api_client: CoreApiClient,
 
     pub pool: ConnectionPool,
     pub tokens: TokenDBCache,
 
     pub forced_exit_checker: ForcedExitChecker,
     pub blocks: BlockDetailsCache,
+    pub exodus_mode: ExodusModeCache,
     /// List of account IDs that do not have to pay fees for operations.
     pub fee_free_accounts: HashSet<AccountId>,
     pub enforce_pubkey_change_fee: bool,
@@ -80,6 +90,10 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
+
+    /// How long the issued fee quotes are honored, `None` if the quotes are disabled.
+    pub fee_quote_lifetime: Option<Duration>,
+    pub fee_quote_secret: String,
 }
 
 #[derive(Debug, Error)]
@@ -100,6 +114,10 @@ pub enum SubmitError {
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
     Toggle2FA(#[from] Toggle2FAError),
+    #[error("Failed to cancel transaction: {0}.")]
+    CancelTx(#[from] CancelTxError),
+    #[error("zkSync contract is in the exodus mode, transactions are not accepted anymore.")]
+    ExodusMode,
 
     #[error("Communication error with the mempool: {0}.")]
     MempoolCommunication(String),
@@ -148,6 +166,7 @@ impl TxSender {
         ticker: FeeTicker,
         config: &CommonApiConfig,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        core_api_client: CoreApiClient,
     ) -> Self {
         let max_number_of_transactions_per_batch =
             config.max_number_of_transactions_per_batch as usize;
@@ -158,12 +177,14 @@ impl TxSender {
             pool: connection_pool,
             sign_verify_requests: sign_verify_request_sender,
             ticker,
+            core_api_client,
             tokens: TokenDBCache::new(config.invalidate_token_cache_period()),
             forced_exit_checker: ForcedExitChecker::new(
                 config.forced_exit_minimum_account_age_secs,
             ),
             enforce_pubkey_change_fee: config.enforce_pubkey_change_fee,
             blocks: BlockDetailsCache::new(config.caches_size),
+            exodus_mode: ExodusModeCache::default(),
 
             fee_free_accounts: HashSet::from_iter(config.fee_free_accounts.clone()),
             max_number_of_transactions_per_batch,
@@ -171,7 +192,24 @@ impl TxSender {
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            fee_quote_lifetime: config
+                .fee_quote_lifetime()
+                .and_then(|lifetime| Duration::from_std(lifetime).ok()),
+            fee_quote_secret: config.fee_quote_secret.clone(),
+        }
+    }
+
+    /// Transactions can't be processed once the zkSync contract has entered the exodus mode.
+    async fn check_exodus_mode(&self) -> Result<(), SubmitError> {
+        let exodus_mode = self
+            .exodus_mode
+            .is_entered(&self.pool)
+            .await
+            .map_err(SubmitError::internal)?;
+        if exodus_mode {
+            return Err(SubmitError::ExodusMode);
         }
+        Ok(())
     }
 
     /// If `ForcedExit` has Ethereum siganture (e.g. it's a part of a batch), an actual signer
@@ -254,6 +292,143 @@ impl TxSender {
         Ok(Toggle2FAResponse { success: true })
     }
 
+    /// Removes the transaction from the mempool. If the transaction is a part of a batch,
+    /// the whole batch is removed. Transactions which are already executed (including the ones
+    /// in the pending block) can't be cancelled.
+    pub async fn cancel_tx(
+        &self,
+        tx_hash: TxHash,
+        cancel_tx: CancelTx,
+    ) -> Result<CancelTxResponse, SubmitError> {
+        let tx = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::DbError))?
+            .chain()
+            .mempool_schema()
+            .get_tx(tx_hash.as_ref())
+            .await
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::DbError))?
+            .ok_or(SubmitError::CancelTx(CancelTxError::NotInMempool))?;
+
+        self.verify_cancel_tx_signature(&tx, tx_hash, cancel_tx)
+            .await?;
+
+        let (sender, receiver) = oneshot::channel();
+        let item = MempoolTransactionRequest::CancelTx(tx_hash, sender);
+        let mut mempool_sender = self.mempool_tx_sender.clone();
+        mempool_sender
+            .send(item)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        let cancelled_txs = receiver
+            .await
+            .map_err(SubmitError::internal)?
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::DbError))?
+            .ok_or(SubmitError::CancelTx(CancelTxError::NotInMempool))?;
+
+        Ok(CancelTxResponse { cancelled_txs })
+    }
+
+    /// Executes the transactions against the state of the state keeper without submitting them.
+    /// More than one transaction is executed as a batch. The reported fees are the ones
+    /// required by the fee ticker.
+    pub async fn simulate_txs(
+        &self,
+        txs: Vec<ZkSyncTx>,
+    ) -> Result<SimulateTxsResponse, SubmitError> {
+        if txs.is_empty() {
+            return Err(SubmitError::invalid_params("No transactions to simulate"));
+        }
+        if txs.len() > self.max_number_of_transactions_per_batch {
+            return Err(SubmitError::TxAdd(TxAddError::BatchTooBig));
+        }
+        self.check_exodus_mode().await?;
+
+        let mut response = self
+            .core_api_client
+            .simulate_txs(&txs)
+            .await
+            .map_err(SubmitError::internal)?;
+        for (simulated_tx, tx) in response.txs.iter_mut().zip(&txs) {
+            simulated_tx.fee = self.required_fee(tx).await?;
+        }
+        Ok(response)
+    }
+
+    /// Returns the fee required by the fee ticker for the transaction,
+    /// or `None` if the transaction doesn't have to pay it.
+    async fn required_fee(&self, tx: &ZkSyncTx) -> Result<Option<SimulatedFee>, SubmitError> {
+        let is_whitelisted_initiator = tx
+            .account_id()
+            .map(|account_id| self.fee_free_accounts.contains(&account_id))
+            .unwrap_or(false);
+        let (tx_type, token, address) = match tx.get_fee_info() {
+            Some((tx_type, token, address, _)) if !is_whitelisted_initiator => {
+                (tx_type, token, address)
+            }
+            _ => return Ok(None),
+        };
+
+        let token = self.token_info_from_id(token).await?;
+        let required_fee = self
+            .ticker
+            .get_fee_from_ticker_in_wei(tx_type, token.id.into(), address)
+            .await?;
+        Ok(Some(SimulatedFee {
+            token: token.id,
+            amount: required_fee.normal_fee.total_fee,
+        }))
+    }
+
+    /// Checks that the cancel request is signed by the current zkSync key of the transaction account.
+    /// If the transaction is the one setting the key, the new key is accepted as well.
+    async fn verify_cancel_tx_signature(
+        &self,
+        tx: &SignedZkSyncTx,
+        tx_hash: TxHash,
+        cancel_tx: CancelTx,
+    ) -> Result<(), SubmitError> {
+        let message = CancelTx::get_zksync_sign_message(&tx_hash);
+        let signer_pub_key = cancel_tx
+            .signature
+            .verify_musig(&message)
+            .ok_or(SubmitError::CancelTx(CancelTxError::IncorrectSignature))?;
+        let signer_pub_key_hash = PubKeyHash::from_pubkey(&signer_pub_key);
+
+        let account_id = tx
+            .account_id()
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::Other))?;
+        let account = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::DbError))?
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(|_| SubmitError::CancelTx(CancelTxError::DbError))?
+            .1;
+
+        let is_account_key = account
+            .map(|account| account.pub_key_hash == signer_pub_key_hash)
+            .unwrap_or(false);
+        let is_new_key = match &tx.tx {
+            ZkSyncTx::ChangePubKey(change_pubkey) => {
+                change_pubkey.new_pk_hash == signer_pub_key_hash
+            }
+            _ => false,
+        };
+
+        if !is_account_key && !is_new_key {
+            return Err(SubmitError::CancelTx(CancelTxError::IncorrectSignature));
+        }
+        Ok(())
+    }
+
     async fn verify_toggle_2fa_request_eth_signature(
         &self,
         toggle_2fa: Toggle2FA,
@@ -360,6 +535,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         fast_processing: Option<bool>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let fast_processing = fast_processing.unwrap_or(false);
@@ -379,7 +555,7 @@ impl TxSender {
         }
 
         let result = self
-            .submit_tx(tx, signature, extracted_request_metadata)
+            .submit_tx(tx, signature, fee_quote, extracted_request_metadata)
             .await;
 
         if let Err(err) = &result {
@@ -484,6 +660,7 @@ impl TxSender {
         &self,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let labels = vec![
@@ -494,9 +671,14 @@ impl TxSender {
         // The initial state of processing tx
         metrics::increment_counter!("process_tx_count", &labels);
 
+        let submitter_ip = extracted_request_metadata
+            .as_ref()
+            .map(|metadata| metadata.ip.clone());
+
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
         }
+        self.check_exodus_mode().await?;
 
         if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
             self.check_forced_exit(forced_exit).await?;
@@ -521,6 +703,11 @@ impl TxSender {
 
         let sign_verify_channel = self.sign_verify_requests.clone();
 
+        let tx_sender = self
+            .get_tx_sender(&tx)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+
         let mut fee_data_for_subsidy: Option<ResponseFee> = None;
 
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
@@ -555,19 +742,25 @@ impl TxSender {
 
             // Converting `BitUint` to `BigInt` is safe.
             let required_fee: BigDecimal = required_fee_data.total_fee.to_bigint().unwrap().into();
-            let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
+            let provided_fee_decimal: BigDecimal = provided_fee.to_bigint().unwrap().into();
             // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee = scale_user_fee_up(provided_fee);
-            if required_fee >= scaled_provided_fee && should_enforce_fee {
+            let scaled_provided_fee = scale_user_fee_up(provided_fee_decimal);
+            if required_fee >= scaled_provided_fee
+                && should_enforce_fee
+                && !self
+                    .is_fee_quoted(
+                        fee_quote.as_ref(),
+                        tx_sender,
+                        token,
+                        vec![(tx_type, address)],
+                        &provided_fee,
+                    )
+                    .await?
+            {
                 return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
             }
         }
 
-        let tx_sender = self
-            .get_tx_sender(&tx)
-            .await
-            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
-
         let verified_tx = verify_tx_info_message_signature(
             &tx,
             tx_sender,
@@ -592,7 +785,7 @@ impl TxSender {
         }
 
         let (sender, receiver) = oneshot::channel();
-        let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
+        let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), submitter_ip, sender);
         let mut mempool_sender = self.mempool_tx_sender.clone();
         mempool_sender
             .send(item)
@@ -633,14 +826,19 @@ impl TxSender {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        fee_quote: Option<ApiFeeQuote>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<SubmitBatchResponse, SubmitError> {
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
+        let submitter_ip = extracted_request_metadata
+            .as_ref()
+            .map(|metadata| metadata.ip.clone());
 
         if txs.is_empty() {
             return Err(SubmitError::TxAdd(TxAddError::EmptyBatch));
         }
+        self.check_exodus_mode().await?;
         // Even though this is going to be checked on the Mempool part,
         // we don't want to verify huge batches as long as this operation
         // is expensive.
@@ -751,14 +949,30 @@ impl TxSender {
 
             // Not enough fee
             if required_normal_fee > user_provided_fee {
-                vlog::error!(
-                    "User provided batch fee in token is too low, required: {}, provided (scaled): {}",
-                    required_normal_fee.to_string(),
-                    user_provided_fee.to_string(),
-                );
-                return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
+                let quoted = match self.batch_fee_payer(&txs).await? {
+                    Some(fee_payer) => {
+                        self.is_fee_quoted(
+                            fee_quote.as_ref(),
+                            fee_payer,
+                            batch_token.into(),
+                            transaction_types,
+                            &fee_paid,
+                        )
+                        .await?
+                    }
+                    None => false,
+                };
+                if !quoted {
+                    vlog::error!(
+                        "User provided batch fee in token is too low, required: {}, provided (scaled): {}",
+                        required_normal_fee.to_string(),
+                        user_provided_fee.to_string(),
+                    );
+                    return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
+                }
             }
         } else {
+            // Fee quotes are only honored for the batches paying the fee in a single token.
             // Calculate required fee for ethereum token
             let required_eth_fee = self
                 .ticker
@@ -877,8 +1091,12 @@ impl TxSender {
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
 
         let (sender, receiver) = oneshot::channel();
-        let item =
-            MempoolTransactionRequest::NewTxsBatch(verified_txs, verified_signatures, sender);
+        let item = MempoolTransactionRequest::NewTxsBatch(
+            verified_txs,
+            verified_signatures,
+            submitter_ip,
+            sender,
+        );
         let mut mempool_sender = self.mempool_tx_sender.clone();
         mempool_sender
             .send(item)
@@ -985,6 +1203,127 @@ impl TxSender {
         })
     }
 
+    /// Issues the quote guaranteeing the given fee for the transactions paid by the sender
+    /// until the quote expires. Returns `None` if the quotes are disabled.
+    ///
+    /// The quote is stored, so that it can be honored only once.
+    pub async fn quote_fee(
+        &self,
+        sender: Address,
+        token: TokenLike,
+        txs: Vec<(TxFeeTypes, Address)>,
+        total_fee: &BigUint,
+    ) -> Result<Option<ApiFeeQuote>, SubmitError> {
+        let lifetime = match self.fee_quote_lifetime {
+            Some(lifetime) => lifetime,
+            None => return Ok(None),
+        };
+        let token = self.token_info_from_id(token).await?;
+        // Only the seconds of the expiration time are signed.
+        let expires_at = Utc.timestamp((Utc::now() + lifetime).timestamp(), 0);
+        let id = self
+            .pool
+            .access_storage()
+            .await?
+            .fee_quotes_schema()
+            .store_quote(sender, token.id, total_fee, expires_at)
+            .await?;
+        let quote = FeeQuote {
+            id,
+            sender,
+            token_id: token.id,
+            txs,
+            total_fee: total_fee.clone(),
+            expires_at,
+        };
+        let signature = hex::encode(self.fee_quote_mac(&quote)?.finalize().into_bytes());
+
+        Ok(Some(ApiFeeQuote::new(quote, signature)))
+    }
+
+    /// Checks whether the provided fee is covered by the submitted quote, i.e. the quote
+    /// was issued for the same transactions paid by the same sender in the same token,
+    /// hasn't expired yet and hasn't been used before. The honored quote is marked as consumed.
+    async fn is_fee_quoted(
+        &self,
+        fee_quote: Option<&ApiFeeQuote>,
+        sender: Address,
+        token: TokenLike,
+        txs: Vec<(TxFeeTypes, Address)>,
+        provided_fee: &BigUint,
+    ) -> Result<bool, SubmitError> {
+        let fee_quote = match fee_quote {
+            Some(fee_quote) if self.fee_quote_lifetime.is_some() => fee_quote,
+            _ => return Ok(false),
+        };
+        if fee_quote.expires_at <= Utc::now() || provided_fee < &fee_quote.total_fee {
+            return Ok(false);
+        }
+        let signature = match hex::decode(&fee_quote.signature) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+
+        let token = self.token_info_from_id(token).await?;
+        let quote = FeeQuote {
+            id: fee_quote.id,
+            sender,
+            token_id: token.id,
+            txs,
+            total_fee: fee_quote.total_fee.clone(),
+            expires_at: fee_quote.expires_at,
+        };
+        if self.fee_quote_mac(&quote)?.verify(&signature).is_err() {
+            return Ok(false);
+        }
+        // The quote is consumed atomically, so it can't be replayed by the concurrent submissions.
+        let quoted = self
+            .pool
+            .access_storage()
+            .await?
+            .fee_quotes_schema()
+            .consume_quote(quote.id)
+            .await?;
+        if quoted {
+            metrics::increment_counter!("api.fee_quote.honored");
+        }
+        Ok(quoted)
+    }
+
+    fn fee_quote_mac(&self, quote: &FeeQuote) -> Result<Hmac<Sha256>, SubmitError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.fee_quote_secret.as_bytes())
+            .map_err(SubmitError::internal)?;
+        mac.update(quote.get_sign_message().as_bytes());
+        Ok(mac)
+    }
+
+    /// Returns the account paying the fees of the batch transactions,
+    /// or `None` if the fees are paid by several accounts.
+    async fn batch_fee_payer(
+        &self,
+        txs: &[TxWithSignature],
+    ) -> Result<Option<Address>, SubmitError> {
+        let mut fee_payer = None;
+        for tx in txs {
+            let pays_fee = tx
+                .tx
+                .get_fee_info()
+                .map_or(false, |(_, _, _, fee)| !fee.is_zero());
+            if !pays_fee {
+                continue;
+            }
+            let sender = self
+                .get_tx_sender(&tx.tx)
+                .await
+                .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+            match fee_payer {
+                Some(fee_payer) if fee_payer != sender => return Ok(None),
+                _ => fee_payer = Some(sender),
+            }
+        }
+        Ok(fee_payer)
+    }
+
     /// Resolves the token from the database.
     pub(crate) async fn token_info_from_id(
         &self,
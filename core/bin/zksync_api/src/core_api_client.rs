@@ -0,0 +1,33 @@
+//! Client for the private API of the zkSync core.
+
+use zksync_api_types::v02::transaction::SimulateTxsResponse;
+use zksync_types::ZkSyncTx;
+
+#[derive(Debug, Clone)]
+pub struct CoreApiClient {
+    client: reqwest::Client,
+    addr: String,
+}
+
+impl CoreApiClient {
+    pub fn new(addr: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            addr,
+        }
+    }
+
+    /// Executes the transactions against the state of the state keeper without applying them.
+    pub async fn simulate_txs(&self, txs: &[ZkSyncTx]) -> anyhow::Result<SimulateTxsResponse> {
+        let endpoint = format!("{}/simulate", self.addr);
+        Ok(self
+            .client
+            .post(&endpoint)
+            .json(txs)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! base formula for calculation:
 //! `( zkp cost of chunk * number of chunks + gas price of transaction) * token risk factor / cost of token is usd`
+//!
+//! When the network is congested, the fee is additionally scaled by the surge multiplier (see `surge` module).
+//!
+//! Fees paid in the tokens with the admin-defined fee token policy are reduced by the policy discount.
 
 // Built-in deps
 use std::collections::{HashMap, HashSet};
@@ -15,7 +19,7 @@ use bigdecimal::BigDecimal;
 use num::{
     rational::Ratio,
     traits::{Inv, Pow},
-    BigUint, CheckedDiv, Zero,
+    BigUint, CheckedDiv, One, Zero,
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -25,6 +29,7 @@ use tokio::time::Instant;
 // Workspace deps
 
 use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
@@ -36,17 +41,27 @@ use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
 // Local deps
 use crate::fee_ticker::constants::AMORTIZED_COST_PER_CHUNK;
+pub use crate::fee_ticker::surge::SurgeConfig;
+use crate::fee_ticker::surge::SurgeMultiplierCache;
 pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI,
+        coinmarkercap::CoinMarketCapAPI,
+        dex::{DexPoolKind, DexPriceAPI},
+        median::{MedianTokenPriceAPI, PriceSource},
+        FeeTickerAPI, TickerApi, CONNECTION_TIMEOUT,
+    },
+    validator::{
+        cache::{TokenCacheWrapper, TokenInDBCache},
+        watcher::UniswapTokenWatcher,
+        MarketUpdater,
     },
-    validator::{watcher::UniswapTokenWatcher, MarketUpdater},
 };
 
 mod constants;
+mod surge;
 mod ticker_api;
 pub(crate) mod ticker_info;
 pub mod validator;
@@ -143,6 +158,7 @@ pub struct TickerConfig {
     pub scale_fee_coefficient: Ratio<BigUint>,
     pub max_blocks_to_aggregate: u32,
     pub subsidy_cpk_price_usd: Ratio<BigUint>,
+    pub surge: SurgeConfig,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -194,6 +210,7 @@ pub struct FeeTicker {
     info: Box<dyn FeeTickerInfo>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    surge_cache: SurgeMultiplierCache,
 }
 
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
@@ -203,9 +220,12 @@ const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
 // for updating names for tokens.
 const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(5 * 60);
 
+/// Runs the token market and price updaters.
+/// The Ethereum gateway is only required if the prices are derived from the DEX pools.
 #[must_use]
 pub fn run_updaters(
     db_pool: ConnectionPool,
+    eth_gateway: Option<EthereumGateway>,
     config: &zksync_config::TickerConfig,
 ) -> Vec<JoinHandle<()>> {
     let cache = (db_pool.clone(), TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
@@ -240,11 +260,100 @@ pub fn run_updaters(
 
             ticker_api.keep_price_updated().await;
         }),
+
+        TokenPriceSource::Median => {
+            let config = config.clone();
+            tokio::spawn(async move {
+                let mut sources = Vec::with_capacity(config.price_sources.len());
+                for &source in &config.price_sources {
+                    let source =
+                        create_price_source(source, client.clone(), eth_gateway.clone(), &config)
+                            .await;
+                    sources.push(source);
+                }
+                let token_price_api = MedianTokenPriceAPI::new(
+                    db_pool.clone(),
+                    sources,
+                    config.price_max_deviation_percent,
+                    config.price_min_sources,
+                    config.price_fallback_max_age(),
+                );
+                let ticker_api = TickerApi::new(db_pool, token_price_api);
+
+                ticker_api.keep_price_updated().await;
+            })
+        }
+
+        TokenPriceSource::Dex => {
+            let config = config.clone();
+            tokio::spawn(async move {
+                let token_price_api = create_dex_price_api(eth_gateway, &config).await;
+                let ticker_api = TickerApi::new(db_pool, token_price_api);
+
+                ticker_api.keep_price_updated().await;
+            })
+        }
     };
     tasks.push(price_updater);
     tasks
 }
 
+/// Creates the API of the single price source aggregated by the `Median` source.
+async fn create_price_source(
+    source: TokenPriceSource,
+    client: reqwest::Client,
+    eth_gateway: Option<EthereumGateway>,
+    config: &zksync_config::TickerConfig,
+) -> PriceSource {
+    let base_url = config.source_url(source).unwrap_or_default();
+    match source {
+        TokenPriceSource::CoinMarketCap => PriceSource::new(
+            "coinmarketcap",
+            CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url")),
+        ),
+        TokenPriceSource::CoinGecko => PriceSource::new(
+            "coingecko",
+            CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
+                .await
+                .expect("failed to init CoinGecko client"),
+        ),
+        TokenPriceSource::Dex => {
+            PriceSource::new("dex", create_dex_price_api(eth_gateway, config).await)
+        }
+        TokenPriceSource::Median => {
+            unreachable!("Nested median price sources are rejected by the config validation")
+        }
+    }
+}
+
+async fn create_dex_price_api(
+    eth_gateway: Option<EthereumGateway>,
+    config: &zksync_config::TickerConfig,
+) -> DexPriceAPI {
+    let eth_gateway = eth_gateway.expect("Ethereum gateway is required for the DEX price source");
+    let pools = config
+        .dex_v2_pools
+        .iter()
+        .map(|&pool| (pool, DexPoolKind::UniswapV2))
+        .chain(
+            config
+                .dex_v3_pools
+                .iter()
+                .map(|&pool| (pool, DexPoolKind::UniswapV3)),
+        );
+    DexPriceAPI::new(
+        eth_gateway,
+        pools,
+        config.dex_reference_token,
+        config.dex_reference_token_decimals,
+        config.dex_wrapped_eth,
+        config.dex_twap_period,
+        config.dex_min_liquidity,
+    )
+    .await
+    .expect("failed to init DEX price API")
+}
+
 impl FeeTicker {
     pub fn new(
         info: Box<dyn FeeTickerInfo>,
@@ -255,6 +364,7 @@ impl FeeTicker {
             info,
             config,
             validator,
+            surge_cache: SurgeMultiplierCache::default(),
         }
     }
 
@@ -262,9 +372,15 @@ impl FeeTicker {
         info: Box<dyn FeeTickerInfo>,
         config: zksync_config::TickerConfig,
         max_blocks_to_aggregate: u32,
+        max_block_chunks: usize,
         connection_pool: ConnectionPool,
-    ) -> Self {
-        let cache = (connection_pool, TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
+        main_connection_pool: ConnectionPool,
+    ) -> anyhow::Result<Self> {
+        let cache = TokenCacheWrapper::DB(TokenInDBCache::new(
+            connection_pool,
+            main_connection_pool,
+            TokenDBCache::new(TOKEN_INVALIDATE_CACHE),
+        ));
         let ticker_config = TickerConfig {
             zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
             gas_cost_tx: GasOperationsCost::from_constants(config.fast_processing_coeff),
@@ -275,6 +391,7 @@ impl FeeTicker {
             ),
             max_blocks_to_aggregate,
             subsidy_cpk_price_usd: config.subsidy_cpk_price_usd(),
+            surge: SurgeConfig::new(&config, max_block_chunks)?,
         };
         let validator = FeeTokenValidator::new(
             cache,
@@ -282,7 +399,7 @@ impl FeeTicker {
             BigDecimal::try_from(config.liquidity_volume).expect("Valid f64 for decimal"),
             HashSet::from_iter(config.unconditionally_valid_tokens),
         );
-        Self::new(info, ticker_config, validator)
+        Ok(Self::new(info, ticker_config, validator))
     }
 }
 
@@ -339,9 +456,12 @@ impl FeeTicker {
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await?;
 
-        let zkp_fee = (zkp_cost_chunk * op_chunks) * &token_usd_risk;
-        let mut normal_gas_fee =
-            (&wei_price_usd * gas_tx_amount.clone() * scale_gas_price.clone()) * &token_usd_risk;
+        let token_discount = self.token_discount(&token).await?;
+
+        let zkp_fee = (zkp_cost_chunk * op_chunks) * &token_usd_risk * &token_discount;
+        let mut normal_gas_fee = (&wei_price_usd * gas_tx_amount.clone() * scale_gas_price.clone())
+            * &token_usd_risk
+            * &token_discount;
 
         // Increase fee only for L2 operations
         if matches!(
@@ -354,13 +474,14 @@ impl FeeTicker {
             normal_gas_fee *= self.config.scale_fee_coefficient.clone();
         }
 
-        let normal_fee = Fee::new(
+        let mut normal_fee = Fee::new(
             fee_type,
             zkp_fee,
             normal_gas_fee,
             gas_tx_amount,
             gas_price_wei.clone(),
         );
+        normal_fee.apply_surge(&self.surge_multiplier().await?);
 
         if fee_type == CPK_CREATE2_FEE_TYPE {
             let token_price = self
@@ -489,12 +610,16 @@ impl FeeTicker {
             }
         }
 
-        let normal_fee = {
-            let total_zkp_fee = (&zkp_cost_chunk * total_op_chunks) * &token_usd_risk;
-            let total_gas_fee =
-                (&wei_price_usd * total_normal_gas_tx_amount * &scale_gas_price) * &token_usd_risk;
+        let mut normal_fee = {
+            let token_discount = self.token_discount(&token).await?;
+            let total_zkp_fee =
+                (&zkp_cost_chunk * total_op_chunks) * &token_usd_risk * &token_discount;
+            let total_gas_fee = (&wei_price_usd * total_normal_gas_tx_amount * &scale_gas_price)
+                * &token_usd_risk
+                * &token_discount;
             BatchFee::new(total_zkp_fee, total_gas_fee)
         };
+        normal_fee.apply_surge(&self.surge_multiplier().await?);
 
         let subsidized_fee = {
             let total_zkp_fee = (zkp_cost_chunk * total_subsidized_op_chunks) * &token_usd_risk;
@@ -530,6 +655,34 @@ impl FeeTicker {
         Ok(res)
     }
 
+    /// Returns the fee multiplier reflecting the current network congestion.
+    pub async fn surge_multiplier(&self) -> anyhow::Result<Ratio<BigUint>> {
+        if !self.config.surge.is_enabled() {
+            return Ok(Ratio::one());
+        }
+        if let Some(multiplier) = self.surge_cache.get(self.config.surge.cache_ttl).await {
+            return Ok(multiplier);
+        }
+        let start = Instant::now();
+        let congestion_info = self
+            .info
+            .congestion_info(self.config.surge.blocks_window)
+            .await?;
+        let multiplier = self.config.surge.multiplier(&congestion_info);
+        self.surge_cache.set(multiplier.clone()).await;
+        metrics::histogram!("ticker.surge_multiplier", start.elapsed());
+        Ok(multiplier)
+    }
+
+    /// Returns the fee multiplier reflecting the discount for the fees paid in the token.
+    async fn token_discount(&self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
+        let discount_percent = self.validator.fee_discount_percent(token.id).await?;
+        Ok(Ratio::new(
+            BigUint::from(100u32 - u32::from(discount_percent)),
+            BigUint::from(100u32),
+        ))
+    }
+
     pub async fn token_usd_risk(&self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
         let token_risk_factor = self
@@ -641,6 +794,11 @@ impl FeeTicker {
     pub async fn token_allowed_for_fees(&self, token: TokenLike) -> anyhow::Result<bool> {
         self.validator.token_allowed(token).await
     }
+
+    /// Makes the fee token policy changes take effect immediately.
+    pub async fn invalidate_fee_token_policies(&self) {
+        self.validator.invalidate_fee_token_policies().await
+    }
 }
 
 fn calculate_cost(base_cost: usize, max_blocks: u32, future_blocks: u32) -> usize {
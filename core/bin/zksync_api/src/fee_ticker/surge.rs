@@ -0,0 +1,220 @@
+//! Surge pricing of the fees under the network congestion.
+//!
+//! Congestion is estimated as the largest of the mempool fill level and the average fullness
+//! of the recent blocks, where the block fullness is the number of chunks used by its operations
+//! relative to the largest available block size. The congestion level is mapped to the fee multiplier with a
+//! piecewise-linear curve, and the multiplier is capped by the configured maximum.
+//! The multiplier is cached for a short time, so the congestion isn't estimated on every fee request.
+
+// Built-in deps
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External deps
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+// Workspace deps
+// Local deps
+use crate::fee_ticker::ticker_info::CongestionInfo;
+
+const FULL_CONGESTION_PERCENT: u64 = 100;
+const NO_SURGE_PERCENT: u32 = 100;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SurgeConfig {
+    /// Curve points as `(congestion percent, multiplier percent)` pairs sorted by congestion.
+    pub curve: Vec<(u32, u32)>,
+    pub max_multiplier_percent: u32,
+    /// Number of transactions in the mempool corresponding to the full congestion.
+    pub mempool_capacity: u32,
+    /// Number of the recent blocks used to estimate the block fullness.
+    pub blocks_window: u32,
+    /// Largest available block size in chunks.
+    pub max_block_chunks: usize,
+    /// Time the multiplier is cached for, zero disables the caching.
+    pub cache_ttl: Duration,
+}
+
+impl SurgeConfig {
+    pub fn new(
+        config: &zksync_config::TickerConfig,
+        max_block_chunks: usize,
+    ) -> anyhow::Result<Self> {
+        let mut curve = config.surge_curve().map_err(anyhow::Error::msg)?;
+        curve.sort_unstable();
+        Ok(Self {
+            curve,
+            max_multiplier_percent: config.surge_max_multiplier_percent,
+            mempool_capacity: config.surge_mempool_capacity,
+            blocks_window: config.surge_blocks_window,
+            max_block_chunks,
+            cache_ttl: config.surge_cache_duration(),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_multiplier_percent > NO_SURGE_PERCENT && !self.curve.is_empty()
+    }
+
+    /// Returns the congestion level in percent, from 0 to 100.
+    pub fn congestion_percent(&self, info: &CongestionInfo) -> u32 {
+        let mempool_percent = if self.mempool_capacity == 0 {
+            0
+        } else {
+            u64::from(info.mempool_size) * FULL_CONGESTION_PERCENT
+                / u64::from(self.mempool_capacity)
+        };
+
+        let blocks_percent =
+            if self.max_block_chunks == 0 || info.recent_blocks_chunks_used.is_empty() {
+                0
+            } else {
+                let total_chunks: u64 = info
+                    .recent_blocks_chunks_used
+                    .iter()
+                    .map(|&size| size as u64)
+                    .sum();
+                total_chunks * FULL_CONGESTION_PERCENT
+                    / (self.max_block_chunks as u64 * info.recent_blocks_chunks_used.len() as u64)
+            };
+
+        mempool_percent
+            .max(blocks_percent)
+            .min(FULL_CONGESTION_PERCENT) as u32
+    }
+
+    /// Returns the fee multiplier in percent for the given congestion level.
+    /// The multiplier never goes below 100 percent or above the configured cap.
+    pub fn multiplier_percent(&self, congestion_percent: u32) -> u32 {
+        if !self.is_enabled() {
+            return NO_SURGE_PERCENT;
+        }
+
+        let (first, last) = (self.curve[0], self.curve[self.curve.len() - 1]);
+        let multiplier = if congestion_percent <= first.0 {
+            first.1
+        } else if congestion_percent >= last.0 {
+            last.1
+        } else {
+            // There is always a segment containing the congestion level here.
+            let (start, end) = self
+                .curve
+                .windows(2)
+                .map(|segment| (segment[0], segment[1]))
+                .find(|(start, end)| start.0 <= congestion_percent && congestion_percent <= end.0)
+                .unwrap();
+            interpolate(start, end, congestion_percent)
+        };
+
+        multiplier.clamp(NO_SURGE_PERCENT, self.max_multiplier_percent)
+    }
+
+    /// Returns the fee multiplier for the given congestion info.
+    pub fn multiplier(&self, info: &CongestionInfo) -> Ratio<BigUint> {
+        let percent = self.multiplier_percent(self.congestion_percent(info));
+        Ratio::new(percent.into(), NO_SURGE_PERCENT.into())
+    }
+}
+
+/// Recently computed surge multiplier, shared between the ticker clones.
+#[derive(Debug, Clone, Default)]
+pub struct SurgeMultiplierCache(Arc<RwLock<Option<(Ratio<BigUint>, Instant)>>>);
+
+impl SurgeMultiplierCache {
+    /// Returns the cached multiplier if it was computed less than `ttl` ago.
+    pub async fn get(&self, ttl: Duration) -> Option<Ratio<BigUint>> {
+        match &*self.0.read().await {
+            Some((multiplier, computed_at)) if computed_at.elapsed() < ttl => {
+                Some(multiplier.clone())
+            }
+            _ => None,
+        }
+    }
+
+    pub async fn set(&self, multiplier: Ratio<BigUint>) {
+        *self.0.write().await = Some((multiplier, Instant::now()));
+    }
+}
+
+fn interpolate(start: (u32, u32), end: (u32, u32), x: u32) -> u32 {
+    if start.0 == end.0 {
+        return end.1;
+    }
+    let (x0, y0) = (i64::from(start.0), i64::from(start.1));
+    let (x1, y1) = (i64::from(end.0), i64::from(end.1));
+    (y0 + (y1 - y0) * (i64::from(x) - x0) / (x1 - x0)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surge_config() -> SurgeConfig {
+        SurgeConfig {
+            curve: vec![(50, 100), (80, 150), (100, 300)],
+            max_multiplier_percent: 250,
+            mempool_capacity: 1000,
+            blocks_window: 10,
+            max_block_chunks: 100,
+            cache_ttl: Duration::from_secs(0),
+        }
+    }
+
+    #[test]
+    fn test_congestion_percent() {
+        let config = surge_config();
+
+        let info = CongestionInfo::default();
+        assert_eq!(config.congestion_percent(&info), 0);
+
+        let info = CongestionInfo {
+            mempool_size: 600,
+            recent_blocks_chunks_used: vec![10, 30],
+        };
+        assert_eq!(config.congestion_percent(&info), 60);
+
+        let info = CongestionInfo {
+            mempool_size: 100,
+            recent_blocks_chunks_used: vec![100, 60],
+        };
+        assert_eq!(config.congestion_percent(&info), 80);
+
+        let info = CongestionInfo {
+            mempool_size: 5000,
+            recent_blocks_chunks_used: vec![],
+        };
+        assert_eq!(config.congestion_percent(&info), 100);
+    }
+
+    #[test]
+    fn test_multiplier_percent() {
+        let config = surge_config();
+
+        assert_eq!(config.multiplier_percent(0), 100);
+        assert_eq!(config.multiplier_percent(50), 100);
+        assert_eq!(config.multiplier_percent(65), 125);
+        assert_eq!(config.multiplier_percent(80), 150);
+        assert_eq!(config.multiplier_percent(90), 225);
+        // Capped by the maximum multiplier.
+        assert_eq!(config.multiplier_percent(100), 250);
+
+        let disabled = SurgeConfig {
+            max_multiplier_percent: 100,
+            ..surge_config()
+        };
+        assert_eq!(disabled.multiplier_percent(100), 100);
+        assert_eq!(SurgeConfig::default().multiplier_percent(100), 100);
+    }
+
+    #[tokio::test]
+    async fn test_multiplier_cache() {
+        let cache = SurgeMultiplierCache::default();
+        assert_eq!(cache.get(Duration::from_secs(60)).await, None);
+
+        let multiplier = Ratio::new(BigUint::from(3u32), BigUint::from(2u32));
+        cache.set(multiplier.clone()).await;
+        assert_eq!(cache.get(Duration::from_secs(60)).await, Some(multiplier));
+        // The cached multiplier is outdated.
+        assert_eq!(cache.get(Duration::from_secs(0)).await, None);
+    }
+}
@@ -5,7 +5,10 @@ use bigdecimal::BigDecimal;
 use chrono::Utc;
 use futures::executor::block_on;
 use std::str::FromStr;
-use zksync_types::{Address, Token, TokenId, TokenKind, TokenPrice};
+use zksync_types::{
+    tokens::{FeeTokenPolicy, MAX_FEE_DISCOUNT_PERCENT},
+    Address, Token, TokenId, TokenKind, TokenPrice,
+};
 use zksync_utils::{
     ratio_to_big_decimal, ratio_to_scaled_u64, scaled_u64_to_ratio, UnsignedRatioSerializeAsDecimal,
 };
@@ -16,7 +19,7 @@ use crate::fee_ticker::{
 };
 
 use super::*;
-use crate::fee_ticker::ticker_info::BlocksInFutureAggregatedOperations;
+use crate::fee_ticker::ticker_info::{BlocksInFutureAggregatedOperations, CongestionInfo};
 
 const TEST_FAST_WITHDRAW_COEFF: f64 = 10.0;
 
@@ -114,6 +117,7 @@ pub fn get_test_ticker_config() -> TickerConfig {
         scale_fee_coefficient: Ratio::new(BigUint::from(150u32), BigUint::from(100u32)),
         max_blocks_to_aggregate: 5,
         subsidy_cpk_price_usd: scaled_u64_to_ratio(SUBSIDY_CPK_PRICE_USD_SCALED),
+        surge: SurgeConfig::default(),
     }
 }
 
@@ -130,6 +134,7 @@ impl FeeTickerAPI for MockApiProvider {
 struct MockTickerInfo {
     pub future_blocks: BlocksInFutureAggregatedOperations,
     pub remaining_chunks: Option<usize>,
+    pub congestion: CongestionInfo,
 }
 
 impl Default for MockTickerInfo {
@@ -141,6 +146,7 @@ impl Default for MockTickerInfo {
                 blocks_to_execute: 0,
             },
             remaining_chunks: None,
+            congestion: CongestionInfo::default(),
         }
     }
 }
@@ -162,6 +168,10 @@ impl FeeTickerInfo for MockTickerInfo {
         Ok(self.remaining_chunks)
     }
 
+    async fn congestion_info(&self, _blocks_window: u32) -> anyhow::Result<CongestionInfo> {
+        Ok(self.congestion.clone())
+    }
+
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         for test_token in TestToken::all_tokens() {
             if TokenLike::Id(test_token.id) == token {
@@ -708,3 +718,124 @@ fn test_zero_price_token_fee() {
     ))
     .unwrap_err();
 }
+
+#[test]
+fn test_surge_fee() {
+    let validator = FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let mut config = get_test_ticker_config();
+    config.surge = SurgeConfig {
+        curve: vec![(0, 100), (100, 300)],
+        max_multiplier_percent: 200,
+        mempool_capacity: 1000,
+        blocks_window: 10,
+        max_block_chunks: 100,
+        cache_ttl: Duration::from_secs(0),
+    };
+    let mut info = MockTickerInfo::default();
+    let mut ticker = FeeTicker::new(Box::new(info.clone()), config, validator);
+    let token = TestToken::eth();
+
+    let get_fees = |ticker: &FeeTicker| {
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Transfer,
+            token.id.into(),
+            Address::default(),
+        ))
+        .unwrap()
+        .normal_fee;
+        let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+            token.id.into(),
+            vec![(TxFeeTypes::Transfer, Address::default())],
+        ))
+        .unwrap()
+        .normal_fee;
+        assert_eq!(fee.surge_fee, batch_fee.surge_fee);
+        assert_eq!(fee.total_fee, batch_fee.total_fee);
+        fee
+    };
+
+    // No congestion, no surge.
+    let base_fee = get_fees(&ticker);
+    assert!(base_fee.surge_fee.is_zero());
+
+    // Half of the mempool is filled: the fee is doubled.
+    info.congestion.mempool_size = 500;
+    ticker.info = Box::new(info.clone());
+    let fee = get_fees(&ticker);
+    assert_eq!(fee.surge_fee, &base_fee.zkp_fee + &base_fee.gas_fee);
+
+    // Multiplier is capped.
+    info.congestion.recent_blocks_chunks_used = vec![100, 100];
+    ticker.info = Box::new(info);
+    let capped_fee = get_fees(&ticker);
+    assert_eq!(capped_fee.surge_fee, fee.surge_fee);
+}
+
+#[test]
+fn test_fee_token_discount() {
+    let cache = TokenInMemoryCache::new();
+    let validator = FeeTokenValidator::new(
+        cache.clone(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+    );
+
+    let config = get_test_ticker_config();
+    let ticker = FeeTicker::new(Box::new(MockTickerInfo::default()), config, validator);
+    let token = TestToken::eth();
+
+    let get_fees = |ticker: &FeeTicker| {
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Transfer,
+            token.id.into(),
+            Address::default(),
+        ))
+        .unwrap()
+        .normal_fee;
+        let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+            token.id.into(),
+            vec![(TxFeeTypes::Transfer, Address::default())],
+        ))
+        .unwrap()
+        .normal_fee;
+        assert_eq!(fee.total_fee, batch_fee.total_fee);
+        fee
+    };
+
+    let base_fee = get_fees(&ticker);
+
+    // Fees in the token are discounted by half.
+    block_on(cache.set_fee_token_policy(FeeTokenPolicy {
+        token_id: token.id,
+        listing: None,
+        discount_percent: 50,
+        updated_at: Utc::now(),
+    }));
+    let fee = get_fees(&ticker);
+    assert!(fee.zkp_fee < base_fee.zkp_fee);
+    assert!(fee.total_fee < base_fee.total_fee);
+
+    // The discount is capped, so the fee is never waived completely.
+    block_on(cache.set_fee_token_policy(FeeTokenPolicy {
+        token_id: token.id,
+        listing: None,
+        discount_percent: MAX_FEE_DISCOUNT_PERCENT,
+        updated_at: Utc::now(),
+    }));
+    let max_discount_fee = get_fees(&ticker);
+    assert!(!max_discount_fee.total_fee.is_zero());
+    block_on(cache.set_fee_token_policy(FeeTokenPolicy {
+        token_id: token.id,
+        listing: None,
+        discount_percent: 100,
+        updated_at: Utc::now(),
+    }));
+    assert_eq!(get_fees(&ticker).total_fee, max_discount_fee.total_fee);
+}
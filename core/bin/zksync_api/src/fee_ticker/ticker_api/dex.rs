@@ -0,0 +1,728 @@
+//! Token price API deriving the prices from the on-chain Uniswap-like pools.
+//!
+//! Tokens are priced in the reference stablecoin, either through the pool pairing the token
+//! with the stablecoin directly, or through the pool pairing it with an intermediate token
+//! which is itself paired with the stablecoin. Both kinds of pools are priced by the
+//! time-weighted average price over `twap_period`, so the price can't be moved within a single
+//! block: Uniswap-v2-style pairs by the cumulative prices observed by the API itself,
+//! Uniswap-v3-style pools by the average tick of the pool oracle. Pools without enough
+//! liquidity are not used.
+
+// Built-in deps
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+// External deps
+use async_trait::async_trait;
+use chrono::Utc;
+use ethabi::{Contract, Token as AbiToken};
+use num::{rational::Ratio, BigInt, BigUint, Integer, One, ToPrimitive};
+use web3::contract::Options;
+// Workspace deps
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, Token, TokenPrice, U256};
+// Local deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+
+const UNISWAP_V2_PAIR_ABI: &str = r#"[
+    {"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"getReserves","outputs":[{"name":"reserve0","type":"uint112"},{"name":"reserve1","type":"uint112"},{"name":"blockTimestampLast","type":"uint32"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"price0CumulativeLast","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"price1CumulativeLast","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"}
+]"#;
+
+const UNISWAP_V3_POOL_ABI: &str = r#"[
+    {"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"secondsAgos","type":"uint32[]"}],"name":"observe","outputs":[{"name":"tickCumulatives","type":"int56[]"},{"name":"secondsPerLiquidityCumulativeX128s","type":"uint160[]"}],"stateMutability":"view","type":"function"}
+]"#;
+
+const ERC20_BALANCE_ABI: &str = r#"[
+    {"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"}
+]"#;
+
+/// Cumulative prices of the Uniswap-v2-style pairs are `UQ112x112` fixed point numbers.
+const UQ112_RESOLUTION: usize = 112;
+/// Precision of the fixed point numbers used to raise the tick base to the power.
+const TICK_PRICE_RESOLUTION: usize = 128;
+/// Maximum tick of the Uniswap-v3-style pools.
+const MAX_TICK: i64 = 887_272;
+
+fn uniswap_v2_pair_contract() -> Contract {
+    Contract::load(UNISWAP_V2_PAIR_ABI.as_bytes()).expect("Uniswap v2 pair abi")
+}
+
+fn uniswap_v3_pool_contract() -> Contract {
+    Contract::load(UNISWAP_V3_POOL_ABI.as_bytes()).expect("Uniswap v3 pool abi")
+}
+
+fn erc20_balance_contract() -> Contract {
+    Contract::load(ERC20_BALANCE_ABI.as_bytes()).expect("ERC20 balance abi")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DexPoolKind {
+    UniswapV2,
+    UniswapV3,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DexPool {
+    pub address: Address,
+    pub kind: DexPoolKind,
+    pub token0: Address,
+    pub token1: Address,
+}
+
+impl DexPool {
+    fn contains(&self, token: Address) -> bool {
+        self.token0 == token || self.token1 == token
+    }
+
+    fn other(&self, token: Address) -> Address {
+        if self.token0 == token {
+            self.token1
+        } else {
+            self.token0
+        }
+    }
+}
+
+/// Cumulative prices of the Uniswap-v2-style pair at the given moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CumulativePrices {
+    price0: U256,
+    price1: U256,
+    /// Timestamp modulo 2^32, as the pair contract stores it.
+    timestamp: u32,
+}
+
+/// Price of the base pool token along with the pool liquidity.
+#[derive(Debug, Clone)]
+struct PoolPrice {
+    /// Amount of the quote token (in its smallest units) paid for the smallest unit of the base token.
+    price: Ratio<BigUint>,
+    /// Reserve of the quote token in the pool.
+    quote_reserve: BigUint,
+}
+
+#[derive(Debug, Clone)]
+pub struct DexPriceAPI {
+    eth_client: EthereumGateway,
+    pools: Vec<DexPool>,
+    reference_token: Address,
+    reference_token_decimals: u8,
+    wrapped_eth: Address,
+    twap_period: u32,
+    /// Minimum liquidity of the used pools, in the smallest units of the reference token.
+    min_liquidity: BigUint,
+    /// Cumulative prices observed for the Uniswap-v2-style pairs, the oldest first.
+    observations: Arc<Mutex<HashMap<Address, VecDeque<CumulativePrices>>>>,
+}
+
+impl DexPriceAPI {
+    /// Creates the API, loading the tokens of the given pools from the chain.
+    pub async fn new(
+        eth_client: EthereumGateway,
+        pools: impl IntoIterator<Item = (Address, DexPoolKind)>,
+        reference_token: Address,
+        reference_token_decimals: u8,
+        wrapped_eth: Address,
+        twap_period: u32,
+        min_liquidity: u64,
+    ) -> anyhow::Result<Self> {
+        let mut dex_pools = Vec::new();
+        for (address, kind) in pools {
+            let token0 = call_pool(&eth_client, address, kind, "token0", ()).await?;
+            let token1 = call_pool(&eth_client, address, kind, "token1", ()).await?;
+            dex_pools.push(DexPool {
+                address,
+                kind,
+                token0,
+                token1,
+            });
+        }
+
+        Ok(Self {
+            eth_client,
+            pools: dex_pools,
+            reference_token,
+            reference_token_decimals,
+            wrapped_eth,
+            twap_period,
+            min_liquidity: BigUint::from(min_liquidity)
+                * BigUint::from(10u32).pow(u32::from(reference_token_decimals)),
+            observations: Default::default(),
+        })
+    }
+
+    /// Finds the pools to convert the token into the reference one.
+    fn find_route(&self, token: Address) -> Option<Vec<&DexPool>> {
+        let direct = self
+            .pools
+            .iter()
+            .find(|pool| pool.contains(token) && pool.contains(self.reference_token));
+        if let Some(pool) = direct {
+            return Some(vec![pool]);
+        }
+
+        self.pools
+            .iter()
+            .filter(|pool| pool.contains(token))
+            .find_map(|first| {
+                let intermediate = first.other(token);
+                self.pools
+                    .iter()
+                    .find(|pool| pool.contains(intermediate) && pool.contains(self.reference_token))
+                    .map(|second| vec![first, second])
+            })
+    }
+
+    /// Returns the time-weighted average price of the `base` pool token as of `now`
+    /// (unix timestamp modulo 2^32).
+    async fn pool_price(
+        &self,
+        pool: &DexPool,
+        base: Address,
+        now: u32,
+    ) -> Result<PoolPrice, PriceError> {
+        let quote = pool.other(base);
+        match pool.kind {
+            DexPoolKind::UniswapV2 => {
+                let (reserve0, reserve1, timestamp): (U256, U256, U256) =
+                    call_pool(&self.eth_client, pool.address, pool.kind, "getReserves", ())
+                        .await
+                        .map_err(PriceError::api_error)?;
+                if reserve0.is_zero() || reserve1.is_zero() {
+                    return Err(PriceError::api_error(format!(
+                        "Pool {:?} has no liquidity",
+                        pool.address
+                    )));
+                }
+                let price0: U256 = call_pool(
+                    &self.eth_client,
+                    pool.address,
+                    pool.kind,
+                    "price0CumulativeLast",
+                    (),
+                )
+                .await
+                .map_err(PriceError::api_error)?;
+                let price1: U256 = call_pool(
+                    &self.eth_client,
+                    pool.address,
+                    pool.kind,
+                    "price1CumulativeLast",
+                    (),
+                )
+                .await
+                .map_err(PriceError::api_error)?;
+
+                let last = CumulativePrices {
+                    price0,
+                    price1,
+                    timestamp: timestamp.low_u32(),
+                };
+                let current = current_cumulative_prices(last, reserve0, reserve1, now);
+                let (price0, price1) = self.v2_average_prices(pool.address, current)?;
+                let (price, quote_reserve) = if base == pool.token0 {
+                    (price0, reserve1)
+                } else {
+                    (price1, reserve0)
+                };
+                Ok(PoolPrice {
+                    price,
+                    quote_reserve: u256_to_biguint(quote_reserve),
+                })
+            }
+            DexPoolKind::UniswapV3 => {
+                let seconds_agos = vec![U256::from(self.twap_period), U256::zero()];
+                let (tick_cumulatives, _): (AbiToken, AbiToken) = call_pool(
+                    &self.eth_client,
+                    pool.address,
+                    pool.kind,
+                    "observe",
+                    seconds_agos,
+                )
+                .await
+                .map_err(PriceError::api_error)?;
+                let price0 = tick_price(average_tick(tick_cumulatives, self.twap_period)?);
+                let price = if base == pool.token0 {
+                    price0
+                } else {
+                    price0.recip()
+                };
+
+                let quote_reserve: U256 = self
+                    .eth_client
+                    .call_contract_function(
+                        "balanceOf",
+                        pool.address,
+                        None,
+                        Options::default(),
+                        None,
+                        quote,
+                        erc20_balance_contract(),
+                    )
+                    .await
+                    .map_err(PriceError::api_error)?;
+                Ok(PoolPrice {
+                    price,
+                    quote_reserve: u256_to_biguint(quote_reserve),
+                })
+            }
+        }
+    }
+
+    /// Records the cumulative prices of the Uniswap-v2-style pair and returns the average
+    /// prices of its tokens over at least `twap_period`.
+    fn v2_average_prices(
+        &self,
+        pool: Address,
+        current: CumulativePrices,
+    ) -> Result<(Ratio<BigUint>, Ratio<BigUint>), PriceError> {
+        let mut observations = self.observations.lock().unwrap();
+        let window = observations.entry(pool).or_default();
+        if window
+            .back()
+            .map_or(true, |last| last.timestamp != current.timestamp)
+        {
+            window.push_back(current);
+        }
+        // The window starts with the latest observation made at least `twap_period` ago.
+        while window.len() > 1
+            && current.timestamp.wrapping_sub(window[1].timestamp) >= self.twap_period
+        {
+            window.pop_front();
+        }
+
+        let start = window[0];
+        let elapsed = current.timestamp.wrapping_sub(start.timestamp);
+        if elapsed < self.twap_period {
+            return Err(PriceError::api_error(format!(
+                "Pool {:?} has not been observed long enough to get the average price",
+                pool
+            )));
+        }
+        let denom = BigUint::from(elapsed) << UQ112_RESOLUTION;
+        let average = |current: U256, start: U256| {
+            // Cumulative prices are expected to overflow.
+            let delta = current.overflowing_sub(start).0;
+            Ratio::new(u256_to_biguint(delta), denom.clone())
+        };
+        Ok((
+            average(current.price0, start.price0),
+            average(current.price1, start.price1),
+        ))
+    }
+
+    /// Returns the price of the token as of `now` (unix timestamp modulo 2^32).
+    async fn get_price_at(&self, token: &Token, now: u32) -> Result<TokenPrice, PriceError> {
+        let address = if token.address == Address::zero() {
+            self.wrapped_eth
+        } else {
+            token.address
+        };
+
+        let mut raw_price = Ratio::one();
+        if address != self.reference_token {
+            let route = self.find_route(address).ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Token '{}, {:?}' has no configured DEX pools",
+                    token.symbol, token.address
+                ))
+            })?;
+            let mut pool_prices = Vec::with_capacity(route.len());
+            let mut base = address;
+            for pool in &route {
+                pool_prices.push(self.pool_price(pool, base, now).await?);
+                base = pool.other(base);
+            }
+
+            // Going back from the reference token, `raw_price` is the price of the quote token
+            // of the pool, so the pool liquidity can be valued in the reference token.
+            for (pool, pool_price) in route.iter().zip(pool_prices).rev() {
+                let liquidity = &raw_price * Ratio::from_integer(pool_price.quote_reserve);
+                if liquidity < Ratio::from_integer(self.min_liquidity.clone()) {
+                    return Err(PriceError::api_error(format!(
+                        "Pool {:?} doesn't have enough liquidity to price token {}",
+                        pool.address, token.symbol
+                    )));
+                }
+                raw_price *= pool_price.price;
+            }
+        }
+
+        // Prices in pools are in the smallest units of the tokens.
+        let usd_price = raw_price
+            * Ratio::new(
+                BigUint::from(10u32).pow(u32::from(token.decimals)),
+                BigUint::from(10u32).pow(u32::from(self.reference_token_decimals)),
+            );
+
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for DexPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+        // Pair contracts store the timestamps modulo 2^32.
+        let now = Utc::now().timestamp() as u32;
+        let price = self.get_price_at(token, now).await?;
+
+        metrics::histogram!("ticker.dex.request", start.elapsed());
+        Ok(price)
+    }
+}
+
+async fn call_pool<R, P>(
+    eth_client: &EthereumGateway,
+    pool: Address,
+    kind: DexPoolKind,
+    func: &str,
+    params: P,
+) -> anyhow::Result<R>
+where
+    R: web3::contract::tokens::Detokenize + Unpin,
+    P: web3::contract::tokens::Tokenize + Clone,
+{
+    let contract = match kind {
+        DexPoolKind::UniswapV2 => uniswap_v2_pair_contract(),
+        DexPoolKind::UniswapV3 => uniswap_v3_pool_contract(),
+    };
+    eth_client
+        .call_contract_function(func, params, None, Options::default(), None, pool, contract)
+        .await
+}
+
+/// Extends the cumulative prices stored in the pair up to `now`, the same way the pair does
+/// on the first trade in a block.
+fn current_cumulative_prices(
+    last: CumulativePrices,
+    reserve0: U256,
+    reserve1: U256,
+    now: u32,
+) -> CumulativePrices {
+    let elapsed = now.wrapping_sub(last.timestamp);
+    // The pair was updated "after" `now` if the local clock is behind the chain.
+    if elapsed == 0 || elapsed > u32::MAX / 2 {
+        return last;
+    }
+    let elapsed = U256::from(elapsed);
+    let spot_price0 = (reserve1 << UQ112_RESOLUTION) / reserve0;
+    let spot_price1 = (reserve0 << UQ112_RESOLUTION) / reserve1;
+    CumulativePrices {
+        price0: last
+            .price0
+            .overflowing_add(spot_price0.overflowing_mul(elapsed).0)
+            .0,
+        price1: last
+            .price1
+            .overflowing_add(spot_price1.overflowing_mul(elapsed).0)
+            .0,
+        timestamp: now,
+    }
+}
+
+/// Computes the average tick from the `observe` tick cumulatives
+/// for the `[twap_period, 0]` seconds ago, rounded towards negative infinity
+/// as the Uniswap oracle library does.
+fn average_tick(tick_cumulatives: AbiToken, twap_period: u32) -> Result<i64, PriceError> {
+    let invalid = || PriceError::api_error("Invalid `observe` response");
+    let ticks = tick_cumulatives.into_array().ok_or_else(invalid)?;
+    let (past, now) = match ticks.as_slice() {
+        [past, now] => (
+            int_to_bigint(past.clone()).ok_or_else(invalid)?,
+            int_to_bigint(now.clone()).ok_or_else(invalid)?,
+        ),
+        _ => return Err(invalid()),
+    };
+    let tick = (now - past)
+        .div_floor(&BigInt::from(twap_period))
+        .to_i64()
+        .ok_or_else(invalid)?;
+    if tick.abs() > MAX_TICK {
+        return Err(invalid());
+    }
+    Ok(tick)
+}
+
+/// Returns `1.0001^tick`, the price of the pool token0 in the smallest units of token1.
+fn tick_price(tick: i64) -> Ratio<BigUint> {
+    let one = BigUint::one() << TICK_PRICE_RESOLUTION;
+    let mut base = &one * BigUint::from(10001u32) / BigUint::from(10000u32);
+    let mut power = one.clone();
+    let mut exponent = tick.unsigned_abs();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            power = (&power * &base) >> TICK_PRICE_RESOLUTION;
+        }
+        base = (&base * &base) >> TICK_PRICE_RESOLUTION;
+        exponent >>= 1;
+    }
+
+    if tick < 0 {
+        Ratio::new(one, power)
+    } else {
+        Ratio::new(power, one)
+    }
+}
+
+/// Decodes two's complement encoded signed integer.
+fn int_to_bigint(token: AbiToken) -> Option<BigInt> {
+    let value = token.into_int()?;
+    if value.bit(255) {
+        let magnitude = (!value).overflowing_add(U256::one()).0;
+        Some(-BigInt::from(u256_to_biguint(magnitude)))
+    } else {
+        Some(BigInt::from(u256_to_biguint(value)))
+    }
+}
+
+fn u256_to_biguint(value: U256) -> BigUint {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    BigUint::from_bytes_be(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_eth_client::clients::mock::MockEthereum;
+    use zksync_types::{TokenId, TokenKind};
+
+    use super::*;
+
+    const USDC_DECIMALS: u8 = 6;
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn int(value: i64) -> AbiToken {
+        let magnitude = U256::from(value.unsigned_abs());
+        if value < 0 {
+            AbiToken::Int((!magnitude).overflowing_add(U256::one()).0)
+        } else {
+            AbiToken::Int(magnitude)
+        }
+    }
+
+    fn uint(value: impl Into<U256>) -> AbiToken {
+        AbiToken::Uint(value.into())
+    }
+
+    /// Encodes `numer / denom` as `UQ112x112`.
+    fn uq112(numer: U256, denom: U256) -> U256 {
+        (numer << UQ112_RESOLUTION) / denom
+    }
+
+    fn token(address: Address, decimals: u8) -> Token {
+        Token::new(TokenId(1), address, "TKN", decimals, TokenKind::ERC20)
+    }
+
+    fn usd(price: &TokenPrice) -> f64 {
+        ratio_to_f64(&price.usd_price)
+    }
+
+    fn ratio_to_f64(ratio: &Ratio<BigUint>) -> f64 {
+        ratio.numer().to_f64().unwrap() / ratio.denom().to_f64().unwrap()
+    }
+
+    async fn set_pool(mock: &MockEthereum, pool: Address, token0: Address, token1: Address) {
+        mock.set_contract_function_call_result(pool, "token0", vec![AbiToken::Address(token0)])
+            .await;
+        mock.set_contract_function_call_result(pool, "token1", vec![AbiToken::Address(token1)])
+            .await;
+    }
+
+    async fn set_v2_state(
+        mock: &MockEthereum,
+        pool: Address,
+        reserves: (U256, U256),
+        cumulative_prices: (U256, U256),
+        timestamp: u32,
+    ) {
+        mock.set_contract_function_call_result(
+            pool,
+            "getReserves",
+            vec![uint(reserves.0), uint(reserves.1), uint(timestamp)],
+        )
+        .await;
+        mock.set_contract_function_call_result(
+            pool,
+            "price0CumulativeLast",
+            vec![uint(cumulative_prices.0)],
+        )
+        .await;
+        mock.set_contract_function_call_result(
+            pool,
+            "price1CumulativeLast",
+            vec![uint(cumulative_prices.1)],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_dex_price() {
+        let usdc = address(0xaa);
+        let weth = address(0xee);
+        let long_tail = address(0x11);
+        let unlisted = address(0x22);
+        let (weth_usdc_pool, long_tail_weth_pool) = (address(0x01), address(0x02));
+
+        let mock = MockEthereum::default();
+        // Uniswap v2: 1 WETH (18 decimals) = 2000 USDC (6 decimals).
+        set_pool(&mock, weth_usdc_pool, usdc, weth).await;
+        let (usdc_reserve, weth_reserve) = (U256::from(2_000_000_000_000u64), U256::exp10(21));
+        set_v2_state(
+            &mock,
+            weth_usdc_pool,
+            (usdc_reserve, weth_reserve),
+            (U256::zero(), U256::zero()),
+            1000,
+        )
+        .await;
+        // Uniswap v3: the long tail token (18 decimals) is token0, WETH is token1,
+        // average tick is -46055, i.e. 1 token ~ 0.01 WETH.
+        set_pool(&mock, long_tail_weth_pool, long_tail, weth).await;
+        mock.set_contract_function_call_result(
+            long_tail_weth_pool,
+            "observe",
+            vec![
+                AbiToken::Array(vec![int(1_000_000), int(1_000_000 - 46055 * 1800)]),
+                AbiToken::Array(vec![uint(0u64), uint(0u64)]),
+            ],
+        )
+        .await;
+        // 1000 WETH in the pool.
+        mock.set_contract_function_call_result(weth, "balanceOf", vec![uint(U256::exp10(21))])
+            .await;
+
+        let api = DexPriceAPI::new(
+            EthereumGateway::Mock(mock.clone()),
+            vec![
+                (weth_usdc_pool, DexPoolKind::UniswapV2),
+                (long_tail_weth_pool, DexPoolKind::UniswapV3),
+            ],
+            usdc,
+            USDC_DECIMALS,
+            weth,
+            1800,
+            1_000_000,
+        )
+        .await
+        .unwrap();
+
+        // The pair wasn't observed for the TWAP period yet.
+        assert!(api.get_price_at(&token(weth, 18), 1000).await.is_err());
+
+        // The price doubles 100 seconds before the second observation.
+        let (price0, price1) = (
+            uq112(weth_reserve, usdc_reserve),
+            uq112(usdc_reserve, weth_reserve),
+        );
+        set_v2_state(
+            &mock,
+            weth_usdc_pool,
+            (usdc_reserve * 2, weth_reserve),
+            (price0 * 1700, price1 * 1700),
+            2700,
+        )
+        .await;
+
+        // ETH is priced through WETH, by the average price rather than the spot one.
+        let eth_price = api
+            .get_price_at(&token(Address::zero(), 18), 2800)
+            .await
+            .unwrap();
+        let expected_eth_price = (2000.0 * 1700.0 + 4000.0 * 100.0) / 1800.0;
+        assert!((usd(&eth_price) - expected_eth_price).abs() < 1e-6);
+
+        // The reference token is worth 1 USD.
+        let usdc_price = api
+            .get_price_at(&token(usdc, USDC_DECIMALS), 2800)
+            .await
+            .unwrap();
+        assert_eq!(usdc_price.usd_price, Ratio::one());
+
+        // Long tail token is priced through WETH.
+        let long_tail_price = api.get_price_at(&token(long_tail, 18), 2800).await.unwrap();
+        assert!((usd(&long_tail_price) - expected_eth_price / 100.0).abs() < 0.01);
+
+        // Tokens without pools are reported as not found.
+        assert!(matches!(
+            api.get_price_at(&token(unlisted, 18), 2800).await,
+            Err(PriceError::TokenNotFound(_))
+        ));
+
+        // Pools without enough liquidity are not used: 100 WETH is worth less than 1M USDC.
+        mock.set_contract_function_call_result(weth, "balanceOf", vec![uint(U256::exp10(20))])
+            .await;
+        assert!(api.get_price_at(&token(long_tail, 18), 2800).await.is_err());
+    }
+
+    #[test]
+    fn test_current_cumulative_prices() {
+        let last = CumulativePrices {
+            price0: U256::from(10u64),
+            price1: U256::MAX,
+            timestamp: 100,
+        };
+        let (reserve0, reserve1) = (U256::from(1u64), U256::from(2u64));
+
+        // The pair was updated at the same second.
+        assert_eq!(
+            current_cumulative_prices(last, reserve0, reserve1, 100),
+            last
+        );
+        // The local clock is behind the chain.
+        assert_eq!(
+            current_cumulative_prices(last, reserve0, reserve1, 90),
+            last
+        );
+
+        let current = current_cumulative_prices(last, reserve0, reserve1, 110);
+        assert_eq!(current.timestamp, 110);
+        assert_eq!(
+            current.price0,
+            U256::from(10u64) + uq112(reserve1, reserve0) * 10
+        );
+        // Cumulative prices overflow.
+        assert_eq!(current.price1, uq112(reserve0, reserve1) * 10 - 1);
+    }
+
+    #[test]
+    fn test_average_tick() {
+        let ticks = AbiToken::Array(vec![int(-100), int(-700)]);
+        assert_eq!(average_tick(ticks, 60).unwrap(), -10);
+
+        let ticks = AbiToken::Array(vec![int(100), int(700)]);
+        assert_eq!(average_tick(ticks, 60).unwrap(), 10);
+
+        // Negative ticks are rounded towards negative infinity.
+        let ticks = AbiToken::Array(vec![int(0), int(-610)]);
+        assert_eq!(average_tick(ticks, 60).unwrap(), -11);
+
+        assert!(average_tick(AbiToken::Array(vec![int(1)]), 60).is_err());
+
+        let ticks = AbiToken::Array(vec![int(0), int((MAX_TICK + 1) * 60)]);
+        assert!(average_tick(ticks, 60).is_err());
+    }
+
+    #[test]
+    fn test_tick_price() {
+        assert_eq!(tick_price(0), Ratio::one());
+        assert!((ratio_to_f64(&tick_price(1)) - 1.0001).abs() < 1e-12);
+        assert!((ratio_to_f64(&tick_price(-46055)) - 1.0001f64.powi(-46055)).abs() < 1e-12);
+
+        let price = tick_price(MAX_TICK) * tick_price(-MAX_TICK);
+        assert!((ratio_to_f64(&price) - 1.0).abs() < 1e-9);
+    }
+}
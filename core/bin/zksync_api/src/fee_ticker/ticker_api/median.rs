@@ -0,0 +1,375 @@
+//! Token price API aggregating several price sources.
+//!
+//! Sources are queried concurrently and the median of their prices is taken, so a single
+//! provider outage or a bad print doesn't affect the fees. Prices deviating from the median
+//! too much are discarded as outliers. If there are not enough sources agreeing on the price,
+//! the last known price is used instead, unless it's too old.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future::join_all;
+use num::{rational::Ratio, BigUint, ToPrimitive, Zero};
+// Workspace deps
+use zksync_storage::ConnectionPool;
+use zksync_types::{Token, TokenPrice};
+use zksync_utils::ratio_to_big_decimal;
+// Local deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+
+/// Named price source, the name is used in logs and metrics.
+pub struct PriceSource {
+    pub name: String,
+    pub api: Box<dyn TokenPriceAPI + Send + Sync>,
+}
+
+impl PriceSource {
+    pub fn new(name: impl Into<String>, api: impl TokenPriceAPI + Send + Sync + 'static) -> Self {
+        Self {
+            name: name.into(),
+            api: Box::new(api),
+        }
+    }
+}
+
+/// Storage of the previously fetched prices.
+#[async_trait]
+pub trait PriceHistory: Send + Sync + 'static {
+    /// Loads the price stored on the previous successful update.
+    async fn last_known_price(&self, token: &Token) -> Result<Option<TokenPrice>, PriceError>;
+}
+
+#[async_trait]
+impl PriceHistory for ConnectionPool {
+    async fn last_known_price(&self, token: &Token) -> Result<Option<TokenPrice>, PriceError> {
+        self.access_storage()
+            .await
+            .map_err(PriceError::db_error)?
+            .tokens_schema()
+            .get_historical_ticker_price(token.id)
+            .await
+            .map_err(PriceError::db_error)
+    }
+}
+
+pub struct MedianTokenPriceAPI<H = ConnectionPool> {
+    price_history: H,
+    sources: Vec<PriceSource>,
+    max_deviation: Ratio<BigUint>,
+    min_sources: usize,
+    fallback_max_age: Duration,
+}
+
+impl<H: PriceHistory> MedianTokenPriceAPI<H> {
+    pub fn new(
+        price_history: H,
+        sources: Vec<PriceSource>,
+        max_deviation_percent: u32,
+        min_sources: usize,
+        fallback_max_age: Duration,
+    ) -> Self {
+        Self {
+            price_history,
+            sources,
+            max_deviation: Ratio::new(max_deviation_percent.into(), 100u32.into()),
+            min_sources,
+            fallback_max_age,
+        }
+    }
+
+    /// Returns the last known price of the token, if it's recent enough.
+    async fn fallback_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let price = self
+            .price_history
+            .last_known_price(token)
+            .await?
+            .ok_or_else(|| {
+                PriceError::api_error(format!(
+                    "Not enough price sources for token {} and no last known price",
+                    token.symbol
+                ))
+            })?;
+        let age = Utc::now().signed_duration_since(price.last_updated);
+        // The age is negative if the clocks are skewed, such a price is considered fresh.
+        if age
+            .to_std()
+            .map_or(false, |age| age > self.fallback_max_age)
+        {
+            return Err(PriceError::api_error(format!(
+                "Not enough price sources for token {} and the last known price is outdated",
+                token.symbol
+            )));
+        }
+        Ok(price)
+    }
+}
+
+#[async_trait]
+impl<H: PriceHistory> TokenPriceAPI for MedianTokenPriceAPI<H> {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let responses = join_all(
+            self.sources
+                .iter()
+                .map(|source| source.api.get_price(token)),
+        )
+        .await;
+
+        let mut not_found = 0;
+        let mut prices = Vec::with_capacity(responses.len());
+        for (source, response) in self.sources.iter().zip(responses) {
+            match response {
+                Ok(price) => prices.push((source.name.as_str(), price)),
+                Err(PriceError::TokenNotFound(_)) => not_found += 1,
+                Err(err) => {
+                    vlog::warn!(
+                        "Can't get price for token {} from {}: {}",
+                        token.symbol,
+                        source.name,
+                        err
+                    );
+                    metrics::increment_counter!("ticker.median.source_error", "source" => source.name.clone());
+                }
+            }
+        }
+        // Token isn't listed anywhere, let the caller handle it as usual.
+        if not_found == self.sources.len() {
+            return Err(PriceError::token_not_found(format!(
+                "Token '{}' is not listed on any of the price sources",
+                token.symbol
+            )));
+        }
+
+        if let Some(price) = aggregate_prices(prices, &self.max_deviation, self.min_sources) {
+            return Ok(price);
+        }
+
+        metrics::increment_counter!("ticker.median.fallback");
+        vlog::warn!(
+            "Not enough price sources agree on the price of token {}, using the last known price",
+            token.symbol
+        );
+        self.fallback_price(token).await
+    }
+}
+
+fn median(prices: &[Ratio<BigUint>]) -> Option<Ratio<BigUint>> {
+    let mut prices = prices.to_vec();
+    prices.sort();
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(prices[mid].clone()),
+        _ => Some((&prices[mid - 1] + &prices[mid]) / BigUint::from(2u32)),
+    }
+}
+
+/// Relative deviation of the price from the non-zero median.
+fn deviation(price: &Ratio<BigUint>, median: &Ratio<BigUint>) -> Ratio<BigUint> {
+    let diff = if price > median {
+        price - median
+    } else {
+        median - price
+    };
+    diff / median
+}
+
+/// Takes the median of the prices reported by the sources, excluding the outliers
+/// and the zero prices. Returns `None` if less than `min_sources` sources agree on the price.
+fn aggregate_prices(
+    prices: Vec<(&str, TokenPrice)>,
+    max_deviation: &Ratio<BigUint>,
+    min_sources: usize,
+) -> Option<TokenPrice> {
+    // Zero price can't be used to charge the fees, the token would be free to use.
+    let prices: Vec<_> = prices
+        .into_iter()
+        .filter(|(source, price)| {
+            if price.usd_price.is_zero() {
+                metrics::increment_counter!("ticker.median.zero_price", "source" => source.to_string());
+                return false;
+            }
+            true
+        })
+        .collect();
+    let usd_prices: Vec<_> = prices
+        .iter()
+        .map(|(_, price)| price.usd_price.clone())
+        .collect();
+    let median_price = median(&usd_prices)?;
+
+    let inliers: Vec<_> = prices
+        .into_iter()
+        .filter(|(source, price)| {
+            let deviation = deviation(&price.usd_price, &median_price);
+            let percent = ratio_to_big_decimal(&(deviation.clone() * BigUint::from(100u32)), 4)
+                .to_f64()
+                .unwrap_or(f64::MAX);
+            metrics::histogram!("ticker.median.deviation_percent", percent, "source" => source.to_string());
+            &deviation <= max_deviation
+        })
+        .map(|(_, price)| price)
+        .collect();
+    if inliers.len() < min_sources.max(1) {
+        return None;
+    }
+
+    let usd_prices: Vec<_> = inliers
+        .iter()
+        .map(|price| price.usd_price.clone())
+        .collect();
+    Some(TokenPrice {
+        usd_price: median(&usd_prices)?,
+        // The price is only as fresh as the oldest price it's based on.
+        last_updated: inliers.iter().map(|price| price.last_updated).min()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use zksync_types::{Address, TokenId, TokenKind};
+
+    use super::*;
+
+    fn price(usd_price: u32) -> TokenPrice {
+        TokenPrice {
+            usd_price: Ratio::from_integer(usd_price.into()),
+            last_updated: Utc::now(),
+        }
+    }
+
+    /// Price source which either returns the fixed price or is unavailable.
+    struct MockPriceAPI(Option<TokenPrice>);
+
+    #[async_trait]
+    impl TokenPriceAPI for MockPriceAPI {
+        async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+            self.0
+                .clone()
+                .ok_or_else(|| PriceError::api_error("Source is unavailable"))
+        }
+    }
+
+    #[async_trait]
+    impl PriceHistory for Option<TokenPrice> {
+        async fn last_known_price(&self, _token: &Token) -> Result<Option<TokenPrice>, PriceError> {
+            Ok(self.clone())
+        }
+    }
+
+    fn median_api(
+        last_known_price: Option<TokenPrice>,
+        prices: Vec<Option<TokenPrice>>,
+    ) -> MedianTokenPriceAPI<Option<TokenPrice>> {
+        let sources = prices
+            .into_iter()
+            .enumerate()
+            .map(|(i, price)| PriceSource::new(i.to_string(), MockPriceAPI(price)))
+            .collect();
+        MedianTokenPriceAPI::new(
+            last_known_price,
+            sources,
+            5,
+            2,
+            std::time::Duration::from_secs(3600),
+        )
+    }
+
+    fn usd(price: Option<TokenPrice>) -> Option<Ratio<BigUint>> {
+        price.map(|price| price.usd_price)
+    }
+
+    #[test]
+    fn test_median() {
+        let ratio = |value: u32| Ratio::from_integer(BigUint::from(value));
+
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[ratio(3)]), Some(ratio(3)));
+        assert_eq!(median(&[ratio(5), ratio(1), ratio(3)]), Some(ratio(3)));
+        assert_eq!(
+            median(&[ratio(4), ratio(1), ratio(2), ratio(100)]),
+            Some(ratio(3))
+        );
+    }
+
+    #[test]
+    fn test_aggregate_prices() {
+        let max_deviation = Ratio::new(BigUint::from(5u32), BigUint::from(100u32));
+
+        // Outlier is discarded.
+        let prices = vec![("a", price(100)), ("b", price(102)), ("c", price(1000))];
+        assert_eq!(
+            usd(aggregate_prices(prices, &max_deviation, 2)),
+            Some(Ratio::from_integer(101u32.into()))
+        );
+
+        // Not enough sources agree on the price.
+        let prices = vec![("a", price(100)), ("b", price(200)), ("c", price(1000))];
+        assert_eq!(usd(aggregate_prices(prices, &max_deviation, 2)), None);
+
+        // Not enough sources answered.
+        let prices = vec![("a", price(100))];
+        assert_eq!(usd(aggregate_prices(prices, &max_deviation, 2)), None);
+        assert_eq!(usd(aggregate_prices(vec![], &max_deviation, 0)), None);
+
+        // Zero prices are discarded before taking the median.
+        let prices = vec![("a", price(0)), ("b", price(0))];
+        assert_eq!(usd(aggregate_prices(prices, &max_deviation, 2)), None);
+        let prices = vec![("a", price(0)), ("b", price(100)), ("c", price(102))];
+        assert_eq!(
+            usd(aggregate_prices(prices, &max_deviation, 2)),
+            Some(Ratio::from_integer(101u32.into()))
+        );
+    }
+
+    #[test]
+    fn test_aggregated_price_age() {
+        let max_deviation = Ratio::new(BigUint::from(5u32), BigUint::from(100u32));
+        let mut stale = price(100);
+        stale.last_updated = stale.last_updated - Duration::hours(1);
+        let stale_time = stale.last_updated;
+
+        let prices = vec![("a", stale), ("b", price(100))];
+        let aggregated = aggregate_prices(prices, &max_deviation, 2).unwrap();
+        assert_eq!(aggregated.last_updated, stale_time);
+    }
+
+    /// Checks that the last known price is used when the sources don't agree on the price,
+    /// unless it's too old.
+    #[tokio::test]
+    async fn test_get_price_fallback() {
+        let token = Token::new(TokenId(1), Address::zero(), "DAI", 18, TokenKind::ERC20);
+        let usd_price =
+            |price: Result<TokenPrice, PriceError>| price.ok().map(|price| price.usd_price);
+
+        // Sources agree on the price.
+        let api = median_api(
+            Some(price(1)),
+            vec![Some(price(100)), Some(price(101)), None],
+        );
+        assert_eq!(
+            usd_price(api.get_price(&token).await),
+            Some(Ratio::new(201u32.into(), 2u32.into()))
+        );
+
+        // Only one source is available, the last known price is used.
+        let api = median_api(Some(price(1)), vec![Some(price(100)), None, None]);
+        assert_eq!(
+            usd_price(api.get_price(&token).await),
+            Some(Ratio::from_integer(1u32.into()))
+        );
+
+        // Sources disagree and there is no last known price.
+        let api = median_api(None, vec![Some(price(100)), Some(price(200))]);
+        assert!(api.get_price(&token).await.is_err());
+
+        // The last known price is too old.
+        let mut outdated = price(1);
+        outdated.last_updated = outdated.last_updated - Duration::hours(2);
+        let api = median_api(Some(outdated), vec![Some(price(100)), Some(price(200))]);
+        assert!(api.get_price(&token).await.is_err());
+    }
+}
@@ -52,6 +52,9 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 
     async fn remaining_chunks_in_pending_block(&self) -> anyhow::Result<Option<usize>>;
 
+    /// Get the mempool size and the sizes of the last `blocks_window` blocks.
+    async fn congestion_info(&self, blocks_window: u32) -> anyhow::Result<CongestionInfo>;
+
     /// Get last price for token from ticker info
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError>;
 
@@ -87,6 +90,14 @@ pub struct BlocksInFutureAggregatedOperations {
     pub blocks_to_execute: u32,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct CongestionInfo {
+    /// Number of transactions awaiting in the mempool.
+    pub mempool_size: u32,
+    /// Number of chunks used by the operations of the recent blocks.
+    pub recent_blocks_chunks_used: Vec<usize>,
+}
+
 #[async_trait]
 impl FeeTickerInfo for TickerInfo {
     async fn is_account_new(&self, address: Address) -> anyhow::Result<bool> {
@@ -162,6 +173,22 @@ impl FeeTickerInfo for TickerInfo {
         Ok(remaining_chunks)
     }
 
+    async fn congestion_info(&self, blocks_window: u32) -> anyhow::Result<CongestionInfo> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let mempool_size = storage.chain().mempool_schema().get_mempool_size().await?;
+        let recent_blocks_chunks_used = storage
+            .chain()
+            .block_schema()
+            .load_last_blocks_chunks_used(blocks_window)
+            .await?;
+        metrics::histogram!("ticker_info.congestion_info", start.elapsed());
+        Ok(CongestionInfo {
+            mempool_size,
+            recent_blocks_chunks_used,
+        })
+    }
+
     /// Get last price from ticker
     async fn get_last_token_price(&self, token: TokenLike) -> Result<TokenPrice, PriceError> {
         let start = Instant::now();
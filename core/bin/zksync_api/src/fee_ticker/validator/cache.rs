@@ -1,11 +1,21 @@
-#[cfg(test)]
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 #[cfg(test)]
 use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 use zksync_token_db_cache::TokenDBCache;
 
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
+use zksync_types::{
+    tokens::{FeeTokenPolicy, TokenMarketVolume},
+    Token, TokenId, TokenLike,
+};
+
+/// Policies changed by another server instance are picked up within this period.
+const FEE_TOKEN_POLICIES_INVALIDATE_CACHE: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 pub(crate) enum TokenCacheWrapper {
@@ -14,10 +24,23 @@ pub(crate) enum TokenCacheWrapper {
     Memory(TokenInMemoryCache),
 }
 
+/// Fee token policies loaded from the database.
+#[derive(Debug, Default)]
+struct FeeTokenPoliciesCache {
+    policies: Option<(HashMap<TokenId, FeeTokenPolicy>, Instant)>,
+    /// Incremented on every invalidation, so the policies loaded before
+    /// the invalidation aren't cached.
+    generation: u64,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TokenInDBCache {
     inner: TokenDBCache,
     pool: ConnectionPool,
+    /// Fee token policies are changed through the main database, so they are loaded from it.
+    /// Replica may lag behind and the stale policies would be cached after the invalidation.
+    main_pool: ConnectionPool,
+    fee_token_policies: Arc<RwLock<FeeTokenPoliciesCache>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -25,11 +48,59 @@ pub(crate) struct TokenInDBCache {
 pub struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    fee_token_policies: Arc<Mutex<HashMap<TokenId, FeeTokenPolicy>>>,
 }
 
 impl TokenInDBCache {
-    pub fn new(pool: ConnectionPool, inner: TokenDBCache) -> Self {
-        Self { inner, pool }
+    pub fn new(pool: ConnectionPool, main_pool: ConnectionPool, inner: TokenDBCache) -> Self {
+        Self {
+            inner,
+            pool,
+            main_pool,
+            fee_token_policies: Default::default(),
+        }
+    }
+
+    /// Returns the fee token policy, reloading all the policies from the database
+    /// if the cache is invalidated or expired.
+    async fn get_fee_token_policy(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<FeeTokenPolicy>> {
+        let generation = {
+            let cache = self.fee_token_policies.read().await;
+            if let Some((policies, loaded_at)) = &cache.policies {
+                if loaded_at.elapsed() < FEE_TOKEN_POLICIES_INVALIDATE_CACHE {
+                    return Ok(policies.get(&token_id).cloned());
+                }
+            }
+            cache.generation
+        };
+
+        let policies: HashMap<_, _> = self
+            .main_pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .load_fee_token_policies()
+            .await?
+            .into_iter()
+            .map(|policy| (policy.token_id, policy))
+            .collect();
+        let policy = policies.get(&token_id).cloned();
+
+        let mut cache = self.fee_token_policies.write().await;
+        // The policies could be changed while they were loaded, such a result may be stale.
+        if cache.generation == generation {
+            cache.policies = Some((policies, Instant::now()));
+        }
+        Ok(policy)
+    }
+
+    async fn invalidate_fee_token_policies(&self) {
+        let mut cache = self.fee_token_policies.write().await;
+        cache.policies = None;
+        cache.generation += 1;
     }
 }
 
@@ -52,6 +123,13 @@ impl TokenInMemoryCache {
             ..self
         }
     }
+
+    pub async fn set_fee_token_policy(&self, policy: FeeTokenPolicy) {
+        self.fee_token_policies
+            .lock()
+            .await
+            .insert(policy.token_id, policy);
+    }
 }
 
 #[cfg(test)]
@@ -63,7 +141,7 @@ impl From<TokenInMemoryCache> for TokenCacheWrapper {
 
 impl From<(ConnectionPool, TokenDBCache)> for TokenCacheWrapper {
     fn from(value: (ConnectionPool, TokenDBCache)) -> Self {
-        Self::DB(TokenInDBCache::new(value.0, value.1))
+        Self::DB(TokenInDBCache::new(value.0.clone(), value.0, value.1))
     }
 }
 
@@ -130,6 +208,33 @@ impl TokenCacheWrapper {
             }
         }
     }
+
+    pub async fn get_fee_token_policy(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<FeeTokenPolicy>> {
+        match self {
+            Self::DB(cache) => cache.get_fee_token_policy(token_id).await,
+            #[cfg(test)]
+            Self::Memory(cache) => Ok(cache
+                .fee_token_policies
+                .lock()
+                .await
+                .get(&token_id)
+                .cloned()),
+        }
+    }
+
+    /// Makes the next policy lookup reload the policies from the database.
+    pub async fn invalidate_fee_token_policies(&self) {
+        match self {
+            Self::DB(cache) => cache.invalidate_fee_token_policies().await,
+            // Policies are always up to date in memory.
+            #[cfg(test)]
+            Self::Memory(_) => {}
+        }
+    }
+
     pub async fn get_all_tokens(&self) -> anyhow::Result<Vec<Token>> {
         match self {
             Self::DB(cache) => {
@@ -15,8 +15,8 @@ use chrono::Utc;
 
 // Workspace uses
 use zksync_types::{
-    tokens::{Token, TokenLike, TokenMarketVolume},
-    Address,
+    tokens::{FeeTokenListing, Token, TokenLike, TokenMarketVolume, MAX_FEE_DISCOUNT_PERCENT},
+    Address, TokenId,
 };
 
 // Local uses
@@ -121,6 +121,13 @@ impl FeeTokenValidator {
     pub(crate) async fn token_allowed(&self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
+            // Explicit listing by the admin takes precedence over any other rule.
+            let policy = self.tokens_cache.get_fee_token_policy(token.id).await?;
+            match policy.and_then(|policy| policy.listing) {
+                Some(FeeTokenListing::Allow) => return Ok(true),
+                Some(FeeTokenListing::Deny) => return Ok(false),
+                None => {}
+            }
             if self.unconditionally_valid.contains(&token.address) {
                 return Ok(true);
             }
@@ -131,6 +138,19 @@ impl FeeTokenValidator {
         }
     }
 
+    /// Returns the discount of the fees paid in the token, in percent.
+    pub(crate) async fn fee_discount_percent(&self, token_id: TokenId) -> anyhow::Result<u8> {
+        let policy = self.tokens_cache.get_fee_token_policy(token_id).await?;
+        Ok(policy.map_or(0, |policy| {
+            policy.discount_percent.min(MAX_FEE_DISCOUNT_PERCENT)
+        }))
+    }
+
+    /// Makes the fee token policy changes take effect immediately.
+    pub(crate) async fn invalidate_fee_token_policies(&self) {
+        self.tokens_cache.invalidate_fee_token_policies().await
+    }
+
     async fn resolve_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
         self.tokens_cache.get_token(token).await
     }
@@ -168,7 +188,7 @@ mod tests {
     use std::str::FromStr;
     use std::sync::Arc;
     use tokio::sync::Mutex;
-    use zksync_types::{TokenId, TokenKind};
+    use zksync_types::{tokens::FeeTokenPolicy, TokenKind};
 
     #[derive(Clone)]
     struct InMemoryTokenWatcher {
@@ -284,4 +304,61 @@ mod tests {
         assert!(!phnx_allowed);
         assert!(eth_allowed);
     }
+
+    #[tokio::test]
+    async fn check_fee_token_policies() {
+        let dai_token_address =
+            Address::from_str("6b175474e89094c44da98b954eedeac495271d0f").unwrap();
+        let dai_token = Token::new(TokenId(1), dai_token_address, "DAI", 18, TokenKind::ERC20);
+        let eth_address = Address::from_str("0000000000000000000000000000000000000000").unwrap();
+        let eth_token = Token::new(TokenId(0), eth_address, "ETH", 18, TokenKind::ERC20);
+
+        let mut tokens = HashMap::new();
+        tokens.insert(TokenLike::Address(dai_token_address), dai_token.clone());
+        tokens.insert(TokenLike::Address(eth_address), eth_token.clone());
+        let mut unconditionally_valid = HashSet::new();
+        unconditionally_valid.insert(eth_address);
+
+        let cache = TokenInMemoryCache::new().with_tokens(tokens);
+        let validator = FeeTokenValidator::new(
+            cache.clone(),
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            unconditionally_valid,
+        );
+
+        // DAI has no market volume, so it's only allowed explicitly.
+        let dai = TokenLike::Address(dai_token_address);
+        assert!(!validator.token_allowed(dai.clone()).await.unwrap());
+        cache
+            .set_fee_token_policy(FeeTokenPolicy {
+                token_id: dai_token.id,
+                listing: Some(FeeTokenListing::Allow),
+                discount_percent: 20,
+                updated_at: Utc::now(),
+            })
+            .await;
+        assert!(validator.token_allowed(dai).await.unwrap());
+        assert_eq!(
+            validator.fee_discount_percent(dai_token.id).await.unwrap(),
+            20
+        );
+
+        // Denied token is not allowed even if it's unconditionally valid.
+        let eth = TokenLike::Address(eth_address);
+        assert!(validator.token_allowed(eth.clone()).await.unwrap());
+        cache
+            .set_fee_token_policy(FeeTokenPolicy {
+                token_id: eth_token.id,
+                listing: Some(FeeTokenListing::Deny),
+                discount_percent: 0,
+                updated_at: Utc::now(),
+            })
+            .await;
+        assert!(!validator.token_allowed(eth).await.unwrap());
+        assert_eq!(
+            validator.fee_discount_percent(eth_token.id).await.unwrap(),
+            0
+        );
+    }
 }
@@ -1,6 +1,7 @@
 #![recursion_limit = "256"]
 
 pub mod api_server;
+pub mod core_api_client;
 pub mod eth_checker;
 pub mod fee_ticker;
 pub mod signature_checker;
@@ -15,3 +15,18 @@ pub enum Toggle2FAError {
     #[error("Request to enable 2FA should not have PubKeyHash field set")]
     UnusedPubKeyHash,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+pub enum CancelTxError {
+    #[error("Internal error")]
+    Other,
+
+    #[error("Database unavailable")]
+    DbError,
+
+    #[error("Transaction is not in the mempool, it may be already executed")]
+    NotInMempool,
+
+    #[error("Request is not signed by the transaction account")]
+    IncorrectSignature,
+}
@@ -0,0 +1,36 @@
+// Built-in uses
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External uses
+use tokio::sync::RwLock;
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+
+/// How long the loaded exodus mode flag is trusted. Transactions may still be accepted
+/// during this period after the zkSync contract has entered the exodus mode.
+const EXODUS_MODE_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Cache of the exodus mode flag, so the database isn't queried on every transaction submission.
+#[derive(Clone, Debug, Default)]
+pub struct ExodusModeCache(Arc<RwLock<Option<(bool, Instant)>>>);
+
+impl ExodusModeCache {
+    pub async fn is_entered(&self, pool: &ConnectionPool) -> QueryResult<bool> {
+        if let Some((entered, loaded_at)) = *self.0.read().await {
+            // The exodus mode can't be left, so the entered flag doesn't expire.
+            if entered || loaded_at.elapsed() < EXODUS_MODE_CACHE_TTL {
+                return Ok(entered);
+            }
+        }
+
+        let entered = pool
+            .access_storage()
+            .await?
+            .ethereum_schema()
+            .load_exodus_mode()
+            .await?
+            .is_some();
+        *self.0.write().await = Some((entered, Instant::now()));
+        Ok(entered)
+    }
+}
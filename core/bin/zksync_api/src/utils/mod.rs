@@ -1,2 +1,3 @@
 pub mod block_details_cache;
+pub mod exodus_mode_cache;
 pub mod shared_lru_cache;
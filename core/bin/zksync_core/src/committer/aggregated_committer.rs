@@ -17,6 +17,8 @@ use zksync_types::{
     BlockNumber, U256,
 };
 
+use super::aggregation_policy::{report_expected_gas_per_block, AdaptiveAggregation};
+
 fn create_new_commit_operation(
     last_committed_block: &Block,
     new_blocks: &[Block],
@@ -25,6 +27,7 @@ fn create_new_commit_operation(
     block_commit_deadline: Duration,
     max_gas_for_tx: U256,
     fast_processing: bool,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> Option<BlocksCommitOperation> {
     let new_blocks = new_blocks
         .iter()
@@ -48,10 +51,15 @@ fn create_new_commit_operation(
     let gas_limit_reached_for_blocks =
         GasCounter::commit_gas_limit_aggregated(&new_blocks) >= max_gas_for_tx;
 
-    let should_commit_blocks = any_block_commit_deadline_triggered
-        || gas_limit_reached_for_blocks
-        || new_blocks.len() == max_blocks_to_commit
-        || fast_processing;
+    let aggregation_required = match adaptive_aggregation {
+        Some(aggregation) => {
+            aggregation.should_aggregate(&new_blocks, max_blocks_to_commit, current_time)
+        }
+        None => any_block_commit_deadline_triggered || new_blocks.len() == max_blocks_to_commit,
+    };
+
+    let should_commit_blocks =
+        aggregation_required || gas_limit_reached_for_blocks || fast_processing;
     if !should_commit_blocks {
         return None;
     }
@@ -80,6 +88,7 @@ fn create_new_create_proof_operation(
     block_verify_deadline: Duration,
     _max_gas_for_tx: U256,
     fast_processing: bool,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> Option<BlocksCreateProofOperation> {
     let max_aggregate_size = available_aggregate_proof_sizes
         .last()
@@ -104,8 +113,19 @@ fn create_new_create_proof_operation(
 
     let can_create_max_aggregate_proof = new_blocks_with_proofs.len() >= max_aggregate_size;
 
-    let should_create_aggregate_proof =
-        any_block_verify_deadline_triggered || can_create_max_aggregate_proof || fast_processing;
+    let aggregation_required = match adaptive_aggregation {
+        Some(aggregation) => {
+            let blocks_to_aggregate = new_blocks_with_proofs.len().min(max_aggregate_size);
+            aggregation.should_aggregate(
+                &new_blocks_with_proofs[..blocks_to_aggregate],
+                max_aggregate_size,
+                current_time,
+            )
+        }
+        None => any_block_verify_deadline_triggered || can_create_max_aggregate_proof,
+    };
+
+    let should_create_aggregate_proof = aggregation_required || fast_processing;
 
     if !should_create_aggregate_proof {
         return None;
@@ -154,6 +174,7 @@ fn create_execute_blocks_operation(
     block_execute_deadline: Duration,
     max_gas_for_tx: U256,
     fast_processing: bool,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> Option<BlocksExecuteOperation> {
     let proven_non_executed_block = proven_non_executed_block
         .iter()
@@ -176,10 +197,20 @@ fn create_execute_blocks_operation(
     let gas_limit_reached_for_blocks =
         GasCounter::execute_gas_limit_aggregated(&proven_non_executed_block) >= max_gas_for_tx;
 
-    let should_execute_blocks = any_block_execute_deadline_triggered
-        || gas_limit_reached_for_blocks
-        || proven_non_executed_block.len() == max_blocks_to_execute
-        || fast_processing;
+    let aggregation_required = match adaptive_aggregation {
+        Some(aggregation) => aggregation.should_aggregate(
+            &proven_non_executed_block,
+            max_blocks_to_execute,
+            current_time,
+        ),
+        None => {
+            any_block_execute_deadline_triggered
+                || proven_non_executed_block.len() == max_blocks_to_execute
+        }
+    };
+
+    let should_execute_blocks =
+        aggregation_required || gas_limit_reached_for_blocks || fast_processing;
     if !should_execute_blocks {
         return None;
     }
@@ -224,6 +255,7 @@ async fn is_fast_processing_requested(
 async fn create_aggregated_commits_storage(
     storage: &mut StorageProcessor<'_>,
     config: &ChainConfig,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> anyhow::Result<bool> {
     let mut transaction = storage.start_transaction().await?;
     let last_aggregate_committed_block = OperationsSchema(&mut transaction)
@@ -256,6 +288,7 @@ async fn create_aggregated_commits_storage(
         config.state_keeper.block_commit_deadline(),
         config.state_keeper.max_aggregated_tx_gas.into(),
         fast_processing_requested,
+        adaptive_aggregation,
     );
 
     let result = if let Some(commit_operation) = commit_operation {
@@ -276,6 +309,7 @@ async fn create_aggregated_commits_storage(
 async fn create_aggregated_prover_task_storage(
     storage: &mut StorageProcessor<'_>,
     config: &ChainConfig,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> anyhow::Result<bool> {
     let mut transaction = storage.start_transaction().await?;
     let last_aggregate_committed_block = OperationsSchema(&mut transaction)
@@ -316,6 +350,7 @@ async fn create_aggregated_prover_task_storage(
         config.state_keeper.block_prove_deadline(),
         config.state_keeper.max_aggregated_tx_gas.into(),
         fast_processing_requested,
+        adaptive_aggregation,
     );
     let result = if let Some(operation) = create_proof_operation {
         let aggregated_op = operation.into();
@@ -404,6 +439,7 @@ async fn create_aggregated_publish_proof_operation_storage(
 async fn create_aggregated_execute_operation_storage(
     storage: &mut StorageProcessor<'_>,
     config: &ChainConfig,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> anyhow::Result<bool> {
     let mut transaction = storage.start_transaction().await?;
     let last_aggregate_executed_block = OperationsSchema(&mut transaction)
@@ -437,6 +473,7 @@ async fn create_aggregated_execute_operation_storage(
         config.state_keeper.block_execute_deadline(),
         config.state_keeper.max_aggregated_tx_gas.into(),
         fast_processing_requested,
+        adaptive_aggregation,
     );
 
     let result = if let Some(operation) = execute_operation {
@@ -454,14 +491,19 @@ async fn create_aggregated_execute_operation_storage(
     result
 }
 
-pub async fn create_aggregated_operations_storage(
+/// Creates the aggregated operations for the available blocks. If `adaptive_aggregation` is not set,
+/// the operations are created according to the blocks deadlines.
+pub(super) async fn create_aggregated_operations_storage(
     storage: &mut StorageProcessor<'_>,
     config: &ChainConfig,
+    adaptive_aggregation: Option<&AdaptiveAggregation>,
 ) -> anyhow::Result<()> {
-    while create_aggregated_commits_storage(storage, config).await? {}
-    while create_aggregated_prover_task_storage(storage, config).await? {}
+    while create_aggregated_commits_storage(storage, config, adaptive_aggregation).await? {}
+    while create_aggregated_prover_task_storage(storage, config, adaptive_aggregation).await? {}
     while create_aggregated_publish_proof_operation_storage(storage).await? {}
-    while create_aggregated_execute_operation_storage(storage, config).await? {}
+    while create_aggregated_execute_operation_storage(storage, config, adaptive_aggregation).await?
+    {
+    }
 
     Ok(())
 }
@@ -474,4 +516,5 @@ fn log_aggregated_op_creation(aggregated_op: &AggregatedOperation) {
         first,
         last
     );
+    report_expected_gas_per_block(aggregated_op);
 }
@@ -0,0 +1,279 @@
+//! Adaptive policy of aggregating the blocks into the L1 operations.
+//!
+//! Every aggregated operation pays the constant base cost, so the more blocks are aggregated
+//! the less gas is paid per block. When the L1 gas is expensive, it's worth waiting for more blocks,
+//! while for the cheap gas the latency can be reduced by aggregating fewer of them. In any case,
+//! the block doesn't wait longer than the configured maximum latency.
+
+// Built-in uses
+use std::{
+    cmp::max,
+    time::{Duration, Instant},
+};
+// External uses
+use chrono::{DateTime, Utc};
+// Workspace uses
+use zksync_config::configs::chain::{AggregationPolicy, StateKeeper as StateKeeperConfig};
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{
+    aggregated_operations::AggregatedOperation, block::Block, gas_counter::GasCounter, U256,
+};
+
+/// Amount of wei in one gwei.
+const WEI_IN_GWEI: u64 = 1_000_000_000;
+
+/// The L1 gas price doesn't change much between the blocks, so there's no need
+/// to request it every time the aggregated operations are checked.
+const GAS_PRICE_UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Parameters of the adaptive aggregation for the current L1 gas price.
+#[derive(Debug, Clone)]
+pub(super) struct AdaptiveAggregation {
+    /// Current L1 gas price in wei.
+    pub gas_price: U256,
+    /// Gas price (in wei) starting from which the maximum amount of blocks is aggregated.
+    pub reference_gas_price: U256,
+    /// Maximum time the block may wait to be aggregated.
+    pub max_latency: Duration,
+}
+
+impl AdaptiveAggregation {
+    pub(super) fn new(config: &StateKeeperConfig, gas_price: U256) -> Self {
+        Self {
+            gas_price,
+            reference_gas_price: U256::from(config.aggregation_reference_gas_price)
+                * U256::from(WEI_IN_GWEI),
+            max_latency: config.max_aggregation_latency(),
+        }
+    }
+
+    /// Amount of blocks worth aggregating for the current gas price, i.e. the maximum amount
+    /// scaled by the ratio of the current gas price to the reference one.
+    pub(super) fn target_blocks(&self, max_blocks: usize) -> usize {
+        if self.reference_gas_price.is_zero() || self.gas_price >= self.reference_gas_price {
+            return max_blocks;
+        }
+        // Rounded up, so that at least one block is aggregated.
+        let target = (U256::from(max_blocks) * self.gas_price + self.reference_gas_price - 1)
+            / self.reference_gas_price;
+        max(target.as_usize(), 1)
+    }
+
+    /// Checks whether the operation has to be created for the available blocks.
+    pub(super) fn should_aggregate(
+        &self,
+        blocks: &[Block],
+        max_blocks: usize,
+        current_time: DateTime<Utc>,
+    ) -> bool {
+        let target_blocks = self.target_blocks(max_blocks);
+        metrics::histogram!("committer.aggregation.target_blocks", target_blocks as f64);
+
+        let max_latency = self.max_latency.as_secs() as i64;
+        let latency_exceeded = blocks.iter().any(|block| {
+            let seconds_since_block_created = current_time
+                .signed_duration_since(block.timestamp_utc())
+                .num_seconds();
+            seconds_since_block_created > max_latency
+        });
+
+        blocks.len() >= target_blocks || latency_exceeded
+    }
+}
+
+/// Keeps the parameters of the adaptive aggregation up to date with the L1 gas price.
+#[derive(Debug)]
+pub(super) struct AdaptiveAggregationUpdater {
+    eth_gateway: EthereumGateway,
+    aggregation: AdaptiveAggregation,
+    update_interval: Duration,
+    /// Time of the last successful gas price update, `None` if the gas price was never loaded.
+    last_update: Option<Instant>,
+}
+
+impl AdaptiveAggregationUpdater {
+    /// Creates the updater if the adaptive aggregation policy is enabled.
+    pub(super) fn from_config(
+        eth_gateway: EthereumGateway,
+        config: &StateKeeperConfig,
+    ) -> Option<Self> {
+        if config.aggregation_policy != AggregationPolicy::Adaptive {
+            return None;
+        }
+        Some(Self::new(
+            eth_gateway,
+            AdaptiveAggregation::new(config, U256::zero()),
+            GAS_PRICE_UPDATE_INTERVAL,
+        ))
+    }
+
+    fn new(
+        eth_gateway: EthereumGateway,
+        aggregation: AdaptiveAggregation,
+        update_interval: Duration,
+    ) -> Self {
+        Self {
+            eth_gateway,
+            aggregation,
+            update_interval,
+            last_update: None,
+        }
+    }
+
+    /// Returns the aggregation parameters, requesting the gas price if the last known one is outdated.
+    /// If the gas price can't be loaded, the last known one is used, and if there's none,
+    /// `None` is returned so that the deadline policy is used until the next attempt.
+    pub(super) async fn aggregation(&mut self) -> Option<&AdaptiveAggregation> {
+        let is_outdated = self.last_update.map_or(true, |last_update| {
+            last_update.elapsed() >= self.update_interval
+        });
+        if is_outdated {
+            match self.eth_gateway.get_gas_price().await {
+                Ok(gas_price) => {
+                    self.aggregation.gas_price = gas_price;
+                    self.last_update = Some(Instant::now());
+                }
+                Err(err) => {
+                    vlog::warn!(
+                        "Failed to get the gas price for the adaptive aggregation: {}",
+                        err
+                    );
+                }
+            }
+        }
+
+        self.last_update.map(|_| &self.aggregation)
+    }
+}
+
+/// Reports the expected amount of gas paid per block of the created operation.
+/// The realized amount is reported by the Ethereum sender once the transaction is confirmed.
+pub(super) fn report_expected_gas_per_block(aggregated_op: &AggregatedOperation) {
+    let (gas_limit, blocks) = match aggregated_op {
+        AggregatedOperation::CommitBlocks(op) => (
+            GasCounter::commit_gas_limit_aggregated(&op.blocks),
+            op.blocks.len(),
+        ),
+        AggregatedOperation::CreateProofBlocks(op) => (
+            U256::from(GasCounter::BASE_PROOF_BLOCKS_TX_COST),
+            op.blocks.len(),
+        ),
+        AggregatedOperation::ExecuteBlocks(op) => (
+            GasCounter::execute_gas_limit_aggregated(&op.blocks),
+            op.blocks.len(),
+        ),
+        // Proof is published for the blocks of the already reported `CreateProofBlocks` operation.
+        AggregatedOperation::PublishProofBlocksOnchain(_) => return,
+    };
+    if blocks == 0 {
+        return;
+    }
+
+    let gas_per_block = (gas_limit / U256::from(blocks)).as_u64();
+    metrics::histogram!(
+        "aggregated_operation.gas_per_block",
+        gas_per_block as f64,
+        "action" => aggregated_op.get_action_type().to_string(),
+        "kind" => "expected"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_crypto::Fr;
+    use zksync_eth_client::clients::mock::MockEthereum;
+    use zksync_types::{AccountId, BlockNumber, H256};
+
+    fn block(timestamp: DateTime<Utc>) -> Block {
+        Block::new(
+            BlockNumber(1),
+            Fr::default(),
+            AccountId(0),
+            Vec::new(),
+            (0, 0),
+            10,
+            U256::zero(),
+            U256::zero(),
+            H256::zero(),
+            timestamp.timestamp() as u64,
+        )
+    }
+
+    fn adaptive_aggregation(gas_price: u64, reference_gas_price: u64) -> AdaptiveAggregation {
+        AdaptiveAggregation {
+            gas_price: gas_price.into(),
+            reference_gas_price: reference_gas_price.into(),
+            max_latency: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn target_blocks() {
+        // The expensive gas requires the maximum amount of blocks.
+        assert_eq!(adaptive_aggregation(100, 100).target_blocks(10), 10);
+        assert_eq!(adaptive_aggregation(500, 100).target_blocks(10), 10);
+        // The cheaper gas requires proportionally fewer blocks, but at least one.
+        assert_eq!(adaptive_aggregation(50, 100).target_blocks(10), 5);
+        assert_eq!(adaptive_aggregation(11, 100).target_blocks(10), 2);
+        assert_eq!(adaptive_aggregation(0, 100).target_blocks(10), 1);
+        // Zero reference price means that the gas price is ignored.
+        assert_eq!(adaptive_aggregation(1, 0).target_blocks(10), 10);
+    }
+
+    #[test]
+    fn should_aggregate_without_blocks() {
+        let aggregation = adaptive_aggregation(0, 100);
+        assert!(!aggregation.should_aggregate(&[], 10, Utc::now()));
+    }
+
+    #[test]
+    fn should_aggregate_by_gas_price_and_latency() {
+        let now = Utc::now();
+        let blocks = vec![block(now); 5];
+
+        // Cheap gas requires only the part of the maximum amount of blocks.
+        assert!(adaptive_aggregation(50, 100).should_aggregate(&blocks, 10, now));
+        // Expensive gas requires the maximum amount.
+        assert!(!adaptive_aggregation(100, 100).should_aggregate(&blocks, 10, now));
+        // Unless the oldest block waits for too long.
+        let mut blocks = blocks;
+        blocks[0] = block(now - chrono::Duration::seconds(61));
+        assert!(adaptive_aggregation(100, 100).should_aggregate(&blocks, 10, now));
+    }
+
+    #[tokio::test]
+    async fn gas_price_updates() {
+        let mut eth_gateway = EthereumGateway::Mock(MockEthereum::default());
+        eth_gateway
+            .get_mut_mock()
+            .unwrap()
+            .set_gas_price(50.into())
+            .await
+            .unwrap();
+        let mut updater = AdaptiveAggregationUpdater::new(
+            eth_gateway,
+            adaptive_aggregation(0, 100),
+            Duration::from_secs(3600),
+        );
+
+        let aggregation = updater.aggregation().await.unwrap();
+        assert_eq!(aggregation.gas_price, 50.into());
+        assert_eq!(aggregation.target_blocks(10), 5);
+
+        // The gas price isn't requested again until the update interval passes.
+        updater
+            .eth_gateway
+            .get_mut_mock()
+            .unwrap()
+            .set_gas_price(100.into())
+            .await
+            .unwrap();
+        assert_eq!(updater.aggregation().await.unwrap().target_blocks(10), 5);
+
+        updater.update_interval = Duration::from_secs(0);
+        let aggregation = updater.aggregation().await.unwrap();
+        assert_eq!(aggregation.gas_price, 100.into());
+        assert_eq!(aggregation.target_blocks(10), 10);
+    }
+}
@@ -1,21 +1,26 @@
 // Built-in uses
 use std::time::{Duration, Instant};
 // External uses
-use futures::{channel::mpsc::Receiver, StreamExt};
+use futures::{channel::mpsc::Receiver, future, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::{task::JoinHandle, time};
 
 // Workspace uses
 use zksync_config::ChainConfig;
 use zksync_crypto::Fr;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
     AccountUpdates, BlockNumber,
 };
+// Local uses
+use self::aggregation_policy::AdaptiveAggregationUpdater;
+use crate::exodus_monitor::ExodusModeFlag;
 
 mod aggregated_committer;
+mod aggregation_policy;
 
 // In this component, the most interesting part of the database is decimals,
 // Usually we don't change them, so we can invalidate the cache once an hour.
@@ -281,21 +286,44 @@ async fn finish_block(request: BlockFinishRequest, pool: &ConnectionPool) {
     metrics::histogram!("committer.finish_block", start.elapsed());
 }
 
-async fn poll_for_new_proofs_task(pool: ConnectionPool, config: ChainConfig) {
+async fn poll_for_new_proofs_task(
+    pool: ConnectionPool,
+    config: ChainConfig,
+    eth_gateway: EthereumGateway,
+    exodus_mode: ExodusModeFlag,
+) {
+    let mut adaptive_aggregation_updater =
+        AdaptiveAggregationUpdater::from_config(eth_gateway, &config.state_keeper);
     let mut timer = time::interval(PROOF_POLL_INTERVAL);
     loop {
         timer.tick().await;
+        if exodus_mode.is_entered() {
+            vlog::warn!("Exodus mode is entered, aggregated operations are not created anymore");
+            break;
+        }
 
+        let adaptive_aggregation = match adaptive_aggregation_updater.as_mut() {
+            Some(updater) => updater.aggregation().await.cloned(),
+            None => None,
+        };
         let mut storage = pool
             .access_storage()
             .await
             .expect("db connection failed for committer");
 
-        aggregated_committer::create_aggregated_operations_storage(&mut storage, &config)
-            .await
-            .map_err(|e| vlog::error!("Failed to create aggregated operation: {}", e))
-            .unwrap_or_default();
+        aggregated_committer::create_aggregated_operations_storage(
+            &mut storage,
+            &config,
+            adaptive_aggregation.as_ref(),
+        )
+        .await
+        .map_err(|e| vlog::error!("Failed to create aggregated operation: {}", e))
+        .unwrap_or_default();
     }
+
+    // Operations can't be processed on L1 in the exodus mode, but the task is expected
+    // to live as long as the server does.
+    future::pending::<()>().await;
 }
 
 #[must_use]
@@ -303,7 +331,14 @@ pub fn run_committer(
     rx_for_ops: Receiver<CommitRequest>,
     pool: ConnectionPool,
     config: ChainConfig,
+    eth_gateway: EthereumGateway,
+    exodus_mode: ExodusModeFlag,
 ) -> JoinHandle<()> {
     tokio::spawn(handle_new_commit_task(rx_for_ops, pool.clone()));
-    tokio::spawn(poll_for_new_proofs_task(pool, config))
+    tokio::spawn(poll_for_new_proofs_task(
+        pool,
+        config,
+        eth_gateway,
+        exodus_mode,
+    ))
 }
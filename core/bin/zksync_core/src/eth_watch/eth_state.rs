@@ -1,11 +1,15 @@
 // Built-in deps
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 // External uses
 // Workspace deps
-use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
+use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId, H256};
 // Local deps
 use super::received_ops::ReceivedPriorityOp;
 
+/// Amount of the processed Ethereum blocks which hashes are remembered to detect reorgs.
+/// A reorg deeper than the oldest remembered block can't be located precisely.
+pub const MAX_STORED_BLOCK_HASHES: usize = 256;
+
 /// Gathered state of the Ethereum network.
 /// Contains information about the known token types and incoming
 /// priority operations (such as `Deposit` and `FullExit`).
@@ -39,6 +43,9 @@ pub struct ETHState {
     new_tokens: Vec<NewTokenEvent>,
     /// List of events denoting registered factories for NFT withdrawing
     register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+    /// Hashes of the last blocks of the processed ranges, keyed by the block number.
+    /// Used to detect whether the already processed blocks were replaced by a reorg.
+    block_hashes: BTreeMap<u64, H256>,
 }
 
 impl ETHState {
@@ -49,6 +56,7 @@ impl ETHState {
         priority_queue: HashMap<SerialId, ReceivedPriorityOp>,
         new_tokens: Vec<NewTokenEvent>,
         register_nft_factory_events: Vec<RegisterNFTFactoryEvent>,
+        mut block_hashes: BTreeMap<u64, H256>,
     ) -> Self {
         assert!(
             last_ethereum_block_backup <= last_ethereum_block,
@@ -59,6 +67,10 @@ impl ETHState {
             .max()
             .map(|serial_id| *serial_id + 1)
             .unwrap_or(0);
+        while block_hashes.len() > MAX_STORED_BLOCK_HASHES {
+            let oldest_block = *block_hashes.keys().next().unwrap();
+            block_hashes.remove(&oldest_block);
+        }
         Self {
             last_ethereum_block,
             last_ethereum_block_backup,
@@ -67,6 +79,7 @@ impl ETHState {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            block_hashes,
         }
     }
 
@@ -90,6 +103,10 @@ impl ETHState {
         &self.new_tokens
     }
 
+    pub fn block_hashes(&self) -> &BTreeMap<u64, H256> {
+        &self.block_hashes
+    }
+
     pub fn next_priority_op_id(&self) -> SerialId {
         self.next_priority_op_id
     }
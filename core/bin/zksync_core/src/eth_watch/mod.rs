@@ -6,7 +6,7 @@
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
 
 // Built-in deps
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 // External uses
@@ -24,7 +24,7 @@ use web3::types::BlockNumber;
 use zksync_config::{ContractsConfig, ETHWatchConfig};
 use zksync_crypto::params::PRIORITY_EXPIRATION;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
-use zksync_mempool::MempoolTransactionRequest;
+use zksync_mempool::{MempoolBlocksRequest, MempoolTransactionRequest};
 use zksync_types::{NewTokenEvent, PriorityOp, RegisterNFTFactoryEvent, SerialId};
 
 // Local deps
@@ -80,6 +80,8 @@ fn is_missing_priority_op_error(error: &anyhow::Error) -> bool {
 pub struct EthWatch<W: EthClient> {
     client: W,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    /// Used to report the latest Ethereum block, which the deadlines of the priority operations are checked against.
+    mempool_block_sender: mpsc::Sender<MempoolBlocksRequest>,
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
@@ -90,11 +92,13 @@ impl<W: EthClient> EthWatch<W> {
     pub fn new(
         client: W,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        mempool_block_sender: mpsc::Sender<MempoolBlocksRequest>,
         number_of_confirmations_for_event: u64,
     ) -> Self {
         Self {
             client,
             mempool_tx_sender,
+            mempool_block_sender,
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
@@ -191,6 +195,9 @@ impl<W: EthClient> EthWatch<W> {
         register_nft_factory_events.sort_by_key(|factory_event| factory_event.creator_address);
         register_nft_factory_events.dedup_by_key(|factory_event| factory_event.creator_address);
 
+        let mut block_hashes = self.eth_state.block_hashes().clone();
+        block_hashes.extend(updated_state.block_hashes());
+
         let new_state = ETHState::new(
             last_ethereum_block,
             previous_ethereum_block,
@@ -198,6 +205,7 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue,
             new_tokens,
             register_nft_factory_events,
+            block_hashes,
         );
         self.set_new_state(new_state);
         Ok(())
@@ -224,6 +232,16 @@ impl<W: EthClient> EthWatch<W> {
         let previous_block_with_accepted_events =
             new_block_with_accepted_events.saturating_sub(unprocessed_blocks_amount);
 
+        // The hash is requested before the logs: if the block is replaced in between,
+        // the reorg will be detected during the next poll.
+        let block_hashes = self
+            .client
+            .block_hash(new_block_with_accepted_events)
+            .await?
+            .map(|hash| (new_block_with_accepted_events, hash))
+            .into_iter()
+            .collect();
+
         let unconfirmed_queue = self.get_unconfirmed_ops(current_ethereum_block).await?;
         let priority_queue = self
             .client
@@ -295,10 +313,173 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue_map,
             new_tokens,
             new_register_nft_factory_events,
+            block_hashes,
         );
         Ok(state)
     }
 
+    /// Checks whether the last processed block is still a part of the canonical chain.
+    /// If it's not, returns the number of the latest processed block which is not affected
+    /// by the reorg.
+    async fn find_reorg_fork_block(&self) -> anyhow::Result<Option<u64>> {
+        let block_hashes = self.eth_state.block_hashes();
+        let (last_block, last_hash) = match block_hashes.iter().next_back() {
+            Some((block, hash)) => (*block, *hash),
+            None => return Ok(None),
+        };
+        match self.client.block_hash(last_block).await? {
+            // The node may lag behind and not know the block yet.
+            None => return Ok(None),
+            Some(hash) if hash == last_hash => return Ok(None),
+            Some(_) => {}
+        }
+
+        for (block, hash) in block_hashes.iter().rev().skip(1) {
+            if self.client.block_hash(*block).await? == Some(*hash) {
+                return Ok(Some(*block));
+            }
+        }
+        // The reorg is deeper than the remembered hashes, so every block
+        // after the oldest of them is processed again.
+        let oldest_block = *block_hashes.keys().next().unwrap();
+        vlog::error!(
+            "L1 reorg is deeper than the oldest remembered block #{}",
+            oldest_block
+        );
+        Ok(Some(oldest_block.saturating_sub(1)))
+    }
+
+    /// Re-fetches the events from the blocks replaced by the reorg and replaces
+    /// the corresponding part of the state with them.
+    ///
+    /// Confirmed priority operations that disappeared from L1 are removed from the mempool.
+    /// If any of them was already executed, the state can't be recovered anymore, so the
+    /// watcher panics to stop the pipeline.
+    async fn process_reorg(&mut self, fork_block: u64) -> anyhow::Result<()> {
+        let last_processed_block = match self.eth_state.block_hashes().keys().next_back() {
+            Some(block) => *block,
+            None => return Ok(()),
+        };
+        vlog::warn!(
+            "L1 reorg detected, re-fetching events from blocks [{},{}]",
+            fork_block + 1,
+            last_processed_block
+        );
+        metrics::increment_counter!("eth_watcher.reorgs");
+
+        let from = BlockNumber::Number((fork_block + 1).into());
+        let to = BlockNumber::Number(last_processed_block.into());
+        let last_processed_hash = self.client.block_hash(last_processed_block).await?;
+        let priority_ops = self.client.get_priority_op_events(from, to).await?;
+        let new_tokens = self.client.get_new_tokens_events(from, to).await?;
+        let register_nft_factory_events = self
+            .client
+            .get_new_register_nft_factory_events(from, to)
+            .await?;
+
+        // Operations are matched by both the serial ID and the L1 transaction hash, since
+        // the serial ID of the replaced operation may be taken by another one.
+        let refetched_ops: HashSet<_> = priority_ops
+            .iter()
+            .map(|op| (op.serial_id, op.eth_hash))
+            .collect();
+        let mut priority_queue = HashMap::new();
+        let mut lost_ops = Vec::new();
+        for (serial_id, op) in self.eth_state.priority_queue() {
+            let op_ref = op.as_ref();
+            if op_ref.eth_block <= fork_block {
+                priority_queue.insert(*serial_id, op.clone());
+            } else if !refetched_ops.contains(&(op_ref.serial_id, op_ref.eth_hash)) {
+                vlog::error!(
+                    "Priority operation #{} from L1 tx {:?} was removed by the reorg",
+                    op_ref.serial_id,
+                    op_ref.eth_hash
+                );
+                metrics::increment_counter!("eth_watcher.reorg_lost_priority_ops");
+                lost_ops.push(*serial_id);
+            }
+        }
+        // Lost operations are removed before the re-fetched ones are added, since the latter
+        // may reuse their serial IDs.
+        if !lost_ops.is_empty() {
+            // Users may have already seen the lost operations as queued, so the operators
+            // have to be alerted. The gauge is never reset, the alert stays until the restart.
+            metrics::gauge!("eth_watcher.reorg_lost_priority_ops_alert", 1.0);
+            let (sender, receiver) = oneshot::channel();
+            self.mempool_tx_sender
+                .send(MempoolTransactionRequest::RemovePriorityOps(
+                    lost_ops, sender,
+                ))
+                .await?;
+            let executed_ops = receiver.await.expect("Mempool actor was dropped")?;
+            if !executed_ops.is_empty() {
+                panic!(
+                    "Priority operations {:?} were removed from L1 by the reorg, \
+                     but they are already executed",
+                    executed_ops
+                );
+            }
+        }
+        priority_queue.extend(
+            priority_ops
+                .iter()
+                .cloned()
+                .map(|op| (op.serial_id, op.into())),
+        );
+
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::NewPriorityOps(
+                priority_ops,
+                true,
+                sender,
+            ))
+            .await?;
+        receiver.await.expect("Mempool actor was dropped")?;
+
+        let mut tokens: Vec<_> = self
+            .eth_state
+            .new_tokens()
+            .iter()
+            .filter(|token| token.eth_block_number <= fork_block)
+            .cloned()
+            .collect();
+        tokens.extend(new_tokens);
+        tokens.sort_by_key(|token_event| token_event.id.0);
+        tokens.dedup_by_key(|token_event| token_event.id.0);
+
+        let mut factory_events: Vec<_> = self
+            .eth_state
+            .new_register_nft_factory_events()
+            .iter()
+            .filter(|event| event.eth_block <= fork_block)
+            .cloned()
+            .collect();
+        factory_events.extend(register_nft_factory_events);
+        factory_events.sort_by_key(|factory_event| factory_event.creator_address);
+        factory_events.dedup_by_key(|factory_event| factory_event.creator_address);
+
+        let mut block_hashes: BTreeMap<_, _> = self
+            .eth_state
+            .block_hashes()
+            .range(..=fork_block)
+            .map(|(block, hash)| (*block, *hash))
+            .collect();
+        block_hashes.extend(last_processed_hash.map(|hash| (last_processed_block, hash)));
+
+        let new_state = ETHState::new(
+            self.eth_state.last_ethereum_block(),
+            self.eth_state.last_ethereum_block_backup(),
+            self.eth_state.unconfirmed_queue().to_vec(),
+            priority_queue,
+            tokens,
+            factory_events,
+            block_hashes,
+        );
+        self.set_new_state(new_state);
+        Ok(())
+    }
+
     fn get_register_factory_event(
         &self,
         last_block_number: Option<u64>,
@@ -334,13 +515,33 @@ impl<W: EthClient> EthWatch<W> {
         let last_block_number = self.client.block_number().await?;
 
         if last_block_number > self.eth_state.last_ethereum_block() {
+            if let Some(fork_block) = self.find_reorg_fork_block().await? {
+                self.process_reorg(fork_block).await?;
+            }
             self.process_new_blocks(last_block_number).await?;
+            self.report_last_block(last_block_number).await;
         }
 
         metrics::histogram!("eth_watcher.poll_eth_node", start.elapsed());
         Ok(())
     }
 
+    /// Sends the number of the latest Ethereum block to the mempool.
+    async fn report_last_block(&mut self, last_block_number: u64) {
+        // The block number is only used to track the deadlines of the priority operations,
+        // so failing to deliver it must not stop the watcher.
+        if let Err(err) = self
+            .mempool_block_sender
+            .send(MempoolBlocksRequest::UpdateEthBlock(last_block_number))
+            .await
+        {
+            vlog::warn!(
+                "Failed to report the last Ethereum block to the mempool: {}",
+                err
+            );
+        }
+    }
+
     // TODO try to move it to eth client
     fn is_backoff_requested(&self, error: &anyhow::Error) -> bool {
         error.to_string().contains("429 Too Many Requests")
@@ -456,6 +657,7 @@ pub async fn start_eth_watch(
     contract_config: &ContractsConfig,
     eth_watcher_config: &ETHWatchConfig,
     mempool_req_sender: mpsc::Sender<MempoolTransactionRequest>,
+    mempool_block_req_sender: mpsc::Sender<MempoolBlocksRequest>,
 ) -> JoinHandle<()> {
     let eth_client = EthHttpClient::new(
         eth_gateway,
@@ -466,6 +668,7 @@ pub async fn start_eth_watch(
     let mut eth_watch = EthWatch::new(
         eth_client,
         mempool_req_sender,
+        mempool_block_req_sender,
         eth_watcher_config.confirmations_for_eth_event,
     );
 
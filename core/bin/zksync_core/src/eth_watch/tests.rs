@@ -1,5 +1,5 @@
 use std::cmp::max;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use web3::types::{Address, BlockNumber};
@@ -12,14 +12,19 @@ use zksync_types::{
 use futures::channel::mpsc;
 use futures::StreamExt;
 use tokio::sync::RwLock;
+use zksync_eth_client::{clients::mock::MockEthereum, ethereum_gateway::EthereumGateway};
 use zksync_mempool::MempoolTransactionRequest;
 
 use super::is_missing_priority_op_error;
-use crate::eth_watch::{client::EthClient, EthWatch};
+use crate::eth_watch::{client::EthClient, eth_state::ETHState, EthHttpClient, EthWatch};
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
     last_block_number: u64,
+    /// Hashes of the blocks replaced by the reorgs. Hashes of the other blocks are
+    /// derived from their numbers.
+    reorged_hashes: HashMap<u64, H256>,
+    reorgs_count: u64,
 }
 
 impl FakeEthClientData {
@@ -27,9 +32,34 @@ impl FakeEthClientData {
         Self {
             priority_ops: Default::default(),
             last_block_number: 0,
+            reorged_hashes: Default::default(),
+            reorgs_count: 0,
         }
     }
 
+    fn block_hash(&self, block_number: u64) -> Option<H256> {
+        if block_number > self.last_block_number {
+            return None;
+        }
+        let hash = self
+            .reorged_hashes
+            .get(&block_number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(block_number));
+        Some(hash)
+    }
+
+    /// Replaces all the blocks starting from `from_block` and their operations.
+    fn reorg(&mut self, from_block: u64, ops: &[PriorityOp]) {
+        self.reorgs_count += 1;
+        self.priority_ops.retain(|block, _| *block < from_block);
+        for block in from_block..=self.last_block_number {
+            let hash = H256::from_low_u64_be(block + (self.reorgs_count << 32));
+            self.reorged_hashes.insert(block, hash);
+        }
+        self.add_operations(ops);
+    }
+
     fn add_operations(&mut self, ops: &[PriorityOp]) {
         for op in ops {
             self.last_block_number = max(op.eth_block, self.last_block_number);
@@ -66,6 +96,10 @@ impl FakeEthClient {
         }
     }
 
+    async fn reorg(&mut self, from_block: u64, ops: &[PriorityOp]) {
+        self.inner.write().await.reorg(from_block, ops);
+    }
+
     async fn set_last_block_number(&mut self, block_number: u64) {
         let mut inner = self.inner.write().await;
         inner.last_block_number = block_number;
@@ -111,6 +145,10 @@ impl EthClient for FakeEthClient {
         Ok(self.inner.read().await.last_block_number)
     }
 
+    async fn block_hash(&self, block_number: u64) -> anyhow::Result<Option<H256>> {
+        Ok(self.inner.read().await.block_hash(block_number))
+    }
+
     async fn get_auth_fact(
         &self,
         _address: Address,
@@ -132,7 +170,9 @@ fn create_watcher<T: EthClient>(
     client: T,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
 ) -> EthWatch<T> {
-    EthWatch::new(client, mempool_tx_sender, 1)
+    // Reports of the last Ethereum block are not checked, so the receiver is dropped.
+    let (mempool_block_sender, _) = mpsc::channel(1);
+    EthWatch::new(client, mempool_tx_sender, mempool_block_sender, 1)
 }
 
 async fn fake_mempool(
@@ -141,7 +181,7 @@ async fn fake_mempool(
 ) {
     while let Some(a) = receiver.next().await {
         match a {
-            MempoolTransactionRequest::NewTx(_, _) => {
+            MempoolTransactionRequest::NewTx(_, _, _) => {
                 unreachable!()
             }
             MempoolTransactionRequest::NewPriorityOps(ops, conf, channel) => {
@@ -151,7 +191,15 @@ async fn fake_mempool(
                 }
                 channel.send(Ok(())).unwrap_or_default()
             }
-            MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::RemovePriorityOps(serial_ids, channel) => {
+                let mut lock = data.write().await;
+                for serial_id in &serial_ids {
+                    lock.remove(serial_id);
+                }
+                channel.send(Ok(Vec::new())).unwrap_or_default()
+            }
+            MempoolTransactionRequest::NewTxsBatch(_, _, _, _) => unreachable!(),
+            MempoolTransactionRequest::CancelTx(_, _) => unreachable!(),
         }
     }
 }
@@ -546,3 +594,126 @@ async fn test_serial_id_gaps() {
     assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 0);
     assert_eq!(watcher.eth_state.last_ethereum_block(), 3);
 }
+
+/// Checks that the operations from the blocks replaced by the L1 reorg are re-fetched,
+/// and the ones that disappeared are removed from the state.
+#[tokio::test]
+async fn test_reorg() {
+    let (sender, receiver) = mpsc::channel(10);
+    let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+        from: Default::default(),
+        token: TokenId(0),
+        amount: Default::default(),
+        to: [2u8; 20].into(),
+    });
+    let priority_op = |serial_id: SerialId, eth_block: u64, eth_hash: u8| PriorityOp {
+        serial_id,
+        data: deposit.clone(),
+        deadline_block: 0,
+        eth_hash: [eth_hash; 32].into(),
+        eth_block,
+        eth_block_index: Some(1),
+    };
+
+    let data = Arc::new(RwLock::new(HashMap::new()));
+    tokio::spawn(fake_mempool(receiver, data.clone()));
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[priority_op(0, 1, 2), priority_op(1, 3, 3)])
+        .await;
+
+    let mut watcher = create_watcher(client.clone(), sender);
+    watcher.restore_state_from_eth(0).await.unwrap();
+    client.set_last_block_number(4).await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 2);
+    assert_eq!(
+        watcher.eth_state.block_hashes().keys().collect::<Vec<_>>(),
+        vec![&0, &3]
+    );
+
+    // The operation #1 is replaced by another one from the different L1 transaction.
+    client
+        .reorg(3, &[priority_op(1, 3, 5), priority_op(2, 4, 6)])
+        .await;
+    client.set_last_block_number(5).await;
+    watcher.poll_eth_node().await.unwrap();
+    let priority_queue = watcher.eth_state.priority_queue();
+    assert_eq!(priority_queue.len(), 3);
+    assert_eq!(
+        priority_queue.get(&1).unwrap().as_ref().eth_hash,
+        H256::from_slice(&[5u8; 32])
+    );
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 3);
+    assert_eq!(
+        data.read().await.get(&1).unwrap().0.eth_hash,
+        H256::from_slice(&[5u8; 32])
+    );
+    assert_eq!(
+        watcher.eth_state.block_hashes().get(&3),
+        client.block_hash(3).await.unwrap().as_ref()
+    );
+
+    // The operation #2 disappears from L1.
+    assert!(data.read().await.contains_key(&2));
+    client.reorg(4, &[]).await;
+    let fork_block = watcher.find_reorg_fork_block().await.unwrap();
+    assert_eq!(fork_block, Some(3));
+    watcher.process_reorg(3).await.unwrap();
+    let priority_queue = watcher.eth_state.priority_queue();
+    assert_eq!(priority_queue.len(), 2);
+    assert!(priority_queue.get(&2).is_none());
+    assert!(!data.read().await.contains_key(&2));
+    assert_eq!(watcher.eth_state.next_priority_op_id(), 2);
+    // The processed range is not changed by the reorg.
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 5);
+    assert_eq!(watcher.eth_state.last_ethereum_block_backup(), 4);
+
+    client.set_last_block_number(6).await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.priority_queue().len(), 2);
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 6);
+}
+
+/// Checks that the reorg is detected by the block hashes returned by the Ethereum node.
+#[tokio::test]
+async fn test_reorg_detection() {
+    let mut eth_client = MockEthereum::default();
+    eth_client.set_block_number(10.into()).await.unwrap();
+    let client = EthHttpClient::new(
+        EthereumGateway::Mock(eth_client.clone()),
+        Default::default(),
+        Default::default(),
+    );
+    let (sender, _receiver) = mpsc::channel(10);
+    let mut watcher = create_watcher(client, sender);
+
+    let block_hashes: BTreeMap<_, _> = vec![4, 7, 10]
+        .into_iter()
+        .map(|block| (block, H256::from_low_u64_be(block)))
+        .collect();
+    watcher.set_new_state(ETHState::new(
+        10,
+        7,
+        Vec::new(),
+        HashMap::new(),
+        Vec::new(),
+        Vec::new(),
+        block_hashes,
+    ));
+    assert_eq!(watcher.find_reorg_fork_block().await.unwrap(), None);
+
+    // Blocks after the 4th one are replaced.
+    for block in 5..=10 {
+        eth_client
+            .set_block_hash(block, H256::from_low_u64_be(block + 100))
+            .await;
+    }
+    assert_eq!(watcher.find_reorg_fork_block().await.unwrap(), Some(4));
+
+    // The reorg is deeper than the oldest remembered block.
+    eth_client
+        .set_block_hash(4, H256::from_low_u64_be(104))
+        .await;
+    assert_eq!(watcher.find_reorg_fork_block().await.unwrap(), Some(3));
+}
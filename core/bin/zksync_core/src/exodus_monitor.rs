@@ -0,0 +1,235 @@
+//! Exodus monitor watches the state of the zkSync contract which can't be observed through its events:
+//! whether the contract has entered the exodus mode, and the priority requests which weren't processed yet.
+//!
+//! The exodus mode is entered on L1 once any priority request misses its deadline. After that no blocks
+//! can be committed anymore, and the users can only withdraw their funds with the exit proofs.
+//! Once the monitor detects it, the state keeper and the committer are stopped, new transactions are
+//! rejected by the API and the corresponding event is emitted.
+
+// Built-in uses
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use futures::future;
+use tokio::{task::JoinHandle, time};
+use web3::contract::{tokens::Detokenize, Options};
+// Workspace uses
+use zksync_config::ETHWatchConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::event::exodus::ExodusEvent;
+
+/// Flag shared by the actors which have to stop once the exodus mode is entered.
+#[derive(Debug, Clone, Default)]
+pub struct ExodusModeFlag(Arc<AtomicBool>);
+
+impl ExodusModeFlag {
+    /// Checks whether the zkSync contract has entered the exodus mode.
+    pub fn is_entered(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    fn enter(&self) {
+        self.0.store(true, Ordering::SeqCst);
+        metrics::gauge!("exodus_monitor.exodus_mode", 1.0);
+    }
+}
+
+/// State of the zkSync contract polled by the monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ContractStatus {
+    exodus_mode: bool,
+    first_priority_request_id: u64,
+    total_open_priority_requests: u64,
+}
+
+#[derive(Debug)]
+struct ExodusMonitor {
+    eth_gateway: EthereumGateway,
+    exodus_mode: ExodusModeFlag,
+}
+
+impl ExodusMonitor {
+    async fn call_contract<R>(&self, func: &str) -> anyhow::Result<R>
+    where
+        R: Detokenize + Unpin,
+    {
+        self.eth_gateway
+            .call_main_contract_function(func, (), None, Options::default(), None)
+            .await
+            .map_err(|e| format_err!("Failed to query contract {}: {}", func, e))
+    }
+
+    async fn load_contract_status(&self) -> anyhow::Result<ContractStatus> {
+        Ok(ContractStatus {
+            exodus_mode: self.call_contract("exodusMode").await?,
+            first_priority_request_id: self.call_contract("firstPriorityRequestId").await?,
+            total_open_priority_requests: self.call_contract("totalOpenPriorityRequests").await?,
+        })
+    }
+
+    /// Polls the contract and returns the event to be emitted if it has entered the exodus mode.
+    async fn check_contract(&self) -> anyhow::Result<Option<ExodusEvent>> {
+        let status = self.load_contract_status().await?;
+        metrics::gauge!(
+            "exodus_monitor.first_priority_request_id",
+            status.first_priority_request_id as f64
+        );
+        metrics::gauge!(
+            "exodus_monitor.total_open_priority_requests",
+            status.total_open_priority_requests as f64
+        );
+
+        if !status.exodus_mode {
+            return Ok(None);
+        }
+        let eth_block = self.eth_gateway.block_number().await?.as_u64();
+        Ok(Some(ExodusEvent {
+            eth_block,
+            first_priority_request_id: status.first_priority_request_id,
+            total_open_priority_requests: status.total_open_priority_requests,
+        }))
+    }
+
+    /// Persists the exodus mode along with its event and notifies the other actors.
+    async fn enter_exodus_mode(
+        &self,
+        connection_pool: &ConnectionPool,
+        event: ExodusEvent,
+    ) -> anyhow::Result<()> {
+        let mut storage = connection_pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let last_executed_block = transaction
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        transaction
+            .ethereum_schema()
+            .save_exodus_mode(event.eth_block, event.first_priority_request_id)
+            .await?;
+        transaction
+            .event_schema()
+            .store_exodus_event(last_executed_block, event)
+            .await?;
+        transaction.commit().await?;
+
+        self.exodus_mode.enter();
+        Ok(())
+    }
+
+    async fn run(self, connection_pool: ConnectionPool, poll_interval: Duration) {
+        let mut timer = time::interval(poll_interval);
+        while !self.exodus_mode.is_entered() {
+            timer.tick().await;
+
+            let result = match self.check_contract().await {
+                Ok(Some(event)) => {
+                    vlog::error!(
+                        "zkSync contract has entered the exodus mode at Ethereum block {}, \
+                         first unprocessed priority request: {}, open priority requests: {}",
+                        event.eth_block,
+                        event.first_priority_request_id,
+                        event.total_open_priority_requests
+                    );
+                    self.enter_exodus_mode(&connection_pool, event).await
+                }
+                Ok(None) => Ok(()),
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                vlog::warn!("Failed to check the exodus mode: {}", error);
+            }
+        }
+
+        // The exodus mode is irreversible, so there is nothing to watch anymore.
+        // Actors are expected to live as long as the server does, so the task stays idle.
+        future::pending::<()>().await;
+    }
+}
+
+/// Restores the exodus mode from the database and starts the monitor.
+/// Returned flag is set once the zkSync contract enters the exodus mode.
+pub async fn start_exodus_monitor(
+    connection_pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    config: &ETHWatchConfig,
+) -> anyhow::Result<(JoinHandle<()>, ExodusModeFlag)> {
+    let exodus_mode = ExodusModeFlag::default();
+    let stored_exodus_mode = connection_pool
+        .access_storage()
+        .await?
+        .ethereum_schema()
+        .load_exodus_mode()
+        .await?;
+    if let Some(stored_exodus_mode) = stored_exodus_mode {
+        vlog::error!(
+            "zkSync contract is in the exodus mode since Ethereum block {}",
+            stored_exodus_mode.eth_block
+        );
+        exodus_mode.enter();
+    }
+
+    let monitor = ExodusMonitor {
+        eth_gateway,
+        exodus_mode: exodus_mode.clone(),
+    };
+    let task = tokio::spawn(monitor.run(connection_pool, config.exodus_mode_poll_interval()));
+    Ok((task, exodus_mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::Token;
+    use zksync_eth_client::clients::mock::MockEthereum;
+
+    async fn create_monitor(exodus_mode: bool) -> ExodusMonitor {
+        let mock = MockEthereum::default();
+        mock.set_contract_call_result("exodusMode", vec![Token::Bool(exodus_mode)])
+            .await;
+        mock.set_contract_call_result("firstPriorityRequestId", vec![Token::Uint(5.into())])
+            .await;
+        mock.set_contract_call_result("totalOpenPriorityRequests", vec![Token::Uint(2.into())])
+            .await;
+
+        ExodusMonitor {
+            eth_gateway: EthereumGateway::Mock(mock),
+            exodus_mode: ExodusModeFlag::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn contract_status() {
+        let monitor = create_monitor(false).await;
+        let status = monitor.load_contract_status().await.unwrap();
+        assert_eq!(
+            status,
+            ContractStatus {
+                exodus_mode: false,
+                first_priority_request_id: 5,
+                total_open_priority_requests: 2,
+            }
+        );
+        assert!(monitor.check_contract().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn exodus_mode_detected() {
+        let monitor = create_monitor(true).await;
+        let event = monitor
+            .check_contract()
+            .await
+            .unwrap()
+            .expect("Exodus mode must be detected");
+        assert_eq!(event.eth_block, 1);
+        assert_eq!(event.first_priority_request_id, 5);
+        assert_eq!(event.total_open_priority_requests, 2);
+        // The flag is only set once the exodus mode is persisted.
+        assert!(!monitor.exodus_mode.is_entered());
+    }
+}
@@ -3,7 +3,10 @@ use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
     committer::run_committer,
     eth_watch::start_eth_watch,
-    state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
+    exodus_monitor::start_exodus_monitor,
+    state_keeper::{
+        start_root_hash_calculator, start_state_keeper, SealCriteriaConfig, ZkSyncStateKeeper,
+    },
     token_handler::run_token_handler,
 };
 use futures::{channel::mpsc, future};
@@ -18,6 +21,7 @@ const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod committer;
 pub mod eth_watch;
+pub mod exodus_monitor;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
@@ -82,6 +86,7 @@ pub async fn genesis_init(config: &ChainConfig) {
 /// Starts the core application, which has the following sub-modules:
 ///
 /// - Ethereum Watcher, module to monitor on-chain operations.
+/// - exodus monitor, module to detect the zkSync contract entering the exodus mode.
 /// - zkSync state keeper, module to execute and seal blocks.
 /// - mempool, module to organize incoming transactions.
 /// - block proposer, module to create block proposals for state keeper.
@@ -103,11 +108,14 @@ pub async fn run_core(
 
     let (processed_tx_events_sender, processed_tx_events_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+    let (state_keeper_request_sender, state_keeper_request_receiver) =
+        mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
 
     let mempool_tx_handler_task = run_mempool_tx_handler(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.mempool.clone(),
     );
 
     // Run health check api for core
@@ -115,6 +123,8 @@ pub async fn run_core(
         connection_pool.clone(),
         read_only_connection_pool,
         eth_gateway.clone(),
+        mempool_block_request_sender.clone(),
+        state_keeper_request_sender,
         config.api.private.clone(),
     );
 
@@ -126,9 +136,18 @@ pub async fn run_core(
         &config.contracts,
         &config.eth_watch,
         mempool_tx_request_sender.clone(),
+        mempool_block_request_sender.clone(),
     )
     .await;
 
+    // Start exodus monitor, it has to restore the exodus mode before the other actors start.
+    let (exodus_monitor_task, exodus_mode) = start_exodus_monitor(
+        connection_pool.clone(),
+        eth_gateway.clone(),
+        &config.eth_watch,
+    )
+    .await?;
+
     // Insert pending withdrawals into database (if required)
     let mut storage_processor = connection_pool.access_storage().await?;
 
@@ -148,6 +167,7 @@ pub async fn run_core(
         config.chain.state_keeper.block_chunk_sizes.clone(),
         config.chain.state_keeper.miniblock_iterations as usize,
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
+        SealCriteriaConfig::from(&config.chain.state_keeper),
         processed_tx_events_sender,
     );
 
@@ -157,6 +177,8 @@ pub async fn run_core(
     let state_keeper_task = start_state_keeper(
         state_keeper,
         config.chain.state_keeper.miniblock_iteration_interval(),
+        exodus_mode.clone(),
+        state_keeper_request_receiver,
     );
     let root_hash_calculator_task = start_root_hash_calculator(root_hash_calculator);
 
@@ -165,6 +187,8 @@ pub async fn run_core(
         proposed_blocks_receiver,
         connection_pool.clone(),
         config.chain.clone(),
+        eth_gateway.clone(),
+        exodus_mode,
     );
 
     // Start mempool.
@@ -172,6 +196,8 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_block_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.state_keeper.transactions_ordering,
+        config.chain.state_keeper.priority_op_deadline_margin,
     );
 
     // Start token handler.
@@ -196,6 +222,7 @@ pub async fn run_core(
 
     let task_futures = vec![
         eth_watch_task,
+        exodus_monitor_task,
         state_keeper_task,
         root_hash_calculator_task,
         committer_task,
@@ -8,31 +8,95 @@
 //! for correctness.
 
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use actix_web::{web, App, HttpResponse, HttpServer};
-use futures::{channel::mpsc, StreamExt};
+use chrono::Utc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
-use zksync_api_types::CoreStatus;
+use zksync_api_types::{v02::transaction::SimulateTxsResponse, CoreStatus};
 
 use zksync_config::configs::api::PrivateApiConfig;
 use zksync_eth_client::EthereumGateway;
+use zksync_mempool::{AccountPendingTxs, MempoolBlocksRequest, MempoolSnapshot};
 use zksync_storage::ConnectionPool;
+use zksync_types::ZkSyncTx;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+use crate::state_keeper::StateKeeperRequest;
+
 const STATUS_INVALIDATION_PERIOD: Duration = Duration::from_secs(60);
 
+/// Summary of the mempool contents. Transactions proposed for the pending block are not counted,
+/// and every transaction of a batch is counted separately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MempoolSummary {
+    /// Timestamp the readiness of the transactions is checked against.
+    timestamp: u64,
+    /// Transactions which can be included into the next block.
+    ready_txs: u64,
+    /// Transactions which can't be executed yet because of their `valid_from` field.
+    pending_txs: u64,
+    /// Priority operations confirmed on L1 and awaiting for execution.
+    priority_ops: u64,
+    /// Age of the oldest L2 transaction in the mempool, in seconds.
+    oldest_tx_age_secs: Option<u64>,
+}
+
 #[derive(Debug)]
 struct AppState {
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
     status_cache: RwLock<Option<(CoreStatus, Instant)>>,
+    mempool_block_request_sender: mpsc::Sender<MempoolBlocksRequest>,
+    state_keeper_request_sender: mpsc::Sender<StateKeeperRequest>,
+}
+
+impl AppState {
+    async fn access_storage(&self) -> actix_web::Result<zksync_storage::StorageProcessor<'_>> {
+        self.connection_pool
+            .access_storage()
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)
+    }
+
+    async fn mempool_snapshot(&self) -> actix_web::Result<MempoolSnapshot> {
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_block_request_sender
+            .clone()
+            .send(MempoolBlocksRequest::GetSnapshot(sender))
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+
+        receiver
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?
+            .map_err(actix_web::error::ErrorInternalServerError)
+    }
+
+    async fn simulate_txs(&self, txs: Vec<ZkSyncTx>) -> actix_web::Result<SimulateTxsResponse> {
+        let (sender, receiver) = oneshot::channel();
+        self.state_keeper_request_sender
+            .clone()
+            .send(StateKeeperRequest::SimulateTxs(txs, sender))
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+
+        receiver
+            .await
+            .map_err(actix_web::error::ErrorInternalServerError)
+    }
 }
 
 /// Health check.
-/// The core actor is expected have connection to web3 and both main/replica databases
+/// The core actor is expected have connection to web3 and both main/replica databases.
+/// Also reports whether the zkSync contract has entered the exodus mode.
 #[actix_web::get("/status")]
 async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
     if let Some((status, data)) = data.status_cache.read().await.as_ref() {
@@ -45,7 +109,13 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
     // with multiple requests from other API nodes when the cache has been invalidated.
 
     let mut status = data.status_cache.write().await;
-    let main_database_status = data.connection_pool.access_storage().await.is_ok();
+    let (main_database_status, exodus_mode) = match data.connection_pool.access_storage().await {
+        Ok(mut storage) => {
+            let exodus_mode = storage.ethereum_schema().load_exodus_mode().await;
+            (true, matches!(exodus_mode, Ok(Some(_))))
+        }
+        Err(_) => (false, false),
+    };
     let replica_database_status = data
         .read_only_connection_pool
         .access_storage()
@@ -57,16 +127,87 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
         main_database_available: main_database_status,
         replica_database_available: replica_database_status,
         web3_available: eth_status,
+        exodus_mode,
     };
     *status = Some((response.clone(), Instant::now()));
 
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Summary of the mempool contents: ready and pending transactions, priority operations backlog
+/// and the age of the oldest transaction. It's calculated by the database without loading
+/// the mempool, see `/mempool/snapshot` for the simulation of the next block.
+#[actix_web::get("/mempool")]
+async fn mempool(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Incorrect system time")
+        .as_secs();
+    let summary = data
+        .access_storage()
+        .await?
+        .chain()
+        .mempool_schema()
+        .get_summary(timestamp)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(MempoolSummary {
+        timestamp,
+        ready_txs: summary.ready_txs,
+        pending_txs: summary.pending_txs,
+        priority_ops: summary.priority_ops,
+        oldest_tx_age_secs: summary
+            .oldest_tx_created_at
+            .map(|created_at| (Utc::now() - created_at).num_seconds().max(0) as u64),
+    }))
+}
+
+/// Amounts of transactions awaiting in the mempool for every account, the largest ones go first.
+#[actix_web::get("/mempool/accounts")]
+async fn mempool_accounts(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let accounts: Vec<_> = data
+        .access_storage()
+        .await?
+        .chain()
+        .mempool_schema()
+        .get_pending_txs_per_account()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .into_iter()
+        .map(|(account_id, txs)| AccountPendingTxs {
+            account_id,
+            txs: txs as usize,
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(accounts))
+}
+
+/// Full snapshot of the mempool, including the simulation of the next proposed block.
+/// Loads the whole mempool, so it's much more expensive than `/mempool`.
+#[actix_web::get("/mempool/snapshot")]
+async fn mempool_snapshot(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let snapshot = data.mempool_snapshot().await?;
+    Ok(HttpResponse::Ok().json(snapshot))
+}
+
+/// Executes the transactions against the state of the state keeper without applying them.
+/// More than one transaction is executed as a batch.
+#[actix_web::post("/simulate")]
+async fn simulate(
+    data: web::Data<AppState>,
+    web::Json(txs): web::Json<Vec<ZkSyncTx>>,
+) -> actix_web::Result<HttpResponse> {
+    let response = data.simulate_txs(txs).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
 pub fn start_private_core_api(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
     eth_client: EthereumGateway,
+    mempool_block_request_sender: mpsc::Sender<MempoolBlocksRequest>,
+    state_keeper_request_sender: mpsc::Sender<StateKeeperRequest>,
     config: PrivateApiConfig,
 ) -> JoinHandle<()> {
     let (panic_sender, mut panic_receiver) = mpsc::channel(1);
@@ -85,6 +226,8 @@ pub fn start_private_core_api(
                         read_only_connection_pool: read_only_connection_pool.clone(),
                         eth_client: eth_client.clone(),
                         status_cache: Default::default(),
+                        mempool_block_request_sender: mempool_block_request_sender.clone(),
+                        state_keeper_request_sender: state_keeper_request_sender.clone(),
                     };
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -94,6 +237,10 @@ pub fn start_private_core_api(
                         .app_data(web::Data::new(app_state))
                         .app_data(web::JsonConfig::default().limit(2usize.pow(32)))
                         .service(status)
+                        .service(mempool)
+                        .service(mempool_accounts)
+                        .service(mempool_snapshot)
+                        .service(simulate)
                 })
                 .bind(&config.bind_addr())
                 .expect("failed to bind")
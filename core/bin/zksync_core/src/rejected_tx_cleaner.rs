@@ -5,6 +5,8 @@
 //! which is useful only for a short period of time. Since such transactions are not actually
 //! included in the block and don't affect the state hash, there is no much sense to keep
 //! them forever.
+//!
+//! Expired fee quotes are removed on the same schedule, since they can't be honored anymore.
 
 // External uses
 use tokio::{task::JoinHandle, time};
@@ -33,6 +35,9 @@ pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> Jo
             {
                 vlog::error!("Can't delete rejected transactions {:?}", e);
             }
+            if let Err(e) = storage.fee_quotes_schema().remove_expired_quotes().await {
+                vlog::error!("Can't delete expired fee quotes {:?}", e);
+            }
             timer.tick().await;
         }
     })
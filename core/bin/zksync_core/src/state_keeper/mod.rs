@@ -4,8 +4,9 @@ use std::time::{Duration, Instant};
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
+    future, select,
     stream::StreamExt,
-    SinkExt,
+    FutureExt, SinkExt,
 };
 use tokio::task::JoinHandle;
 use tokio::time;
@@ -26,24 +27,29 @@ use zksync_types::{
 use self::{
     pending_block::PendingBlock,
     root_hash_calculator::{BlockRootHashJob, RootHashCalculator},
+    seal_criteria::{CandidateOps, SealCriteria},
     types::{ApplyOutcome, StateKeeperConfig},
     utils::system_time_timestamp,
 };
 use crate::{
     committer::{BlockCommitRequest, CommitRequest},
+    exodus_monitor::ExodusModeFlag,
     tx_event_emitter::ProcessedOperations,
 };
 
 pub use self::{
     init_params::ZkSyncStateInitParams,
     root_hash_calculator::{start_root_hash_calculator, BlockRootHashJobQueue},
-    types::StateKeeperTestkitRequest,
+    seal_criteria::SealCriteriaConfig,
+    types::{StateKeeperRequest, StateKeeperTestkitRequest},
 };
 
 mod init_params;
 mod pending_block;
 mod root_hash_calculator;
+mod seal_criteria;
 mod state_restore;
+mod tx_simulator;
 mod types;
 mod utils;
 
@@ -56,6 +62,11 @@ pub struct ZkSyncStateKeeper {
     state: ZkSyncState,
     pending_block: PendingBlock,
     config: StateKeeperConfig,
+    /// Rules deciding when the pending block has to be sealed.
+    seal_criteria: SealCriteria,
+    /// Whether the deadline of the oldest unprocessed priority operation is approaching,
+    /// as reported by the mempool for the last proposed block.
+    priority_op_deadline_approaching: bool,
 
     tx_for_commitments: mpsc::Sender<CommitRequest>,
     tx_for_mempool: mpsc::Sender<MempoolBlocksRequest>,
@@ -85,6 +96,7 @@ impl ZkSyncStateKeeper {
         available_block_chunk_sizes: Vec<usize>,
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
+        seal_criteria: SealCriteriaConfig,
         processed_tx_events_sender: mpsc::Sender<ProcessedOperations>,
     ) -> (Self, RootHashCalculator) {
         // We need two copies of state:
@@ -134,6 +146,11 @@ impl ZkSyncStateKeeper {
             max_miniblock_iterations,
             fast_miniblock_iterations,
         );
+        let seal_criteria = SealCriteria::new(
+            &seal_criteria,
+            config.max_miniblock_iterations,
+            config.fast_miniblock_iterations,
+        );
 
         let pending_block = {
             // Keeper starts with the NEXT block
@@ -151,6 +168,8 @@ impl ZkSyncStateKeeper {
             state: sk_state,
             pending_block,
             config,
+            seal_criteria,
+            priority_op_deadline_approaching: false,
 
             tx_for_commitments,
             tx_for_mempool,
@@ -169,6 +188,9 @@ impl ZkSyncStateKeeper {
     // which is not a good practice.
     fn initialize(&mut self, pending_block: Option<SendablePendingBlock>) {
         let start = Instant::now();
+        // Restored operations were already accepted into the block, so the optional sealing rules
+        // must not reject them even if the configuration has changed.
+        self.seal_criteria.set_optional_enabled(false);
 
         if let Some(pending_block) = pending_block {
             // Transform executed operations into non-executed, so they will be executed again.
@@ -240,13 +262,17 @@ impl ZkSyncStateKeeper {
             vlog::info!("There is no pending block to restore");
         }
 
+        self.seal_criteria.set_optional_enabled(true);
         metrics::histogram!("state_keeper.initialize", start.elapsed());
     }
 
     pub async fn execute_reverted_blocks(&mut self) {
+        // Reverted blocks are re-executed as they were, regardless of the optional sealing rules.
+        self.seal_criteria.set_optional_enabled(false);
         while let Some(block) = self.reverted_blocks.pop_front() {
             self.execute_incomplete_block(block).await;
         }
+        self.seal_criteria.set_optional_enabled(true);
     }
 
     // Run StateKeeper with manual generating and executing blocks and miniblocks
@@ -275,11 +301,22 @@ impl ZkSyncStateKeeper {
     }
 
     // Generate and execute new miniblock every miniblock_interval
-    async fn run(mut self, miniblock_interval: Duration) {
+    async fn run(
+        mut self,
+        miniblock_interval: Duration,
+        exodus_mode: ExodusModeFlag,
+        mut requests: mpsc::Receiver<StateKeeperRequest>,
+    ) {
         let mut timer = time::interval(miniblock_interval);
         loop {
             let start = Instant::now();
-            timer.tick().await;
+            self.wait_for_miniblock_iteration(&mut timer, &mut requests)
+                .await;
+            if exodus_mode.is_entered() {
+                // Blocks can't be committed anymore, so the pending block is left as is.
+                vlog::warn!("Exodus mode is entered, state keeper is stopped");
+                break;
+            }
             // Report timings between two miniblocks.
             // If reported value stays at 0, most likely we have `miniblock_interval` variable too small and
             // spend more time in the loop iteration than this interval.
@@ -297,6 +334,42 @@ impl ZkSyncStateKeeper {
 
             self.execute_proposed_block(proposed_block).await;
         }
+
+        // Actors are expected to live as long as the server does, so the stopped
+        // state keeper stays idle. The requests are rejected from now on.
+        drop(requests);
+        future::pending::<()>().await;
+    }
+
+    /// Waits for the next miniblock iteration, serving the incoming requests meanwhile.
+    async fn wait_for_miniblock_iteration(
+        &self,
+        timer: &mut time::Interval,
+        requests: &mut mpsc::Receiver<StateKeeperRequest>,
+    ) {
+        loop {
+            select! {
+                _ = timer.tick().fuse() => return,
+                request = requests.next() => {
+                    if let Some(request) = request {
+                        self.process_request(request);
+                    }
+                },
+            }
+        }
+    }
+
+    fn process_request(&self, request: StateKeeperRequest) {
+        match request {
+            StateKeeperRequest::SimulateTxs(txs, sender) => {
+                let start = Instant::now();
+                // Transactions are executed on top of the pending block, with its timestamp.
+                let state = tx_simulator::partial_state(&self.state, &txs);
+                let response = tx_simulator::simulate_txs(state, txs, self.pending_block.timestamp);
+                sender.send(response).unwrap_or_default();
+                metrics::histogram!("state_keeper.simulate_txs", start.elapsed());
+            }
+        }
     }
 
     async fn propose_new_block(&mut self, block_timestamp: u64) -> ProposedBlock {
@@ -383,6 +456,7 @@ impl ZkSyncStateKeeper {
 
         // We want to store this variable before moving anything from the pending block.
         let empty_proposed_block = proposed_block.is_empty();
+        self.priority_op_deadline_approaching = proposed_block.priority_op_deadline_approaching;
 
         let mut priority_op_queue = proposed_block
             .priority_ops
@@ -452,16 +526,12 @@ impl ZkSyncStateKeeper {
         // Iteration is complete, increment it in the pending block.
         self.pending_block.increment_iteration();
 
-        // If pending block contains withdrawals we seal it faster
-        let max_miniblock_iterations = if self.pending_block.fast_processing_required {
-            self.config.fast_miniblock_iterations
-        } else {
-            self.config.max_miniblock_iterations
-        };
-
         // Check whether we should seal this block and start processing the next one, or we just need
         // to persist the pending block.
-        if self.pending_block.should_seal(max_miniblock_iterations) {
+        if self
+            .seal_criteria
+            .should_seal(&self.pending_block, system_time_timestamp())
+        {
             self.seal_pending_block().await;
         } else {
             // State keeper may process empty blocks (or blocks containing rejected transactions only), and it's an
@@ -480,27 +550,27 @@ impl ZkSyncStateKeeper {
     fn apply_priority_op(&mut self, priority_op: &PriorityOp) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
         let chunks_needed = priority_op.data.chunks();
-        if self.pending_block.chunks_left < chunks_needed {
-            return ApplyOutcome::NotIncluded;
-        }
-
-        // Check if adding this transaction to the block won't make the contract operations
-        // too expensive.
         let non_executed_op = self
             .state
             .priority_op_to_zksync_op(priority_op.data.clone());
 
-        #[allow(clippy::question_mark)] // False positive, we aren't returning `Result`.
-        if self
-            .pending_block
-            .gas_counter
-            .add_op(&non_executed_op)
-            .is_err()
+        // Check if adding this operation to the block doesn't violate any of the sealing rules,
+        // e.g. the block size or the gas limit. Otherwise we seal the block and this operation
+        // will go into the next one.
+        let candidate = CandidateOps {
+            chunks: chunks_needed,
+            ops: std::slice::from_ref(&non_executed_op),
+        };
+        if !self
+            .seal_criteria
+            .can_include(&self.pending_block, candidate)
         {
-            // We've reached the gas limit, seal the block.
-            // This transaction will go into the next one.
             return ApplyOutcome::NotIncluded;
         }
+        self.pending_block
+            .gas_counter
+            .add_op(&non_executed_op)
+            .expect("We have already checked that we can include this op");
 
         let OpSuccess {
             fee,
@@ -522,6 +592,11 @@ impl ZkSyncStateKeeper {
             fee,
             exec_result.clone(),
         );
+        if self.priority_op_deadline_approaching {
+            // The block has to be processed on L1 before the deadline of the operation.
+            self.pending_block.urgent_priority_ops = true;
+            self.pending_block.fast_processing_required = true;
+        }
 
         metrics::histogram!("state_keeper.apply_priority_op", start.elapsed());
         ApplyOutcome::Included(exec_result)
@@ -537,12 +612,6 @@ impl ZkSyncStateKeeper {
 
         let chunks_needed = self.state.chunks_for_batch(txs);
 
-        // If we can't add the tx to the block due to the size limit, we return this tx,
-        // seal the block and execute it again.
-        if self.pending_block.chunks_left < chunks_needed {
-            return ApplyOutcome::NotIncluded;
-        }
-
         let ops: Vec<_> = txs
             .iter()
             .filter_map(|tx| self.state.zksync_tx_to_zksync_op(tx.tx.clone()).ok())
@@ -572,9 +641,16 @@ impl ZkSyncStateKeeper {
             return ApplyOutcome::Included(executed_operations);
         }
 
-        // If we can't add the tx to the block due to the gas limit, we return this tx,
-        // seal the block and execute it again.
-        if !self.pending_block.gas_counter.can_include(&ops) {
+        // If we can't add the batch to the block due to the sealing rules (e.g. the size or gas limit),
+        // we return this batch, seal the block and execute it again.
+        let candidate = CandidateOps {
+            chunks: chunks_needed,
+            ops: &ops,
+        };
+        if !self
+            .seal_criteria
+            .can_include(&self.pending_block, candidate)
+        {
             return ApplyOutcome::NotIncluded;
         }
 
@@ -648,29 +724,25 @@ impl ZkSyncStateKeeper {
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_tx(tx);
 
-        // If we can't add the tx to the block due to the size limit, we return this tx,
-        // seal the block and execute it again.
-        if self.pending_block.chunks_left < chunks_needed {
+        // We only care about successful conversions, since if conversion failed,
+        // then transaction will fail as well (as it shares the same code base).
+        let non_executed_op = self.state.zksync_tx_to_zksync_op(tx.tx.clone()).ok();
+
+        // If we can't add the tx to the block due to the sealing rules (e.g. the size or gas limit),
+        // we return this tx, seal the block and execute it again.
+        let candidate = CandidateOps {
+            chunks: chunks_needed,
+            ops: non_executed_op
+                .as_ref()
+                .map_or(&[][..], std::slice::from_ref),
+        };
+        if !self
+            .seal_criteria
+            .can_include(&self.pending_block, candidate)
+        {
             return ApplyOutcome::NotIncluded;
         }
 
-        // Check if adding this transaction to the block won't make the contract operations
-        // too expensive.
-        let non_executed_op = self.state.zksync_tx_to_zksync_op(tx.tx.clone());
-        if let Ok(non_executed_op) = non_executed_op {
-            // We only care about successful conversions, since if conversion failed,
-            // then transaction will fail as well (as it shares the same code base).
-            if !self
-                .pending_block
-                .gas_counter
-                .can_include(&[non_executed_op])
-            {
-                // We've reached the gas limit, seal the block.
-                // This transaction will go into the next one.
-                return ApplyOutcome::NotIncluded;
-            }
-        }
-
         if let ZkSyncTx::Withdraw(tx) = &tx.tx {
             // Check if we should mark this block as requiring fast processing.
             if tx.fast {
@@ -887,6 +959,11 @@ impl ZkSyncStateKeeper {
 }
 
 #[must_use]
-pub fn start_state_keeper(sk: ZkSyncStateKeeper, miniblock_interval: Duration) -> JoinHandle<()> {
-    tokio::spawn(sk.run(miniblock_interval))
+pub fn start_state_keeper(
+    sk: ZkSyncStateKeeper,
+    miniblock_interval: Duration,
+    exodus_mode: ExodusModeFlag,
+    requests: mpsc::Receiver<StateKeeperRequest>,
+) -> JoinHandle<()> {
+    tokio::spawn(sk.run(miniblock_interval, exodus_mode, requests))
 }
@@ -1,5 +1,6 @@
 // External uses
 // Workspace uses
+use zksync_crypto::params::CHUNK_BYTES;
 use zksync_state::state::CollectedFee;
 use zksync_types::{
     block::{ExecutedOperations, ExecutedTx, PendingBlock as SendablePendingBlock},
@@ -9,6 +10,7 @@ use zksync_types::{
 
 use crate::committer::AppliedUpdatesRequest;
 // Local uses
+use super::seal_criteria::is_withdrawal;
 
 #[derive(Debug, Clone)]
 pub(super) struct PendingBlock {
@@ -25,11 +27,17 @@ pub(super) struct PendingBlock {
     pub(super) gas_counter: GasCounter,
     /// Option denoting if this block should be generated faster than usual.
     pub(super) fast_processing_required: bool,
+    /// Whether the block contains priority operations with the approaching deadline.
+    pub(super) urgent_priority_ops: bool,
     /// Fee should be applied only when sealing the block (because of corresponding logic in the circuit)
     pub(super) collected_fees: Vec<CollectedFee>,
     /// Number of stored account updates in the db (from `account_updates` field)
     pub(super) stored_account_updates: usize,
     pub(super) timestamp: u64,
+    /// Amount of successful operations which lead to the withdrawal on L1.
+    pub(super) withdrawals: usize,
+    /// Size of the public data of the successful operations.
+    pub(super) pubdata_bytes: usize,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -58,9 +66,12 @@ impl PendingBlock {
             pending_block_iteration: 0,
             gas_counter: GasCounter::new(),
             fast_processing_required: false,
+            urgent_priority_ops: false,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
             timestamp,
+            withdrawals: 0,
+            pubdata_bytes: 0,
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -77,11 +88,6 @@ impl PendingBlock {
         self.failed_txs.is_empty() && self.success_operations.is_empty()
     }
 
-    pub(super) fn should_seal(&self, miniblock_iterations: usize) -> bool {
-        // `>=` in condition since iterations start with 0.
-        self.chunks_left == 0 || self.pending_block_iteration >= miniblock_iterations
-    }
-
     pub(super) fn add_successful_execution(
         &mut self,
         chunks_used: usize,
@@ -110,6 +116,10 @@ impl PendingBlock {
         if exec_result.is_priority() {
             self.unprocessed_priority_op_current += 1;
         }
+        if exec_result.get_executed_op().map_or(false, is_withdrawal) {
+            self.withdrawals += 1;
+        }
+        self.pubdata_bytes += chunks_used * CHUNK_BYTES;
 
         self.success_operations.push(exec_result);
     }
@@ -166,6 +176,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::state_keeper::seal_criteria::{SealCriteria, SealCriteriaConfig};
 
     const STARTING_BLOCK: BlockNumber = BlockNumber(1);
     const CHUNKS_PER_BLOCK: usize = 100;
@@ -241,6 +252,12 @@ mod tests {
     #[test]
     fn basic_properties() {
         let mut pending_block = pending_block();
+        let seal_criteria = SealCriteria::new(
+            &SealCriteriaConfig::default(),
+            MAX_ITERATIONS,
+            MAX_ITERATIONS,
+        );
+        let now = 0;
 
         // Checks for empty block.
         assert_eq!(
@@ -255,7 +272,7 @@ mod tests {
         // Methods testing on the empty block.
         assert!(pending_block.is_empty(), "Block should be empty");
         assert!(
-            !pending_block.should_seal(MAX_ITERATIONS),
+            !seal_criteria.should_seal(&pending_block, now),
             "Should no seal empty block with no enough iterations"
         );
 
@@ -284,7 +301,7 @@ mod tests {
         );
 
         assert!(
-            !pending_block.should_seal(MAX_ITERATIONS),
+            !seal_criteria.should_seal(&pending_block, now),
             "Block should not be sealed after 1 iteration"
         );
 
@@ -295,7 +312,7 @@ mod tests {
         );
 
         assert!(
-            pending_block.should_seal(MAX_ITERATIONS),
+            seal_criteria.should_seal(&pending_block, now),
             "Block should be sealed after 2 iteration"
         );
 
@@ -0,0 +1,279 @@
+//! Rules deciding when the pending block has to be sealed.
+//!
+//! Block size and gas limits are always applied, since a block violating them can't be processed
+//! on L1. Blocks with the priority operations close to their deadline are always sealed right away,
+//! since missing the deadline puts the rollup into the exodus mode. The other rules are optional
+//! and are chosen in the configuration, so that the latency of the blocks can be traded against
+//! the L1 cost of their processing.
+
+// Built-in uses
+use std::fmt;
+use std::time::Duration;
+// Workspace uses
+use zksync_config::configs::chain::{SealCriterionKind, StateKeeper as ChainStateKeeperConfig};
+use zksync_crypto::params::CHUNK_BYTES;
+use zksync_types::ZkSyncOp;
+// Local uses
+use super::pending_block::PendingBlock;
+
+/// Optional rules of sealing the block used by the state keeper, along with their parameters.
+#[derive(Debug, Clone)]
+pub struct SealCriteriaConfig {
+    /// Rules applied in addition to the block size and gas limits.
+    pub criteria: Vec<SealCriterionKind>,
+    /// Maximum age of the non-empty block before it's sealed.
+    pub max_block_age: Duration,
+    /// Maximum amount of withdrawal operations in the block.
+    pub max_withdrawals_per_block: usize,
+    /// Maximum size of the block public data in bytes.
+    pub max_block_pubdata_bytes: usize,
+}
+
+impl Default for SealCriteriaConfig {
+    /// Only the miniblock iterations rule is used, which matches the behavior of the state keeper
+    /// before the sealing rules became configurable.
+    fn default() -> Self {
+        Self {
+            criteria: vec![SealCriterionKind::MiniblockIterations],
+            max_block_age: Duration::from_secs(60),
+            max_withdrawals_per_block: 100,
+            max_block_pubdata_bytes: usize::MAX,
+        }
+    }
+}
+
+impl From<&ChainStateKeeperConfig> for SealCriteriaConfig {
+    fn from(config: &ChainStateKeeperConfig) -> Self {
+        Self {
+            criteria: config.seal_criteria.clone(),
+            max_block_age: config.max_block_age(),
+            max_withdrawals_per_block: config.max_withdrawals_per_block,
+            max_block_pubdata_bytes: config.max_block_pubdata_bytes,
+        }
+    }
+}
+
+/// Operations which the state keeper attempts to add to the pending block.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CandidateOps<'a> {
+    /// Amount of chunks required for the operations.
+    pub chunks: usize,
+    /// Operations to be executed. May be empty if the transaction can't be converted into
+    /// the operation (such a transaction will fail during the execution anyway).
+    pub ops: &'a [ZkSyncOp],
+}
+
+/// Rule of sealing the pending block.
+pub(super) trait SealCriterion: fmt::Debug + Send + Sync {
+    /// Checks whether the operations can be added to the pending block.
+    /// If they can't, the pending block is sealed and the operations go into the next one.
+    fn can_include(&self, _block: &PendingBlock, _candidate: CandidateOps<'_>) -> bool {
+        true
+    }
+
+    /// Checks whether the pending block has to be sealed after the miniblock iteration.
+    /// `now` is the current timestamp in seconds.
+    fn should_seal(&self, _block: &PendingBlock, _now: u64) -> bool {
+        false
+    }
+}
+
+/// Block can't contain more chunks than the largest supported block size.
+#[derive(Debug)]
+struct BlockSizeCriterion;
+
+impl SealCriterion for BlockSizeCriterion {
+    fn can_include(&self, block: &PendingBlock, candidate: CandidateOps<'_>) -> bool {
+        block.chunks_left >= candidate.chunks
+    }
+
+    fn should_seal(&self, block: &PendingBlock, _now: u64) -> bool {
+        block.chunks_left == 0
+    }
+}
+
+/// Block operations must fit into the gas limit of the L1 transaction.
+#[derive(Debug)]
+struct GasLimitCriterion;
+
+impl SealCriterion for GasLimitCriterion {
+    fn can_include(&self, block: &PendingBlock, candidate: CandidateOps<'_>) -> bool {
+        block.gas_counter.can_include(candidate.ops)
+    }
+}
+
+/// Block containing the priority operations with the approaching deadline is sealed
+/// after the miniblock iteration, so that it can be sent to L1 as soon as possible.
+#[derive(Debug)]
+struct PriorityOpDeadlineCriterion;
+
+impl SealCriterion for PriorityOpDeadlineCriterion {
+    fn should_seal(&self, block: &PendingBlock, _now: u64) -> bool {
+        block.urgent_priority_ops
+    }
+}
+
+/// Block is sealed after the certain amount of miniblock iterations, which is lower
+/// for the blocks containing fast withdrawals.
+#[derive(Debug)]
+struct MiniblockIterationsCriterion {
+    max_iterations: usize,
+    fast_iterations: usize,
+}
+
+impl SealCriterion for MiniblockIterationsCriterion {
+    fn should_seal(&self, block: &PendingBlock, _now: u64) -> bool {
+        let max_iterations = if block.fast_processing_required {
+            self.fast_iterations
+        } else {
+            self.max_iterations
+        };
+        // `>=` in condition since iterations start with 0.
+        block.pending_block_iteration >= max_iterations
+    }
+}
+
+/// Block is sealed once the configured time has passed since its first operation.
+#[derive(Debug)]
+struct BlockAgeCriterion {
+    max_age: Duration,
+}
+
+impl SealCriterion for BlockAgeCriterion {
+    fn should_seal(&self, block: &PendingBlock, now: u64) -> bool {
+        !block.success_operations.is_empty()
+            && now >= block.timestamp.saturating_add(self.max_age.as_secs())
+    }
+}
+
+/// Limits the amount of withdrawals in the block, since each of them has to be completed on L1.
+///
+/// Empty block always accepts the operations, otherwise they would never be included.
+#[derive(Debug)]
+struct WithdrawalsCriterion {
+    max_withdrawals: usize,
+}
+
+impl SealCriterion for WithdrawalsCriterion {
+    fn can_include(&self, block: &PendingBlock, candidate: CandidateOps<'_>) -> bool {
+        let new_withdrawals = candidate.ops.iter().filter(|op| is_withdrawal(op)).count();
+        block.success_operations.is_empty()
+            || block.withdrawals + new_withdrawals <= self.max_withdrawals
+    }
+
+    fn should_seal(&self, block: &PendingBlock, _now: u64) -> bool {
+        block.withdrawals >= self.max_withdrawals
+    }
+}
+
+/// Limits the size of the block public data published on L1.
+///
+/// Empty block always accepts the operations, otherwise they would never be included.
+#[derive(Debug)]
+struct PubdataCriterion {
+    max_bytes: usize,
+}
+
+impl SealCriterion for PubdataCriterion {
+    fn can_include(&self, block: &PendingBlock, candidate: CandidateOps<'_>) -> bool {
+        block.success_operations.is_empty()
+            || block.pubdata_bytes + candidate.chunks * CHUNK_BYTES <= self.max_bytes
+    }
+
+    fn should_seal(&self, block: &PendingBlock, _now: u64) -> bool {
+        block.pubdata_bytes >= self.max_bytes
+    }
+}
+
+/// Checks whether the operation leads to the withdrawal on L1.
+pub(super) fn is_withdrawal(op: &ZkSyncOp) -> bool {
+    matches!(
+        op,
+        ZkSyncOp::Withdraw(_)
+            | ZkSyncOp::WithdrawNFT(_)
+            | ZkSyncOp::ForcedExit(_)
+            | ZkSyncOp::FullExit(_)
+    )
+}
+
+/// Set of the rules used by the state keeper.
+#[derive(Debug)]
+pub(super) struct SealCriteria {
+    /// Block size and gas limits along with the priority operations deadline, which are always applied.
+    limits: Vec<Box<dyn SealCriterion>>,
+    /// Rules chosen in the configuration.
+    optional: Vec<Box<dyn SealCriterion>>,
+    /// Whether the optional rules are applied.
+    optional_enabled: bool,
+}
+
+impl SealCriteria {
+    pub(super) fn new(
+        config: &SealCriteriaConfig,
+        max_miniblock_iterations: usize,
+        fast_miniblock_iterations: usize,
+    ) -> Self {
+        let limits: Vec<Box<dyn SealCriterion>> = vec![
+            Box::new(BlockSizeCriterion),
+            Box::new(GasLimitCriterion),
+            Box::new(PriorityOpDeadlineCriterion),
+        ];
+        let optional = config
+            .criteria
+            .iter()
+            .map(|kind| -> Box<dyn SealCriterion> {
+                match kind {
+                    SealCriterionKind::MiniblockIterations => {
+                        Box::new(MiniblockIterationsCriterion {
+                            max_iterations: max_miniblock_iterations,
+                            fast_iterations: fast_miniblock_iterations,
+                        })
+                    }
+                    SealCriterionKind::BlockAge => Box::new(BlockAgeCriterion {
+                        max_age: config.max_block_age,
+                    }),
+                    SealCriterionKind::Withdrawals => Box::new(WithdrawalsCriterion {
+                        max_withdrawals: config.max_withdrawals_per_block,
+                    }),
+                    SealCriterionKind::Pubdata => Box::new(PubdataCriterion {
+                        max_bytes: config.max_block_pubdata_bytes,
+                    }),
+                }
+            })
+            .collect();
+
+        Self {
+            limits,
+            optional,
+            optional_enabled: true,
+        }
+    }
+
+    /// Enables or disables the optional rules. They are disabled while the blocks formed before
+    /// (e.g. the restored pending block) are re-executed, since the configuration may have changed
+    /// since then.
+    pub(super) fn set_optional_enabled(&mut self, enabled: bool) {
+        self.optional_enabled = enabled;
+    }
+
+    fn active(&self) -> impl Iterator<Item = &dyn SealCriterion> {
+        let optional: &[Box<dyn SealCriterion>] = if self.optional_enabled {
+            &self.optional
+        } else {
+            &[]
+        };
+        self.limits.iter().chain(optional).map(Box::as_ref)
+    }
+
+    /// Checks whether the operations can be added to the pending block according to all the rules.
+    pub(super) fn can_include(&self, block: &PendingBlock, candidate: CandidateOps<'_>) -> bool {
+        self.active()
+            .all(|criterion| criterion.can_include(block, candidate))
+    }
+
+    /// Checks whether any of the rules requires the pending block to be sealed.
+    pub(super) fn should_seal(&self, block: &PendingBlock, now: u64) -> bool {
+        self.active()
+            .any(|criterion| criterion.should_seal(block, now))
+    }
+}
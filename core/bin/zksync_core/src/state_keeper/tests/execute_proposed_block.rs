@@ -117,6 +117,7 @@ async fn small() {
             SignedTxVariant::Tx(bad_withdraw),
         ],
         priority_ops: vec![deposit],
+        priority_op_deadline_approaching: false,
     };
     let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
     tester
@@ -159,6 +160,7 @@ async fn few_chunks() {
             SignedTxVariant::Tx(bad_withdraw),
         ],
         priority_ops: vec![deposit],
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -196,6 +198,7 @@ async fn few_iterations() {
             SignedTxVariant::Tx(bad_withdraw),
         ],
         priority_ops: vec![deposit],
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -225,6 +228,7 @@ async fn fast_withdrawal() {
     let proposed_block = ProposedBlock {
         priority_ops: Vec::new(),
         txs: vec![withdraw.into()],
+        priority_op_deadline_approaching: false,
     };
 
     tester
@@ -250,6 +254,7 @@ async fn pending_block_updates() {
     let proposed_block = ProposedBlock {
         txs: vec![],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     tester
@@ -278,6 +283,7 @@ async fn pending_block_updates() {
     let proposed_block = ProposedBlock {
         txs: vec![SignedTxVariant::Tx(bad_withdraw)],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     tester
@@ -306,6 +312,7 @@ async fn pending_block_updates() {
     let proposed_block = ProposedBlock {
         txs: vec![SignedTxVariant::Tx(good_withdraw)],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -335,6 +342,7 @@ async fn pending_block_updates() {
     let proposed_block = ProposedBlock {
         txs: vec![SignedTxVariant::Tx(bad_withdraw)],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -356,6 +364,7 @@ async fn pending_block_updates() {
     let proposed_block = ProposedBlock {
         txs: vec![],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     let pending_block_iteration = tester.state_keeper.pending_block.pending_block_iteration;
@@ -400,6 +409,7 @@ async fn pending_block_diff() {
             SignedTxVariant::Tx(bad_withdraw_1.clone()),
         ],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     let good_withdraw_2 = create_account_and_withdrawal(
@@ -424,6 +434,7 @@ async fn pending_block_diff() {
             SignedTxVariant::Tx(bad_withdraw_2.clone()),
         ],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     tester
@@ -533,6 +544,7 @@ async fn transfers_with_different_timestamps() {
             SignedTxVariant::Tx(correct_transfer.clone()),
         ],
         priority_ops: vec![],
+        priority_op_deadline_approaching: false,
     };
 
     tester
@@ -565,3 +577,35 @@ async fn transfers_with_different_timestamps() {
         })
         .await;
 }
+
+/// Checks that the block with the priority operations close to their deadline
+/// is sealed right away and requires the fast processing.
+#[tokio::test]
+async fn priority_op_deadline_approaching() {
+    let mut tester = StateKeeperTester::new(20, 5, 5);
+
+    let proposed_block = ProposedBlock {
+        txs: vec![],
+        priority_ops: vec![create_deposit(TokenId(0), 12u32)],
+        priority_op_deadline_approaching: false,
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    tester.assert_pending().await;
+
+    let proposed_block = ProposedBlock {
+        txs: vec![],
+        priority_ops: vec![create_deposit(TokenId(0), 12u32)],
+        priority_op_deadline_approaching: true,
+    };
+    tester
+        .state_keeper
+        .execute_proposed_block(proposed_block)
+        .await;
+    let (block_commit_request, _) = tester.unwrap_sealed_update().await;
+    assert_eq!(block_commit_request.block.block_transactions.len(), 2);
+    assert!(block_commit_request.block_metadata.fast_processing);
+    assert!(!tester.state_keeper.pending_block.urgent_priority_ops);
+}
@@ -85,6 +85,7 @@ async fn gas_count_change() {
             eth_signatures: Vec::new(),
         })],
         priority_ops: Vec::new(),
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -171,6 +172,7 @@ async fn gas_limit_sealing() {
     let proposed_block = ProposedBlock {
         txs,
         priority_ops: Vec::new(),
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -219,6 +221,7 @@ async fn batch_gas_limit() {
             eth_signatures: Vec::new(),
         })],
         priority_ops: Vec::new(),
+        priority_op_deadline_approaching: false,
     };
     // Execute big batch.
     tester
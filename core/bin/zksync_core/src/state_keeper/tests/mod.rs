@@ -7,6 +7,7 @@ mod apply_tx;
 mod execute_proposed_block;
 mod gas_limit;
 mod pending_block;
+mod seal_criteria;
 mod utils;
 
 /// Checks that StateKeeper will panic with incorrect initialization data
@@ -37,6 +38,7 @@ fn test_create_incorrect_state_keeper() {
         vec![1, 2, 2], // `available_block_chunk_sizes` must be strictly increasing.
         MAX_ITERATIONS,
         FAST_ITERATIONS,
+        Default::default(),
         events_sender,
     );
 }
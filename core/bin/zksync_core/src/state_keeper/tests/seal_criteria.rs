@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use zksync_config::configs::chain::SealCriterionKind;
+use zksync_crypto::params::CHUNK_BYTES;
+use zksync_types::{AccountId, DepositOp, TokenId};
+
+use super::utils::*;
+use crate::state_keeper::SealCriteriaConfig;
+
+fn seal_criteria(criteria: Vec<SealCriterionKind>) -> SealCriteriaConfig {
+    SealCriteriaConfig {
+        criteria,
+        ..Default::default()
+    }
+}
+
+/// Checks that the block can't contain more withdrawals than allowed,
+/// while other transactions can still be added to it.
+#[test]
+fn withdrawals_limit() {
+    let config = SealCriteriaConfig {
+        max_withdrawals_per_block: 1,
+        ..seal_criteria(vec![SealCriterionKind::Withdrawals])
+    };
+    let mut tester = StateKeeperTester::with_seal_criteria(100, 10, 10, config);
+
+    let first_withdrawal = create_account_and_withdrawal(
+        &mut tester,
+        TokenId(0),
+        AccountId(1),
+        200u32,
+        145u32,
+        Default::default(),
+    );
+    let second_withdrawal = create_account_and_withdrawal(
+        &mut tester,
+        TokenId(0),
+        AccountId(2),
+        200u32,
+        145u32,
+        Default::default(),
+    );
+    let transfer =
+        create_account_and_transfer(&mut tester, TokenId(0), AccountId(3), 200u32, 100u32);
+
+    assert!(tester
+        .state_keeper
+        .apply_tx(&first_withdrawal)
+        .is_included());
+    assert!(tester
+        .state_keeper
+        .apply_tx(&second_withdrawal)
+        .is_not_included());
+    assert!(tester.state_keeper.apply_tx(&transfer).is_included());
+    assert_eq!(tester.state_keeper.pending_block.withdrawals, 1);
+}
+
+/// Checks that the block public data can't exceed the limit,
+/// unless the operation is the first one in the block.
+#[test]
+fn pubdata_limit() {
+    let config = SealCriteriaConfig {
+        max_block_pubdata_bytes: DepositOp::CHUNKS * CHUNK_BYTES + 1,
+        ..seal_criteria(vec![SealCriterionKind::Pubdata])
+    };
+    let mut tester = StateKeeperTester::with_seal_criteria(100, 10, 10, config);
+
+    let deposit = create_deposit(TokenId(0), 1u32);
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_included());
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_not_included());
+
+    // Even a single operation exceeds the limit, but an empty block must accept it.
+    let config = SealCriteriaConfig {
+        max_block_pubdata_bytes: 1,
+        ..seal_criteria(vec![SealCriterionKind::Pubdata])
+    };
+    let mut tester = StateKeeperTester::with_seal_criteria(100, 10, 10, config);
+    assert!(tester
+        .state_keeper
+        .apply_priority_op(&deposit)
+        .is_included());
+}
+
+/// Checks that the non-empty block is sealed once it gets old enough,
+/// regardless of the amount of miniblock iterations.
+#[tokio::test]
+async fn block_age() {
+    let config = SealCriteriaConfig {
+        max_block_age: Duration::from_secs(0),
+        ..seal_criteria(vec![SealCriterionKind::BlockAge])
+    };
+    let mut tester = StateKeeperTester::with_seal_criteria(100, 10, 10, config);
+
+    // Empty block is not sealed.
+    tester
+        .state_keeper
+        .execute_proposed_block(Default::default())
+        .await;
+    tester.assert_empty().await;
+
+    apply_single_transfer(&mut tester).await;
+    tester.assert_sealed().await;
+}
+
+/// Checks that without the miniblock iterations rule the block is sealed only when it's full.
+#[tokio::test]
+async fn no_miniblock_iterations() {
+    let mut tester = StateKeeperTester::with_seal_criteria(6, 1, 1, seal_criteria(Vec::new()));
+
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_pending_with(|block| assert_eq!(block.chunks_left, 4))
+        .await;
+    apply_single_transfer(&mut tester).await;
+    tester
+        .assert_pending_with(|block| assert_eq!(block.chunks_left, 2))
+        .await;
+    apply_single_transfer(&mut tester).await;
+    tester.assert_sealed().await;
+}
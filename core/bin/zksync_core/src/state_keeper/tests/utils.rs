@@ -1,5 +1,7 @@
 use crate::committer::{AppliedUpdatesRequest, BlockCommitRequest};
-use crate::state_keeper::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
+use crate::state_keeper::{
+    CommitRequest, SealCriteriaConfig, ZkSyncStateInitParams, ZkSyncStateKeeper,
+};
 use chrono::Utc;
 use futures::{channel::mpsc, stream::StreamExt};
 use num::BigUint;
@@ -23,6 +25,20 @@ pub struct StateKeeperTester {
 
 impl StateKeeperTester {
     pub fn new(available_chunk_size: usize, max_iterations: usize, fast_iterations: usize) -> Self {
+        Self::with_seal_criteria(
+            available_chunk_size,
+            max_iterations,
+            fast_iterations,
+            SealCriteriaConfig::default(),
+        )
+    }
+
+    pub fn with_seal_criteria(
+        available_chunk_size: usize,
+        max_iterations: usize,
+        fast_iterations: usize,
+        seal_criteria: SealCriteriaConfig,
+    ) -> Self {
         const CHANNEL_SIZE: usize = 32768;
         let (events_sender, _events_receiver) = mpsc::channel(CHANNEL_SIZE);
         let (request_tx, _request_rx) = mpsc::channel(CHANNEL_SIZE);
@@ -43,6 +59,7 @@ impl StateKeeperTester {
             vec![available_chunk_size],
             max_iterations,
             fast_iterations,
+            seal_criteria,
             events_sender,
         );
 
@@ -312,6 +329,7 @@ pub async fn apply_single_transfer(tester: &mut StateKeeperTester) {
     let proposed_block = ProposedBlock {
         txs: vec![SignedTxVariant::Tx(transfer)],
         priority_ops: Vec::new(),
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -331,6 +349,7 @@ pub async fn apply_batch_with_two_transfers(tester: &mut StateKeeperTester) {
             eth_signatures: Vec::new(),
         })],
         priority_ops: Vec::new(),
+        priority_op_deadline_approaching: false,
     };
     tester
         .state_keeper
@@ -0,0 +1,245 @@
+//! Dry-run execution of the transactions against the state of the state keeper.
+//!
+//! Only the accounts affected by the transactions are copied from the state keeper,
+//! so the simulation doesn't require the whole state tree to be cloned.
+
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+// Workspace uses
+use zksync_api_types::v02::transaction::{SimulateTxsResponse, SimulatedTx};
+use zksync_crypto::params::NFT_STORAGE_ACCOUNT_ID;
+use zksync_state::state::{OpSuccess, ZkSyncState};
+use zksync_types::{tx::SignedZkSyncTx, AccountId, AccountMap, Address, TokenId, ZkSyncTx};
+
+/// Accounts and NFTs which are touched by the transactions.
+#[derive(Debug, Default)]
+struct AffectedEntities {
+    account_ids: HashSet<AccountId>,
+    addresses: HashSet<Address>,
+    nfts: HashSet<TokenId>,
+}
+
+impl AffectedEntities {
+    fn new(txs: &[ZkSyncTx]) -> Self {
+        let mut entities = Self::default();
+        for tx in txs {
+            match tx {
+                ZkSyncTx::Transfer(tx) => {
+                    entities.account_ids.insert(tx.account_id);
+                    entities.addresses.extend([tx.from, tx.to].iter());
+                }
+                ZkSyncTx::Withdraw(tx) => {
+                    entities.account_ids.insert(tx.account_id);
+                    entities.addresses.insert(tx.from);
+                }
+                ZkSyncTx::Close(tx) => {
+                    entities.addresses.insert(tx.account);
+                }
+                ZkSyncTx::ChangePubKey(tx) => {
+                    entities.account_ids.insert(tx.account_id);
+                    entities.addresses.insert(tx.account);
+                }
+                ZkSyncTx::ForcedExit(tx) => {
+                    entities.account_ids.insert(tx.initiator_account_id);
+                    entities.addresses.insert(tx.target);
+                }
+                ZkSyncTx::Swap(tx) => {
+                    entities.account_ids.insert(tx.submitter_id);
+                    entities.addresses.insert(tx.submitter_address);
+                    for order in [&tx.orders.0, &tx.orders.1].iter() {
+                        entities.account_ids.insert(order.account_id);
+                        entities.addresses.insert(order.recipient_address);
+                    }
+                }
+                ZkSyncTx::MintNFT(tx) => {
+                    entities
+                        .account_ids
+                        .extend([tx.creator_id, NFT_STORAGE_ACCOUNT_ID].iter());
+                    entities
+                        .addresses
+                        .extend([tx.creator_address, tx.recipient].iter());
+                }
+                ZkSyncTx::WithdrawNFT(tx) => {
+                    entities.account_ids.insert(tx.account_id);
+                    entities.addresses.insert(tx.from);
+                    entities.nfts.insert(tx.token);
+                }
+            }
+        }
+        entities
+    }
+}
+
+/// Copies the part of the state required to execute the transactions.
+pub(super) fn partial_state(state: &ZkSyncState, txs: &[ZkSyncTx]) -> ZkSyncState {
+    let AffectedEntities {
+        mut account_ids,
+        mut addresses,
+        nfts,
+    } = AffectedEntities::new(txs);
+
+    let mut nft_map = HashMap::new();
+    for token_id in nfts {
+        if let Some(nft) = state.nfts.get(&token_id) {
+            // Creator of the NFT is recorded in the withdrawal operation.
+            addresses.insert(nft.creator_address);
+            nft_map.insert(token_id, nft.clone());
+        }
+    }
+
+    for address in addresses {
+        if let Some((account_id, _)) = state.get_account_by_address(&address) {
+            account_ids.insert(account_id);
+        }
+    }
+    // The last created account is copied so that the new accounts get the correct IDs.
+    let next_free_id = state.get_free_account_id();
+    if *next_free_id > 0 {
+        account_ids.insert(AccountId(*next_free_id - 1));
+    }
+
+    let mut accounts = AccountMap::default();
+    for account_id in account_ids {
+        if let Some(account) = state.get_account(account_id) {
+            accounts.insert(account_id, account);
+        }
+    }
+
+    let mut state = ZkSyncState::from_acc_map(accounts);
+    state.nfts.extend(nft_map);
+    state
+}
+
+/// Executes the transactions on the provided state. More than one transaction is executed
+/// as a batch.
+pub(super) fn simulate_txs(
+    mut state: ZkSyncState,
+    txs: Vec<ZkSyncTx>,
+    block_timestamp: u64,
+) -> SimulateTxsResponse {
+    let chunks: Vec<_> = txs.iter().map(|tx| state.chunks_for_tx(tx)).collect();
+    let results: Vec<Result<OpSuccess, String>> = if txs.len() == 1 {
+        vec![state
+            .execute_tx(txs[0].clone(), block_timestamp)
+            .map_err(|err| err.to_string())]
+    } else {
+        let signed_txs: Vec<_> = txs.iter().cloned().map(SignedZkSyncTx::from).collect();
+        state
+            .execute_txs_batch(&signed_txs, block_timestamp)
+            .into_iter()
+            .map(|result| result.map_err(|err| err.to_string()))
+            .collect()
+    };
+
+    let txs = txs
+        .iter()
+        .zip(chunks)
+        .zip(results)
+        .map(|((tx, chunks), result)| match result {
+            Ok(success) => SimulatedTx {
+                tx_hash: tx.hash(),
+                success: true,
+                fail_reason: None,
+                chunks: success.executed_op.chunks(),
+                // The fee required for the transaction is reported by the API server.
+                fee: None,
+                updates: success.updates,
+            },
+            Err(fail_reason) => SimulatedTx {
+                tx_hash: tx.hash(),
+                success: false,
+                fail_reason: Some(fail_reason),
+                chunks,
+                fee: None,
+                updates: Vec::new(),
+            },
+        })
+        .collect();
+
+    SimulateTxsResponse {
+        block_timestamp,
+        txs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use zksync_types::{tx::Transfer, Account, Nonce, PubKeyHash};
+
+    use super::*;
+
+    fn state_with_account(balance: u32) -> (ZkSyncState, Address) {
+        let address = Address::repeat_byte(0x11);
+        let mut account = Account::default_with_address(&address);
+        account.pub_key_hash = PubKeyHash { data: [0xaa; 20] };
+        account.set_balance(TokenId(0), BigUint::from(balance));
+        let mut accounts = AccountMap::default();
+        accounts.insert(AccountId(1), account);
+        (ZkSyncState::from_acc_map(accounts), address)
+    }
+
+    fn transfer(from: Address, amount: u32, nonce: u32) -> ZkSyncTx {
+        Transfer::new(
+            AccountId(1),
+            from,
+            Address::repeat_byte(0x22),
+            TokenId(0),
+            BigUint::from(amount),
+            BigUint::from(1u32),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        )
+        .into()
+    }
+
+    /// Checks that only the affected accounts and the last created one are copied,
+    /// so the new accounts get the correct IDs.
+    #[test]
+    fn partial_state_accounts() {
+        let (mut state, address) = state_with_account(100);
+        for (id, byte) in [(2, 0x33), (3, 0x44)].iter() {
+            let account = Account::default_with_address(&Address::repeat_byte(*byte));
+            state.insert_account(AccountId(*id), account);
+        }
+
+        let txs = vec![transfer(address, 10, 0)];
+        let partial = partial_state(&state, &txs);
+        assert!(partial.get_account(AccountId(1)).is_some());
+        assert!(partial.get_account(AccountId(2)).is_none());
+        assert!(partial.get_account(AccountId(3)).is_some());
+        assert_eq!(partial.get_free_account_id(), AccountId(4));
+
+        // The recipient doesn't exist, so it's created with the next free ID.
+        let response = simulate_txs(partial, txs, 0);
+        let tx = &response.txs[0];
+        assert!(tx.success, "{:?}", tx.fail_reason);
+        assert!(tx.updates.iter().any(|(id, _)| *id == AccountId(4)));
+    }
+
+    /// Checks that unsigned transactions are executed and the failed batch reports no updates.
+    #[test]
+    fn simulate_transfers() {
+        let (state, address) = state_with_account(100);
+        let response = simulate_txs(state, vec![transfer(address, 10, 0)], 0);
+        assert_eq!(response.txs.len(), 1);
+        let tx = &response.txs[0];
+        assert!(tx.success, "{:?}", tx.fail_reason);
+        assert!(!tx.updates.is_empty());
+
+        // The second transfer exceeds the balance, so the whole batch fails.
+        let (state, address) = state_with_account(100);
+        let response = simulate_txs(
+            state,
+            vec![transfer(address, 10, 0), transfer(address, 100, 1)],
+            0,
+        );
+        assert_eq!(response.txs.len(), 2);
+        for tx in response.txs {
+            assert!(!tx.success);
+            assert!(tx.fail_reason.is_some());
+            assert!(tx.updates.is_empty());
+        }
+    }
+}
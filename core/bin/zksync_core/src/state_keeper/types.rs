@@ -2,8 +2,9 @@
 use futures::channel::oneshot;
 use itertools::Itertools;
 // Workspace uses
+use zksync_api_types::v02::transaction::SimulateTxsResponse;
 use zksync_mempool::ProposedBlock;
-use zksync_types::{Account, AccountId, Address};
+use zksync_types::{Account, AccountId, Address, ZkSyncTx};
 // Local uses
 use crate::state_keeper::init_params::ZkSyncStateInitParams;
 
@@ -17,6 +18,14 @@ pub enum StateKeeperTestkitRequest {
     GetCurrentState(oneshot::Sender<ZkSyncStateInitParams>),
 }
 
+/// Requests served by the state keeper between the miniblock iterations.
+#[derive(Debug)]
+pub enum StateKeeperRequest {
+    /// Executes the transactions against the current state without applying them.
+    /// More than one transaction is executed as a batch.
+    SimulateTxs(Vec<ZkSyncTx>, oneshot::Sender<SimulateTxsResponse>),
+}
+
 #[derive(Debug)]
 pub enum ApplyOutcome<T> {
     Included(T),
@@ -0,0 +1,40 @@
+//! Tool to replay a recorded sequence of aggregated operations against `ETHSender`
+//! with a scripted L1 timeline, so that its configuration can be tuned offline.
+//!
+//! `ETHSender` and `GasAdjuster` settings are loaded from the environment, e.g.
+//! `ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT=5 eth_sender_simulator scenario.json`.
+//! The simulation keeps all the data in memory and doesn't require a database.
+
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+use zksync_config::ETHSenderConfig;
+use zksync_eth_sender::simulator::{run_simulation, Scenario};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync eth_sender simulator", author = "Matter Labs")]
+#[structopt(about = "Tool to replay aggregated operations against eth_sender with a scripted L1")]
+struct Opt {
+    /// Path to the JSON file with the scenario to replay.
+    #[structopt(parse(from_os_str))]
+    scenario: PathBuf,
+    /// Print the report in the JSON format.
+    #[structopt(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let scenario: Scenario = serde_json::from_str(&fs::read_to_string(&opt.scenario)?)?;
+    let config = ETHSenderConfig::from_env();
+
+    let report = run_simulation(config, scenario).await?;
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+
+    Ok(())
+}
@@ -12,56 +12,97 @@ use super::transactions::ETHStats;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::block::Block;
 
+/// Connection passed to the `DatabaseInterface` methods.
+///
+/// Databases which don't keep the data in the storage (e.g. the one used by the simulator)
+/// use the detached connection, so no actual database is required to run `ETHSender` with them.
+pub(super) enum DatabaseConnection<'a> {
+    Storage(StorageProcessor<'a>),
+    Detached,
+}
+
+impl<'a> DatabaseConnection<'a> {
+    pub async fn start_transaction<'c: 'b, 'b>(
+        &'c mut self,
+    ) -> anyhow::Result<DatabaseConnection<'b>> {
+        match self {
+            Self::Storage(storage) => Ok(DatabaseConnection::Storage(
+                storage.start_transaction().await?,
+            )),
+            Self::Detached => Ok(DatabaseConnection::Detached),
+        }
+    }
+
+    pub async fn commit(self) -> anyhow::Result<()> {
+        match self {
+            Self::Storage(storage) => Ok(storage.commit().await?),
+            Self::Detached => Ok(()),
+        }
+    }
+
+    /// Returns the underlying storage connection.
+    pub fn storage(&mut self) -> anyhow::Result<&mut StorageProcessor<'a>> {
+        match self {
+            Self::Storage(storage) => Ok(storage),
+            Self::Detached => Err(anyhow::format_err!(
+                "Connection is not backed by the storage"
+            )),
+        }
+    }
+}
+
 /// Abstract database access trait, optimized for the needs of `ETHSender`.
 #[async_trait::async_trait]
 pub(super) trait DatabaseInterface {
     /// Returns connection to the database.
-    async fn acquire_connection(&self) -> anyhow::Result<StorageProcessor<'_>>;
+    async fn acquire_connection(&self) -> anyhow::Result<DatabaseConnection<'_>>;
 
     /// Loads the unconfirmed and unprocessed operations from the database.
     /// Unconfirmed operations are Ethereum operations that were started, but not confirmed yet.
     /// Unprocessed operations are zkSync operations that were not started at all.
     async fn load_unconfirmed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<VecDeque<ETHOperation>>;
 
     /// Load all the aggregated operations that have no confirmation yet and have not yet been sent to Ethereum.
     /// Should be used after server restart only.
     async fn restore_unprocessed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<()>;
 
     /// Loads the unprocessed operations from the database.
     /// Unprocessed operations are zkSync operations that were not started at all.
     async fn load_new_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<Vec<(i64, AggregatedOperation)>>;
 
     /// Remove the unprocessed operations from the database.
     async fn remove_unprocessed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         operations_id: Vec<i64>,
     ) -> anyhow::Result<()>;
 
     /// Saves a new unconfirmed operation to the database.
+    #[allow(clippy::too_many_arguments)]
     async fn save_new_eth_tx(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         eth_op_id: i64,
         hash: &H256,
     ) -> anyhow::Result<()>;
@@ -69,40 +110,42 @@ pub(super) trait DatabaseInterface {
     /// Adds a new tx info to the previously started Ethereum operation.
     async fn update_eth_tx(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Marks an operation as completed in the database.
     async fn confirm_operation(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         hash: &H256,
         op: &ETHOperation,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
-    async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats>;
+    async fn load_stats(&self, connection: &mut DatabaseConnection<'_>)
+        -> anyhow::Result<ETHStats>;
 
     /// Loads the stored gas price limit.
     async fn load_gas_price_limit(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<U256>;
 
     /// Updates the stored gas price limit.
     async fn update_gas_price_params(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         gas_price_limit: U256,
         average_gas_price: U256,
     ) -> anyhow::Result<()>;
 
     async fn is_previous_operation_confirmed(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
 }
@@ -123,17 +166,18 @@ impl Database {
 
 #[async_trait::async_trait]
 impl DatabaseInterface for Database {
-    async fn acquire_connection(&self) -> anyhow::Result<StorageProcessor<'_>> {
+    async fn acquire_connection(&self) -> anyhow::Result<DatabaseConnection<'_>> {
         let connection = self.db_pool.access_storage().await?;
 
-        Ok(connection)
+        Ok(DatabaseConnection::Storage(connection))
     }
 
     async fn load_unconfirmed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<VecDeque<ETHOperation>> {
         let unconfirmed_ops = connection
+            .storage()?
             .ethereum_schema()
             .load_unconfirmed_operations()
             .await?;
@@ -143,9 +187,10 @@ impl DatabaseInterface for Database {
 
     async fn restore_unprocessed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<()> {
         connection
+            .storage()?
             .ethereum_schema()
             .restore_unprocessed_operations()
             .await?;
@@ -155,9 +200,10 @@ impl DatabaseInterface for Database {
 
     async fn load_new_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<Vec<(i64, AggregatedOperation)>> {
         let unprocessed_ops = connection
+            .storage()?
             .ethereum_schema()
             .load_unprocessed_operations()
             .await?;
@@ -166,10 +212,11 @@ impl DatabaseInterface for Database {
 
     async fn remove_unprocessed_operations(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         operations_id: Vec<i64>,
     ) -> anyhow::Result<()> {
         connection
+            .storage()?
             .ethereum_schema()
             .remove_unprocessed_operations(operations_id)
             .await?;
@@ -179,20 +226,23 @@ impl DatabaseInterface for Database {
 
     async fn save_new_eth_tx(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
+            .storage()?
             .ethereum_schema()
             .save_new_eth_tx(
                 op_type,
                 op,
                 deadline_block,
                 BigUint::from_str(&used_gas_price.to_string()).unwrap(),
+                used_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
                 raw_tx,
             )
             .await?;
@@ -202,11 +252,12 @@ impl DatabaseInterface for Database {
 
     async fn add_hash_entry(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         eth_op_id: i64,
         hash: &H256,
     ) -> anyhow::Result<()> {
         Ok(connection
+            .storage()?
             .ethereum_schema()
             .add_hash_entry(eth_op_id, hash)
             .await?)
@@ -214,24 +265,27 @@ impl DatabaseInterface for Database {
 
     async fn update_eth_tx(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         Ok(connection
+            .storage()?
             .ethereum_schema()
             .update_eth_tx(
                 eth_op_id,
                 new_deadline_block,
                 BigUint::from_str(&new_gas_value.to_string()).unwrap(),
+                new_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
             )
             .await?)
     }
 
     async fn is_previous_operation_confirmed(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool> {
         // If the ID of the current operation is 1, then this is the first transaction
@@ -244,6 +298,7 @@ impl DatabaseInterface for Database {
         // we simply consider the operation with ID less by one.
         let previous_op = op.id - 1;
         let confirmed = connection
+            .storage()?
             .ethereum_schema()
             .is_aggregated_op_confirmed(previous_op)
             .await?;
@@ -253,11 +308,11 @@ impl DatabaseInterface for Database {
 
     async fn confirm_operation(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         hash: &H256,
         op: &ETHOperation,
     ) -> anyhow::Result<()> {
-        let mut transaction = connection.start_transaction().await?;
+        let mut transaction = connection.storage()?.start_transaction().await?;
 
         match &op.op {
             Some((_, AggregatedOperation::CommitBlocks(op))) => {
@@ -318,26 +373,34 @@ impl DatabaseInterface for Database {
         Ok(())
     }
 
-    async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats> {
-        let stats = connection.ethereum_schema().load_stats().await?;
+    async fn load_stats(
+        &self,
+        connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<ETHStats> {
+        let stats = connection.storage()?.ethereum_schema().load_stats().await?;
         Ok(stats.into())
     }
 
     async fn load_gas_price_limit(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<U256> {
-        let limit = connection.ethereum_schema().load_gas_price_limit().await?;
+        let limit = connection
+            .storage()?
+            .ethereum_schema()
+            .load_gas_price_limit()
+            .await?;
         Ok(limit)
     }
 
     async fn update_gas_price_params(
         &self,
-        connection: &mut StorageProcessor<'_>,
+        connection: &mut DatabaseConnection<'_>,
         gas_price_limit: U256,
         average_gas_price: U256,
     ) -> anyhow::Result<()> {
         connection
+            .storage()?
             .ethereum_schema()
             .update_gas_price(gas_price_limit, average_gas_price)
             .await?;
@@ -0,0 +1,37 @@
+//! `clock` module provides the source of time for `GasAdjuster`.
+//!
+//! `GasAdjuster` updates the gas price limit and gathers the samples periodically,
+//! and the time passed between the updates is measured with the clock. The actual server uses
+//! the system clock, while the simulator provides its own one to stay deterministic.
+
+// Built-in deps
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Source of time for `GasAdjuster`.
+pub(crate) trait Clock: fmt::Debug + Send + Sync {
+    /// Returns the time passed since the clock start.
+    fn now(&self) -> Duration;
+}
+
+/// Clock based on the system monotonic clock.
+#[derive(Debug)]
+pub(crate) struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
@@ -1,16 +1,72 @@
 // Built-in deps
-use std::{collections::VecDeque, marker::PhantomData, time::Instant};
+use std::{collections::VecDeque, marker::PhantomData, time::Duration};
 // External deps
+use web3::contract::Options;
 use zksync_basic_types::U256;
 use zksync_eth_client::EthereumGateway;
+use zksync_types::ethereum::ETHOperation;
 // Local deps
+use self::strategy::PriceStrategy;
 use crate::database::DatabaseInterface;
 
+pub(crate) use self::clock::{Clock, SystemClock};
+
+mod clock;
 mod parameters;
+mod strategy;
 
 #[cfg(test)]
 mod tests;
 
+/// Fees to be used in the Ethereum transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TxFees {
+    /// Gas price of the legacy transaction, or `max_fee_per_gas` for EIP-1559 transactions.
+    pub gas_price: U256,
+    /// Priority fee per gas, set only for EIP-1559 transactions.
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+impl TxFees {
+    pub fn legacy(gas_price: U256) -> Self {
+        Self {
+            gas_price,
+            max_priority_fee_per_gas: None,
+        }
+    }
+
+    pub fn eip1559(max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> Self {
+        Self {
+            gas_price: max_fee_per_gas,
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+        }
+    }
+
+    /// Returns the fees used in the last transaction sent for the operation.
+    pub fn from_operation(op: &ETHOperation) -> Self {
+        Self {
+            gas_price: op.last_used_gas_price,
+            max_priority_fee_per_gas: op.last_used_priority_fee_per_gas,
+        }
+    }
+
+    /// Sets the fee-related fields of the transaction options.
+    pub fn apply(&self, options: &mut Options) {
+        match self.max_priority_fee_per_gas {
+            Some(max_priority_fee_per_gas) => {
+                options.gas_price = None;
+                options.max_fee_per_gas = Some(self.gas_price);
+                options.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+            }
+            None => {
+                options.gas_price = Some(self.gas_price);
+                options.max_fee_per_gas = None;
+                options.max_priority_fee_per_gas = None;
+            }
+        }
+    }
+}
+
 /// Gas adjuster is an entity capable of scaling the gas price for
 /// all the Ethereum transactions.
 ///
@@ -25,14 +81,35 @@ pub(super) struct GasAdjuster<DB: DatabaseInterface> {
     /// Collected statistics about recently used gas prices.
     statistics: GasStatistics,
     /// Timestamp of the last maximum gas price update.
-    last_price_renewal: Instant,
+    last_price_renewal: Duration,
     /// Timestamp of the last sample added to the `statistics`.
-    last_sample_added: Instant,
+    last_sample_added: Duration,
+    /// Strategy used to suggest the gas price.
+    strategy: Box<dyn PriceStrategy>,
+    /// Source of the timestamps.
+    clock: Box<dyn Clock>,
     _db: PhantomData<DB>,
 }
 
 impl<DB: DatabaseInterface> GasAdjuster<DB> {
     pub async fn new(db: &DB) -> Self {
+        Self::with_clock(db, Box::new(SystemClock::default())).await
+    }
+
+    pub async fn with_clock(db: &DB, clock: Box<dyn Clock>) -> Self {
+        let strategy = strategy::from_kind(parameters::price_strategy());
+        Self::with_strategy_and_clock(db, strategy, clock).await
+    }
+
+    pub async fn with_strategy(db: &DB, strategy: Box<dyn PriceStrategy>) -> Self {
+        Self::with_strategy_and_clock(db, strategy, Box::new(SystemClock::default())).await
+    }
+
+    async fn with_strategy_and_clock(
+        db: &DB,
+        strategy: Box<dyn PriceStrategy>,
+        clock: Box<dyn Clock>,
+    ) -> Self {
         let mut connection = db
             .acquire_connection()
             .await
@@ -43,8 +120,10 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             .expect("Can't load the gas price limit");
         Self {
             statistics: GasStatistics::new(gas_price_limit),
-            last_price_renewal: Instant::now(),
-            last_sample_added: Instant::now(),
+            last_price_renewal: clock.now(),
+            last_sample_added: clock.now(),
+            strategy,
+            clock,
 
             _db: PhantomData,
         }
@@ -55,19 +134,9 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         ethereum: &EthereumGateway,
         old_tx_gas_price: Option<U256>,
     ) -> anyhow::Result<U256> {
-        if let Some(price) = self.statistics.get_average_price() {
-            return Ok(price);
-        }
-
-        let network_price = ethereum.get_gas_price().await?;
-        let scaled_price = if let Some(old_price) = old_tx_gas_price {
-            // Stuck transaction, scale it up.
-            self.scale_up(old_price, network_price)
-        } else {
-            // New transaction, use the network price as the base.
-            network_price
-        };
-        Ok(scaled_price)
+        self.strategy
+            .suggest_price(ethereum, &self.statistics, old_tx_gas_price)
+            .await
     }
 
     /// Calculates a new gas amount for the replacement of the stuck tx.
@@ -93,17 +162,69 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         Ok(price)
     }
 
+    /// Calculates the fees for an EIP-1559 transaction.
+    ///
+    /// For a new transaction the priority fee is suggested by the price strategy, and the fee cap
+    /// is set to `2 * base_fee + priority_fee`, so the transaction stays includable even if
+    /// the base fee keeps growing for several blocks.
+    /// For the replacement of the stuck tx both the priority fee and the fee cap are scaled up
+    /// the same way as the legacy gas price, since the node requires both of them to be increased.
+    pub async fn get_eip1559_fees(
+        &mut self,
+        ethereum: &EthereumGateway,
+        old_tx_fees: Option<TxFees>,
+    ) -> anyhow::Result<TxFees> {
+        let base_fee = ethereum.get_base_fee_per_gas().await?;
+        let suggested_priority_fee = self.strategy.suggest_priority_fee(ethereum).await?;
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) = match old_tx_fees {
+            Some(old_fees) => {
+                // Stuck transaction, scale it up. The previous transaction may be
+                // a legacy one, in that case only the fee cap is based on it.
+                let priority_fee = match old_fees.max_priority_fee_per_gas {
+                    Some(old_priority_fee) => scale_up(old_priority_fee, suggested_priority_fee),
+                    None => suggested_priority_fee,
+                };
+                let max_fee = scale_up(old_fees.gas_price, base_fee * 2 + priority_fee);
+                (max_fee, priority_fee)
+            }
+            None => (
+                base_fee * 2 + suggested_priority_fee,
+                suggested_priority_fee,
+            ),
+        };
+
+        // Now, cut the fee cap if it's too big. Priority fee can't exceed the fee cap.
+        let max_fee_per_gas = self.limit_max(max_fee_per_gas);
+        let max_priority_fee_per_gas = std::cmp::min(max_priority_fee_per_gas, max_fee_per_gas);
+
+        if max_fee_per_gas == self.get_current_max_price() {
+            // We're suggesting the max price, so we must notify the log
+            // entry about it.
+            vlog::warn!(
+                "Maximum possible fee per gas will be used: <{}>",
+                max_fee_per_gas
+            );
+        }
+
+        // Report the price that is expected to be paid to be gathered by the statistics module.
+        let expected_price = std::cmp::min(base_fee + max_priority_fee_per_gas, max_fee_per_gas);
+        self.statistics.add_sample(expected_price);
+
+        Ok(TxFees::eip1559(max_fee_per_gas, max_priority_fee_per_gas))
+    }
+
     /// Performs an actualization routine for `GasAdjuster`:
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
     pub async fn keep_updated(&mut self, ethereum: &EthereumGateway, db: &DB) {
-        if self.last_sample_added.elapsed() >= parameters::sample_adding_interval() {
+        if self.clock.now() - self.last_sample_added >= parameters::sample_adding_interval() {
             // Report the current price to be gathered by the statistics module.
             match ethereum.get_gas_price().await {
                 Ok(network_price) => {
                     self.statistics.add_sample(network_price);
 
-                    self.last_sample_added = Instant::now();
+                    self.last_sample_added = self.clock.now();
                 }
                 Err(err) => {
                     vlog::warn!("Cannot add the sample gas price: {}", err);
@@ -111,11 +232,11 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             }
         }
 
-        if self.last_price_renewal.elapsed() >= parameters::limit_update_interval() {
+        if self.clock.now() - self.last_price_renewal >= parameters::limit_update_interval() {
             // It's time to update the maximum price.
             let scale_factor = parameters::limit_scale_factor();
             self.statistics.update_limit(scale_factor);
-            self.last_price_renewal = Instant::now();
+            self.last_price_renewal = self.clock.now();
 
             // Update the value in the database as well.
             let mut connection = match db.acquire_connection().await {
@@ -150,11 +271,6 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         }
     }
 
-    fn scale_up(&self, price_to_scale: U256, current_network_price: U256) -> U256 {
-        let replacement_price = (price_to_scale * U256::from(115)) / U256::from(100);
-        std::cmp::max(current_network_price, replacement_price)
-    }
-
     fn limit_max(&self, price: U256) -> U256 {
         let limit = self.get_current_max_price();
 
@@ -167,6 +283,13 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     }
 }
 
+/// Calculates the price for the replacement of the stuck tx.
+/// Replacement price is usually suggested to be at least 10% higher, we make it 15% higher.
+fn scale_up(price_to_scale: U256, current_network_price: U256) -> U256 {
+    let replacement_price = (price_to_scale * U256::from(115)) / U256::from(100);
+    std::cmp::max(current_network_price, replacement_price)
+}
+
 /// Helper structure responsible for collecting the data about recent transactions,
 /// calculating the average gas price, and providing the gas price limit.
 #[derive(Debug)]
@@ -5,6 +5,13 @@
 //!   gas price suggested by `GasAdjuster`.
 //! - Maximum gas price scale: multiplier to be applied to the average gas price to
 //!   calculate the upper limit for gas price in `GasAdjuster`.
+//! - Default priority fee: priority fee per gas to be used for new EIP-1559 transactions.
+//! - Gas price strategy: the way `GasAdjuster` suggests the gas price, along with the
+//!   parameters of the `eth_feeHistory`-based strategy.
+//!
+//! The values are not cached internally, as they may be changed for the already running
+//! server by an administrator. This may be required if existing settings aren't flexible
+//! enough to match the current network price.
 //!
 //! The module uses a child module `parameters_impl` which contains two implementations
 //! for functions declared in module: one for the actual usage, and one for tests.
@@ -13,71 +20,106 @@
 
 // Built-in deps.
 use std::time::Duration;
+// Workspace deps
+use zksync_basic_types::U256;
+use zksync_config::configs::eth_sender::GasPriceStrategy;
 
 /// Obtains the interval for renewing the maximum gas price.
-///
-/// This value is not cached internally, as it may be changed for the already running
-/// server by an administrator. This may be required if existing settings aren't flexible
-/// enough to match the current network price.
 pub fn limit_update_interval() -> Duration {
     parameters_impl::limit_update_interval()
 }
 
 /// Obtains the scaling factor for the maximum gas price.
-///
-/// This value is not cached internally, as it may be changed for the already running
-/// server by an administrator. This may be required if existing settings aren't flexible
-/// enough to match the current network price.
 pub fn limit_scale_factor() -> f64 {
     parameters_impl::limit_scale_factor()
 }
 
 /// Obtains the interval for the gas price samples to be added into `gas_adjuster`.
-///
-/// This value is not cached internally, as it may be changed for the already running
-/// server by an administrator. This may be required if existing settings aren't flexible
-/// enough to match the current network price.
 pub fn sample_adding_interval() -> Duration {
     parameters_impl::sample_adding_interval()
 }
 
+/// Obtains the priority fee per gas to be used for new EIP-1559 transactions.
+pub fn default_priority_fee() -> U256 {
+    parameters_impl::default_priority_fee()
+}
+
+/// Obtains the strategy to be used to suggest the gas price.
+pub fn price_strategy() -> GasPriceStrategy {
+    parameters_impl::price_strategy()
+}
+
+/// Obtains the amount of the latest blocks to request the fee history for.
+pub fn fee_history_block_count() -> usize {
+    parameters_impl::fee_history_block_count()
+}
+
+/// Obtains the percentile of the recent priority fees to be used for the gas price.
+pub fn fee_history_percentile() -> f64 {
+    parameters_impl::fee_history_percentile()
+}
+
+/// Obtains the maximum ratio between the suggested gas price and the next block base fee.
+pub fn fee_history_max_scale() -> f64 {
+    parameters_impl::fee_history_max_scale()
+}
+
 // Actual methods implementation for non-test purposes.
 #[cfg(not(test))]
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
     // Workspace deps
-    use zksync_config::configs::eth_sender::ETHSenderConfig;
+    use zksync_basic_types::U256;
+    use zksync_config::configs::eth_sender::{ETHSenderConfig, GasPriceStrategy};
 
     /// Obtains the interval for renewing the maximum gas price.
-    ///
-    /// This value is not cached internally, as it may be changed for the already running
-    /// server by an administrator. This may be required if existing settings aren't flexible
-    /// enough to match the current network price.
     pub fn limit_update_interval() -> Duration {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.update_interval()
     }
 
     /// Obtains the scaling factor for the maximum gas price.
-    ///
-    /// This value is not cached internally, as it may be changed for the already running
-    /// server by an administrator. This may be required if existing settings aren't flexible
-    /// enough to match the current network price.
     pub fn limit_scale_factor() -> f64 {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.scale_factor
     }
 
     /// Obtains the interval for the gas price samples to be added into `gas_adjuster`.
-    ///
-    /// This value is not cached internally, as it may be changed for the already running
-    /// server by an administrator. This may be required if existing settings aren't flexible
-    /// enough to match the current network price.
     pub fn sample_adding_interval() -> Duration {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.sample_interval()
     }
+
+    /// Obtains the priority fee per gas to be used for new EIP-1559 transactions.
+    pub fn default_priority_fee() -> U256 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.default_priority_fee.into()
+    }
+
+    /// Obtains the strategy to be used to suggest the gas price.
+    pub fn price_strategy() -> GasPriceStrategy {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.strategy
+    }
+
+    /// Obtains the amount of the latest blocks to request the fee history for.
+    pub fn fee_history_block_count() -> usize {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.fee_history_block_count as usize
+    }
+
+    /// Obtains the percentile of the recent priority fees to be used for the gas price.
+    pub fn fee_history_percentile() -> f64 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.fee_history_percentile
+    }
+
+    /// Obtains the maximum ratio between the suggested gas price and the next block base fee.
+    pub fn fee_history_max_scale() -> f64 {
+        let config = ETHSenderConfig::from_env();
+        config.gas_price_limit.fee_history_max_scale
+    }
 }
 
 // Hard-coded implementation for tests.
@@ -85,6 +127,9 @@ mod parameters_impl {
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
+    // Workspace deps
+    use zksync_basic_types::U256;
+    use zksync_config::configs::eth_sender::GasPriceStrategy;
 
     /// `limit_update_interval` version for tests not looking for an environment variable value
     /// but using a zero interval instead.
@@ -103,4 +148,34 @@ mod parameters_impl {
     pub fn sample_adding_interval() -> Duration {
         Duration::from_secs(0)
     }
+
+    /// `default_priority_fee` version for tests not looking for an environment variable value
+    /// but using a fixed priority fee (10 wei) instead.
+    pub fn default_priority_fee() -> U256 {
+        U256::from(10)
+    }
+
+    /// `price_strategy` version for tests not looking for an environment variable value
+    /// but using the statistics-based strategy instead.
+    pub fn price_strategy() -> GasPriceStrategy {
+        GasPriceStrategy::Statistics
+    }
+
+    /// `fee_history_block_count` version for tests not looking for an environment variable value
+    /// but using a fixed amount of blocks (4) instead.
+    pub fn fee_history_block_count() -> usize {
+        4
+    }
+
+    /// `fee_history_percentile` version for tests not looking for an environment variable value
+    /// but using a fixed percentile (50) instead.
+    pub fn fee_history_percentile() -> f64 {
+        50.0f64
+    }
+
+    /// `fee_history_max_scale` version for tests not looking for an environment variable value
+    /// but using a fixed scale (2.0) instead.
+    pub fn fee_history_max_scale() -> f64 {
+        2.0f64
+    }
 }
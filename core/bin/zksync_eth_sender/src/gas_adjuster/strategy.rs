@@ -0,0 +1,154 @@
+//! `strategy` module provides the ways `GasAdjuster` can use to suggest the gas price
+//! for new transactions and for the replacement of the stuck ones, as well as the
+//! priority fee for EIP-1559 transactions.
+//!
+//! The strategy only suggests the price: clamping it with the current gas price limit
+//! and gathering the statistics is done by `GasAdjuster` itself.
+
+// Built-in deps
+use std::fmt;
+// External deps
+use zksync_basic_types::U256;
+use zksync_config::configs::eth_sender::GasPriceStrategy;
+use zksync_eth_client::EthereumGateway;
+// Local deps
+use super::{parameters, scale_up, GasStatistics};
+
+/// Strategy of suggesting the gas price used by `GasAdjuster`.
+#[async_trait::async_trait]
+pub(super) trait PriceStrategy: fmt::Debug + Send + Sync {
+    /// Suggests the gas price for the transaction.
+    /// If the price of the previous (stuck) transaction is provided, the suggested price
+    /// must be high enough for the new transaction to replace the stuck one.
+    async fn suggest_price(
+        &self,
+        ethereum: &EthereumGateway,
+        statistics: &GasStatistics,
+        old_tx_gas_price: Option<U256>,
+    ) -> anyhow::Result<U256>;
+
+    /// Suggests the priority fee per gas for the new EIP-1559 transaction.
+    async fn suggest_priority_fee(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256>;
+}
+
+/// Creates the strategy of the chosen kind.
+pub(super) fn from_kind(kind: GasPriceStrategy) -> Box<dyn PriceStrategy> {
+    match kind {
+        GasPriceStrategy::Statistics => Box::new(StatisticsStrategy),
+        GasPriceStrategy::FeeHistory => Box::new(FeeHistoryStrategy),
+    }
+}
+
+/// Default strategy: uses the average price of the recently sent transactions
+/// once enough statistics is gathered, and the `eth_gasPrice` before that.
+#[derive(Debug, Default)]
+pub(super) struct StatisticsStrategy;
+
+#[async_trait::async_trait]
+impl PriceStrategy for StatisticsStrategy {
+    async fn suggest_price(
+        &self,
+        ethereum: &EthereumGateway,
+        statistics: &GasStatistics,
+        old_tx_gas_price: Option<U256>,
+    ) -> anyhow::Result<U256> {
+        if let Some(price) = statistics.get_average_price() {
+            return Ok(price);
+        }
+
+        let network_price = ethereum.get_gas_price().await?;
+        let scaled_price = if let Some(old_price) = old_tx_gas_price {
+            // Stuck transaction, scale it up.
+            scale_up(old_price, network_price)
+        } else {
+            // New transaction, use the network price as the base.
+            network_price
+        };
+        Ok(scaled_price)
+    }
+
+    async fn suggest_priority_fee(&self, _ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        Ok(parameters::default_priority_fee())
+    }
+}
+
+/// Strategy based on `eth_feeHistory`: the suggested price is the next block base fee plus
+/// the configured percentile of the priority fees paid in the recent blocks.
+///
+/// To not overpay because of a few blocks with abnormally high tips, the price is capped with
+/// the next block base fee multiplied by the configured max scale.
+///
+/// The priority fee of EIP-1559 transactions is the same percentile of the recent priority fees,
+/// or the configured default one if no priority fees were paid in the recent blocks.
+#[derive(Debug, Default)]
+pub(super) struct FeeHistoryStrategy;
+
+impl FeeHistoryStrategy {
+    /// Multiplies the price by the scale, same way as `GasStatistics::update_limit` does.
+    fn scale(price: U256, scale: f64) -> U256 {
+        let multiplier = U256::from((scale * 100.0f64).round() as u64);
+        price * multiplier / U256::from(100)
+    }
+
+    /// Returns the next block base fee and the average of the configured percentile
+    /// of the priority fees paid in the recent blocks, if there are any.
+    async fn fee_history(ethereum: &EthereumGateway) -> anyhow::Result<(U256, Option<U256>)> {
+        let block_count = parameters::fee_history_block_count();
+        let percentile = parameters::fee_history_percentile();
+
+        let fee_history = ethereum.fee_history(block_count, vec![percentile]).await?;
+        // The last base fee in the history is the one of the next block.
+        let next_base_fee = *fee_history
+            .base_fee_per_gas
+            .last()
+            .ok_or_else(|| anyhow::format_err!("Fee history contains no base fees"))?;
+        let rewards: Vec<U256> = fee_history
+            .reward
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|block_rewards| block_rewards.first().copied())
+            .collect();
+        let priority_fee = if rewards.is_empty() {
+            None
+        } else {
+            Some(
+                rewards
+                    .iter()
+                    .fold(U256::zero(), |sum, reward| sum + *reward)
+                    / rewards.len(),
+            )
+        };
+        Ok((next_base_fee, priority_fee))
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceStrategy for FeeHistoryStrategy {
+    async fn suggest_price(
+        &self,
+        ethereum: &EthereumGateway,
+        _statistics: &GasStatistics,
+        old_tx_gas_price: Option<U256>,
+    ) -> anyhow::Result<U256> {
+        let (next_base_fee, priority_fee) = Self::fee_history(ethereum).await?;
+        let priority_fee = priority_fee.unwrap_or_default();
+
+        let max_price = Self::scale(next_base_fee, parameters::fee_history_max_scale());
+        let network_price = std::cmp::min(next_base_fee + priority_fee, max_price);
+
+        let scaled_price = if let Some(old_price) = old_tx_gas_price {
+            // Stuck transaction, scale it up.
+            scale_up(old_price, network_price)
+        } else {
+            network_price
+        };
+        Ok(scaled_price)
+    }
+
+    async fn suggest_priority_fee(&self, ethereum: &EthereumGateway) -> anyhow::Result<U256> {
+        let (_, priority_fee) = Self::fee_history(ethereum).await?;
+        Ok(priority_fee
+            .filter(|fee| !fee.is_zero())
+            .unwrap_or_else(parameters::default_priority_fee))
+    }
+}
@@ -3,7 +3,11 @@
 use zksync_basic_types::U256;
 // Local uses
 use crate::{
-    gas_adjuster::{parameters::limit_scale_factor, GasStatistics},
+    gas_adjuster::{
+        parameters::{default_priority_fee, fee_history_max_scale, limit_scale_factor},
+        strategy::FeeHistoryStrategy,
+        GasStatistics, TxFees,
+    },
     tests::mock::{default_eth_sender, MockDatabase},
     DatabaseInterface, GasAdjuster,
 };
@@ -91,6 +95,202 @@ async fn lower_gas_limit() {
     }
 }
 
+/// Checks the `eth_feeHistory`-based strategy: the price is the next block base fee plus
+/// the percentile of the recent priority fees, capped by the scaled base fee.
+#[tokio::test]
+async fn fee_history_strategy() {
+    const BASE_FEE: u64 = 80;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::with_strategy(&db, Box::new(FeeHistoryStrategy)).await;
+
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee_per_gas(BASE_FEE.into())
+        .await
+        .unwrap();
+    // The mock reward is proportional to the percentile, so with the 50th percentile
+    // used in tests the priority fee is 10.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_max_priority_fee_reward(20.into())
+        .await
+        .unwrap();
+
+    let price = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+    assert_eq!(price, (BASE_FEE + 10).into());
+
+    // Stuck transaction is scaled up by 15% or replaced with the network price.
+    let price = gas_adjuster
+        .get_gas_price(&ethereum, Some(100.into()))
+        .await
+        .unwrap();
+    assert_eq!(price, 115.into());
+    let price = gas_adjuster
+        .get_gas_price(&ethereum, Some(10.into()))
+        .await
+        .unwrap();
+    assert_eq!(price, (BASE_FEE + 10).into());
+
+    // Abnormally high priority fees are capped.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_max_priority_fee_reward((BASE_FEE * 100).into())
+        .await
+        .unwrap();
+    let max_scale = (fee_history_max_scale() * 100.0).round() as u64;
+    let price = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+    assert_eq!(price, (BASE_FEE * max_scale / 100).into());
+}
+
+/// Checks the EIP-1559 fees suggested for new and stuck transactions:
+/// new transactions use the default priority fee with the fee cap of `2 * base_fee + priority_fee`,
+/// while for stuck transactions both values are increased by at least 15%.
+#[tokio::test]
+async fn eip1559_fees() {
+    const BASE_FEE: u64 = 80;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee_per_gas(BASE_FEE.into())
+        .await
+        .unwrap();
+
+    let priority_fee = default_priority_fee();
+    let expected_max_fee = U256::from(BASE_FEE * 2) + priority_fee;
+
+    // New transaction.
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, None)
+        .await
+        .unwrap();
+    assert_eq!(fees, TxFees::eip1559(expected_max_fee, priority_fee));
+
+    // Test vector of pairs (fees of the last tx, expected fees).
+    let test_vector = vec![
+        // Both fees are increased by 15%.
+        (
+            TxFees::eip1559(expected_max_fee, priority_fee),
+            TxFees::eip1559(expected_max_fee * 115 / 100, priority_fee * 115 / 100),
+        ),
+        // Old fee cap is too low, use the network-based one.
+        (
+            TxFees::eip1559(1.into(), priority_fee),
+            TxFees::eip1559(
+                U256::from(BASE_FEE * 2) + priority_fee * 115 / 100,
+                priority_fee * 115 / 100,
+            ),
+        ),
+        // Stuck legacy transaction is replaced with an EIP-1559 one.
+        (
+            TxFees::legacy(expected_max_fee * 2),
+            TxFees::eip1559(expected_max_fee * 2 * 115 / 100, priority_fee),
+        ),
+    ];
+
+    for (old_fees, expected_fees) in test_vector {
+        let fees = gas_adjuster
+            .get_eip1559_fees(&ethereum, Some(old_fees))
+            .await
+            .unwrap();
+        assert_eq!(fees, expected_fees);
+    }
+}
+
+/// Checks that EIP-1559 priority fee is suggested by the configured strategy:
+/// the `eth_feeHistory`-based one uses the percentile of the recent priority fees,
+/// and falls back to the default priority fee if no priority fees were paid recently.
+#[tokio::test]
+async fn eip1559_fees_fee_history_strategy() {
+    const BASE_FEE: u64 = 80;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> =
+        GasAdjuster::with_strategy(&db, Box::new(FeeHistoryStrategy)).await;
+
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee_per_gas(BASE_FEE.into())
+        .await
+        .unwrap();
+    // With the 50th percentile used in tests the priority fee is 15.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_max_priority_fee_reward(30.into())
+        .await
+        .unwrap();
+
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, None)
+        .await
+        .unwrap();
+    assert_eq!(fees, TxFees::eip1559((BASE_FEE * 2 + 15).into(), 15.into()));
+
+    // No priority fees were paid recently.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_max_priority_fee_reward(0.into())
+        .await
+        .unwrap();
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, None)
+        .await
+        .unwrap();
+    assert_eq!(fees.max_priority_fee_per_gas, Some(default_priority_fee()));
+}
+
+/// Checks that EIP-1559 fee cap is clamped according to the current limit,
+/// and the priority fee never exceeds the fee cap.
+#[tokio::test]
+async fn eip1559_fees_upper_limit() {
+    // Initial price limit to set.
+    const PRICE_LIMIT: i64 = 1000;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+
+    db.update_gas_price_limit(PRICE_LIMIT).await.unwrap();
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    // Set the base fee in Ethereum, which is greater than the current limit.
+    ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee_per_gas(PRICE_LIMIT.into())
+        .await
+        .unwrap();
+
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        fees,
+        TxFees::eip1559(PRICE_LIMIT.into(), default_priority_fee())
+    );
+
+    // Priority fee of the stuck tx is already above the limit.
+    let old_fees = TxFees::eip1559(PRICE_LIMIT.into(), PRICE_LIMIT.into());
+    let fees = gas_adjuster
+        .get_eip1559_fees(&ethereum, Some(old_fees))
+        .await
+        .unwrap();
+    assert_eq!(
+        fees,
+        TxFees::eip1559(PRICE_LIMIT.into(), PRICE_LIMIT.into())
+    );
+}
+
 // Checks that after re-creation the price limit is restored from the database.
 #[tokio::test]
 async fn gas_price_limit_restore() {
@@ -21,17 +21,19 @@ use zksync_types::ethereum::ETHOperation;
 // Local uses
 use self::{
     database::{Database, DatabaseInterface},
-    gas_adjuster::GasAdjuster,
+    gas_adjuster::{GasAdjuster, TxFees},
     transactions::*,
     tx_queue::{TxData, TxQueue, TxQueueBuilder},
 };
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     gas_counter::GasCounter,
+    ExecutedOperations,
 };
 
 mod database;
 mod gas_adjuster;
+pub mod simulator;
 mod transactions;
 mod tx_queue;
 
@@ -338,10 +340,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     /// Stores the new operation in the database and sends the corresponding transaction.
     async fn initialize_operation(&mut self, tx: TxData, current_block: u64) -> anyhow::Result<()> {
         let deadline_block = self.get_deadline_block(current_block);
-        let gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, None)
-            .await?;
+        let tx_fees = self.suggest_tx_fees(None).await?;
 
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
@@ -357,7 +356,8 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     tx.op_type,
                     Some(tx.operation.clone()),
                     deadline_block as i64,
-                    gas_price,
+                    tx_fees.gas_price,
+                    tx_fees.max_priority_fee_per_gas,
                     tx.raw.clone(),
                 )
                 .await?;
@@ -368,7 +368,8 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op: Some(tx.operation),
                 nonce: assigned_data.nonce,
                 last_deadline_block: deadline_block,
-                last_used_gas_price: gas_price,
+                last_used_gas_price: tx_fees.gas_price,
+                last_used_priority_fee_per_gas: tx_fees.max_priority_fee_per_gas,
                 used_tx_hashes: vec![], // No hash yet, will be added below.
                 encoded_tx_data: tx.raw,
                 confirmed: false,
@@ -409,15 +410,42 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         Ok(())
     }
 
+    /// Obtains the fees for the new transaction, or for the replacement of the stuck one
+    /// if the fees of the previous transaction are provided.
+    /// Depending on the configuration, either EIP-1559 or legacy fees are suggested.
+    async fn suggest_tx_fees(&mut self, old_tx_fees: Option<TxFees>) -> anyhow::Result<TxFees> {
+        if self.options.sender.use_eip1559_txs {
+            self.gas_adjuster
+                .get_eip1559_fees(&self.ethereum, old_tx_fees)
+                .await
+        } else {
+            let old_tx_gas_price = old_tx_fees.map(|fees| fees.gas_price);
+            let gas_price = self
+                .gas_adjuster
+                .get_gas_price(&self.ethereum, old_tx_gas_price)
+                .await?;
+            Ok(TxFees::legacy(gas_price))
+        }
+    }
+
     /// Helper method to obtain the string representation of the Ethereum transaction.
     /// Intended to be used for log entries.
     fn eth_tx_description(&self, tx: &SignedCallResult) -> String {
         // Gas price in gwei (wei / 10^9).
         let gas_price = tx.gas_price / (1_000_000_000);
-        format!(
-            "<hash: {:#x}; gas price: {} gwei; nonce: {}>",
-            tx.hash, gas_price, tx.nonce
-        )
+        match tx.max_priority_fee_per_gas {
+            Some(priority_fee) => format!(
+                "<hash: {:#x}; max fee: {} gwei; priority fee: {} gwei; nonce: {}>",
+                tx.hash,
+                gas_price,
+                priority_fee / (1_000_000_000),
+                tx.nonce
+            ),
+            None => format!(
+                "<hash: {:#x}; gas price: {} gwei; nonce: {}>",
+                tx.hash, gas_price, tx.nonce
+            ),
+        }
     }
 
     /// Helper method to obtain the string representation of the zkSync operation.
@@ -454,6 +482,19 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             "OperationETHState should have at least one transaction"
         );
 
+        if let Some(deadline_block) = self.priority_op_deadline_at_risk(op, current_block) {
+            // Failing to execute the priority operations in time puts the rollup into the exodus mode.
+            vlog::error!(
+                "Execution of the priority operations may not land before their deadline: \
+                 [ETH Operation <id: {}>. ZKSync operation: {}. Deadline block: {}, current block: {}]",
+                op.id,
+                self.zksync_operation_description(op),
+                deadline_block,
+                current_block
+            );
+            metrics::increment_counter!("eth_sender.priority_op_deadline_at_risk");
+        }
+
         // Check statuses of existing transactions.
         // Go through every transaction in a loop. We will exit this method early
         // if there will be discovered a pending or successfully committed transaction.
@@ -545,6 +586,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op.id,
                 deadline_block as i64,
                 new_tx.gas_price,
+                new_tx.max_priority_fee_per_gas,
             )
             .await?;
         self.db
@@ -583,6 +625,30 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         current_block + self.options.sender.expected_wait_time_block
     }
 
+    /// Returns the earliest deadline of the priority operations executed by the operation,
+    /// if the operation is not expected to be mined before it.
+    fn priority_op_deadline_at_risk(&self, op: &ETHOperation, current_block: u64) -> Option<u64> {
+        let execute_op = match &op.op {
+            Some((_, AggregatedOperation::ExecuteBlocks(execute_op))) => execute_op,
+            _ => return None,
+        };
+        let deadline_block = execute_op
+            .blocks
+            .iter()
+            .flat_map(|block| block.block_transactions.iter())
+            .filter_map(|tx| match tx {
+                ExecutedOperations::PriorityOp(op) => Some(op.priority_op.deadline_block),
+                ExecutedOperations::Tx(_) => None,
+            })
+            .min()?;
+
+        if self.get_deadline_block(current_block) >= deadline_block {
+            Some(deadline_block)
+        } else {
+            None
+        }
+    }
+
     /// Looks up for a transaction state on the Ethereum chain
     /// and reduces it to the simpler `TxCheckOutcome` report.
     async fn check_transaction_state(
@@ -602,6 +668,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
+                    if let Some(gas_used) = status.gas_used {
+                        Self::report_realized_gas_per_block(op, gas_used);
+                    }
                     TxCheckOutcome::Committed
                 } else {
                     TxCheckOutcome::Pending
@@ -634,6 +703,23 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         Ok(outcome)
     }
 
+    /// Reports the amount of gas actually paid per block of the confirmed operation.
+    fn report_realized_gas_per_block(op: &ETHOperation, gas_used: U256) {
+        let (first_block, last_block) = match &op.op {
+            Some((_, aggregated_op)) => aggregated_op.get_block_range(),
+            None => return,
+        };
+        let blocks = u64::from(last_block.saturating_sub(*first_block)) + 1;
+
+        let gas_per_block = (gas_used / U256::from(blocks)).as_u64();
+        metrics::histogram!(
+            "aggregated_operation.gas_per_block",
+            gas_per_block as f64,
+            "action" => op.op_type.to_string(),
+            "kind" => "realized"
+        );
+    }
+
     /// Creates a new Ethereum operation.
     async fn sign_new_tx(
         ethereum: &EthereumGateway,
@@ -656,14 +742,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 gas_limit
             );
 
-            Options {
+            let mut options = Options {
                 nonce: Some(op.nonce),
-                gas_price: Some(op.last_used_gas_price),
                 gas: Some(gas_limit),
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
                 ..Default::default()
-            }
+            };
+            TxFees::from_operation(op).apply(&mut options);
+            options
         };
 
         let signed_tx = ethereum
@@ -709,6 +794,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
+        stuck_tx.last_used_priority_fee_per_gas = signed_tx.max_priority_fee_per_gas;
         stuck_tx.used_tx_hashes.push(signed_tx.hash);
 
         Ok(signed_tx)
@@ -720,12 +806,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         &mut self,
         stuck_tx: &ETHOperation,
     ) -> anyhow::Result<Options> {
-        let old_tx_gas_price = stuck_tx.last_used_gas_price;
+        let old_tx_fees = TxFees::from_operation(stuck_tx);
 
-        let new_gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.ethereum, Some(old_tx_gas_price))
-            .await?;
+        let new_tx_fees = self.suggest_tx_fees(Some(old_tx_fees)).await?;
         let nonce = stuck_tx.nonce;
         let gas_limit = Self::gas_limit_for_op(stuck_tx);
 
@@ -736,16 +819,18 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         );
 
         vlog::info!(
-            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, used nonce: {}, gas limit: {}",
+            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, old_priority_fee: {:?}, new_priority_fee: {:?}, used nonce: {}, gas limit: {}",
             stuck_tx.used_tx_hashes.last().unwrap(),
-            old_tx_gas_price,
-            new_gas_price,
+            old_tx_fees.gas_price,
+            new_tx_fees.gas_price,
+            old_tx_fees.max_priority_fee_per_gas,
+            new_tx_fees.max_priority_fee_per_gas,
             nonce,
             gas_limit,
         );
 
         Ok(Options::with(move |opt| {
-            opt.gas_price = Some(new_gas_price);
+            new_tx_fees.apply(opt);
             opt.nonce = Some(nonce);
             opt.gas = Some(gas_limit);
         }))
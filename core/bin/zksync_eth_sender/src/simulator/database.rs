@@ -0,0 +1,227 @@
+//! In-memory implementation of the `DatabaseInterface` used by the simulator.
+
+// Built-in deps
+use std::collections::VecDeque;
+// External uses
+use tokio::sync::RwLock;
+use zksync_basic_types::{H256, U256};
+// Workspace uses
+use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
+use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
+// Local uses
+use crate::database::{DatabaseConnection, DatabaseInterface};
+use crate::transactions::ETHStats;
+
+/// Database which keeps all the `ETHSender` state in memory, so no actual database
+/// is required to run the simulation.
+#[derive(Debug)]
+pub(super) struct SimulatedDatabase {
+    eth_operations: RwLock<Vec<ETHOperation>>,
+    unprocessed_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
+    gas_price_limit: RwLock<U256>,
+    stats: ETHStats,
+}
+
+impl SimulatedDatabase {
+    /// Creates an empty database. `stats` describe the blocks which are considered
+    /// to be processed before the simulation is started.
+    pub fn new(gas_price_limit: U256, stats: ETHStats) -> Self {
+        Self {
+            eth_operations: RwLock::new(Vec::new()),
+            unprocessed_operations: RwLock::new(Vec::new()),
+            gas_price_limit: RwLock::new(gas_price_limit),
+            stats,
+        }
+    }
+
+    /// Simulates the operation of `OperationsSchema`, makes the operation available for `ETHSender`.
+    pub async fn send_aggregated_operation(
+        &self,
+        aggregated_operation: (i64, AggregatedOperation),
+    ) {
+        self.unprocessed_operations
+            .write()
+            .await
+            .push(aggregated_operation);
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseInterface for SimulatedDatabase {
+    async fn acquire_connection(&self) -> anyhow::Result<DatabaseConnection<'_>> {
+        Ok(DatabaseConnection::Detached)
+    }
+
+    async fn load_unconfirmed_operations(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<VecDeque<ETHOperation>> {
+        let unconfirmed_operations = self
+            .eth_operations
+            .read()
+            .await
+            .iter()
+            .filter(|eth_op| !eth_op.confirmed)
+            .cloned()
+            .collect();
+
+        Ok(unconfirmed_operations)
+    }
+
+    async fn restore_unprocessed_operations(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<()> {
+        // Simulation always starts from scratch, there is nothing to restore.
+        Ok(())
+    }
+
+    async fn load_new_operations(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<Vec<(i64, AggregatedOperation)>> {
+        Ok(self.unprocessed_operations.read().await.clone())
+    }
+
+    async fn remove_unprocessed_operations(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        operations_id: Vec<i64>,
+    ) -> anyhow::Result<()> {
+        self.unprocessed_operations
+            .write()
+            .await
+            .retain(|(id, _)| !operations_id.contains(id));
+
+        Ok(())
+    }
+
+    async fn save_new_eth_tx(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        op_type: AggregatedActionType,
+        op: Option<(i64, AggregatedOperation)>,
+        deadline_block: i64,
+        used_gas_price: U256,
+        used_priority_fee: Option<U256>,
+        encoded_tx_data: Vec<u8>,
+    ) -> anyhow::Result<InsertedOperationResponse> {
+        let mut eth_operations = self.eth_operations.write().await;
+        let id = eth_operations.len() as i64;
+        let nonce = U256::from(eth_operations.len());
+
+        eth_operations.push(ETHOperation {
+            id,
+            op_type,
+            op,
+            nonce,
+            last_deadline_block: deadline_block as u64,
+            last_used_gas_price: used_gas_price,
+            last_used_priority_fee_per_gas: used_priority_fee,
+            used_tx_hashes: vec![],
+            encoded_tx_data,
+            confirmed: false,
+            final_hash: None,
+        });
+
+        Ok(InsertedOperationResponse { id, nonce })
+    }
+
+    async fn add_hash_entry(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        eth_op_id: i64,
+        hash: &H256,
+    ) -> anyhow::Result<()> {
+        let mut eth_operations = self.eth_operations.write().await;
+        let eth_op = eth_operations
+            .iter_mut()
+            .find(|eth_op| eth_op.id == eth_op_id && !eth_op.confirmed)
+            .ok_or_else(|| anyhow::format_err!("Attempt to update tx that is not unconfirmed"))?;
+        eth_op.used_tx_hashes.push(*hash);
+
+        Ok(())
+    }
+
+    async fn update_eth_tx(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        eth_op_id: EthOpId,
+        new_deadline_block: i64,
+        new_gas_value: U256,
+        new_priority_fee: Option<U256>,
+    ) -> anyhow::Result<()> {
+        let mut eth_operations = self.eth_operations.write().await;
+        let eth_op = eth_operations
+            .iter_mut()
+            .find(|eth_op| eth_op.id == eth_op_id && !eth_op.confirmed)
+            .ok_or_else(|| anyhow::format_err!("Attempt to update tx that is not unconfirmed"))?;
+        eth_op.last_deadline_block = new_deadline_block as u64;
+        eth_op.last_used_gas_price = new_gas_value;
+        eth_op.last_used_priority_fee_per_gas = new_priority_fee;
+
+        Ok(())
+    }
+
+    async fn confirm_operation(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        hash: &H256,
+        _op: &ETHOperation,
+    ) -> anyhow::Result<()> {
+        let mut eth_operations = self.eth_operations.write().await;
+        let eth_op = eth_operations
+            .iter_mut()
+            .find(|eth_op| eth_op.used_tx_hashes.contains(hash))
+            .ok_or_else(|| {
+                anyhow::format_err!("Request to confirm operation that was not stored")
+            })?;
+        eth_op.confirmed = true;
+        eth_op.final_hash = Some(*hash);
+
+        Ok(())
+    }
+
+    async fn load_stats(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<ETHStats> {
+        Ok(self.stats.clone())
+    }
+
+    async fn load_gas_price_limit(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<U256> {
+        Ok(*self.gas_price_limit.read().await)
+    }
+
+    async fn update_gas_price_params(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        gas_price_limit: U256,
+        _average_gas_price: U256,
+    ) -> anyhow::Result<()> {
+        *self.gas_price_limit.write().await = gas_price_limit;
+
+        Ok(())
+    }
+
+    async fn is_previous_operation_confirmed(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+        op: &ETHOperation,
+    ) -> anyhow::Result<bool> {
+        // Operations are sent one by one, so all the operations sent before
+        // the provided one must be confirmed already.
+        let confirmed = self
+            .eth_operations
+            .read()
+            .await
+            .iter()
+            .filter(|eth_op| eth_op.id < op.id)
+            .all(|eth_op| eth_op.confirmed);
+
+        Ok(confirmed)
+    }
+}
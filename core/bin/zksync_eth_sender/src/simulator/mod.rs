@@ -0,0 +1,393 @@
+//! Deterministic simulator of the `ETHSender` behavior.
+//!
+//! Simulator replays a recorded sequence of aggregated operations against `ETHSender`,
+//! which is connected to the `MockEthereum` and an in-memory database. The L1 side follows
+//! a scripted timeline: for every block it defines the gas price reported by the node, the
+//! base fee, and the minimal gas price required for a transaction to be included.
+//!
+//! Time in the simulation is measured in L1 blocks: every iteration of the simulation loop
+//! corresponds to one block, which makes the result independent of the machine it's run on.
+//! `GasAdjuster` measures its update intervals with the simulated clock, which advances
+//! by the scenario block time on every simulated block.
+//!
+//! The simulation result is a report with the gas spent, the number of resubmitted transactions
+//! and the confirmation latency for every `AggregatedActionType`.
+
+// Built-in deps
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External uses
+use serde::{Deserialize, Serialize};
+use web3::types::{H256, U256, U64};
+// Workspace uses
+use zksync_config::ETHSenderConfig;
+use zksync_eth_client::{
+    clients::mock::MockEthereum, ethereum_gateway::ExecutedTxStatus, EthereumGateway,
+};
+use zksync_types::aggregated_operations::AggregatedOperation;
+use zksync_types::ethereum::ETHOperation;
+// Local uses
+use self::database::SimulatedDatabase;
+use crate::{
+    gas_adjuster::{Clock, GasAdjuster},
+    transactions::ETHStats,
+    ETHSender,
+};
+
+/// Average L1 block time used if the scenario doesn't define one.
+const DEFAULT_BLOCK_TIME_SEC: u64 = 15;
+
+mod database;
+
+#[cfg(test)]
+mod tests;
+
+/// Operation which becomes available for `ETHSender` at the certain L1 block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioOperation {
+    /// L1 block at which the operation is added to the database.
+    pub block: u64,
+    /// Operation itself along with its ID.
+    pub operation: (i64, AggregatedOperation),
+}
+
+/// State of the L1 network, which is actual starting from the certain block
+/// and until the next timeline entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    /// L1 block at which this state takes effect.
+    pub from_block: u64,
+    /// Gas price reported by the node (`eth_gasPrice`).
+    pub gas_price: U256,
+    /// Base fee of the blocks (EIP-1559).
+    pub base_fee_per_gas: U256,
+    /// Priority fee paid by the 100th percentile in the fee history.
+    pub max_priority_fee_reward: U256,
+    /// Minimal effective gas price for a transaction to be included into the block.
+    pub inclusion_price: U256,
+}
+
+/// Input of the simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Amount of L1 blocks to simulate.
+    pub blocks: u64,
+    /// Time between the L1 blocks, used by the simulated clock.
+    #[serde(default = "Scenario::default_block_time_sec")]
+    pub block_time_sec: u64,
+    /// Operations to be sent to L1.
+    pub operations: Vec<ScenarioOperation>,
+    /// Scripted state of the L1 network.
+    pub timeline: Vec<TimelineEntry>,
+}
+
+impl Scenario {
+    fn default_block_time_sec() -> u64 {
+        DEFAULT_BLOCK_TIME_SEC
+    }
+
+    /// Returns the state of the L1 network at the provided block.
+    fn network_state(&self, block: u64) -> Option<&TimelineEntry> {
+        self.timeline
+            .iter()
+            .filter(|entry| entry.from_block <= block)
+            .max_by_key(|entry| entry.from_block)
+    }
+
+    /// Calculates the stats of the blocks which are considered to be processed before
+    /// the first operation of each type.
+    fn initial_stats(&self) -> ETHStats {
+        let last_processed_block = |is_action_type: fn(&AggregatedOperation) -> bool| {
+            self.operations
+                .iter()
+                .filter(|op| is_action_type(&op.operation.1))
+                .map(|op| *op.operation.1.get_block_range().0 as usize)
+                .min()
+                .map(|first_block| first_block.saturating_sub(1))
+                .unwrap_or_default()
+        };
+
+        ETHStats {
+            last_committed_block: last_processed_block(AggregatedOperation::is_commit),
+            last_verified_block: last_processed_block(AggregatedOperation::is_publish_proofs),
+            last_executed_block: last_processed_block(AggregatedOperation::is_execute),
+        }
+    }
+}
+
+/// Aggregated results for operations of the same type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionTypeReport {
+    /// Amount of operations of this type in the scenario.
+    pub operations: usize,
+    /// Amount of operations confirmed before the end of the simulation.
+    pub confirmed: usize,
+    /// Total gas limit of the confirmed operations transactions.
+    pub gas_spent: U256,
+    /// Total fee paid for the confirmed operations (in wei).
+    pub fee_paid: U256,
+    /// Amount of transactions sent to replace the stuck ones.
+    pub resubmissions: usize,
+    /// Average amount of L1 blocks between the operation creation and its confirmation.
+    pub avg_latency_blocks: f64,
+    /// Maximum amount of L1 blocks between the operation creation and its confirmation.
+    pub max_latency_blocks: u64,
+}
+
+/// Output of the simulation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Results per `AggregatedActionType`.
+    pub action_types: BTreeMap<String, ActionTypeReport>,
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (action_type, report) in &self.action_types {
+            writeln!(f, "{}:", action_type)?;
+            writeln!(
+                f,
+                "  confirmed operations: {}/{}",
+                report.confirmed, report.operations
+            )?;
+            writeln!(f, "  gas spent: {}", report.gas_spent)?;
+            writeln!(f, "  fee paid: {} wei", report.fee_paid)?;
+            writeln!(f, "  resubmissions: {}", report.resubmissions)?;
+            writeln!(
+                f,
+                "  latency: avg {:.2} blocks, max {} blocks",
+                report.avg_latency_blocks, report.max_latency_blocks
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Clock which is advanced manually by the simulation loop.
+#[derive(Debug, Clone, Default)]
+struct SimulatedClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl SimulatedClock {
+    fn set(&self, now: Duration) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// Fees used by the sent transaction.
+#[derive(Debug, Clone, Copy)]
+struct SentTx {
+    gas_price: U256,
+    max_priority_fee_per_gas: Option<U256>,
+}
+
+impl SentTx {
+    /// Price actually paid for the gas if the transaction is included into the block.
+    fn effective_price(&self, base_fee_per_gas: U256) -> U256 {
+        match self.max_priority_fee_per_gas {
+            Some(priority_fee) => std::cmp::min(self.gas_price, base_fee_per_gas + priority_fee),
+            None => self.gas_price,
+        }
+    }
+}
+
+/// Transaction included into the L1 block.
+#[derive(Debug, Clone, Copy)]
+struct IncludedTx {
+    hash: H256,
+    block: u64,
+    effective_price: U256,
+}
+
+/// Replays the scenario against `ETHSender` and reports the results.
+///
+/// `ETHSender` is configured with the provided `config`, while the `GasAdjuster`
+/// parameters are taken from the environment as usual.
+pub async fn run_simulation(
+    config: ETHSenderConfig,
+    scenario: Scenario,
+) -> anyhow::Result<SimulationReport> {
+    anyhow::ensure!(
+        scenario.network_state(0).is_some(),
+        "Scenario timeline must start from the block 0"
+    );
+
+    let db = SimulatedDatabase::new(
+        config.gas_price_limit.default.into(),
+        scenario.initial_stats(),
+    );
+    let ethereum = EthereumGateway::Mock(MockEthereum::default());
+    let mut eth_sender = ETHSender::new(config, db, ethereum).await;
+    let clock = SimulatedClock::default();
+    eth_sender.gas_adjuster =
+        GasAdjuster::with_clock(&eth_sender.db, Box::new(clock.clone())).await;
+
+    let mut report = SimulationReport::default();
+    for op in &scenario.operations {
+        let action_type = op.operation.1.get_action_type().to_string();
+        report
+            .action_types
+            .entry(action_type)
+            .or_default()
+            .operations += 1;
+    }
+
+    // Block at which each aggregated operation was added, by operation ID.
+    let mut creation_blocks = HashMap::new();
+    let mut sent_txs: HashMap<H256, SentTx> = HashMap::new();
+    // Included transactions, by ETH operation ID.
+    let mut included_txs: HashMap<i64, IncludedTx> = HashMap::new();
+    let mut latencies: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut last_used_block = 0;
+
+    for block in 0..scenario.blocks {
+        let state = scenario.network_state(block).unwrap().clone();
+        clock.set(Duration::from_secs(block * scenario.block_time_sec));
+        {
+            let mock = eth_sender.ethereum.get_mut_mock().unwrap();
+            mock.set_block_number(U64::from(block)).await?;
+            mock.set_gas_price(state.gas_price).await?;
+            mock.set_base_fee_per_gas(state.base_fee_per_gas).await?;
+            mock.set_max_priority_fee_reward(state.max_priority_fee_reward)
+                .await?;
+        }
+
+        for op in scenario.operations.iter().filter(|op| op.block == block) {
+            creation_blocks.insert(op.operation.0, block);
+            eth_sender
+                .db
+                .send_aggregated_operation(op.operation.clone())
+                .await;
+        }
+        eth_sender.load_new_operations().await?;
+
+        include_transactions(
+            &eth_sender.ongoing_ops,
+            &sent_txs,
+            &mut included_txs,
+            &state,
+            block,
+        );
+        // Report the amount of confirmations for every included transaction.
+        for included_tx in included_txs.values() {
+            let status = ExecutedTxStatus {
+                confirmations: block - included_tx.block + 1,
+                success: true,
+                receipt: None,
+                gas_used: None,
+            };
+            eth_sender
+                .ethereum
+                .get_mut_mock()
+                .unwrap()
+                .add_execution(&included_tx.hash, &status)
+                .await;
+        }
+
+        let ongoing_ops = eth_sender.ongoing_ops.clone();
+        last_used_block = eth_sender.proceed_next_operations(last_used_block).await;
+        eth_sender
+            .gas_adjuster
+            .keep_updated(&eth_sender.ethereum, &eth_sender.db)
+            .await;
+
+        // Remember the fees of the newly sent transactions.
+        for op in &eth_sender.ongoing_ops {
+            if let Some(hash) = op.used_tx_hashes.last() {
+                sent_txs.entry(*hash).or_insert(SentTx {
+                    gas_price: op.last_used_gas_price,
+                    max_priority_fee_per_gas: op.last_used_priority_fee_per_gas,
+                });
+            }
+        }
+
+        // Operations which are not ongoing anymore are confirmed.
+        let confirmed_ops = ongoing_ops
+            .iter()
+            .filter(|op| !eth_sender.ongoing_ops.iter().any(|new| new.id == op.id));
+        for op in confirmed_ops {
+            let included_tx = included_txs
+                .remove(&op.id)
+                .expect("Operation is confirmed without being included");
+            let action_type = op.op_type.to_string();
+            let gas = ETHSender::<SimulatedDatabase>::gas_limit_for_op(op);
+            let created_at = op
+                .op
+                .as_ref()
+                .and_then(|(id, _)| creation_blocks.get(id))
+                .copied()
+                .unwrap_or_default();
+
+            let action_report = report.action_types.entry(action_type.clone()).or_default();
+            action_report.confirmed += 1;
+            action_report.gas_spent += gas;
+            action_report.fee_paid += gas * included_tx.effective_price;
+            action_report.resubmissions += op.used_tx_hashes.len() - 1;
+            latencies
+                .entry(action_type)
+                .or_default()
+                .push(block - created_at);
+        }
+    }
+
+    for (action_type, latencies) in latencies {
+        let action_report = report.action_types.entry(action_type).or_default();
+        action_report.avg_latency_blocks =
+            latencies.iter().sum::<u64>() as f64 / latencies.len() as f64;
+        action_report.max_latency_blocks = latencies.into_iter().max().unwrap_or_default();
+    }
+
+    Ok(report)
+}
+
+/// Includes the pending transactions into the current block.
+///
+/// Transactions are included in the order of their nonces, and a transaction can be
+/// included only if its effective gas price is not lower than the inclusion price
+/// of the block. If several transactions were sent for the same operation, the
+/// latest one (which has the highest price) is chosen.
+fn include_transactions(
+    ongoing_ops: &VecDeque<ETHOperation>,
+    sent_txs: &HashMap<H256, SentTx>,
+    included_txs: &mut HashMap<i64, IncludedTx>,
+    state: &TimelineEntry,
+    block: u64,
+) {
+    let mut pending_ops: Vec<_> = ongoing_ops
+        .iter()
+        .filter(|op| !included_txs.contains_key(&op.id))
+        .collect();
+    pending_ops.sort_by_key(|op| op.nonce);
+
+    for op in pending_ops {
+        let includable_tx = op.used_tx_hashes.iter().rev().find_map(|hash| {
+            let effective_price = sent_txs.get(hash)?.effective_price(state.base_fee_per_gas);
+            if effective_price >= state.inclusion_price {
+                Some(IncludedTx {
+                    hash: *hash,
+                    block,
+                    effective_price,
+                })
+            } else {
+                None
+            }
+        });
+
+        match includable_tx {
+            Some(included_tx) => {
+                included_txs.insert(op.id, included_tx);
+            }
+            // Transactions with the higher nonces can't be included until this one is.
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Built-in uses
+// Workspace uses
+use zksync_basic_types::U256;
+// Local uses
+use super::{run_simulation, Scenario, ScenarioOperation, TimelineEntry};
+use crate::tests::{
+    mock::eth_sender_config, test_data::commit_blocks_operation, EXPECTED_WAIT_TIME_BLOCKS,
+    WAIT_CONFIRMATIONS,
+};
+
+/// Network gas price used in the tests.
+const GAS_PRICE: u64 = 100;
+
+/// Creates a timeline entry with the fixed network prices.
+fn timeline_entry(from_block: u64, inclusion_price: u64) -> TimelineEntry {
+    TimelineEntry {
+        from_block,
+        gas_price: GAS_PRICE.into(),
+        base_fee_per_gas: 80.into(),
+        max_priority_fee_reward: 20.into(),
+        inclusion_price: inclusion_price.into(),
+    }
+}
+
+/// Checks that the operation which is included right after being sent is reported
+/// as confirmed without resubmissions.
+#[tokio::test]
+async fn simulation_without_stuck_txs() {
+    let scenario = Scenario {
+        blocks: 10,
+        block_time_sec: 15,
+        operations: vec![ScenarioOperation {
+            block: 0,
+            operation: commit_blocks_operation(0),
+        }],
+        timeline: vec![timeline_entry(0, 0)],
+    };
+
+    let report = run_simulation(eth_sender_config(1), scenario)
+        .await
+        .unwrap();
+    let commit_report = &report.action_types["CommitBlocks"];
+
+    assert_eq!(commit_report.operations, 1);
+    assert_eq!(commit_report.confirmed, 1);
+    assert_eq!(commit_report.resubmissions, 0);
+    // Transaction is sent at block 0, included at block 1 and gets enough confirmations
+    // after `WAIT_CONFIRMATIONS` blocks.
+    assert_eq!(commit_report.max_latency_blocks, WAIT_CONFIRMATIONS);
+    assert_eq!(
+        commit_report.fee_paid,
+        commit_report.gas_spent * U256::from(GAS_PRICE)
+    );
+}
+
+/// Checks that the transaction which can't be included for a while is reported
+/// with the resubmission and the increased latency.
+#[tokio::test]
+async fn simulation_with_stuck_tx() {
+    // The network doesn't accept transactions until the first tx becomes stuck.
+    let inclusion_block = EXPECTED_WAIT_TIME_BLOCKS + 5;
+    let scenario = Scenario {
+        blocks: inclusion_block + 10,
+        block_time_sec: 15,
+        operations: vec![ScenarioOperation {
+            block: 0,
+            operation: commit_blocks_operation(0),
+        }],
+        timeline: vec![
+            timeline_entry(0, GAS_PRICE * 10),
+            timeline_entry(inclusion_block, 0),
+        ],
+    };
+
+    let report = run_simulation(eth_sender_config(1), scenario)
+        .await
+        .unwrap();
+    let commit_report = &report.action_types["CommitBlocks"];
+
+    assert_eq!(commit_report.confirmed, 1);
+    assert_eq!(commit_report.resubmissions, 1);
+    assert_eq!(
+        commit_report.max_latency_blocks,
+        inclusion_block + WAIT_CONFIRMATIONS - 1
+    );
+}
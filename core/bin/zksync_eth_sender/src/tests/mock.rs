@@ -8,14 +8,14 @@ use tokio::sync::RwLock;
 use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, GasPriceStrategy, Sender};
 use zksync_eth_client::EthereumGateway;
-use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
+use zksync_storage::ethereum::records::ETHParams;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
 // Local uses
 use super::ETHSender;
-use crate::database::DatabaseInterface;
+use crate::database::{DatabaseConnection, DatabaseInterface};
 use crate::transactions::ETHStats;
 use zksync_eth_client::clients::mock::MockEthereum;
 
@@ -98,16 +98,15 @@ impl MockDatabase {
 
 #[async_trait::async_trait]
 impl DatabaseInterface for MockDatabase {
-    /// Creates a new database connection, used as a stub
-    /// and nothing will be sent through this connection.
-    async fn acquire_connection(&self) -> anyhow::Result<StorageProcessor<'_>> {
-        StorageProcessor::establish_connection().await
+    /// Returns a detached connection, since everything is stored in memory.
+    async fn acquire_connection(&self) -> anyhow::Result<DatabaseConnection<'_>> {
+        Ok(DatabaseConnection::Detached)
     }
 
     /// Returns all unprocessed operations.
     async fn load_new_operations(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<Vec<(i64, AggregatedOperation)>> {
         let unprocessed_operations = self
             .unprocessed_operations
@@ -123,7 +122,7 @@ impl DatabaseInterface for MockDatabase {
     /// Remove the unprocessed operations from the database.
     async fn remove_unprocessed_operations(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         operations_id: Vec<i64>,
     ) -> anyhow::Result<()> {
         let mut old_unprocessed_operations = self.unprocessed_operations.write().await;
@@ -141,7 +140,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn update_gas_price_params(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         gas_price_limit: U256,
         average_gas_price: U256,
     ) -> anyhow::Result<()> {
@@ -156,7 +155,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn restore_unprocessed_operations(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<()> {
         let aggregated_operations = self.aggregated_operations.read().await;
         let eth_operations = self.eth_operations.read().await;
@@ -183,7 +182,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn load_unconfirmed_operations(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<VecDeque<ETHOperation>> {
         let unconfirmed_operations = self
             .eth_operations
@@ -199,11 +198,12 @@ impl DatabaseInterface for MockDatabase {
 
     async fn save_new_eth_tx(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         op_type: AggregatedActionType,
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         encoded_tx_data: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
@@ -218,6 +218,7 @@ impl DatabaseInterface for MockDatabase {
             nonce: nonce.into(),
             last_deadline_block: deadline_block as u64,
             last_used_gas_price: used_gas_price,
+            last_used_priority_fee_per_gas: used_priority_fee,
             used_tx_hashes: vec![],
             encoded_tx_data,
             confirmed: false,
@@ -237,7 +238,7 @@ impl DatabaseInterface for MockDatabase {
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         eth_op_id: i64,
         hash: &H256,
     ) -> anyhow::Result<()> {
@@ -257,10 +258,11 @@ impl DatabaseInterface for MockDatabase {
 
     async fn update_eth_tx(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let eth_op = eth_operations
@@ -270,6 +272,7 @@ impl DatabaseInterface for MockDatabase {
         if let Some(eth_op) = eth_op {
             eth_op.last_deadline_block = new_deadline_block as u64;
             eth_op.last_used_gas_price = new_gas_value;
+            eth_op.last_used_priority_fee_per_gas = new_priority_fee;
         } else {
             panic!("Attempt to update tx that is not unconfirmed");
         }
@@ -279,7 +282,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn confirm_operation(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         hash: &H256,
         _op: &ETHOperation,
     ) -> anyhow::Result<()> {
@@ -304,7 +307,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn load_gas_price_limit(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
     ) -> anyhow::Result<U256> {
         let eth_parameters = self.eth_parameters.read().await;
         let gas_price_limit = eth_parameters.gas_price_limit.into();
@@ -312,7 +315,10 @@ impl DatabaseInterface for MockDatabase {
         Ok(gas_price_limit)
     }
 
-    async fn load_stats(&self, _connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats> {
+    async fn load_stats(
+        &self,
+        _connection: &mut DatabaseConnection<'_>,
+    ) -> anyhow::Result<ETHStats> {
         let eth_parameters = self.eth_parameters.read().await;
         let eth_stats = ETHStats {
             last_committed_block: eth_parameters.last_committed_block as usize,
@@ -325,7 +331,7 @@ impl DatabaseInterface for MockDatabase {
 
     async fn is_previous_operation_confirmed(
         &self,
-        _connection: &mut StorageProcessor<'_>,
+        _connection: &mut DatabaseConnection<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool> {
         let confirmed = {
@@ -397,6 +403,14 @@ pub(in crate) async fn concurrent_eth_sender(max_txs_in_flight: u64) -> ETHSende
     .await
 }
 
+/// Creates an `ETHSender` with mock Ethereum connection/database and no operations in DB
+/// which sends EIP-1559 transactions instead of the legacy ones.
+pub(in crate) async fn eip1559_eth_sender() -> ETHSender<MockDatabase> {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.options.sender.use_eip1559_txs = true;
+    eth_sender
+}
+
 /// Creates an `ETHSender` with mock Ethereum connection/database and restores its state "from DB".
 /// Returns the `ETHSender` itself along with communication channels to interact with it.
 pub(in crate) async fn restored_eth_sender(
@@ -433,7 +447,14 @@ async fn build_eth_sender(
         eth_parameters,
     );
 
-    let options = ETHSenderConfig {
+    let options = eth_sender_config(max_txs_in_flight);
+
+    ETHSender::new(options, db, ethereum).await
+}
+
+/// Creates an `ETHSender` configuration for use in tests.
+pub(in crate) fn eth_sender_config(max_txs_in_flight: u64) -> ETHSenderConfig {
+    ETHSenderConfig {
         sender: Sender {
             max_txs_in_flight,
             expected_wait_time_block: super::EXPECTED_WAIT_TIME_BLOCKS,
@@ -442,16 +463,20 @@ async fn build_eth_sender(
             is_enabled: true,
             operator_commit_eth_addr: Default::default(),
             operator_private_key: Default::default(),
+            use_eip1559_txs: false,
         },
         gas_price_limit: GasLimit {
             default: 1000,
             sample_interval: 15,
             update_interval: 15,
             scale_factor: 1.0f64,
+            default_priority_fee: 10,
+            strategy: GasPriceStrategy::Statistics,
+            fee_history_block_count: 4,
+            fee_history_percentile: 50.0,
+            fee_history_max_scale: 2.0,
         },
-    };
-
-    ETHSender::new(options, db, ethereum).await
+    }
 }
 
 /// Behaves the same as `ETHSender::sign_new_tx`, but does not affect nonce.
@@ -485,6 +510,7 @@ pub(in crate) async fn create_signed_tx(
         nonce: signed_tx.nonce,
         last_deadline_block: deadline_block,
         last_used_gas_price: signed_tx.gas_price,
+        last_used_priority_fee_per_gas: signed_tx.max_priority_fee_per_gas,
         used_tx_hashes: vec![signed_tx.hash],
         encoded_tx_data: raw_tx,
         confirmed: false,
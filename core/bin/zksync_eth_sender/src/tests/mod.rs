@@ -1,17 +1,22 @@
 // Local uses
 use self::mock::{
     concurrent_eth_sender, create_signed_tx, default_eth_parameters, default_eth_sender,
-    restored_eth_sender,
+    eip1559_eth_sender, restored_eth_sender,
 };
 use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
 use web3::types::U64;
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
+use zksync_types::{
+    aggregated_operations::{AggregatedActionType, AggregatedOperation},
+    ethereum::ETHOperation,
+    ExecutedOperations,
+};
 
-const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
-const WAIT_CONFIRMATIONS: u64 = 3;
+pub const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
+pub const WAIT_CONFIRMATIONS: u64 = 3;
 
 pub mod mock;
-mod test_data;
+pub mod test_data;
 
 /// Basic test that `ETHSender` creation does not panic and initializes correctly.
 #[tokio::test]
@@ -34,6 +39,52 @@ async fn deadline_block() {
     );
 }
 
+/// Checks that the execute operations are reported if they may not be mined
+/// before the deadline of their priority operations.
+#[tokio::test]
+async fn priority_op_deadline_at_risk() {
+    let eth_sender = default_eth_sender().await;
+
+    let eth_operation = |operation| ETHOperation {
+        id: 0,
+        op_type: AggregatedActionType::ExecuteBlocks,
+        op: Some(operation),
+        nonce: Default::default(),
+        last_deadline_block: 0,
+        last_used_gas_price: Default::default(),
+        last_used_priority_fee_per_gas: None,
+        used_tx_hashes: Vec::new(),
+        encoded_tx_data: Vec::new(),
+        confirmed: false,
+        final_hash: None,
+    };
+
+    let (id, mut execute_op) = test_data::execute_blocks_operations(0);
+    if let AggregatedOperation::ExecuteBlocks(op) = &mut execute_op {
+        for tx in &mut op.blocks[0].block_transactions {
+            if let ExecutedOperations::PriorityOp(op) = tx {
+                op.priority_op.deadline_block = 100 + EXPECTED_WAIT_TIME_BLOCKS;
+            }
+        }
+    }
+    let execute_op = eth_operation((id, execute_op));
+    assert_eq!(
+        eth_sender.priority_op_deadline_at_risk(&execute_op, 10),
+        None
+    );
+    assert_eq!(
+        eth_sender.priority_op_deadline_at_risk(&execute_op, 100),
+        Some(100 + EXPECTED_WAIT_TIME_BLOCKS)
+    );
+
+    // Only the execution of the priority operations is checked.
+    let commit_op = eth_operation(test_data::commit_blocks_operation(0));
+    assert_eq!(
+        eth_sender.priority_op_deadline_at_risk(&commit_op, 100),
+        None
+    );
+}
+
 /// Checks that received transaction response is reduced to the
 /// `TxCheckOutcome` correctly.
 ///
@@ -78,6 +129,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -91,6 +143,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -104,6 +157,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -117,6 +171,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -410,6 +465,67 @@ async fn stuck_transaction() {
     eth_sender.db.assert_confirmed(&stuck_tx).await;
 }
 
+/// Checks that with EIP-1559 transactions enabled, both the fee cap and the priority fee
+/// are used for the new transaction and increased for the replacement of the stuck one.
+#[tokio::test]
+async fn eip1559_stuck_transaction() {
+    let mut eth_sender = eip1559_eth_sender().await;
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // Mock base fee is 80 and the default priority fee for tests is 10,
+    // so the fee cap is `2 * 80 + 10`.
+    let op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(op.last_used_gas_price, 170.into());
+    assert_eq!(op.last_used_priority_fee_per_gas, Some(10.into()));
+    eth_sender.db.assert_stored(&op).await;
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .assert_sent(&op.used_tx_hashes[0].as_bytes().to_vec())
+        .await;
+
+    // Skip some blocks and expect sender to send a new tx.
+    let block_number = eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .block_number()
+        .await
+        .unwrap()
+        + EXPECTED_WAIT_TIME_BLOCKS;
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .set_block_number(block_number)
+        .await
+        .unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // Both fees are increased by 15% (rounded down).
+    let op = eth_sender.ongoing_ops[0].clone();
+    assert_eq!(op.used_tx_hashes.len(), 2);
+    assert_eq!(op.last_used_gas_price, 195.into());
+    assert_eq!(op.last_used_priority_fee_per_gas, Some(11.into()));
+    eth_sender.db.assert_stored(&op).await;
+    eth_sender
+        .ethereum
+        .get_mut_mock()
+        .unwrap()
+        .assert_sent(&op.used_tx_hashes[1].as_bytes().to_vec())
+        .await;
+}
+
 /// This test verifies that with multiple operations received all-together,
 /// their order is respected and no processing of the next operation is started until
 /// the previous one is committed.
@@ -4,7 +4,8 @@
 
 // Built-in uses
 // Workspace uses
-use zksync_config::ZkSyncConfig;
+use zksync_config::{configs::EventListenerConfig, ZkSyncConfig};
+use zksync_storage::ConnectionPool;
 // External uses
 use actix::prelude::*;
 use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer};
@@ -13,24 +14,56 @@ use actix_web_actors::ws;
 use listener::EventListener;
 use messages::RegisterServerHandle;
 use monitor::ServerMonitor;
-use subscriber::Subscriber;
+use sink::{BusSink, EventExporter, FileSink, KafkaRestProxy};
+use subscriber::{EventReplay, ReplayQuery, Subscriber};
 
 pub mod listener;
 pub mod messages;
 pub mod monitor;
+pub mod sink;
 pub mod subscriber;
 
+/// Size of the connection pool used for replaying the past events.
+const REPLAY_POOL_SIZE: u32 = 4;
+/// Size of the connection pool shared by the events exporters.
+const SINK_POOL_SIZE: u32 = 2;
+
+/// Spawns exporters for the sinks enabled in the configuration.
+fn run_event_exporters(config: &EventListenerConfig) {
+    let db_pool = ConnectionPool::new(Some(SINK_POOL_SIZE));
+    if config.file_sink_enabled {
+        let sink = FileSink::new(&config.file_sink_dir, config.file_sink_max_file_size)
+            .expect("Failed to initialize the file sink");
+        let exporter = EventExporter::new(db_pool.clone(), sink, config);
+        actix_web::rt::spawn(exporter.run());
+    }
+    if config.bus_sink_enabled {
+        let bus = KafkaRestProxy::new(config.bus_sink_url.clone());
+        let sink = BusSink::new(bus, config.bus_sink_topic.clone());
+        let exporter = EventExporter::new(db_pool, sink, config);
+        actix_web::rt::spawn(exporter.run());
+    }
+}
+
 #[derive(Debug)]
 struct AppState {
     server_monitor: Addr<ServerMonitor>,
+    db_pool: ConnectionPool,
+    config: EventListenerConfig,
 }
 
 async fn ws_index(
     req: HttpRequest,
     stream: web::Payload,
+    query: web::Query<ReplayQuery>,
     data: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
-    ws::start(Subscriber::new(data.server_monitor.clone()), &req, stream)
+    let replay = EventReplay::new(data.db_pool.clone(), query.into_inner(), &data.config).await?;
+    ws::start(
+        Subscriber::new(data.server_monitor.clone(), replay),
+        &req,
+        stream,
+    )
 }
 
 pub async fn run_event_server(config: ZkSyncConfig) {
@@ -39,9 +72,12 @@ pub async fn run_event_server(config: ZkSyncConfig) {
         .await
         .unwrap()
         .start();
+    run_event_exporters(&config.event_listener);
 
     let state = web::Data::new(AppState {
         server_monitor: monitor.clone(),
+        db_pool: ConnectionPool::new(Some(REPLAY_POOL_SIZE)),
+        config: config.event_listener.clone(),
     });
 
     let server = HttpServer::new(move || {
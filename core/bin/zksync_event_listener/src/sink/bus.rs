@@ -0,0 +1,165 @@
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+// External uses
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+// Workspace uses
+use zksync_types::event::ZkSyncEvent;
+// Local uses
+use super::{EventSink, ExportedEvent};
+
+/// Message published to the bus.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BusRecord {
+    /// Key of the record, messages with the same key preserve their order.
+    /// Equal to the event type.
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+/// Client of the message bus, e.g. Kafka.
+#[async_trait]
+pub trait MessageBus: Send + Sync + 'static {
+    /// Publishes records to the topic. Records are either all accepted by the
+    /// bus or the error is returned.
+    async fn publish(&self, topic: &str, records: Vec<BusRecord>) -> anyhow::Result<()>;
+}
+
+/// Publishes events to the topic of the message bus.
+#[derive(Debug)]
+pub struct BusSink<B> {
+    bus: B,
+    topic: String,
+}
+
+impl<B: MessageBus> BusSink<B> {
+    pub fn new(bus: B, topic: String) -> Self {
+        Self { bus, topic }
+    }
+}
+
+#[async_trait]
+impl<B: MessageBus> EventSink for BusSink<B> {
+    fn name(&self) -> String {
+        format!("bus:{}", self.topic)
+    }
+
+    async fn export(&mut self, events: &[ZkSyncEvent]) -> anyhow::Result<()> {
+        let records = events
+            .iter()
+            .map(|event| {
+                let value = serde_json::to_value(ExportedEvent::from(event))?;
+                let key = value["type"].as_str().unwrap_or_default().to_string();
+                Ok(BusRecord { key, value })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        self.bus.publish(&self.topic, records).await
+    }
+}
+
+/// Publishes records through the Kafka REST proxy.
+#[derive(Debug, Clone)]
+pub struct KafkaRestProxy {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl KafkaRestProxy {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl MessageBus for KafkaRestProxy {
+    async fn publish(&self, topic: &str, records: Vec<BusRecord>) -> anyhow::Result<()> {
+        self.client
+            .post(format!("{}/topics/{}", self.url, topic))
+            .header("Content-Type", "application/vnd.kafka.json.v2+json")
+            .body(serde_json::to_vec(&json!({ "records": records }))?)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// In-process message bus, which keeps all published records in memory.
+/// Can be made unavailable to simulate the bus outage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryBus {
+    topics: Arc<Mutex<HashMap<String, Vec<BusRecord>>>>,
+    unavailable: Arc<Mutex<bool>>,
+}
+
+impl InMemoryBus {
+    pub fn records(&self, topic: &str) -> Vec<BusRecord> {
+        self.topics
+            .lock()
+            .unwrap()
+            .get(topic)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_unavailable(&self, unavailable: bool) {
+        *self.unavailable.lock().unwrap() = unavailable;
+    }
+}
+
+#[async_trait]
+impl MessageBus for InMemoryBus {
+    async fn publish(&self, topic: &str, records: Vec<BusRecord>) -> anyhow::Result<()> {
+        if *self.unavailable.lock().unwrap() {
+            anyhow::bail!("Message bus is unavailable");
+        }
+        self.topics
+            .lock()
+            .unwrap()
+            .entry(topic.to_string())
+            .or_default()
+            .extend(records);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::{block::BlockStatus, test_data::*, EventId};
+
+    #[tokio::test]
+    async fn test_bus_sink() -> anyhow::Result<()> {
+        let bus = InMemoryBus::default();
+        let mut sink = BusSink::new(bus.clone(), "events".to_string());
+        assert_eq!(sink.name(), "bus:events");
+
+        let mut block_event = get_block_event(BlockStatus::Finalized);
+        block_event.id = EventId(1);
+        let mut exodus_event = get_exodus_event();
+        exodus_event.id = EventId(2);
+        let events = vec![block_event, exodus_event];
+
+        // Nothing is published while the bus is unavailable.
+        bus.set_unavailable(true);
+        assert!(sink.export(&events).await.is_err());
+        assert!(bus.records("events").is_empty());
+
+        bus.set_unavailable(false);
+        sink.export(&events).await?;
+        let records = bus.records("events");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].key, "block");
+        assert_eq!(records[0].value["id"], 1);
+        assert_eq!(records[1].key, "exodus");
+        assert_eq!(records[1].value["id"], 2);
+        Ok(())
+    }
+}
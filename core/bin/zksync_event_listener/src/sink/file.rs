@@ -0,0 +1,157 @@
+// Built-in uses
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+// External uses
+use async_trait::async_trait;
+// Workspace uses
+use zksync_types::event::{EventId, ZkSyncEvent};
+// Local uses
+use super::{EventSink, ExportedEvent};
+
+/// Appends events to the newline-delimited JSON files in the given directory.
+///
+/// A new file is started once the current one exceeds the maximum size. Files are
+/// named after the id of the first event they contain, so their lexicographical
+/// order matches the order of events.
+#[derive(Debug)]
+pub struct FileSink {
+    dir: PathBuf,
+    max_file_size: u64,
+    /// File which is currently being written and its size.
+    current: Option<(File, u64)>,
+}
+
+impl FileSink {
+    pub fn new(dir: impl Into<PathBuf>, max_file_size: u64) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            max_file_size,
+            current: None,
+        })
+    }
+
+    fn file_path(&self, first_event_id: EventId) -> PathBuf {
+        self.dir
+            .join(format!("events-{:020}.ndjson", *first_event_id))
+    }
+
+    /// Appends the serialized batch to the current file, starting a new one at `new_file_path`
+    /// if the current file is too big. Performs blocking I/O.
+    fn write(
+        current: &mut Option<(File, u64)>,
+        max_file_size: u64,
+        new_file_path: PathBuf,
+        buffer: &[u8],
+    ) -> anyhow::Result<()> {
+        let needs_rotation = match current {
+            Some((_, size)) => *size >= max_file_size,
+            None => true,
+        };
+        if needs_rotation {
+            // The file may already exist if the same events are exported again
+            // after restart, keep appending to it.
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(new_file_path)?;
+            let size = file.metadata()?.len();
+            *current = Some((file, size));
+        }
+
+        let (file, size) = current.as_mut().unwrap();
+        let result = file.write_all(buffer).and_then(|_| file.sync_data());
+        if let Err(err) = result {
+            // Cut off the partially written batch, it will be exported again.
+            file.set_len(*size)?;
+            return Err(err.into());
+        }
+        *size += buffer.len() as u64;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSink for FileSink {
+    fn name(&self) -> String {
+        "file".to_string()
+    }
+
+    async fn export(&mut self, events: &[ZkSyncEvent]) -> anyhow::Result<()> {
+        let first_event_id = match events.first() {
+            Some(event) => event.id,
+            None => return Ok(()),
+        };
+        let mut buffer = Vec::new();
+        for event in events {
+            serde_json::to_writer(&mut buffer, &ExportedEvent::from(event))?;
+            buffer.push(b'\n');
+        }
+
+        // Writing and syncing the file may take a while, don't block the server's thread.
+        let mut current = self.current.take();
+        let max_file_size = self.max_file_size;
+        let new_file_path = self.file_path(first_event_id);
+        let (current, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::write(&mut current, max_file_size, new_file_path, &buffer);
+            (current, result)
+        })
+        .await?;
+        self.current = current;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::{block::BlockStatus, test_data::get_block_event};
+
+    fn get_events(ids: std::ops::RangeInclusive<u64>) -> Vec<ZkSyncEvent> {
+        ids.map(|id| {
+            let mut event = get_block_event(BlockStatus::Committed);
+            event.id = EventId(id);
+            event
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_rotation() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("zksync_file_sink_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        // Every batch exceeds the maximum size, so each one starts a new file.
+        let mut sink = FileSink::new(&dir, 1)?;
+        sink.export(&get_events(1..=2)).await?;
+        sink.export(&get_events(3..=5)).await?;
+
+        let mut files = fs::read_dir(&dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        files.sort();
+        assert_eq!(
+            files,
+            vec![sink.file_path(EventId(1)), sink.file_path(EventId(3))]
+        );
+
+        let content = fs::read_to_string(&files[1])?;
+        let ids = content
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["id"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![3, 4, 5]);
+
+        // Re-exporting events after restart appends them to the existing file.
+        let mut sink = FileSink::new(&dir, 1)?;
+        sink.export(&get_events(3..=3)).await?;
+        assert_eq!(fs::read_to_string(&files[1])?.lines().count(), 4);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! Exporters of the events stream into the external systems.
+//!
+//! Each sink is driven by its own [`EventExporter`], which loads new events
+//! from the database in batches and stores the id of the last exported event
+//! after each successful export. Delivery is at-least-once: if the exporter
+//! is restarted between the export and the cursor update, the same events
+//! are exported again, so consumers should deduplicate them by `id`.
+
+// Built-in uses
+use std::{convert::TryFrom, time::Duration};
+// External uses
+use async_trait::async_trait;
+use serde::Serialize;
+// Workspace uses
+use zksync_config::configs::EventListenerConfig;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::event::{EventId, ZkSyncEvent};
+// Local uses
+
+pub use self::{
+    bus::{BusRecord, BusSink, InMemoryBus, KafkaRestProxy, MessageBus},
+    file::FileSink,
+};
+
+mod bus;
+mod file;
+
+/// Destination of the exported events.
+#[async_trait]
+pub trait EventSink: Send + 'static {
+    /// Unique name of the sink, the export progress is persisted under it.
+    fn name(&self) -> String;
+
+    /// Exports the batch of events. Once the method returns `Ok`,
+    /// the events are considered to be delivered.
+    async fn export(&mut self, events: &[ZkSyncEvent]) -> anyhow::Result<()>;
+}
+
+/// Representation of the exported event. Unlike [`ZkSyncEvent`] it features
+/// the event id, so that the consumers can deduplicate events.
+#[derive(Debug, Serialize)]
+pub struct ExportedEvent<'a> {
+    pub id: EventId,
+    #[serde(flatten)]
+    pub event: &'a ZkSyncEvent,
+}
+
+impl<'a> From<&'a ZkSyncEvent> for ExportedEvent<'a> {
+    fn from(event: &'a ZkSyncEvent) -> Self {
+        Self {
+            id: event.id,
+            event,
+        }
+    }
+}
+
+/// Periodically exports new events from the database into the sink.
+#[derive(Debug)]
+pub struct EventExporter<S> {
+    db_pool: ConnectionPool,
+    sink: S,
+    /// Id of the last exported event, loaded from the database on the first iteration.
+    cursor: Option<EventId>,
+    batch_size: u32,
+    poll_interval: Duration,
+}
+
+impl<S: EventSink> EventExporter<S> {
+    pub fn new(db_pool: ConnectionPool, sink: S, config: &EventListenerConfig) -> Self {
+        Self {
+            db_pool,
+            sink,
+            cursor: None,
+            batch_size: config.sink_batch_size,
+            poll_interval: config.sink_poll_interval(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        let sink_name = self.sink.name();
+        vlog::info!("Starting the events export into the {} sink", sink_name);
+        loop {
+            match self.export_next_batch(&sink_name).await {
+                // The batch was full, there may be more events to export.
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(err) => {
+                    vlog::error!(
+                        "Couldn't export events into the {} sink, reason: {}",
+                        sink_name,
+                        err
+                    );
+                }
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Exports the batch of events following the cursor. Returns `true`
+    /// if the batch was full.
+    async fn export_next_batch(&mut self, sink_name: &str) -> anyhow::Result<bool> {
+        let mut storage = self.db_pool.access_storage().await?;
+        let events = self.load_next_batch(&mut storage, sink_name).await?;
+        // Don't hold the connection while the sink is exporting events.
+        drop(storage);
+        if events.is_empty() {
+            return Ok(false);
+        }
+
+        self.sink.export(&events).await?;
+        let mut storage = self.db_pool.access_storage().await?;
+        self.commit_batch(&mut storage, sink_name, &events).await?;
+
+        Ok(events.len() == self.batch_size as usize)
+    }
+
+    /// Loads the batch of events following the cursor.
+    async fn load_next_batch(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        sink_name: &str,
+    ) -> anyhow::Result<Vec<ZkSyncEvent>> {
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None => storage
+                .event_schema()
+                .get_sink_cursor(sink_name)
+                .await?
+                .unwrap_or(EventId(0)),
+        };
+        self.cursor = Some(cursor);
+
+        let events = storage
+            .event_schema()
+            .fetch_new_events_page(cursor, self.batch_size)
+            .await?
+            .into_iter()
+            .map(ZkSyncEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(events)
+    }
+
+    /// Moves the cursor past the events exported into the sink.
+    async fn commit_batch(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        sink_name: &str,
+        events: &[ZkSyncEvent],
+    ) -> anyhow::Result<()> {
+        let last_event_id = match events.last() {
+            Some(event) => event.id,
+            None => return Ok(()),
+        };
+        storage
+            .event_schema()
+            .update_sink_cursor(sink_name, last_event_id)
+            .await?;
+        self.cursor = Some(last_event_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{event::exodus::ExodusEvent, BlockNumber};
+
+    /// Checks that the cursor isn't moved past the events the sink failed to export,
+    /// so they are exported again on the next iteration.
+    #[tokio::test]
+    #[cfg_attr(not(feature = "db_test"), ignore)]
+    async fn test_exporter_keeps_cursor_on_failure() -> anyhow::Result<()> {
+        // The exporter accesses the database on its own, so the events have to be committed.
+        let db_pool = ConnectionPool::new(Some(1));
+        let bus = InMemoryBus::default();
+        let sink = BusSink::new(bus.clone(), "events".to_string());
+        let sink_name = sink.name();
+
+        let last_event_id = {
+            let mut storage = db_pool.access_storage().await?;
+            let last_event_id = storage
+                .event_schema()
+                .get_last_event_id()
+                .await?
+                .unwrap_or(EventId(0));
+            // Only the events stored by the test are exported.
+            storage
+                .event_schema()
+                .update_sink_cursor(&sink_name, last_event_id)
+                .await?;
+            for eth_block in 0..3 {
+                let exodus_event = ExodusEvent {
+                    eth_block,
+                    first_priority_request_id: 0,
+                    total_open_priority_requests: 1,
+                };
+                storage
+                    .event_schema()
+                    .store_exodus_event(BlockNumber(1), exodus_event)
+                    .await?;
+            }
+            last_event_id
+        };
+
+        let mut exporter = EventExporter {
+            db_pool: db_pool.clone(),
+            sink,
+            cursor: None,
+            batch_size: 2,
+            poll_interval: Duration::from_secs(1),
+        };
+
+        bus.set_unavailable(true);
+        assert!(exporter.export_next_batch(&sink_name).await.is_err());
+        assert_eq!(exporter.cursor, Some(last_event_id));
+        assert_eq!(
+            db_pool
+                .access_storage()
+                .await?
+                .event_schema()
+                .get_sink_cursor(&sink_name)
+                .await?,
+            Some(last_event_id)
+        );
+        assert!(bus.records("events").is_empty());
+
+        // The same batch is exported on the next iteration.
+        bus.set_unavailable(false);
+        assert!(exporter.export_next_batch(&sink_name).await?);
+        let records = bus.records("events");
+        assert_eq!(records.len(), 2);
+        let exported_id = EventId(records[1].value["id"].as_u64().unwrap());
+        assert!(exported_id > last_event_id);
+        assert_eq!(exporter.cursor, Some(exported_id));
+        assert_eq!(
+            db_pool
+                .access_storage()
+                .await?
+                .event_schema()
+                .get_sink_cursor(&sink_name)
+                .await?,
+            Some(exported_id)
+        );
+
+        assert!(!exporter.export_next_batch(&sink_name).await?);
+        assert_eq!(bus.records("events").len(), 3);
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use zksync_types::{
     AccountId, TokenId,
 };
 // Local uses
+use super::{range::BlockRange, EventMatcher};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -15,10 +16,13 @@ pub struct AccountFilter {
     pub accounts: Option<HashSet<AccountId>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<AccountStateChangeStatus>,
+    pub block_range: Option<BlockRange>,
+    /// If set, only accept (or, if `false`, deny) the updates of NFT balances.
+    pub nft: Option<bool>,
 }
 
-impl AccountFilter {
-    pub fn matches(&self, event: &ZkSyncEvent) -> bool {
+impl EventMatcher for AccountFilter {
+    fn matches(&self, event: &ZkSyncEvent) -> bool {
         let account_event = match &event.data {
             EventData::Account(account_event) => account_event,
             _ => return false,
@@ -45,6 +49,16 @@ impl AccountFilter {
                 return false;
             }
         }
+        if let Some(block_range) = &self.block_range {
+            if !block_range.contains(event.block_number) {
+                return false;
+            }
+        }
+        if let Some(nft) = self.nft {
+            if account_event.is_nft() != nft {
+                return false;
+            }
+        }
         true
     }
 }
@@ -52,7 +66,8 @@ impl AccountFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zksync_types::event::test_data::get_account_event;
+    use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+    use zksync_types::{event::test_data::get_account_event, BlockNumber};
 
     #[test]
     fn test_account_filter() {
@@ -61,6 +76,8 @@ mod tests {
             accounts: None,
             tokens: None,
             status: None,
+            block_range: None,
+            nft: None,
         };
 
         let event = get_account_event(
@@ -122,5 +139,30 @@ mod tests {
             AccountStateChangeStatus::Committed,
         );
         assert!(account_filter.matches(&event));
+
+        // Filter out non-NFT balances.
+        account_filter.nft = Some(true);
+        assert!(!account_filter.matches(&event));
+        let nft_event = get_account_event(
+            AccountId(1000),
+            Some(TokenId(MIN_NFT_TOKEN_ID)),
+            AccountStateChangeStatus::Committed,
+        );
+        account_filter.tokens = None;
+        assert!(account_filter.matches(&nft_event));
+        // The opposite.
+        account_filter.nft = Some(false);
+        assert!(account_filter.matches(&event));
+        assert!(!account_filter.matches(&nft_event));
+
+        // Only accept events from the block window.
+        account_filter.block_range = Some(BlockRange {
+            from: Some(BlockNumber(1)),
+            to: None,
+        });
+        assert!(!account_filter.matches(&event));
+        let mut event = event;
+        event.block_number = BlockNumber(1);
+        assert!(account_filter.matches(&event));
     }
 }
@@ -4,15 +4,17 @@ use serde::Deserialize;
 // Workspace uses
 use zksync_types::event::{block::*, EventData, ZkSyncEvent};
 // Local uses
+use super::{range::BlockRange, EventMatcher};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct BlockFilter {
     pub status: Option<BlockStatus>,
+    pub block_range: Option<BlockRange>,
 }
 
-impl BlockFilter {
-    pub fn matches(&self, event: &ZkSyncEvent) -> bool {
+impl EventMatcher for BlockFilter {
+    fn matches(&self, event: &ZkSyncEvent) -> bool {
         let block_event = match &event.data {
             EventData::Block(block_event) => block_event,
             _ => return false,
@@ -22,6 +24,11 @@ impl BlockFilter {
                 return false;
             }
         }
+        if let Some(block_range) = &self.block_range {
+            if !block_range.contains(event.block_number) {
+                return false;
+            }
+        }
         true
     }
 }
@@ -29,12 +36,15 @@ impl BlockFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zksync_types::event::test_data::get_block_event;
+    use zksync_types::{event::test_data::get_block_event, BlockNumber};
 
     #[test]
     fn test_block_filter() {
         // Match all block events.
-        let block_filter = BlockFilter { status: None };
+        let block_filter = BlockFilter {
+            status: None,
+            block_range: None,
+        };
         for block_status in &[
             BlockStatus::Committed,
             BlockStatus::Finalized,
@@ -44,13 +54,23 @@ mod tests {
             assert!(block_filter.matches(&block_event));
         }
         // Only match committed blocks.
-        let block_filter = BlockFilter {
+        let mut block_filter = BlockFilter {
             status: Some(BlockStatus::Committed),
+            block_range: None,
         };
         let block_event = get_block_event(BlockStatus::Committed);
         assert!(block_filter.matches(&block_event));
         // Should be filtered out.
         let block_event = get_block_event(BlockStatus::Finalized);
         assert!(!block_filter.matches(&block_event));
+        // Only match blocks up to the 10th.
+        block_filter.block_range = Some(BlockRange {
+            from: None,
+            to: Some(BlockNumber(10)),
+        });
+        let mut block_event = get_block_event(BlockStatus::Committed);
+        assert!(block_filter.matches(&block_event));
+        block_event.block_number = BlockNumber(11);
+        assert!(!block_filter.matches(&block_event));
     }
 }
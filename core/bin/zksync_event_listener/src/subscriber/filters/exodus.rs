@@ -0,0 +1,32 @@
+// Built-in uses
+// External uses
+use serde::Deserialize;
+// Workspace uses
+use zksync_types::event::{EventData, ZkSyncEvent};
+// Local uses
+use super::EventMatcher;
+
+/// The exodus mode is entered only once, so there is nothing to filter by
+/// except for the event type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExodusFilter {}
+
+impl EventMatcher for ExodusFilter {
+    fn matches(&self, event: &ZkSyncEvent) -> bool {
+        matches!(event.data, EventData::Exodus(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::{block::BlockStatus, test_data::*};
+
+    #[test]
+    fn test_exodus_filter() {
+        let exodus_filter = ExodusFilter {};
+        assert!(exodus_filter.matches(&get_exodus_event()));
+        assert!(!exodus_filter.matches(&get_block_event(BlockStatus::Committed)));
+    }
+}
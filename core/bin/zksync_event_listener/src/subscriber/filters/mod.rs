@@ -8,20 +8,66 @@ use zksync_types::event::ZkSyncEvent;
 use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 // Local uses
-use self::{account::AccountFilter, block::BlockFilter, transaction::TransactionFilter};
+use self::{
+    account::AccountFilter, block::BlockFilter, exodus::ExodusFilter,
+    transaction::TransactionFilter,
+};
 
 mod account;
 mod block;
+mod exodus;
+mod range;
 mod transaction;
 
 #[cfg(test)]
 mod tests;
 
+/// Common interface of the filters for the particular event type.
+pub trait EventMatcher {
+    fn matches(&self, event: &ZkSyncEvent) -> bool;
+}
+
+/// Boolean composition of the filters for the same event type.
+/// Expressions can be nested, e.g.
+///
+/// ```json
+/// {
+///     "any": [
+///         { "types": ["Deposit"] },
+///         { "all": [{ "tokens": [0] }, { "amount": { "min": "1000" } }] }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum FilterExpr<F> {
+    /// Matches if at least one of the inner expressions matches.
+    Any {
+        any: Vec<FilterExpr<F>>,
+    },
+    /// Matches if all of the inner expressions match.
+    All {
+        all: Vec<FilterExpr<F>>,
+    },
+    Filter(F),
+}
+
+impl<F: EventMatcher> EventMatcher for FilterExpr<F> {
+    fn matches(&self, event: &ZkSyncEvent) -> bool {
+        match self {
+            FilterExpr::Any { any } => any.iter().any(|expr| expr.matches(event)),
+            FilterExpr::All { all } => all.iter().all(|expr| expr.matches(event)),
+            FilterExpr::Filter(filter) => filter.matches(event),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EventFilter {
-    Account(AccountFilter),
-    Block(BlockFilter),
-    Transaction(TransactionFilter),
+    Account(FilterExpr<AccountFilter>),
+    Block(FilterExpr<BlockFilter>),
+    Transaction(FilterExpr<TransactionFilter>),
+    Exodus(ExodusFilter),
 }
 
 impl EventFilter {
@@ -30,6 +76,7 @@ impl EventFilter {
             EventFilter::Account(account_filter) => account_filter.matches(event),
             EventFilter::Block(block_filter) => block_filter.matches(event),
             EventFilter::Transaction(tx_filter) => tx_filter.matches(event),
+            EventFilter::Exodus(exodus_filter) => exodus_filter.matches(event),
         }
     }
 }
@@ -64,11 +111,10 @@ impl<'de> Visitor<'de> for EventFiltersVisitor {
 
         while let Some(key) = access.next_key()? {
             let value = match key {
-                EventType::Account => EventFilter::Account(access.next_value::<AccountFilter>()?),
-                EventType::Block => EventFilter::Block(access.next_value::<BlockFilter>()?),
-                EventType::Transaction => {
-                    EventFilter::Transaction(access.next_value::<TransactionFilter>()?)
-                }
+                EventType::Account => EventFilter::Account(access.next_value()?),
+                EventType::Block => EventFilter::Block(access.next_value()?),
+                EventType::Transaction => EventFilter::Transaction(access.next_value()?),
+                EventType::Exodus => EventFilter::Exodus(access.next_value::<ExodusFilter>()?),
             };
 
             map.insert(key, value);
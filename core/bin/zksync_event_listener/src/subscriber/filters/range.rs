@@ -0,0 +1,80 @@
+// Built-in uses
+// External uses
+use num::BigUint;
+use serde::Deserialize;
+// Workspace uses
+use zksync_types::BlockNumber;
+use zksync_utils::BigUintSerdeWrapper;
+// Local uses
+
+/// Inclusive window of block numbers. Both bounds are optional.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockRange {
+    pub from: Option<BlockNumber>,
+    pub to: Option<BlockNumber>,
+}
+
+impl BlockRange {
+    pub fn contains(&self, block_number: BlockNumber) -> bool {
+        self.from.map_or(true, |from| block_number >= from)
+            && self.to.map_or(true, |to| block_number <= to)
+    }
+}
+
+/// Inclusive range of token amounts. Both bounds are optional
+/// and can be specified either as a number or as a decimal string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AmountRange {
+    pub min: Option<BigUintSerdeWrapper>,
+    pub max: Option<BigUintSerdeWrapper>,
+}
+
+impl AmountRange {
+    pub fn contains(&self, amount: &BigUint) -> bool {
+        self.min.as_ref().map_or(true, |min| *amount >= min.0)
+            && self.max.as_ref().map_or(true, |max| *amount <= max.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_range() {
+        let range = BlockRange {
+            from: Some(BlockNumber(10)),
+            to: None,
+        };
+        assert!(!range.contains(BlockNumber(9)));
+        assert!(range.contains(BlockNumber(10)));
+        assert!(range.contains(BlockNumber(u32::MAX)));
+
+        let range = BlockRange {
+            from: Some(BlockNumber(10)),
+            to: Some(BlockNumber(20)),
+        };
+        assert!(range.contains(BlockNumber(20)));
+        assert!(!range.contains(BlockNumber(21)));
+    }
+
+    #[test]
+    fn test_amount_range() {
+        let range = AmountRange {
+            min: None,
+            max: Some(BigUint::from(100u32).into()),
+        };
+        assert!(range.contains(&BigUint::from(0u32)));
+        assert!(range.contains(&BigUint::from(100u32)));
+        assert!(!range.contains(&BigUint::from(101u32)));
+
+        let range = AmountRange {
+            min: Some(BigUint::from(50u32).into()),
+            max: Some(BigUint::from(100u32).into()),
+        };
+        assert!(!range.contains(&BigUint::from(49u32)));
+        assert!(range.contains(&BigUint::from(50u32)));
+    }
+}
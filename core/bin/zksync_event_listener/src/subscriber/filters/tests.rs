@@ -62,6 +62,46 @@ fn test_filters_deserialize() {
                 "accounts": [1, 2, 3]
             }
         }"#,
+        // Exodus events can't be filtered by properties.
+        r#"{
+            "exodus": {
+                "status": "committed"
+            }
+        }"#,
+        r#"{
+            "exodus": {
+                "any": [{}]
+            }
+        }"#,
+        // Composition can't be mixed with the filter fields.
+        r#"{
+            "transaction": {
+                "any": [{ "status": "committed" }],
+                "tokens": [0]
+            }
+        }"#,
+        r#"{
+            "account": {
+                "any": [{ "accounts": [1] }],
+                "all": [{ "tokens": [0] }]
+            }
+        }"#,
+        r#"{
+            "block": {
+                "any": { "status": "committed" }
+            }
+        }"#,
+        // Invalid ranges.
+        r#"{
+            "block": {
+                "block_range": { "from": 1, "until": 10 }
+            }
+        }"#,
+        r#"{
+            "transaction": {
+                "amount": { "min": "-1" }
+            }
+        }"#,
     ];
     for (i, input) in INVALID.iter().enumerate() {
         let result = serde_json::from_str::<SubscriberFilters>(input);
@@ -103,6 +143,32 @@ fn test_filters_deserialize() {
             },
             "transaction": {}
         }"#,
+        r#"{
+            "exodus": {}
+        }"#,
+        r#"{
+            "transaction": {
+                "types": ["Transfer", "Withdraw"],
+                "amount": { "min": "1000000", "max": 2000000 },
+                "block_range": { "from": 100 },
+                "nft": false
+            },
+            "account": {
+                "nft": true,
+                "block_range": { "from": 100, "to": 200 }
+            }
+        }"#,
+        r#"{
+            "transaction": {
+                "any": [
+                    { "types": ["Deposit"] },
+                    { "all": [{ "tokens": [0] }, { "amount": { "min": "1000" } }] }
+                ]
+            },
+            "block": {
+                "all": []
+            }
+        }"#,
     ];
     for (i, input) in VALID.iter().enumerate() {
         let result = serde_json::from_str::<SubscriberFilters>(input);
@@ -201,3 +267,43 @@ fn test_subscriber_filters() {
     assert!(filters.matches(&block_event));
     assert!(filters.matches(&tx_event));
 }
+
+/// Checks the composition of filters with `any` and `all` expressions.
+#[test]
+fn test_filters_composition() {
+    let deposit = get_transaction_event(
+        TransactionType::Deposit,
+        AccountId(1),
+        TokenId(1),
+        TransactionStatus::Committed,
+    );
+    let small_transfer =
+        get_transaction_event_with_amount(TransactionType::Transfer, TokenId(0), 10);
+    let large_transfer =
+        get_transaction_event_with_amount(TransactionType::Transfer, TokenId(0), 5000);
+    let large_withdraw =
+        get_transaction_event_with_amount(TransactionType::Withdraw, TokenId(1), 5000);
+
+    let input = r#"{
+        "transaction": {
+            "any": [
+                { "types": ["Deposit"] },
+                { "all": [{ "tokens": [0] }, { "amount": { "min": "1000" } }] }
+            ]
+        }
+    }"#;
+    let filters = deserialize_valid(input);
+    assert!(filters.matches(&deposit));
+    assert!(filters.matches(&large_transfer));
+    assert!(!filters.matches(&small_transfer));
+    assert!(!filters.matches(&large_withdraw));
+
+    // Empty `any` matches nothing, empty `all` matches everything.
+    let input = r#"{
+        "transaction": { "any": [] },
+        "block": { "all": [] }
+    }"#;
+    let filters = deserialize_valid(input);
+    assert!(!filters.matches(&deposit));
+    assert!(filters.matches(&get_block_event(BlockStatus::Finalized)));
+}
@@ -8,6 +8,10 @@ use zksync_types::{
     AccountId, TokenId,
 };
 // Local uses
+use super::{
+    range::{AmountRange, BlockRange},
+    EventMatcher,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -16,10 +20,16 @@ pub struct TransactionFilter {
     pub accounts: Option<HashSet<AccountId>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<TransactionStatus>,
+    /// Only transfers and withdrawals with the amount within the range
+    /// are accepted if set.
+    pub amount: Option<AmountRange>,
+    pub block_range: Option<BlockRange>,
+    /// If set, only accept (or, if `false`, deny) the NFT transactions.
+    pub nft: Option<bool>,
 }
 
-impl TransactionFilter {
-    pub fn matches(&self, event: &ZkSyncEvent) -> bool {
+impl EventMatcher for TransactionFilter {
+    fn matches(&self, event: &ZkSyncEvent) -> bool {
         let tx_event = match &event.data {
             EventData::Transaction(tx_event) => tx_event,
             _ => return false,
@@ -47,6 +57,22 @@ impl TransactionFilter {
                 return false;
             }
         }
+        if let Some(block_range) = &self.block_range {
+            if !block_range.contains(event.block_number) {
+                return false;
+            }
+        }
+        if let Some(nft) = self.nft {
+            if tx_event.is_nft() != nft {
+                return false;
+            }
+        }
+        if let Some(amount_range) = &self.amount {
+            match tx_event.amount() {
+                Some(amount) if amount_range.contains(&amount) => {}
+                _ => return false,
+            }
+        }
         true
     }
 }
@@ -54,7 +80,9 @@ impl TransactionFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zksync_types::event::test_data::get_transaction_event;
+    use num::BigUint;
+    use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+    use zksync_types::event::test_data::*;
 
     #[test]
     fn test_transaction_filter() {
@@ -64,6 +92,9 @@ mod tests {
             accounts: None,
             tokens: None,
             status: None,
+            amount: None,
+            block_range: None,
+            nft: None,
         };
 
         let event = get_transaction_event(
@@ -142,4 +173,57 @@ mod tests {
             assert!(tx_filter.matches(&event));
         }
     }
+
+    #[test]
+    fn test_transaction_filter_amount_and_nft() {
+        let mut tx_filter = TransactionFilter {
+            types: None,
+            accounts: None,
+            tokens: None,
+            status: None,
+            amount: Some(AmountRange {
+                min: Some(BigUint::from(100u32).into()),
+                max: Some(BigUint::from(200u32).into()),
+            }),
+            block_range: None,
+            nft: None,
+        };
+        for &tx_type in &[TransactionType::Transfer, TransactionType::Withdraw] {
+            let event = get_transaction_event_with_amount(tx_type, TokenId(0), 150);
+            assert!(tx_filter.matches(&event));
+            let event = get_transaction_event_with_amount(tx_type, TokenId(0), 250);
+            assert!(!tx_filter.matches(&event));
+        }
+        // Transactions without amount are filtered out.
+        let event = get_transaction_event(
+            TransactionType::ChangePubKey,
+            AccountId(0),
+            TokenId(0),
+            TransactionStatus::Committed,
+        );
+        assert!(!tx_filter.matches(&event));
+
+        // Only accept NFT transactions.
+        tx_filter.amount = None;
+        tx_filter.nft = Some(true);
+        assert!(!tx_filter.matches(&event));
+        let nft_transfer = get_transaction_event_with_amount(
+            TransactionType::Transfer,
+            TokenId(MIN_NFT_TOKEN_ID),
+            1,
+        );
+        assert!(tx_filter.matches(&nft_transfer));
+        let mint_nft = get_transaction_event(
+            TransactionType::MintNFT,
+            AccountId(0),
+            TokenId(0),
+            TransactionStatus::Committed,
+        );
+        assert!(tx_filter.matches(&mint_nft));
+        // The opposite.
+        tx_filter.nft = Some(false);
+        assert!(tx_filter.matches(&event));
+        assert!(!tx_filter.matches(&nft_transfer));
+        assert!(!tx_filter.matches(&mint_nft));
+    }
 }
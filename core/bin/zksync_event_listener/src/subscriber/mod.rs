@@ -3,12 +3,16 @@
 use actix::prelude::*;
 use actix_web_actors::ws;
 // Workspace uses
+use zksync_types::event::{EventId, ZkSyncEvent};
 // Local uses
 use crate::messages::{NewEvents, RegisterSubscriber, RemoveSubscriber, Shutdown};
 use crate::monitor::ServerMonitor;
+use crate::sink::ExportedEvent;
 use filters::SubscriberFilters;
+pub use replay::{EventReplay, ReplayQuery};
 
 mod filters;
+mod replay;
 
 /// The WebSocket actor. Created for each connected client.
 #[derive(Debug)]
@@ -19,16 +23,89 @@ pub struct Subscriber {
     filters: Option<SubscriberFilters>,
     /// The address of the [`ServerMonitor`] for registering.
     monitor: Addr<ServerMonitor>,
+    /// Replay of the past events requested by the client. While it's
+    /// in progress, new events are ignored since they will be loaded
+    /// from the database with the next pages anyway.
+    replay: Option<EventReplay>,
+    /// Id of the last processed event. Used to not send the same event twice
+    /// when switching from the replay to the new events.
+    last_event_id: Option<EventId>,
 }
 
 impl Subscriber {
-    pub fn new(monitor: Addr<ServerMonitor>) -> Self {
+    pub fn new(monitor: Addr<ServerMonitor>, replay: Option<EventReplay>) -> Self {
+        let last_event_id = replay.as_ref().map(|replay| replay.cursor);
         Self {
             filters: None,
             monitor,
+            replay,
+            last_event_id,
         }
     }
 
+    /// Sends the events matching the subscriber's filters to the client,
+    /// skipping the ones that were already processed.
+    fn send_events(&mut self, events: &[ZkSyncEvent], ctx: &mut <Self as Actor>::Context) {
+        for json in self.serialize_events(events) {
+            ctx.text(json);
+        }
+    }
+
+    /// Serializes the events matching the subscriber's filters along with their ids,
+    /// so that the client is able to resume the stream after reconnecting.
+    /// Events with ids not greater than the last processed one are skipped.
+    fn serialize_events(&mut self, events: &[ZkSyncEvent]) -> Vec<String> {
+        let filters = match &self.filters {
+            Some(filters) => filters,
+            None => return Vec::new(),
+        };
+        let mut messages = Vec::new();
+        for event in events {
+            if matches!(self.last_event_id, Some(id) if event.id <= id) {
+                continue;
+            }
+            self.last_event_id = Some(event.id);
+            if !filters.matches(event) {
+                continue;
+            }
+            let json = serde_json::to_string(&ExportedEvent::from(event)).unwrap();
+            messages.push(json);
+        }
+        messages
+    }
+
+    /// Loads the next page of the replayed events and sends it to the client.
+    /// The last page, which is not full, finishes the replay.
+    fn replay_next_page(&mut self, ctx: &mut <Self as Actor>::Context) {
+        let (db_pool, cursor, page_size) = match (&self.replay, self.last_event_id) {
+            (Some(replay), Some(cursor)) => (replay.db_pool(), cursor, replay.page_size),
+            _ => return,
+        };
+        EventReplay::fetch_page(db_pool, cursor, page_size)
+            .into_actor(self)
+            .map(move |response, act, ctx| match response {
+                Ok(events) => {
+                    act.send_events(&events, ctx);
+                    if events.len() < page_size as usize {
+                        // Caught up with the database, switch to the new events.
+                        act.replay = None;
+                    } else {
+                        act.replay_next_page(ctx);
+                    }
+                }
+                Err(err) => {
+                    vlog::error!("Couldn't replay events, reason: {:?}", err);
+                    let reason = Some(ws::CloseReason {
+                        code: ws::CloseCode::Error,
+                        description: Some("internal server error".to_string()),
+                    });
+                    ctx.close(reason);
+                    act.shutdown(ctx);
+                }
+            })
+            .wait(ctx);
+    }
+
     /// Remove the subscriber's address from the monitor's set and stop
     /// the execution context completely. Should be called instead of
     /// `ctx.stop()`.
@@ -92,6 +169,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Subscriber {
                 match serde_json::from_str(&text) {
                     Ok(filters) => {
                         self.filters = Some(filters);
+                        self.replay_next_page(ctx);
                     }
                     Err(err) => {
                         // The client provided invalid JSON, give
@@ -132,17 +210,10 @@ impl Handler<NewEvents> for Subscriber {
     type Result = ();
 
     fn handle(&mut self, msg: NewEvents, ctx: &mut Self::Context) {
-        let filters = match &self.filters {
-            Some(filters) => filters,
-            None => return,
-        };
-        for event in msg.0.as_ref() {
-            if !filters.matches(event) {
-                continue;
-            }
-            let json = serde_json::to_string(&event).unwrap();
-            ctx.text(json);
+        if self.replay.is_some() {
+            return;
         }
+        self.send_events(msg.0.as_ref(), ctx);
     }
 }
 
@@ -159,3 +230,61 @@ impl Handler<Shutdown> for Subscriber {
         ctx.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::event::{block::BlockStatus, test_data::get_block_event};
+
+    fn get_events(ids: std::ops::RangeInclusive<u64>, status: BlockStatus) -> Vec<ZkSyncEvent> {
+        ids.map(|id| {
+            let mut event = get_block_event(status);
+            event.id = EventId(id);
+            event
+        })
+        .collect()
+    }
+
+    fn sent_ids(messages: Vec<String>) -> Vec<u64> {
+        messages
+            .iter()
+            .map(|json| {
+                let value: serde_json::Value = serde_json::from_str(json).unwrap();
+                value["id"].as_u64().unwrap()
+            })
+            .collect()
+    }
+
+    /// Checks that the events are sent with their ids, and the events overlapping
+    /// between the replayed pages and the new events are sent only once.
+    #[test]
+    fn test_replay_deduplication() {
+        actix_web::rt::System::new().block_on(async {
+            let mut subscriber = Subscriber {
+                filters: None,
+                monitor: ServerMonitor::new().start(),
+                replay: None,
+                // The client has already received the events up to the third one.
+                last_event_id: Some(EventId(3)),
+            };
+            // Events are ignored until the client sends the filters.
+            let events = get_events(1..=5, BlockStatus::Committed);
+            assert!(subscriber.serialize_events(&events).is_empty());
+            assert_eq!(subscriber.last_event_id, Some(EventId(3)));
+
+            subscriber.filters =
+                Some(serde_json::from_str(r#"{ "block": { "status": "committed" } }"#).unwrap());
+            // The replayed page.
+            assert_eq!(sent_ids(subscriber.serialize_events(&events)), vec![4, 5]);
+            // New events emitted while the replay was in progress are not sent again.
+            let events = get_events(4..=7, BlockStatus::Committed);
+            assert_eq!(sent_ids(subscriber.serialize_events(&events)), vec![6, 7]);
+            // Events which don't match the filters are skipped, but still move the cursor.
+            let events = get_events(8..=9, BlockStatus::Finalized);
+            assert!(subscriber.serialize_events(&events).is_empty());
+            assert_eq!(subscriber.last_event_id, Some(EventId(9)));
+            let events = get_events(9..=10, BlockStatus::Committed);
+            assert_eq!(sent_ids(subscriber.serialize_events(&events)), vec![10]);
+        });
+    }
+}
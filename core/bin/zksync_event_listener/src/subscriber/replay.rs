@@ -0,0 +1,121 @@
+// Built-in uses
+use std::convert::TryFrom;
+// External uses
+use actix_web::error::{ErrorBadRequest, ErrorInternalServerError};
+use serde::Deserialize;
+// Workspace uses
+use zksync_config::configs::EventListenerConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    event::{EventId, ZkSyncEvent},
+    BlockNumber,
+};
+// Local uses
+
+/// Point of the events stream the client wants to resume from.
+/// Passed as the query parameters of the WebSocket handshake request.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayQuery {
+    /// Id of the last event received by the client, events with greater ids are replayed.
+    pub last_event_id: Option<u64>,
+    /// Number of the block, the events emitted for this and later blocks are replayed.
+    pub from_block: Option<u32>,
+}
+
+/// Replays the past events to the client before it switches to the new ones,
+/// so that the client doesn't miss the events emitted while it was disconnected.
+///
+/// Events are loaded by pages on the subscriber's context. The context is only polled
+/// while the connection is able to accept more data, so the slow client doesn't make
+/// the server buffer the whole replay in memory.
+#[derive(Debug)]
+pub struct EventReplay {
+    db_pool: ConnectionPool,
+    /// Id of the last event which was already sent to the client.
+    pub cursor: EventId,
+    pub page_size: u32,
+}
+
+impl EventReplay {
+    /// Resolves the point to resume the stream from. Returns `None` if the client
+    /// didn't request the replay, or there are no events to replay.
+    pub async fn new(
+        db_pool: ConnectionPool,
+        query: ReplayQuery,
+        config: &EventListenerConfig,
+    ) -> actix_web::Result<Option<Self>> {
+        // Don't occupy the connection of the small replay pool if there's nothing to replay.
+        match (query.last_event_id, query.from_block) {
+            (Some(_), Some(_)) => {
+                return Err(ErrorBadRequest(
+                    "Only one of `last_event_id` and `from_block` can be specified",
+                ))
+            }
+            (None, None) => return Ok(None),
+            _ => {}
+        }
+
+        let mut storage = db_pool
+            .access_storage()
+            .await
+            .map_err(ErrorInternalServerError)?;
+        let cursor = match (query.last_event_id, query.from_block) {
+            (Some(last_event_id), _) => EventId(last_event_id),
+            (None, Some(from_block)) => {
+                let first_event_id = storage
+                    .event_schema()
+                    .get_first_event_id_from_block(BlockNumber(from_block))
+                    .await
+                    .map_err(ErrorInternalServerError)?;
+                match first_event_id {
+                    Some(id) => EventId(id.0.saturating_sub(1)),
+                    // No events were emitted for this block yet.
+                    None => return Ok(None),
+                }
+            }
+            (None, None) => unreachable!("the absence of the replay is checked above"),
+        };
+
+        let last_event_id = storage
+            .event_schema()
+            .get_last_event_id()
+            .await
+            .map_err(ErrorInternalServerError)?
+            .unwrap_or(EventId(0));
+        if last_event_id.0.saturating_sub(cursor.0) > config.max_replay_window {
+            return Err(ErrorBadRequest(format!(
+                "Can't replay more than {} events, the last event id is {}",
+                config.max_replay_window, *last_event_id
+            )));
+        }
+        drop(storage);
+
+        Ok(Some(Self {
+            db_pool,
+            cursor,
+            page_size: config.replay_page_size,
+        }))
+    }
+
+    /// Loads the page of events following the cursor.
+    pub async fn fetch_page(
+        db_pool: ConnectionPool,
+        cursor: EventId,
+        page_size: u32,
+    ) -> anyhow::Result<Vec<ZkSyncEvent>> {
+        Ok(db_pool
+            .access_storage()
+            .await?
+            .event_schema()
+            .fetch_new_events_page(cursor, page_size)
+            .await?
+            .into_iter()
+            .map(ZkSyncEvent::try_from)
+            .collect::<Result<_, _>>()?)
+    }
+
+    pub fn db_pool(&self) -> ConnectionPool {
+        self.db_pool.clone()
+    }
+}
@@ -3,16 +3,9 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 // External
-use actix_web::dev::ServiceRequest;
 use actix_web::{web, App, HttpResponse, HttpServer};
-use actix_web_httpauth::extractors::{
-    bearer::{BearerAuth, Config},
-    AuthenticationError,
-};
 use actix_web_httpauth::middleware::HttpAuthentication;
 
-use jsonwebtoken::errors::Error as JwtError;
-use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 // Workspace deps
@@ -34,7 +27,10 @@ use zksync_types::prover::{
     ProverJobType, AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
 };
 use zksync_types::BlockNumber;
-use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
+use zksync_utils::{
+    http_auth::AuthTokenValidator,
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+};
 
 #[cfg(test)]
 mod tests;
@@ -44,14 +40,6 @@ mod database_interface;
 mod scaler;
 mod witness_generator;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PayloadAuthToken {
-    /// Subject (whom auth token refers to).
-    sub: String,
-    /// Expiration time (as UTC timestamp).
-    exp: usize,
-}
-
 #[derive(Debug, Clone)]
 struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
@@ -81,39 +69,6 @@ impl<DB: DatabaseInterface> AppState<DB> {
     }
 }
 
-/// The structure that stores the secret key for checking JsonWebToken matching.
-struct AuthTokenValidator<'a> {
-    decoding_key: DecodingKey<'a>,
-}
-
-impl<'a> AuthTokenValidator<'a> {
-    fn new(secret: &'a str) -> Self {
-        Self {
-            decoding_key: DecodingKey::from_secret(secret.as_ref()),
-        }
-    }
-
-    /// Checks whether the secret key and the authorization token match.
-    fn validate_auth_token(&self, token: &str) -> Result<(), JwtError> {
-        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
-
-        Ok(())
-    }
-
-    async fn validator(
-        &self,
-        req: ServiceRequest,
-        credentials: BearerAuth,
-    ) -> actix_web::Result<ServiceRequest> {
-        let config = req.app_data::<Config>().cloned().unwrap_or_default();
-
-        self.validate_auth_token(credentials.token())
-            .map_err(|_| AuthenticationError::from(config))?;
-
-        Ok(req)
-    }
-}
-
 async fn status() -> actix_web::Result<String> {
     Ok("alive".into())
 }
@@ -75,6 +75,18 @@ impl Client {
             url,
         }
     }
+
+    pub(crate) fn delete_with_scope(
+        &self,
+        scope: impl AsRef<str>,
+        method: impl AsRef<str>,
+    ) -> ClientRequestBuilder {
+        let url = self.endpoint(scope.as_ref(), method.as_ref());
+        ClientRequestBuilder {
+            inner: self.inner.delete(&url),
+            url,
+        }
+    }
 }
 
 /// API specific wrapper over the `reqwest::RequestBuilder`.
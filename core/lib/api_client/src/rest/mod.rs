@@ -2,3 +2,4 @@ pub mod client;
 pub mod error;
 pub mod forced_exit_requests;
 pub mod v02;
+pub mod webhooks;
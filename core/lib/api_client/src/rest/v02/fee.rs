@@ -18,6 +18,26 @@ impl Client {
                 tx_type,
                 address,
                 token_like,
+                quote_sender: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Requests the fee along with the quote guaranteeing it for the transaction sender.
+    pub async fn get_txs_fee_quote(
+        &self,
+        tx_type: ApiTxFeeTypes,
+        address: Address,
+        token_like: TokenLike,
+        sender: Address,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "fee")
+            .body(&TxFeeRequest {
+                tx_type,
+                address,
+                token_like,
+                quote_sender: Some(sender),
             })
             .send()
             .await
@@ -32,6 +52,24 @@ impl Client {
             .body(&BatchFeeRequest {
                 transactions,
                 token_like,
+                quote_sender: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Requests the batch fee along with the quote guaranteeing it for the account paying the fee.
+    pub async fn get_batch_fee_quote(
+        &self,
+        transactions: Vec<TxInBatchFeeRequest>,
+        token_like: TokenLike,
+        sender: Address,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "fee/batch")
+            .body(&BatchFeeRequest {
+                transactions,
+                token_like,
+                quote_sender: Some(sender),
             })
             .send()
             .await
@@ -1,18 +1,36 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        fee::ApiFeeQuote,
+        transaction::{CancelTx, IncomingTx, IncomingTxBatch, SimulateTxsRequest},
+        Response,
+    },
     TxWithSignature,
 };
-use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
+use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, TxSignature, ZkSyncTx};
 
 impl Client {
     pub async fn submit_tx(
         &self,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.submit_tx_with_fee_quote(tx, signature, None).await
+    }
+
+    /// Submits the transaction paying the fee guaranteed by the quote.
+    pub async fn submit_tx_with_fee_quote(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        fee_quote: Option<ApiFeeQuote>,
     ) -> Result<Response> {
         self.post_with_scope(super::API_V02_SCOPE, "transactions")
-            .body(&TxWithSignature { tx, signature })
+            .body(&IncomingTx {
+                tx,
+                signature,
+                fee_quote,
+            })
             .send()
             .await
     }
@@ -21,9 +39,40 @@ impl Client {
         &self,
         txs: Vec<TxWithSignature>,
         signature: Option<EthBatchSignatures>,
+    ) -> Result<Response> {
+        self.submit_batch_with_fee_quote(txs, signature, None).await
+    }
+
+    /// Submits the batch paying the fee guaranteed by the quote.
+    pub async fn submit_batch_with_fee_quote(
+        &self,
+        txs: Vec<TxWithSignature>,
+        signature: Option<EthBatchSignatures>,
+        fee_quote: Option<ApiFeeQuote>,
     ) -> Result<Response> {
         self.post_with_scope(super::API_V02_SCOPE, "transactions/batches")
-            .body(&IncomingTxBatch { txs, signature })
+            .body(&IncomingTxBatch {
+                txs,
+                signature,
+                fee_quote,
+            })
+            .send()
+            .await
+    }
+
+    pub async fn cancel_tx(&self, tx_hash: TxHash, signature: TxSignature) -> Result<Response> {
+        self.delete_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}", tx_hash.to_string()),
+        )
+        .body(&CancelTx { signature })
+        .send()
+        .await
+    }
+
+    pub async fn simulate_txs(&self, txs: Vec<ZkSyncTx>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/simulate")
+            .body(&SimulateTxsRequest { txs })
             .send()
             .await
     }
@@ -0,0 +1,148 @@
+//! Webhooks part of API implementation.
+
+// Built-in uses
+
+// External uses
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{tx::PackedEthSignature, webhooks::WebhookId, Address};
+
+// Local uses
+use crate::rest::client::{Client, Result as ClientResult};
+
+/// Request to register a new webhook for the address.
+/// Must be signed by the Ethereum key of the address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterWebhookRequest {
+    pub address: Address,
+    pub url: String,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: PackedEthSignature,
+}
+
+impl RegisterWebhookRequest {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "Register zkSync webhook\n\
+            Address: {:?}\n\
+            Url: {}\n\
+            Timestamp: {}",
+            self.address,
+            self.url,
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Request to remove the webhook registered for the address.
+/// Must be signed by the Ethereum key of the address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveWebhookRequest {
+    pub id: WebhookId,
+    pub address: Address,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: PackedEthSignature,
+}
+
+impl RemoveWebhookRequest {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "Remove zkSync webhook\n\
+            Address: {:?}\n\
+            Id: {}\n\
+            Timestamp: {}",
+            self.address,
+            self.id,
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Request to list the webhooks registered for the address.
+/// Must be signed by the Ethereum key of the address, since the urls are private.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksRequest {
+    pub address: Address,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: PackedEthSignature,
+}
+
+impl ListWebhooksRequest {
+    pub fn get_ethereum_sign_message(&self) -> String {
+        format!(
+            "List zkSync webhooks\n\
+            Address: {:?}\n\
+            Timestamp: {}",
+            self.address,
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+/// Newly registered webhook. This is the only response which reveals the secret
+/// used to sign the notifications.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredWebhook {
+    pub id: WebhookId,
+    pub address: Address,
+    pub url: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInfo {
+    pub id: WebhookId,
+    pub address: Address,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoveWebhookResponse {
+    pub removed: bool,
+}
+
+const WEBHOOKS_SCOPE: &str = "/api/webhooks/v0.1/";
+
+impl Client {
+    pub async fn register_webhook(
+        &self,
+        request: &RegisterWebhookRequest,
+    ) -> ClientResult<RegisteredWebhook> {
+        self.post_with_scope(WEBHOOKS_SCOPE, "register")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn remove_webhook(
+        &self,
+        request: &RemoveWebhookRequest,
+    ) -> ClientResult<RemoveWebhookResponse> {
+        self.post_with_scope(WEBHOOKS_SCOPE, "remove")
+            .body(request)
+            .send()
+            .await
+    }
+
+    pub async fn list_webhooks(
+        &self,
+        request: &ListWebhooksRequest,
+    ) -> ClientResult<Vec<WebhookInfo>> {
+        self.post_with_scope(WEBHOOKS_SCOPE, "list")
+            .body(request)
+            .send()
+            .await
+    }
+}
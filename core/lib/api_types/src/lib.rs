@@ -34,4 +34,7 @@ pub struct CoreStatus {
     pub main_database_available: bool,
     pub replica_database_available: bool,
     pub web3_available: bool,
+    /// Whether the zkSync contract has entered the exodus mode, so no transactions are processed anymore.
+    #[serde(default)]
+    pub exodus_mode: bool,
 }
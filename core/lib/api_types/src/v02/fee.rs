@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
-use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
+use zksync_types::{
+    fee::FeeQuote, tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -10,8 +13,14 @@ pub struct ApiFee {
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub zkp_fee: BigUint,
+    /// Part of the total fee charged due to the network congestion.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub surge_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Quote guaranteeing the `total_fee`, only returned if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<ApiFeeQuote>,
 }
 
 impl From<Fee> for ApiFee {
@@ -19,7 +28,9 @@ impl From<Fee> for ApiFee {
         ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
+            surge_fee: fee.surge_fee,
             total_fee: fee.total_fee,
+            quote: None,
         }
     }
 }
@@ -29,11 +40,48 @@ impl From<BatchFee> for ApiFee {
         ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
+            surge_fee: fee.surge_fee,
             total_fee: fee.total_fee,
+            quote: None,
         }
     }
 }
 
+/// Server-signed promise to accept the quoted fee for the transactions paid by the sender until
+/// the quote expires. The quote should be submitted back along with the transactions,
+/// and can be used only once.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFeeQuote {
+    pub id: i64,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    pub expires_at: DateTime<Utc>,
+    /// Hex-encoded HMAC-SHA256 of the quote, keyed with the server secret.
+    pub signature: String,
+}
+
+impl ApiFeeQuote {
+    pub fn new(quote: FeeQuote, signature: String) -> Self {
+        Self {
+            id: quote.id,
+            total_fee: quote.total_fee,
+            expires_at: quote.expires_at,
+            signature,
+        }
+    }
+}
+
+/// Response of the `get_tx_fee_quote` JSON RPC method.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeWithQuote {
+    #[serde(flatten)]
+    pub fee: Fee,
+    /// `None` if the quotes are disabled on the server.
+    pub quote: Option<ApiFeeQuote>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ApiTxFeeTypes {
     /// Fee for the `Withdraw` transaction.
@@ -77,6 +125,9 @@ pub struct TxFeeRequest {
     pub tx_type: ApiTxFeeTypes,
     pub address: Address,
     pub token_like: TokenLike,
+    /// Sender of the transaction, the quote guaranteeing the fee for them is returned if set.
+    #[serde(default)]
+    pub quote_sender: Option<Address>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -91,4 +142,7 @@ pub struct TxInBatchFeeRequest {
 pub struct BatchFeeRequest {
     pub transactions: Vec<TxInBatchFeeRequest>,
     pub token_like: TokenLike,
+    /// Account paying the fee for the batch, the quote guaranteeing the fee for them is returned if set.
+    #[serde(default)]
+    pub quote_sender: Option<Address>,
 }
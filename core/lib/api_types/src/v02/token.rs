@@ -12,7 +12,7 @@ pub struct ApiToken {
     pub enabled_for_fees: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenPrice {
     pub token_id: TokenId,
@@ -1,23 +1,41 @@
-use crate::{v02::block::BlockStatus, TxWithSignature};
+use crate::{
+    v02::{block::BlockStatus, fee::ApiFeeQuote},
+    TxWithSignature,
+};
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_types::{
+    block::ExecutedOperations,
     tx::{
         ChangePubKey, Close, EthBatchSignatures, ForcedExit, MintNFT, Swap, Transfer,
-        TxEthSignature, TxHash, Withdraw, WithdrawNFT,
+        TxEthSignature, TxEthSignatureVariant, TxHash, TxSignature, Withdraw, WithdrawNFT,
     },
-    AccountId, Address, BlockNumber, EthBlockId, PubKeyHash, SerialId, TokenId, ZkSyncOp,
-    ZkSyncPriorityOp, H256,
+    AccountId, AccountUpdates, Address, BlockNumber, EthBlockId, PubKeyHash, SerialId, TokenId,
+    ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingTx {
+    pub tx: ZkSyncTx,
+    #[serde(default)]
+    pub signature: TxEthSignatureVariant,
+    /// Quote guaranteeing the transaction fee, the fee is checked against it if set.
+    #[serde(default)]
+    pub fee_quote: Option<ApiFeeQuote>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingTxBatch {
     pub txs: Vec<TxWithSignature>,
     pub signature: Option<EthBatchSignatures>,
+    /// Quote guaranteeing the batch fee, the fee is checked against it if set.
+    #[serde(default)]
+    pub fee_quote: Option<ApiFeeQuote>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
@@ -27,6 +45,7 @@ pub enum TxInBlockStatus {
     Committed,
     Finalized,
     Rejected,
+    Cancelled,
 }
 
 impl From<BlockStatus> for TxInBlockStatus {
@@ -71,7 +90,7 @@ pub enum Receipt {
     L2(L2Receipt),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
@@ -85,7 +104,59 @@ pub struct Transaction {
     pub batch_id: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Transaction {
+    /// Creates the transaction from the operation executed in the block with the given status.
+    ///
+    /// Returns [`None`] if the priority operation doesn't match its executed operation.
+    pub fn from_executed_operation(
+        op: &ExecutedOperations,
+        block_number: BlockNumber,
+        block_status: BlockStatus,
+    ) -> Option<Self> {
+        let tx = match op {
+            ExecutedOperations::Tx(exec_tx) => Self {
+                tx_hash: exec_tx.signed_tx.hash(),
+                block_index: exec_tx.block_index,
+                block_number: Some(block_number),
+                op: TransactionData::L2(L2Transaction::from_zksync_tx(
+                    exec_tx.signed_tx.tx.clone(),
+                    None,
+                )),
+                status: if exec_tx.success {
+                    block_status.into()
+                } else {
+                    TxInBlockStatus::Rejected
+                },
+                fail_reason: exec_tx.fail_reason.clone(),
+                created_at: Some(exec_tx.created_at),
+                batch_id: exec_tx.batch_id.map(|id| id as u32),
+            },
+            ExecutedOperations::PriorityOp(exec_prior_op) => {
+                let priority_op = &exec_prior_op.priority_op;
+                let tx_hash = priority_op.tx_hash();
+                let op = L1Transaction::from_executed_op(
+                    exec_prior_op.op.clone(),
+                    priority_op.eth_hash,
+                    priority_op.serial_id,
+                    tx_hash,
+                )?;
+                Self {
+                    tx_hash,
+                    block_index: Some(exec_prior_op.block_index),
+                    block_number: Some(block_number),
+                    op: TransactionData::L1(op),
+                    status: block_status.into(),
+                    fail_reason: None,
+                    created_at: Some(exec_prior_op.created_at),
+                    batch_id: None,
+                }
+            }
+        };
+        Some(tx)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TransactionData {
     L1(L1Transaction),
@@ -106,6 +177,32 @@ pub enum L2Transaction {
     WithdrawNFT(Box<WithdrawNFTData>),
 }
 
+impl L2Transaction {
+    /// Hash of the Ethereum transaction completing the withdrawal is only known
+    /// for the withdrawals which were already executed on L1.
+    pub fn from_zksync_tx(tx: ZkSyncTx, complete_withdrawals_tx_hash: Option<H256>) -> Self {
+        match tx {
+            ZkSyncTx::ChangePubKey(tx) => Self::ChangePubKey(tx),
+            ZkSyncTx::Close(tx) => Self::Close(tx),
+            ZkSyncTx::ForcedExit(tx) => Self::ForcedExit(Box::new(ForcedExitData {
+                tx: *tx,
+                eth_tx_hash: complete_withdrawals_tx_hash,
+            })),
+            ZkSyncTx::Transfer(tx) => Self::Transfer(tx),
+            ZkSyncTx::Withdraw(tx) => Self::Withdraw(Box::new(WithdrawData {
+                tx: *tx,
+                eth_tx_hash: complete_withdrawals_tx_hash,
+            })),
+            ZkSyncTx::MintNFT(tx) => Self::MintNFT(tx),
+            ZkSyncTx::WithdrawNFT(tx) => Self::WithdrawNFT(Box::new(WithdrawNFTData {
+                tx: *tx,
+                eth_tx_hash: complete_withdrawals_tx_hash,
+            })),
+            ZkSyncTx::Swap(tx) => Self::Swap(tx),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ForcedExitData {
@@ -130,7 +227,7 @@ pub struct WithdrawNFTData {
     pub eth_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum L1Transaction {
     Deposit(ApiDeposit),
@@ -194,7 +291,7 @@ impl L1Transaction {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiDeposit {
     pub from: Address,
@@ -209,7 +306,7 @@ pub struct ApiDeposit {
     pub tx_hash: TxHash,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiFullExit {
     pub account_id: AccountId,
@@ -296,3 +393,67 @@ impl Toggle2FA {
 pub struct Toggle2FAResponse {
     pub success: bool,
 }
+
+/// Request to remove the transaction from the mempool.
+/// Must be signed by the zkSync key of the transaction account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTx {
+    pub signature: TxSignature,
+}
+
+impl CancelTx {
+    /// Returns the message to be signed in order to cancel the transaction.
+    pub fn get_zksync_sign_message(tx_hash: &TxHash) -> Vec<u8> {
+        format!("Cancel transaction: {}", tx_hash.to_string()).into_bytes()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTxResponse {
+    /// Hashes of the removed transactions. If the transaction was a part
+    /// of a batch, the whole batch is removed.
+    pub cancelled_txs: Vec<TxHash>,
+}
+
+/// Transactions to be executed without submission. More than one transaction
+/// is executed as a batch, i.e. either all of them succeed or all of them fail.
+/// Signatures are not required, transactions with the valid zkSync signature are checked
+/// to be signed by the account key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateTxsRequest {
+    pub txs: Vec<ZkSyncTx>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedFee {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+/// Outcome of the transaction dry-run execution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTx {
+    pub tx_hash: TxHash,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    /// Chunks the transaction takes in the block.
+    pub chunks: usize,
+    /// Fee required by the server for the transaction, if the transaction has to pay it.
+    pub fee: Option<SimulatedFee>,
+    /// Changes of the accounts state, if the transaction succeeds.
+    pub updates: AccountUpdates,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateTxsResponse {
+    /// Timestamp the transactions were executed with.
+    pub block_timestamp: u64,
+    pub txs: Vec<SimulatedTx>,
+}
@@ -34,7 +34,7 @@ pub struct ApiConfig {
 impl ApiConfig {
     pub fn from_env() -> Self {
         Self {
-            common: envy_load!("common", "API_COMMON_"),
+            common: CommonApiConfig::from_env(),
             admin: envy_load!("admin", "API_ADMIN_"),
             rest: envy_load!("rest", "API_REST_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
@@ -46,6 +46,9 @@ impl ApiConfig {
     }
 }
 
+/// Minimum length of the secret used to sign the fee quotes.
+const MIN_FEE_QUOTE_SECRET_LEN: usize = 32;
+
 impl CommonApiConfig {
     pub fn max_subsidy_usd(&self) -> Ratio<BigUint> {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
@@ -55,6 +58,28 @@ impl CommonApiConfig {
         Duration::from_secs(self.invalidate_token_cache_period_sec)
     }
 
+    /// Returns the lifetime of the fee quotes, `None` if the quotes are disabled.
+    pub fn fee_quote_lifetime(&self) -> Option<Duration> {
+        if self.fee_quote_lifetime_sec == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.fee_quote_lifetime_sec))
+        }
+    }
+
+    /// Checks that the fee quotes can't be forged if they're enabled.
+    pub fn validate_fee_quote_secret(&self) -> Result<(), String> {
+        if self.fee_quote_lifetime_sec != 0
+            && self.fee_quote_secret.len() < MIN_FEE_QUOTE_SECRET_LEN
+        {
+            return Err(format!(
+                "Fee quotes are enabled, but the fee quote secret is shorter than {} bytes",
+                MIN_FEE_QUOTE_SECRET_LEN
+            ));
+        }
+        Ok(())
+    }
+
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
@@ -127,6 +152,13 @@ pub struct CommonApiConfig {
 
     /// The interval of updating tokens from database
     pub invalidate_token_cache_period_sec: u64,
+
+    /// Time during which the quoted fee is accepted regardless of the price changes.
+    /// Quotes are disabled if set to zero.
+    pub fee_quote_lifetime_sec: u64,
+    /// Secret used to sign the fee quotes, must be set if the quotes are enabled.
+    #[serde(default)]
+    pub fee_quote_secret: String,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -253,6 +285,8 @@ mod tests {
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
                 invalidate_token_cache_period_sec: 10,
+                fee_quote_lifetime_sec: 60,
+                fee_quote_secret: "0123456789abcdef0123456789abcdef".into(),
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -301,6 +335,8 @@ API_COMMON_SUBSIDY_NAME=PartnerName
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
 API_COMMON_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
+API_COMMON_FEE_QUOTE_LIFETIME_SEC="60"
+API_COMMON_FEE_QUOTE_SECRET="0123456789abcdef0123456789abcdef"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
@@ -358,4 +394,17 @@ API_PROMETHEUS_PORT="3312"
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
     }
+
+    #[test]
+    fn short_fee_quote_secret() {
+        let mut config = expected_config().common;
+        assert_eq!(config.validate_fee_quote_secret(), Ok(()));
+
+        config.fee_quote_secret = "sample".into();
+        assert!(config.validate_fee_quote_secret().is_err());
+
+        // The secret isn't used if the quotes are disabled.
+        config.fee_quote_lifetime_sec = 0;
+        assert_eq!(config.validate_fee_quote_secret(), Ok(()));
+    }
 }
@@ -16,6 +16,8 @@ pub struct ChainConfig {
     pub eth: Eth,
     /// State keeper / block generating configuration.
     pub state_keeper: StateKeeper,
+    /// Mempool transactions admission configuration.
+    pub mempool: Mempool,
 }
 
 impl ChainConfig {
@@ -24,6 +26,7 @@ impl ChainConfig {
             circuit: envy_load!("circuit", "CHAIN_CIRCUIT_"),
             eth: envy_load!("eth", "CHAIN_ETH_"),
             state_keeper: envy_load!("state_keeper", "CHAIN_STATE_KEEPER_"),
+            mempool: envy_load!("mempool", "CHAIN_MEMPOOL_"),
         }
     }
     pub fn max_blocks_to_aggregate(&self) -> u32 {
@@ -94,6 +97,73 @@ pub struct StateKeeper {
     pub block_prove_deadline: u64,
     pub block_execute_deadline: u64,
     pub max_aggregated_tx_gas: usize,
+    /// Order in which the ready L2 transactions are taken from the mempool into the block.
+    pub transactions_ordering: TransactionsOrdering,
+    /// Rules of sealing the block. Block size and gas limits are always applied in addition to these.
+    pub seal_criteria: Vec<SealCriterionKind>,
+    /// Maximum age (in seconds) of the non-empty block before it's sealed.
+    pub max_block_age: u64,
+    /// Maximum amount of withdrawal operations in the block.
+    pub max_withdrawals_per_block: usize,
+    /// Maximum size of the block public data in bytes.
+    pub max_block_pubdata_bytes: usize,
+    /// Policy of choosing the amount of blocks aggregated into the L1 operations.
+    pub aggregation_policy: AggregationPolicy,
+    /// Maximum time (in seconds) the block may wait to be aggregated when the adaptive policy is used.
+    pub max_aggregation_latency: u64,
+    /// L1 gas price (in gwei) starting from which the adaptive policy aggregates the maximum amount of blocks.
+    /// For the lower prices proportionally fewer blocks are aggregated.
+    pub aggregation_reference_gas_price: u64,
+    /// Amount of L1 blocks before the deadline of the oldest unprocessed priority operation, starting from which
+    /// the priority operations are processed with the highest priority. Missing the deadline leads to the exodus mode.
+    pub priority_op_deadline_margin: u64,
+}
+
+/// Policy of ordering the ready L2 transactions in the mempool.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionsOrdering {
+    /// Transactions are taken in the order they became ready for execution.
+    Fifo,
+    /// Transactions paying the highest fee (in USD) per chunk are taken first.
+    /// The nonce order of transactions sent by the same account is preserved.
+    FeePerChunk,
+}
+
+/// Optional rule of sealing the pending block.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SealCriterionKind {
+    /// Block is sealed after the configured amount of miniblock iterations.
+    MiniblockIterations,
+    /// Block is sealed once it's older than `max_block_age`.
+    BlockAge,
+    /// Block can't contain more than `max_withdrawals_per_block` withdrawals.
+    Withdrawals,
+    /// Block public data can't exceed `max_block_pubdata_bytes`.
+    Pubdata,
+}
+
+/// Policy of aggregating the blocks into the commit, proof and execute operations.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationPolicy {
+    /// Blocks are aggregated once the maximum amount of them is reached or the deadline is triggered.
+    Deadline,
+    /// Amount of the aggregated blocks depends on the current L1 gas price, so that the amortized
+    /// gas cost per block is minimized within the `max_aggregation_latency`.
+    Adaptive,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Mempool {
+    /// Maximum amount of transactions of the same account awaiting in the mempool.
+    pub max_pending_txs_per_account: u32,
+    /// Maximum amount of transactions submitted from the same IP address and awaiting in the mempool.
+    pub max_pending_txs_per_ip: u32,
+    /// Minimal fee increase (in percents) required for a transaction to replace the queued one
+    /// with the same account and nonce.
+    pub replacement_fee_bump_percent: u32,
 }
 
 impl StateKeeper {
@@ -113,6 +183,14 @@ impl StateKeeper {
     pub fn block_execute_deadline(&self) -> Duration {
         Duration::from_secs(self.block_execute_deadline)
     }
+
+    pub fn max_block_age(&self) -> Duration {
+        Duration::from_secs(self.max_block_age)
+    }
+
+    pub fn max_aggregation_latency(&self) -> Duration {
+        Duration::from_secs(self.max_aggregation_latency)
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +225,23 @@ mod tests {
                 block_prove_deadline: 3_000,
                 block_execute_deadline: 4_000,
                 max_aggregated_tx_gas: 4_000_000,
+                transactions_ordering: TransactionsOrdering::FeePerChunk,
+                seal_criteria: vec![
+                    SealCriterionKind::MiniblockIterations,
+                    SealCriterionKind::Withdrawals,
+                ],
+                max_block_age: 60,
+                max_withdrawals_per_block: 100,
+                max_block_pubdata_bytes: 1_820,
+                aggregation_policy: AggregationPolicy::Adaptive,
+                max_aggregation_latency: 3_600,
+                aggregation_reference_gas_price: 100,
+                priority_op_deadline_margin: 5_760,
+            },
+            mempool: Mempool {
+                max_pending_txs_per_account: 100,
+                max_pending_txs_per_ip: 1_000,
+                replacement_fee_bump_percent: 10,
             },
         }
     }
@@ -175,6 +270,18 @@ CHAIN_STATE_KEEPER_BLOCK_COMMIT_DEADLINE="300"
 CHAIN_STATE_KEEPER_BLOCK_PROVE_DEADLINE="3000"
 CHAIN_STATE_KEEPER_BLOCK_EXECUTE_DEADLINE="4000"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
+CHAIN_STATE_KEEPER_TRANSACTIONS_ORDERING="fee_per_chunk"
+CHAIN_STATE_KEEPER_SEAL_CRITERIA="miniblock_iterations,withdrawals"
+CHAIN_STATE_KEEPER_MAX_BLOCK_AGE="60"
+CHAIN_STATE_KEEPER_MAX_WITHDRAWALS_PER_BLOCK="100"
+CHAIN_STATE_KEEPER_MAX_BLOCK_PUBDATA_BYTES="1820"
+CHAIN_STATE_KEEPER_AGGREGATION_POLICY="adaptive"
+CHAIN_STATE_KEEPER_MAX_AGGREGATION_LATENCY="3600"
+CHAIN_STATE_KEEPER_AGGREGATION_REFERENCE_GAS_PRICE="100"
+CHAIN_STATE_KEEPER_PRIORITY_OP_DEADLINE_MARGIN="5760"
+CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="100"
+CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_IP="1000"
+CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="10"
         "#;
         set_env(config);
 
@@ -191,5 +298,13 @@ CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
             config.state_keeper.miniblock_iteration_interval(),
             Duration::from_millis(config.state_keeper.miniblock_iteration_interval)
         );
+        assert_eq!(
+            config.state_keeper.max_block_age(),
+            Duration::from_secs(config.state_keeper.max_block_age)
+        );
+        assert_eq!(
+            config.state_keeper.max_aggregation_latency(),
+            Duration::from_secs(config.state_keeper.max_aggregation_latency)
+        );
     }
 }
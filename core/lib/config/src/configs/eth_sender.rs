@@ -44,6 +44,8 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// Whether sender should send EIP-1559 (type-2) transactions instead of legacy ones.
+    pub use_eip1559_txs: bool,
 }
 
 impl Sender {
@@ -63,6 +65,26 @@ pub struct GasLimit {
     pub sample_interval: u64,
     /// Scale factor for gas price limit (used by GasAdjuster).
     pub scale_factor: f64,
+    /// Priority fee per gas (in wei) for new EIP-1559 transactions (used by GasAdjuster).
+    pub default_priority_fee: u64,
+    /// Strategy used by GasAdjuster to suggest the gas price.
+    pub strategy: GasPriceStrategy,
+    /// Amount of the latest blocks to request the fee history for (`fee_history` strategy).
+    pub fee_history_block_count: u64,
+    /// Percentile of the priority fees paid in the recent blocks to be used (`fee_history` strategy).
+    pub fee_history_percentile: f64,
+    /// Maximum ratio between the suggested gas price and the next block base fee (`fee_history` strategy).
+    pub fee_history_max_scale: f64,
+}
+
+/// Strategy used by GasAdjuster to suggest the gas price for new transactions.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPriceStrategy {
+    /// Average of the recently used gas prices, falling back to `eth_gasPrice`.
+    Statistics,
+    /// Next block base fee plus a percentile of the recent priority fees (`eth_feeHistory`).
+    FeeHistory,
 }
 
 impl GasLimit {
@@ -90,6 +112,7 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
+                use_eip1559_txs: false,
                 operator_private_key: hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
                 ),
@@ -100,6 +123,11 @@ mod tests {
                 update_interval: 150,
                 sample_interval: 15,
                 scale_factor: 1.0f64,
+                default_priority_fee: 2000000000,
+                strategy: GasPriceStrategy::FeeHistory,
+                fee_history_block_count: 20,
+                fee_history_percentile: 50.0,
+                fee_history_max_scale: 2.0,
             },
         }
     }
@@ -112,12 +140,18 @@ ETH_SENDER_SENDER_EXPECTED_WAIT_TIME_BLOCK="30"
 ETH_SENDER_SENDER_TX_POLL_PERIOD="3"
 ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
+ETH_SENDER_SENDER_USE_EIP1559_TXS="false"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT_PRIORITY_FEE="2000000000"
+ETH_SENDER_GAS_PRICE_LIMIT_STRATEGY="fee_history"
+ETH_SENDER_GAS_PRICE_LIMIT_FEE_HISTORY_BLOCK_COUNT="20"
+ETH_SENDER_GAS_PRICE_LIMIT_FEE_HISTORY_PERCENTILE="50"
+ETH_SENDER_GAS_PRICE_LIMIT_FEE_HISTORY_MAX_SCALE="2"
         "#;
         set_env(config);
 
@@ -14,6 +14,9 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// How often we want to check whether the zkSync contract has entered the exodus mode.
+    /// Value in milliseconds.
+    pub exodus_mode_poll_interval: u64,
 }
 
 impl ETHWatchConfig {
@@ -25,6 +28,11 @@ impl ETHWatchConfig {
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Converts `self.exodus_mode_poll_interval` into `Duration`.
+    pub fn exodus_mode_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.exodus_mode_poll_interval)
+    }
 }
 
 #[cfg(test)]
@@ -36,6 +44,7 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            exodus_mode_poll_interval: 10_000,
         }
     }
 
@@ -44,6 +53,7 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_EXODUS_MODE_POLL_INTERVAL="10000"
         "#;
         set_env(config);
 
@@ -60,5 +70,9 @@ ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             config.poll_interval(),
             Duration::from_millis(config.eth_node_poll_interval)
         );
+        assert_eq!(
+            config.exodus_mode_poll_interval(),
+            Duration::from_millis(config.exodus_mode_poll_interval)
+        );
     }
 }
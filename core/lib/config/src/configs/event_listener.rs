@@ -1,5 +1,6 @@
 // Built-in uses
 use std::net::SocketAddr;
+use std::time::Duration;
 
 // External uses
 use serde::Deserialize;
@@ -17,6 +18,28 @@ pub struct EventListenerConfig {
     /// PostgreSQL channel name to listen on. Must be equal to the one
     /// hardcoded into database migrations.
     pub channel_name: String,
+    /// Amount of events loaded from the database at once while replaying
+    /// the past events to the client.
+    pub replay_page_size: u32,
+    /// Maximum amount of the past events which can be replayed to the client.
+    /// Clients lagging behind further have to be resynchronized by other means.
+    pub max_replay_window: u64,
+    /// Whether the events should be exported into the local files.
+    pub file_sink_enabled: bool,
+    /// Directory for the exported events files.
+    pub file_sink_dir: String,
+    /// Size of the events file in bytes, after which a new file is started.
+    pub file_sink_max_file_size: u64,
+    /// Whether the events should be exported into the message bus.
+    pub bus_sink_enabled: bool,
+    /// URL of the Kafka REST proxy used to publish the events.
+    pub bus_sink_url: String,
+    /// Topic the events are published to.
+    pub bus_sink_topic: String,
+    /// Amount of events exported by the sinks at once.
+    pub sink_batch_size: u32,
+    /// Interval of polling the database for new events to export (in ms).
+    pub sink_poll_interval: u64,
 }
 
 impl EventListenerConfig {
@@ -27,6 +50,11 @@ impl EventListenerConfig {
     pub fn ws_bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
     }
+
+    /// Converts `self.sink_poll_interval` into `Duration`.
+    pub fn sink_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.sink_poll_interval)
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +68,16 @@ mod tests {
             ws_port: 65535,
             ws_url: "ws://localhost:12345".into(),
             channel_name: "zksync_event_channel".into(),
+            replay_page_size: 500,
+            max_replay_window: 100_000,
+            file_sink_enabled: true,
+            file_sink_dir: "/var/lib/zksync/events".into(),
+            file_sink_max_file_size: 104_857_600,
+            bus_sink_enabled: false,
+            bus_sink_url: "http://127.0.0.1:8082".into(),
+            bus_sink_topic: "zksync_events".into(),
+            sink_batch_size: 1000,
+            sink_poll_interval: 500,
         }
     }
 
@@ -49,6 +87,16 @@ mod tests {
 EVENT_LISTENER_WS_URL="ws://localhost:12345"
 EVENT_LISTENER_WS_PORT="65535"
 EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
+EVENT_LISTENER_REPLAY_PAGE_SIZE="500"
+EVENT_LISTENER_MAX_REPLAY_WINDOW="100000"
+EVENT_LISTENER_FILE_SINK_ENABLED="true"
+EVENT_LISTENER_FILE_SINK_DIR="/var/lib/zksync/events"
+EVENT_LISTENER_FILE_SINK_MAX_FILE_SIZE="104857600"
+EVENT_LISTENER_BUS_SINK_ENABLED="false"
+EVENT_LISTENER_BUS_SINK_URL="http://127.0.0.1:8082"
+EVENT_LISTENER_BUS_SINK_TOPIC="zksync_events"
+EVENT_LISTENER_SINK_BATCH_SIZE="1000"
+EVENT_LISTENER_SINK_POLL_INTERVAL="500"
         "#;
         set_env(config);
 
@@ -1,5 +1,7 @@
-use num::{rational::Ratio, BigUint};
+// Built-in uses
+use std::time::Duration;
 // External uses
+use num::{rational::Ratio, BigUint};
 use serde::Deserialize;
 // Workspace uses
 use zksync_types::Address;
@@ -11,6 +13,10 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Median of the prices reported by the `price_sources`.
+    Median,
+    /// Prices derived from the Uniswap-like pools configured with `dex_*` fields.
+    Dex,
 }
 
 /// Configuration for the fee ticker.
@@ -40,6 +46,58 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Sources queried for the `Median` token price source.
+    pub price_sources: Vec<TokenPriceSource>,
+    /// Maximum deviation of the source price from the median in percent,
+    /// prices deviating further are discarded as outliers.
+    pub price_max_deviation_percent: u32,
+    /// Minimum number of agreeing sources required to update the price.
+    /// Otherwise the last known price is used.
+    pub price_min_sources: usize,
+    /// Maximum age of the last known price used when not enough sources agree
+    /// on the price, in seconds. Older prices are not used.
+    pub price_fallback_max_age: u64,
+    /// Uniswap-v2-style pairs used by the `Dex` price source.
+    #[serde(default)]
+    pub dex_v2_pools: Vec<Address>,
+    /// Uniswap-v3-style pools used by the `Dex` price source.
+    #[serde(default)]
+    pub dex_v3_pools: Vec<Address>,
+    /// Stablecoin the `Dex` prices are quoted in, assumed to be worth 1 USD.
+    pub dex_reference_token: Address,
+    /// Decimals of the reference stablecoin.
+    pub dex_reference_token_decimals: u8,
+    /// Address of the wrapped ETH token, used to price ETH in the pools.
+    pub dex_wrapped_eth: Address,
+    /// Period of the time-weighted average price of the pools, in seconds.
+    pub dex_twap_period: u32,
+    /// Minimum liquidity of the pools used by the `Dex` price source, in whole reference tokens.
+    /// Only the reserve of the token the price is quoted in is counted.
+    pub dex_min_liquidity: u64,
+    /// Congestion levels of the surge curve points in percent.
+    #[serde(default)]
+    pub surge_curve_congestion_percent: Vec<u32>,
+    /// Fee multipliers of the surge curve points in percent.
+    #[serde(default)]
+    pub surge_curve_multiplier_percent: Vec<u32>,
+    /// Cap of the surge fee multiplier in percent, `100` disables the surge pricing.
+    pub surge_max_multiplier_percent: u32,
+    /// Number of transactions in the mempool corresponding to the full congestion.
+    pub surge_mempool_capacity: u32,
+    /// Number of the recent blocks used to estimate the block fullness.
+    pub surge_blocks_window: u32,
+    /// Time in seconds the surge multiplier is cached for, `0` disables the caching.
+    #[serde(default)]
+    pub surge_cache_sec: u64,
+}
+
+// Checks that the `Median` source only aggregates the sources
+// which fetch the prices on their own.
+fn validate_price_sources(price_sources: &[TokenPriceSource]) {
+    assert!(
+        !price_sources.contains(&TokenPriceSource::Median),
+        "Median price source can't aggregate itself"
+    )
 }
 
 impl TickerConfig {
@@ -48,17 +106,66 @@ impl TickerConfig {
     }
 
     pub fn from_env() -> Self {
-        envy_load!("fee_ticker", "FEE_TICKER_")
+        let config: Self = envy_load!("fee_ticker", "FEE_TICKER_");
+        validate_price_sources(&config.price_sources);
+        if let Err(err) = config.surge_curve() {
+            panic!("{}", err);
+        }
+        config
+    }
+
+    /// Converts `self.price_fallback_max_age` into `Duration`.
+    pub fn price_fallback_max_age(&self) -> Duration {
+        Duration::from_secs(self.price_fallback_max_age)
     }
 
     /// Returns the token price source type and the corresponding API URL.
+    /// URL is empty for the `Median` and `Dex` sources, since they don't use an HTTP API.
     pub fn price_source(&self) -> (TokenPriceSource, String) {
-        let url = match self.token_price_source {
-            TokenPriceSource::CoinGecko => self.coingecko_base_url.clone(),
-            TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.clone(),
-        };
+        let url = self.source_url(self.token_price_source).unwrap_or_default();
         (self.token_price_source, url)
     }
+
+    /// Returns `true` if the prices are derived from the DEX pools, either directly
+    /// or as one of the `Median` sources.
+    pub fn uses_dex_price_source(&self) -> bool {
+        match self.token_price_source {
+            TokenPriceSource::Dex => true,
+            TokenPriceSource::Median => self.price_sources.contains(&TokenPriceSource::Dex),
+            TokenPriceSource::CoinGecko | TokenPriceSource::CoinMarketCap => false,
+        }
+    }
+
+    /// Returns the points of the surge curve as `(congestion percent, multiplier percent)` pairs.
+    pub fn surge_curve(&self) -> Result<Vec<(u32, u32)>, String> {
+        if self.surge_curve_congestion_percent.len() != self.surge_curve_multiplier_percent.len() {
+            return Err(format!(
+                "Surge curve congestion and multiplier lists must have the same length, got {} and {}",
+                self.surge_curve_congestion_percent.len(),
+                self.surge_curve_multiplier_percent.len()
+            ));
+        }
+        Ok(self
+            .surge_curve_congestion_percent
+            .iter()
+            .copied()
+            .zip(self.surge_curve_multiplier_percent.iter().copied())
+            .collect())
+    }
+
+    /// Converts `self.surge_cache_sec` into `Duration`.
+    pub fn surge_cache_duration(&self) -> Duration {
+        Duration::from_secs(self.surge_cache_sec)
+    }
+
+    /// Returns the API URL of the given price source.
+    pub fn source_url(&self, source: TokenPriceSource) -> Option<String> {
+        match source {
+            TokenPriceSource::CoinGecko => Some(self.coingecko_base_url.clone()),
+            TokenPriceSource::CoinMarketCap => Some(self.coinmarketcap_base_url.clone()),
+            TokenPriceSource::Median | TokenPriceSource::Dex => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +187,26 @@ mod tests {
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            price_sources: vec![TokenPriceSource::CoinGecko, TokenPriceSource::CoinMarketCap],
+            price_max_deviation_percent: 5,
+            price_min_sources: 2,
+            price_fallback_max_age: 3600,
+            dex_v2_pools: vec![addr("1111111111111111111111111111111111111111")],
+            dex_v3_pools: vec![
+                addr("2222222222222222222222222222222222222222"),
+                addr("3333333333333333333333333333333333333333"),
+            ],
+            dex_reference_token: addr("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            dex_reference_token_decimals: 6,
+            dex_wrapped_eth: addr("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+            dex_twap_period: 1800,
+            dex_min_liquidity: 1000000,
+            surge_curve_congestion_percent: vec![50, 80, 100],
+            surge_curve_multiplier_percent: vec![100, 150, 300],
+            surge_max_multiplier_percent: 300,
+            surge_mempool_capacity: 1000,
+            surge_blocks_window: 10,
+            surge_cache_sec: 5,
         }
     }
 
@@ -99,6 +226,23 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_PRICE_SOURCES="CoinGecko,CoinMarketCap"
+FEE_TICKER_PRICE_MAX_DEVIATION_PERCENT=5
+FEE_TICKER_PRICE_MIN_SOURCES=2
+FEE_TICKER_PRICE_FALLBACK_MAX_AGE=3600
+FEE_TICKER_DEX_V2_POOLS="0x1111111111111111111111111111111111111111"
+FEE_TICKER_DEX_V3_POOLS="0x2222222222222222222222222222222222222222,0x3333333333333333333333333333333333333333"
+FEE_TICKER_DEX_REFERENCE_TOKEN="0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+FEE_TICKER_DEX_REFERENCE_TOKEN_DECIMALS=6
+FEE_TICKER_DEX_WRAPPED_ETH="0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+FEE_TICKER_DEX_TWAP_PERIOD=1800
+FEE_TICKER_DEX_MIN_LIQUIDITY=1000000
+FEE_TICKER_SURGE_CURVE_CONGESTION_PERCENT="50,80,100"
+FEE_TICKER_SURGE_CURVE_MULTIPLIER_PERCENT="100,150,300"
+FEE_TICKER_SURGE_MAX_MULTIPLIER_PERCENT=300
+FEE_TICKER_SURGE_MEMPOOL_CAPACITY=1000
+FEE_TICKER_SURGE_BLOCKS_WINDOW=10
+FEE_TICKER_SURGE_CACHE_SEC=5
         "#;
         set_env(config);
 
@@ -128,5 +272,37 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        config.token_price_source = TokenPriceSource::Median;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::Median, String::new())
+        );
+        assert_eq!(
+            config.source_url(TokenPriceSource::CoinGecko),
+            Some(COINGECKO_URL.into())
+        );
+        assert!(!config.uses_dex_price_source());
+        config.price_sources.push(TokenPriceSource::Dex);
+        assert!(config.uses_dex_price_source());
+
+        config.token_price_source = TokenPriceSource::Dex;
+        assert!(config.uses_dex_price_source());
+        config.token_price_source = TokenPriceSource::CoinGecko;
+        assert!(!config.uses_dex_price_source());
+
+        assert_eq!(
+            config.surge_curve(),
+            Ok(vec![(50, 100), (80, 150), (100, 300)])
+        );
+        assert_eq!(config.surge_cache_duration(), Duration::from_secs(5));
+        config.surge_curve_multiplier_percent.pop();
+        assert!(config.surge_curve().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Median price source can't aggregate itself")]
+    fn median_of_medians() {
+        validate_price_sources(&[TokenPriceSource::CoinGecko, TokenPriceSource::Median]);
     }
 }
@@ -0,0 +1,108 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the webhook notifications about the account activity.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WebhooksConfig {
+    /// Whether the API for registering webhooks is enabled.
+    pub enabled: bool,
+    /// Maximum amount of webhooks registered for a single address.
+    pub max_webhooks_per_address: u32,
+    /// Time in seconds during which the signed API request remains valid.
+    pub request_lifetime: u64,
+    /// Interval of polling for new events and pending deliveries (in ms).
+    pub poll_interval: u64,
+    /// Amount of events or deliveries processed at once.
+    pub batch_size: u32,
+    /// Timeout of a single delivery attempt in seconds.
+    pub delivery_timeout: u64,
+    /// Maximum amount of attempts to deliver a notification.
+    pub max_attempts: u32,
+    /// Delay before the first retry in seconds, doubled with every next attempt.
+    pub retry_backoff: u64,
+    /// Maximum delay between the retries in seconds.
+    pub max_retry_backoff: u64,
+}
+
+impl WebhooksConfig {
+    pub fn from_env() -> Self {
+        envy_load!("webhooks", "WEBHOOKS_")
+    }
+
+    /// Converts `self.request_lifetime` into `Duration`.
+    pub fn request_lifetime(&self) -> Duration {
+        Duration::from_secs(self.request_lifetime)
+    }
+
+    /// Converts `self.poll_interval` into `Duration`.
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+
+    /// Converts `self.delivery_timeout` into `Duration`.
+    pub fn delivery_timeout(&self) -> Duration {
+        Duration::from_secs(self.delivery_timeout)
+    }
+
+    /// Returns the delay before the next delivery attempt given the amount
+    /// of already failed attempts.
+    pub fn retry_backoff(&self, failed_attempts: u32) -> Duration {
+        let multiplier = 2u64.saturating_pow(failed_attempts.saturating_sub(1));
+        let backoff = self.retry_backoff.saturating_mul(multiplier);
+        Duration::from_secs(backoff.min(self.max_retry_backoff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> WebhooksConfig {
+        WebhooksConfig {
+            enabled: true,
+            max_webhooks_per_address: 10,
+            request_lifetime: 300,
+            poll_interval: 1000,
+            batch_size: 100,
+            delivery_timeout: 10,
+            max_attempts: 8,
+            retry_backoff: 5,
+            max_retry_backoff: 600,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+WEBHOOKS_ENABLED="true"
+WEBHOOKS_MAX_WEBHOOKS_PER_ADDRESS="10"
+WEBHOOKS_REQUEST_LIFETIME="300"
+WEBHOOKS_POLL_INTERVAL="1000"
+WEBHOOKS_BATCH_SIZE="100"
+WEBHOOKS_DELIVERY_TIMEOUT="10"
+WEBHOOKS_MAX_ATTEMPTS="8"
+WEBHOOKS_RETRY_BACKOFF="5"
+WEBHOOKS_MAX_RETRY_BACKOFF="600"
+        "#;
+        set_env(config);
+
+        let actual = WebhooksConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let config = expected_config();
+        assert_eq!(config.retry_backoff(1), Duration::from_secs(5));
+        assert_eq!(config.retry_backoff(2), Duration::from_secs(10));
+        assert_eq!(config.retry_backoff(4), Duration::from_secs(40));
+        // The delay is capped.
+        assert_eq!(config.retry_backoff(10), Duration::from_secs(600));
+        assert_eq!(config.retry_backoff(100), Duration::from_secs(600));
+    }
+}
@@ -13,7 +13,7 @@ use web3::{
     },
     transports::Http,
     types::{
-        Address, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
+        Address, BlockId, BlockNumber, Bytes, FeeHistory, Filter, Log, Transaction, TransactionId,
         TransactionReceipt, H160, H256, U256, U64,
     },
     Web3,
@@ -153,6 +153,43 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let block = self
+            .block(BlockId::Number(BlockNumber::Latest))
+            .await?
+            .ok_or_else(|| anyhow::format_err!("Latest block is not available"))?;
+        let base_fee_per_gas = block.base_fee_per_gas.ok_or_else(|| {
+            anyhow::format_err!("Latest block has no base fee, EIP-1559 is not supported")
+        })?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_base_fee_per_gas", start.elapsed());
+        Ok(base_fee_per_gas)
+    }
+
+    pub async fn fee_history(
+        &self,
+        block_count: usize,
+        reward_percentiles: Vec<f64>,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let fee_history = self
+            .inner
+            .web3
+            .eth()
+            .fee_history(
+                block_count.into(),
+                BlockNumber::Latest,
+                Some(reward_percentiles),
+            )
+            .await?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.fee_history", start.elapsed());
+        Ok(fee_history)
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -171,10 +208,20 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         #[cfg(feature = "with-metrics")]
         let start = Instant::now();
 
+        // EIP-1559 transaction is sent only if both fee caps are provided,
+        // otherwise a legacy transaction is created.
+        let eip1559_fees = match (options.max_fee_per_gas, options.max_priority_fee_per_gas) {
+            (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                Some((max_fee_per_gas, max_priority_fee_per_gas))
+            }
+            _ => None,
+        };
+
         // fetch current gas_price
-        let gas_price = match options.gas_price {
-            Some(gas_price) => gas_price,
-            None => self.get_gas_price().await?,
+        let gas_price = match (eip1559_fees, options.gas_price) {
+            (Some((max_fee_per_gas, _)), _) => max_fee_per_gas,
+            (None, Some(gas_price)) => gas_price,
+            (None, None) => self.get_gas_price().await?,
         };
 
         let nonce = match options.nonce {
@@ -204,6 +251,9 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             to: Some(contract_addr),
             value: options.value.unwrap_or_default(),
             gas_price,
+            max_fee_per_gas: eip1559_fees.map(|(max_fee_per_gas, _)| max_fee_per_gas),
+            max_priority_fee_per_gas: eip1559_fees
+                .map(|(_, max_priority_fee_per_gas)| max_priority_fee_per_gas),
             gas,
             data,
         };
@@ -224,6 +274,8 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(SignedCallResult {
             raw_tx: signed_tx,
             gas_price,
+            max_priority_fee_per_gas: eip1559_fees
+                .map(|(_, max_priority_fee_per_gas)| max_priority_fee_per_gas),
             nonce,
             hash,
         })
@@ -438,6 +490,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                 };
                 let confirmations = current_block.saturating_sub(tx_block_number.as_u64());
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -450,6 +503,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
                 }))
             }
             _ => Ok(None),
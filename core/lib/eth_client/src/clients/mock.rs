@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use anyhow::Error;
-use ethabi::{Address, Contract};
+use ethabi::{Address, Contract, Token};
 use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::Options;
 use web3::transports::Http;
-use web3::types::{BlockId, Filter, Log, Transaction, U64};
+use web3::types::{Block, BlockId, BlockNumber, FeeHistory, Filter, Log, Transaction, U64};
 
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
@@ -20,8 +20,14 @@ use crate::{
 struct MockEthereumInner {
     block_number: u64,
     gas_price: U256,
+    base_fee_per_gas: U256,
+    max_priority_fee_reward: U256,
+    /// Hashes of the blocks set explicitly, hashes of the other blocks are derived from their numbers.
+    block_hashes: Arc<RwLock<HashMap<u64, H256>>>,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    contract_call_results: Arc<RwLock<HashMap<String, Vec<Token>>>>,
+    contract_function_call_results: Arc<RwLock<HashMap<(Address, String), Vec<Token>>>>,
 }
 
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
@@ -35,8 +41,13 @@ impl Default for MockEthereumInner {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            base_fee_per_gas: 80.into(),
+            max_priority_fee_reward: 20.into(),
+            block_hashes: Default::default(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            contract_call_results: Default::default(),
+            contract_function_call_results: Default::default(),
         }
     }
 }
@@ -83,6 +94,7 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,9 +107,34 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
+
+    /// Sets the tokens returned by the calls of the main contract function with the given name.
+    pub async fn set_contract_call_result(&self, func: &str, result: Vec<Token>) {
+        self.inner
+            .contract_call_results
+            .write()
+            .await
+            .insert(func.to_string(), result);
+    }
+
+    /// Sets the tokens returned by the calls of the given contract function with the given name.
+    pub async fn set_contract_function_call_result(
+        &self,
+        contract: Address,
+        func: &str,
+        result: Vec<Token>,
+    ) {
+        self.inner
+            .contract_function_call_results
+            .write()
+            .await
+            .insert((contract, func.to_string()), result);
+    }
+
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -106,6 +143,47 @@ impl MockEthereum {
         Ok(self.inner.tx_statuses.read().await.get(&hash).cloned())
     }
 
+    /// Sets the hash of the block with the given number, e.g. to simulate the chain reorganization.
+    pub async fn set_block_hash(&self, block_number: u64, hash: H256) {
+        self.inner
+            .block_hashes
+            .write()
+            .await
+            .insert(block_number, hash);
+    }
+
+    /// Returns the block header with only the number and the hash set.
+    /// Blocks after the current block number are not known, and blocks can't be requested by hash.
+    pub async fn block(&self, id: BlockId) -> anyhow::Result<Option<Block<H256>>> {
+        let block_number = match id {
+            BlockId::Number(BlockNumber::Number(number)) => number.as_u64(),
+            BlockId::Number(BlockNumber::Latest) | BlockId::Number(BlockNumber::Pending) => {
+                self.inner.block_number
+            }
+            BlockId::Number(BlockNumber::Earliest) => 0,
+            BlockId::Hash(hash) => {
+                anyhow::bail!("Loading blocks by hash is not supported: {:?}", hash)
+            }
+        };
+        if block_number > self.inner.block_number {
+            return Ok(None);
+        }
+        let hash = self
+            .inner
+            .block_hashes
+            .read()
+            .await
+            .get(&block_number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(block_number));
+
+        Ok(Some(Block {
+            number: Some(block_number.into()),
+            hash: Some(hash),
+            ..Default::default()
+        }))
+    }
+
     pub async fn block_number(&self) -> anyhow::Result<U64> {
         Ok(self.inner.block_number.into())
     }
@@ -124,6 +202,51 @@ impl MockEthereum {
         Ok(self.inner.gas_price)
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> anyhow::Result<U256> {
+        Ok(self.inner.base_fee_per_gas)
+    }
+
+    pub async fn set_base_fee_per_gas(&mut self, val: U256) -> anyhow::Result<U256> {
+        Arc::get_mut(&mut self.inner).unwrap().base_fee_per_gas = val;
+        Ok(self.inner.base_fee_per_gas)
+    }
+
+    /// Returns the fee history with the same base fee for every block.
+    /// Rewards are proportional to the requested percentile: 100th percentile reward
+    /// is equal to the configured max priority fee reward.
+    pub async fn fee_history(
+        &self,
+        block_count: usize,
+        reward_percentiles: Vec<f64>,
+    ) -> anyhow::Result<FeeHistory> {
+        let block_reward: Vec<_> = reward_percentiles
+            .iter()
+            .map(|percentile| {
+                self.inner.max_priority_fee_reward * U256::from(percentile.round() as u64)
+                    / U256::from(100)
+            })
+            .collect();
+        Ok(FeeHistory {
+            oldest_block: BlockNumber::Number(
+                self.inner
+                    .block_number
+                    .saturating_sub((block_count as u64).saturating_sub(1))
+                    .into(),
+            ),
+            // Base fee for the next block is included as well.
+            base_fee_per_gas: vec![self.inner.base_fee_per_gas; block_count + 1],
+            gas_used_ratio: vec![0.5; block_count],
+            reward: Some(vec![block_reward; block_count]),
+        })
+    }
+
+    pub async fn set_max_priority_fee_reward(&mut self, val: U256) -> anyhow::Result<U256> {
+        Arc::get_mut(&mut self.inner)
+            .unwrap()
+            .max_priority_fee_reward = val;
+        Ok(self.inner.max_priority_fee_reward)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -137,7 +260,13 @@ impl MockEthereum {
         raw_tx: Vec<u8>,
         options: Options,
     ) -> anyhow::Result<SignedCallResult> {
-        let gas_price = options.gas_price.unwrap_or(self.inner.gas_price);
+        let max_priority_fee_per_gas = options
+            .max_fee_per_gas
+            .and(options.max_priority_fee_per_gas);
+        let gas_price = match max_priority_fee_per_gas {
+            Some(_) => options.max_fee_per_gas.unwrap(),
+            None => options.gas_price.unwrap_or(self.inner.gas_price),
+        };
         let nonce = options.nonce.expect("Nonce must be set for every tx");
 
         // Nonce and gas_price are appended to distinguish the same transactions
@@ -145,6 +274,9 @@ impl MockEthereum {
         let mut data_for_hash = raw_tx.clone();
         data_for_hash.append(&mut ethabi::encode(gas_price.into_tokens().as_ref()));
         data_for_hash.append(&mut ethabi::encode(nonce.into_tokens().as_ref()));
+        if let Some(priority_fee) = max_priority_fee_per_gas {
+            data_for_hash.append(&mut ethabi::encode(priority_fee.into_tokens().as_ref()));
+        }
         let hash = Self::fake_sha256(data_for_hash.as_ref()); // Okay for test purposes.
                                                               // Concatenate raw_tx plus hash for test purposes
         let mut new_raw_tx = hash.as_bytes().to_vec();
@@ -152,6 +284,7 @@ impl MockEthereum {
         Ok(SignedCallResult {
             raw_tx: new_raw_tx,
             gas_price,
+            max_priority_fee_per_gas,
             nonce,
             hash,
         })
@@ -219,7 +352,7 @@ impl MockEthereum {
 
     pub async fn call_main_contract_function<R, A, P, B>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
@@ -231,7 +364,15 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        let result = self
+            .inner
+            .contract_call_results
+            .read()
+            .await
+            .get(func)
+            .cloned()
+            .ok_or_else(|| anyhow::format_err!("No result set for the `{}` call", func))?;
+        Ok(R::from_tokens(result)?)
     }
 
     pub async fn logs(&self, _filter: Filter) -> anyhow::Result<Vec<Log>> {
@@ -241,12 +382,12 @@ impl MockEthereum {
     #[allow(clippy::too_many_arguments)]
     pub async fn call_contract_function<R, A, B, P>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
         _block: B,
-        _token_address: Address,
+        token_address: Address,
         _erc20_abi: ethabi::Contract,
     ) -> Result<R, anyhow::Error>
     where
@@ -255,7 +396,21 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        let result = self
+            .inner
+            .contract_function_call_results
+            .read()
+            .await
+            .get(&(token_address, func.to_string()))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::format_err!(
+                    "No result set for the `{}` call of {:?}",
+                    func,
+                    token_address
+                )
+            })?;
+        Ok(R::from_tokens(result)?)
     }
 
     pub fn create_contract(
@@ -5,7 +5,7 @@ use web3::{
     contract::tokens::{Detokenize, Tokenize},
     contract::Options,
     transports::Http,
-    types::{Address, BlockId, Filter, Log, Transaction, U64},
+    types::{Address, Block, BlockId, FeeHistory, Filter, Log, Transaction, U64},
 };
 use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
@@ -98,6 +98,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, current_nonce());
     }
 
+    pub async fn block(&self, id: BlockId) -> Result<Option<Block<H256>>, anyhow::Error> {
+        multiple_call!(self, block(id));
+    }
+
     pub async fn block_number(&self) -> Result<U64, anyhow::Error> {
         multiple_call!(self, block_number());
     }
@@ -106,6 +110,18 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        multiple_call!(self, get_base_fee_per_gas());
+    }
+
+    pub async fn fee_history(
+        &self,
+        block_count: usize,
+        reward_percentiles: Vec<f64>,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        multiple_call!(self, fee_history(block_count, reward_percentiles));
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_eth_balance());
     }
@@ -1,7 +1,7 @@
 use web3::contract::tokens::{Detokenize, Tokenize};
 use web3::contract::{Contract, Options};
 use web3::transports::Http;
-use web3::types::{Address, BlockId, Filter, Log, Transaction, U64};
+use web3::types::{Address, Block, BlockId, FeeHistory, Filter, Log, Transaction, U64};
 
 use std::fmt::Debug;
 use zksync_config::{ETHClientConfig, ETHSenderConfig};
@@ -16,7 +16,10 @@ use crate::ETHDirectClient;
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignedCallResult {
     pub raw_tx: Vec<u8>,
+    /// Gas price of the legacy transaction, or `max_fee_per_gas` for EIP-1559 transactions.
     pub gas_price: U256,
+    /// Priority fee per gas, set only for EIP-1559 transactions.
+    pub max_priority_fee_per_gas: Option<U256>,
     pub nonce: U256,
     pub hash: H256,
 }
@@ -31,6 +34,8 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction, if reported by the node.
+    pub gas_used: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -119,6 +124,10 @@ impl EthereumGateway {
         delegate_call!(self.current_nonce())
     }
 
+    pub async fn block(&self, id: BlockId) -> Result<Option<Block<H256>>, anyhow::Error> {
+        delegate_call!(self.block(id))
+    }
+
     pub async fn block_number(&self) -> Result<U64, anyhow::Error> {
         delegate_call!(self.block_number())
     }
@@ -126,6 +135,22 @@ impl EthereumGateway {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the base fee per gas of the latest block (EIP-1559).
+    pub async fn get_base_fee_per_gas(&self) -> Result<U256, anyhow::Error> {
+        delegate_call!(self.get_base_fee_per_gas())
+    }
+
+    /// Returns the fee history for the `block_count` latest blocks (`eth_feeHistory`),
+    /// with the priority fees (rewards) calculated for each of the requested percentiles.
+    pub async fn fee_history(
+        &self,
+        block_count: usize,
+        reward_percentiles: Vec<f64>,
+    ) -> Result<FeeHistory, anyhow::Error> {
+        delegate_call!(self.fee_history(block_count, reward_percentiles))
+    }
+
     /// Returns the account balance.
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_eth_balance())
@@ -288,6 +288,7 @@ impl JsonRpcSigner {
 }
 
 mod messages {
+    use crate::raw_ethereum_tx::EIP1559_TX_TYPE;
     use crate::RawTransaction;
     use hex::encode;
     use zksync_types::Address;
@@ -341,27 +342,34 @@ mod messages {
         pub fn sign_transaction(from: Address, tx_data: RawTransaction) -> Self {
             let mut params = Vec::new();
 
+            let mut tx = serde_json::json!({
+                "from": serde_json::to_value(from).expect("serialization fail"),
+                "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
+                "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
+                "data": serde_json::to_value(format!("0x{}", encode(&tx_data.data))).expect("serialization fail"),
+                "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
+            });
+
             // Parameter `To` is optional, so we add it only if it is not None
-            let tx = if let Some(to) = tx_data.to {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "to": serde_json::to_value(to).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
-            } else {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
-            };
+            if let Some(to) = tx_data.to {
+                tx["to"] = serde_json::to_value(to).expect("serialization fail");
+            }
+
+            // EIP-1559 transactions are described by the fee caps instead of the gas price.
+            match (tx_data.max_fee_per_gas, tx_data.max_priority_fee_per_gas) {
+                (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                    tx["type"] = serde_json::to_value(format!("{:#x}", EIP1559_TX_TYPE))
+                        .expect("serialization fail");
+                    tx["maxFeePerGas"] =
+                        serde_json::to_value(max_fee_per_gas).expect("serialization fail");
+                    tx["maxPriorityFeePerGas"] =
+                        serde_json::to_value(max_priority_fee_per_gas).expect("serialization fail");
+                }
+                _ => {
+                    tx["gasPrice"] =
+                        serde_json::to_value(tx_data.gas_price).expect("serialization fail");
+                }
+            }
             params.push(tx);
             Self::create("eth_signTransaction", params)
         }
@@ -373,15 +381,17 @@ mod tests {
     use actix_web::{post, web, App, HttpResponse, HttpServer, Responder};
     use futures::future::{AbortHandle, Abortable};
     use jsonrpc_core::{Failure, Id, Output, Success, Version};
-    use parity_crypto::publickey::{Generator, KeyPair, Random};
-    use serde_json::json;
+    use parity_crypto::publickey::{sign, Generator, KeyPair, Random};
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
 
     use zksync_types::{
         tx::{PackedEthSignature, TxEthSignature},
-        Address,
+        Address, U256,
     };
 
     use super::{is_signature_from_address, messages::JsonRpcRequest};
+    use crate::raw_ethereum_tx::EIP1559_TX_TYPE;
     use crate::{EthereumSigner, JsonRpcSigner, RawTransaction};
 
     #[post("/")]
@@ -405,16 +415,37 @@ mod tests {
                 create_success(json!(signature))
             }
             "eth_signTransaction" => {
-                let tx_value = json!(req.params[0].clone()).to_string();
-                let tx = tx_value.as_bytes();
-                let hex_data = hex::encode(tx);
-                create_success(json!({ "raw": hex_data }))
+                let raw_tx = parse_transaction(&req.params[0]);
+                let signature = sign(state.key_pairs[0].secret(), &raw_tx.hash().into()).unwrap();
+                let signed_tx = raw_tx.rlp_encode_tx(signature);
+                create_success(json!({ "raw": format!("0x{}", hex::encode(signed_tx)) }))
             }
             _ => create_fail(req.method.clone()),
         };
         HttpResponse::Ok().json(json!(resp))
     }
 
+    /// Restores the transaction from the `eth_signTransaction` parameters.
+    fn parse_transaction(tx: &Value) -> RawTransaction {
+        fn field<T: DeserializeOwned>(tx: &Value, name: &str) -> Option<T> {
+            tx.get(name)
+                .map(|value| serde_json::from_value(value.clone()).unwrap())
+        }
+
+        let data: String = field(tx, "data").unwrap();
+        RawTransaction {
+            chain_id: 0,
+            nonce: field(tx, "nonce").unwrap(),
+            to: field(tx, "to"),
+            value: field(tx, "value").unwrap(),
+            gas_price: field(tx, "gasPrice").unwrap_or_default(),
+            max_fee_per_gas: field(tx, "maxFeePerGas"),
+            max_priority_fee_per_gas: field(tx, "maxPriorityFeePerGas"),
+            gas: field(tx, "gas").unwrap(),
+            data: hex::decode(&data["0x".len()..]).unwrap(),
+        }
+    }
+
     fn create_fail(method: String) -> Output {
         Output::Failure(Failure {
             jsonrpc: Some(Version::V2),
@@ -488,13 +519,41 @@ mod tests {
                 nonce: Default::default(),
                 to: None,
                 value: Default::default(),
-                gas_price: Default::default(),
+                gas_price: 10.into(),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
                 gas: Default::default(),
                 data: vec![],
             })
             .await
             .unwrap();
-        assert_ne!(transaction_signature.len(), 0);
+        // Legacy transaction is a plain RLP list with the gas price.
+        let legacy_rlp = rlp::Rlp::new(&transaction_signature);
+        assert!(legacy_rlp.is_list());
+        assert_eq!(legacy_rlp.item_count().unwrap(), 9);
+        assert_eq!(legacy_rlp.val_at::<U256>(1).unwrap(), U256::from(10));
+
+        let eip1559_transaction_signature = client
+            .sign_transaction(RawTransaction {
+                chain_id: 0,
+                nonce: Default::default(),
+                to: None,
+                value: Default::default(),
+                gas_price: Default::default(),
+                max_fee_per_gas: Some(100.into()),
+                max_priority_fee_per_gas: Some(2.into()),
+                gas: Default::default(),
+                data: vec![1, 2, 3],
+            })
+            .await
+            .unwrap();
+        // Typed transaction envelope must carry the fee caps instead of the gas price.
+        assert_eq!(eip1559_transaction_signature[0], EIP1559_TX_TYPE);
+        let eip1559_rlp = rlp::Rlp::new(&eip1559_transaction_signature[1..]);
+        assert_eq!(eip1559_rlp.item_count().unwrap(), 12);
+        assert_eq!(eip1559_rlp.val_at::<U256>(2).unwrap(), U256::from(2));
+        assert_eq!(eip1559_rlp.val_at::<U256>(3).unwrap(), U256::from(100));
+        assert_eq!(eip1559_rlp.val_at::<Vec<u8>>(7).unwrap(), vec![1, 2, 3]);
         abort_handle.abort();
     }
 }
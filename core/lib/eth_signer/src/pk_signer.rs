@@ -51,7 +51,9 @@ impl EthereumSigner for PrivateKeySigner {
 mod test {
     use super::PrivateKeySigner;
     use super::RawTransaction;
+    use crate::raw_ethereum_tx::EIP1559_TX_TYPE;
     use crate::EthereumSigner;
+    use parity_crypto::publickey::sign;
     use zksync_types::{H160, H256, U256};
 
     #[tokio::test]
@@ -64,6 +66,8 @@ mod test {
             to: Some(H160::zero()),
             value: U256::from(10),
             gas_price: U256::from(1),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             gas: U256::from(2),
             data: vec![1, 2, 3],
         };
@@ -82,4 +86,60 @@ mod test {
         ];
         assert_eq!(signature, precalculated_signature);
     }
+
+    #[tokio::test]
+    async fn test_generating_eip1559_signature() {
+        let private_key = H256::from([5; 32]);
+        let signer = PrivateKeySigner::new(private_key);
+        let raw_transaction = RawTransaction {
+            chain_id: 1,
+            nonce: U256::from(1),
+            to: Some(H160::zero()),
+            value: U256::from(10),
+            gas_price: U256::zero(),
+            max_fee_per_gas: Some(U256::from(300)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            gas: U256::from(2),
+            data: vec![1, 2, 3],
+        };
+        let signed_tx = signer
+            .sign_transaction(raw_transaction.clone())
+            .await
+            .unwrap();
+
+        // Typed transaction envelope must start with the transaction type.
+        assert_eq!(signed_tx[0], EIP1559_TX_TYPE);
+
+        let rlp = rlp::Rlp::new(&signed_tx[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 12);
+        assert_eq!(rlp.val_at::<U256>(1).unwrap(), raw_transaction.nonce);
+        assert_eq!(rlp.val_at::<U256>(2).unwrap(), U256::from(2));
+        assert_eq!(rlp.val_at::<U256>(3).unwrap(), U256::from(300));
+        assert_eq!(rlp.val_at::<U256>(4).unwrap(), raw_transaction.gas);
+        assert_eq!(rlp.val_at::<Vec<u8>>(7).unwrap(), raw_transaction.data);
+        assert_eq!(rlp.at(8).unwrap().item_count().unwrap(), 0);
+
+        // Signature must be calculated over the typed transaction hash.
+        let expected_signature = sign(&private_key.into(), &raw_transaction.hash().into()).unwrap();
+        assert_eq!(rlp.val_at::<u8>(9).unwrap(), expected_signature.v());
+
+        // Leading zeroes are stripped from `r` and `s` during encoding.
+        let pad = |value: Vec<u8>| {
+            let mut padded = vec![0u8; 32 - value.len()];
+            padded.extend(value);
+            padded
+        };
+        let r = pad(rlp.val_at::<Vec<u8>>(10).unwrap());
+        let s = pad(rlp.val_at::<Vec<u8>>(11).unwrap());
+        assert_eq!(r, expected_signature.r().to_vec());
+        assert_eq!(s, expected_signature.s().to_vec());
+
+        // Legacy signature must differ from the typed one.
+        let legacy_transaction = RawTransaction {
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            ..raw_transaction.clone()
+        };
+        assert_ne!(legacy_transaction.hash(), raw_transaction.hash());
+    }
 }
@@ -3,6 +3,9 @@ use rlp::RlpStream;
 use serde::{Deserialize, Serialize};
 use zksync_types::{H160, U256};
 
+/// Type identifier of the EIP-1559 (type-2) transaction envelope.
+pub const EIP1559_TX_TYPE: u8 = 2;
+
 /// Description of a Transaction, pending or in the chain.
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct RawTransaction {
@@ -14,9 +17,23 @@ pub struct RawTransaction {
     pub to: Option<H160>,
     /// Transfered value
     pub value: U256,
-    /// Gas Price
+    /// Gas Price (ignored for EIP-1559 transactions)
     #[serde(rename = "gasPrice")]
     pub gas_price: U256,
+    /// Max fee per gas (EIP-1559 transactions only)
+    #[serde(
+        rename = "maxFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas (EIP-1559 transactions only)
+    #[serde(
+        rename = "maxPriorityFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_priority_fee_per_gas: Option<U256>,
     /// Gas amount
     pub gas: U256,
     /// Input data
@@ -37,7 +54,17 @@ fn find_first_nonzero(vector: &[u8]) -> usize {
 }
 
 impl RawTransaction {
+    /// Returns `true` if the transaction has to be sent as an EIP-1559 (type-2) transaction,
+    /// i.e. both `max_fee_per_gas` and `max_priority_fee_per_gas` are set.
+    pub fn is_eip1559(&self) -> bool {
+        self.max_fee_per_gas.is_some() && self.max_priority_fee_per_gas.is_some()
+    }
+
     pub fn rlp_encode_tx(&self, sig: Signature) -> Vec<u8> {
+        if self.is_eip1559() {
+            return self.rlp_encode_eip1559_tx(sig);
+        }
+
         let signature = to_ecdsa(sig, self.chain_id);
         let mut tx = RlpStream::new();
         tx.begin_unbounded_list();
@@ -54,6 +81,10 @@ impl RawTransaction {
     }
 
     pub fn hash(&self) -> [u8; 32] {
+        if self.is_eip1559() {
+            return self.eip1559_hash();
+        }
+
         let mut hash = RlpStream::new();
         hash.begin_unbounded_list();
         self.encode(&mut hash);
@@ -76,6 +107,56 @@ impl RawTransaction {
         s.append(&self.value);
         s.append(&self.data);
     }
+
+    /// Encodes the signed transaction as an EIP-2718 typed envelope:
+    /// `0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas, to, value, data, access_list, y_parity, r, s])`.
+    fn rlp_encode_eip1559_tx(&self, sig: Signature) -> Vec<u8> {
+        let mut tx = RlpStream::new();
+        tx.begin_unbounded_list();
+        self.encode_eip1559(&mut tx);
+        tx.append(&sig.v());
+        let r_start = find_first_nonzero(sig.r());
+        let r = &sig.r()[r_start..];
+        tx.append(&r);
+        let s_start = find_first_nonzero(sig.s());
+        let s = &sig.s()[s_start..];
+        tx.append(&s);
+        tx.finalize_unbounded_list();
+
+        let mut encoded = vec![EIP1559_TX_TYPE];
+        encoded.extend_from_slice(&tx.out());
+        encoded
+    }
+
+    /// Calculates the signing hash of the EIP-1559 transaction:
+    /// `keccak256(0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas, to, value, data, access_list]))`.
+    fn eip1559_hash(&self) -> [u8; 32] {
+        let mut hash = RlpStream::new();
+        hash.begin_unbounded_list();
+        self.encode_eip1559(&mut hash);
+        hash.finalize_unbounded_list();
+
+        let mut payload = vec![EIP1559_TX_TYPE];
+        payload.extend_from_slice(&hash.out());
+        payload.keccak256()
+    }
+
+    fn encode_eip1559(&self, s: &mut RlpStream) {
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&self.max_priority_fee_per_gas.unwrap_or_default());
+        s.append(&self.max_fee_per_gas.unwrap_or_default());
+        s.append(&self.gas);
+        if let Some(ref t) = self.to {
+            s.append(t);
+        } else {
+            s.append(&vec![]);
+        }
+        s.append(&self.value);
+        s.append(&self.data);
+        // Access lists are not used, so an empty list is encoded.
+        s.begin_list(0);
+    }
 }
 
 fn to_ecdsa(sig: Signature, chain_id: u8) -> EcdsaSig {
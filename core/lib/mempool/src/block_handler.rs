@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 
+use zksync_config::configs::chain::TransactionsOrdering;
 use zksync_types::{
     mempool::SignedTxVariant,
     tx::{error::TxAddError, TxHash},
-    PriorityOp,
+    AccountId, PriorityOp,
 };
 
 use crate::state::MempoolState;
@@ -13,6 +18,9 @@ use crate::state::MempoolState;
 pub struct ProposedBlock {
     pub priority_ops: Vec<PriorityOp>,
     pub txs: Vec<SignedTxVariant>,
+    /// Whether the deadline of the oldest unprocessed priority operation is approaching.
+    /// Blocks with such operations have to be sealed and processed on L1 as soon as possible.
+    pub priority_op_deadline_approaching: bool,
 }
 
 impl ProposedBlock {
@@ -37,19 +45,210 @@ pub struct GetBlockRequest {
     pub response_sender: oneshot::Sender<ProposedBlock>,
 }
 
+/// Amount of the transactions of the same account awaiting in the mempool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountPendingTxs {
+    pub account_id: AccountId,
+    pub txs: usize,
+}
+
+/// Contents of the mempool transactions queue at the moment of the request.
+///
+/// L2 transactions are counted one by one, i.e. every transaction of a batch is counted separately.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MempoolSnapshot {
+    /// Timestamp the readiness of the transactions is checked against.
+    pub timestamp: u64,
+    /// Transactions which can be included into the next block.
+    pub ready_txs: usize,
+    /// Transactions which can't be executed yet because of their `valid_from` field.
+    pub pending_txs: usize,
+    /// Priority operations confirmed on L1 and awaiting for execution.
+    pub priority_ops: usize,
+    /// Age of the oldest L2 transaction in the mempool, in seconds.
+    pub oldest_tx_age_secs: Option<u64>,
+    /// Amounts of awaiting transactions per account, the largest ones go first.
+    pub accounts: Vec<AccountPendingTxs>,
+    /// Amount of priority operations the next proposed block would contain.
+    pub next_block_priority_ops: usize,
+    /// Amount of L2 transactions the next proposed block would contain.
+    pub next_block_txs: usize,
+    /// Chunks the next proposed block would take.
+    pub next_block_chunks: usize,
+    /// Maximum amount of chunks in the block.
+    pub max_block_chunks: usize,
+}
+
 #[derive(Debug)]
 pub enum MempoolBlocksRequest {
     /// Get transactions from the mempool.
     GetBlock(GetBlockRequest),
+    /// Get the current contents of the mempool without changing it.
+    GetSnapshot(oneshot::Sender<Result<MempoolSnapshot, TxAddError>>),
+    /// Update the number of the latest Ethereum block, which the deadlines
+    /// of the priority operations are checked against.
+    UpdateEthBlock(u64),
 }
 
 pub(crate) struct MempoolBlocksHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolBlocksRequest>,
     pub max_block_size_chunks: usize,
+    pub transactions_ordering: TransactionsOrdering,
+    /// Number of the first unprocessed priority operation from the last block request.
+    pub last_priority_op_number: Option<u64>,
+    /// Transactions executed in the pending block as of the last block request.
+    pub executed_txs: Vec<TxHash>,
+    /// Amount of Ethereum blocks before the priority operation deadline, starting from which
+    /// the priority operations are proposed without the L2 transactions.
+    pub priority_op_deadline_margin: u64,
+    /// Number of the latest known Ethereum block.
+    pub last_eth_block: Option<u64>,
+    /// Whether the deadline of the oldest unprocessed priority operation was approaching
+    /// as of the last block request.
+    pub priority_op_deadline_approaching: bool,
+}
+
+/// Checks whether the deadline of the priority operation is within the margin
+/// from the latest known Ethereum block.
+fn is_deadline_approaching(
+    deadline_block: Option<u64>,
+    last_eth_block: Option<u64>,
+    margin: u64,
+) -> bool {
+    match (deadline_block, last_eth_block) {
+        (Some(deadline_block), Some(last_eth_block)) => {
+            deadline_block <= last_eth_block.saturating_add(margin)
+        }
+        _ => false,
+    }
+}
+
+/// State of the blocks handler as of the snapshot request.
+///
+/// Snapshot loads the whole mempool, so it's built separately from the blocks handler
+/// in order not to delay the proposal of the blocks.
+struct MempoolSnapshotBuilder {
+    mempool_state: MempoolState,
+    max_block_size_chunks: usize,
+    transactions_ordering: TransactionsOrdering,
+    last_priority_op_number: Option<u64>,
+    executed_txs: Vec<TxHash>,
+    priority_op_deadline_margin: u64,
+    last_eth_block: Option<u64>,
+}
+
+impl MempoolSnapshotBuilder {
+    /// Builds the snapshot of the mempool and simulates the proposal of the next block on it.
+    ///
+    /// Since the state keeper is not involved, the transactions executed in the pending block
+    /// and the number of the next priority operation are taken from the last block request.
+    async fn build(self) -> Result<MempoolSnapshot, TxAddError> {
+        let start = std::time::Instant::now();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Incorrect system time")
+            .as_secs();
+
+        let mut tx_queue = self
+            .mempool_state
+            .get_transaction_queue(&self.executed_txs, self.transactions_ordering)
+            .await?;
+
+        let mut ready_txs = 0;
+        let mut pending_txs = 0;
+        let mut oldest_tx_age = None;
+        let mut accounts: HashMap<AccountId, usize> = HashMap::new();
+        for (tx, is_ready) in tx_queue.l2_transactions(timestamp) {
+            if is_ready {
+                ready_txs += 1;
+            } else {
+                pending_txs += 1;
+            }
+            oldest_tx_age = oldest_tx_age.max(Some(tx.elapsed().as_secs()));
+            if let Ok(account_id) = tx.tx.account_id() {
+                *accounts.entry(account_id).or_default() += 1;
+            }
+        }
+        let mut accounts: Vec<_> = accounts
+            .into_iter()
+            .map(|(account_id, txs)| AccountPendingTxs { account_id, txs })
+            .collect();
+        accounts.sort_unstable_by(|a, b| b.txs.cmp(&a.txs).then(a.account_id.cmp(&b.account_id)));
+
+        let priority_ops_count = tx_queue.priority_ops_count();
+        // Operations from the last proposed block may be already removed from the queue,
+        // so the first one in the queue is the next to be processed in this case.
+        let current_unprocessed_priority_op = self
+            .last_priority_op_number
+            .max(tx_queue.first_priority_op_serial_id())
+            .unwrap_or_default();
+        let priority_op_deadline_approaching = is_deadline_approaching(
+            tx_queue.priority_op_deadline(current_unprocessed_priority_op),
+            self.last_eth_block,
+            self.priority_op_deadline_margin,
+        );
+        let (txs, priority_ops, chunks_left) = tx_queue
+            .select_transactions(
+                self.max_block_size_chunks,
+                current_unprocessed_priority_op,
+                timestamp,
+                &self.mempool_state,
+                priority_op_deadline_approaching,
+            )
+            .await?;
+
+        metrics::histogram!("mempool.snapshot", start.elapsed());
+        Ok(MempoolSnapshot {
+            timestamp,
+            ready_txs,
+            pending_txs,
+            priority_ops: priority_ops_count,
+            oldest_tx_age_secs: oldest_tx_age,
+            accounts,
+            next_block_priority_ops: priority_ops.len(),
+            next_block_txs: txs.iter().map(|tx| tx.hashes().len()).sum(),
+            next_block_chunks: self.max_block_size_chunks - chunks_left,
+            max_block_chunks: self.max_block_size_chunks,
+        })
+    }
 }
 
 impl MempoolBlocksHandler {
+    /// Updates the tracked deadline of the oldest unprocessed priority operation
+    /// and reports whether it's approaching.
+    fn track_priority_op_deadline(&mut self, deadline_block: Option<u64>) -> bool {
+        let deadline_approaching = is_deadline_approaching(
+            deadline_block,
+            self.last_eth_block,
+            self.priority_op_deadline_margin,
+        );
+        if let (Some(deadline_block), Some(last_eth_block)) = (deadline_block, self.last_eth_block)
+        {
+            metrics::gauge!(
+                "mempool.priority_op_deadline_blocks_left",
+                deadline_block.saturating_sub(last_eth_block) as f64
+            );
+        }
+
+        if deadline_approaching != self.priority_op_deadline_approaching {
+            if deadline_approaching {
+                vlog::warn!(
+                    "Deadline of the priority operations is approaching (deadline block: {:?}, last Ethereum block: {:?}), \
+                     proposing the priority operations only",
+                    deadline_block,
+                    self.last_eth_block
+                );
+            } else {
+                vlog::info!(
+                    "Priority operations backlog is processed, proposing the L2 transactions again"
+                );
+            }
+        }
+        self.priority_op_deadline_approaching = deadline_approaching;
+        deadline_approaching
+    }
+
     async fn propose_new_block(
         &mut self,
         current_unprocessed_priority_op: u64,
@@ -61,17 +260,24 @@ impl MempoolBlocksHandler {
         // will be empty unless the server is restarted after reverting blocks.
         let mut tx_queue = self
             .mempool_state
-            .get_transaction_queue(executed_txs)
+            .get_transaction_queue(executed_txs, self.transactions_ordering)
             .await?;
 
+        let priority_op_deadline_approaching = self.track_priority_op_deadline(
+            tx_queue.priority_op_deadline(current_unprocessed_priority_op),
+        );
+
         let (txs, priority_ops, chunks_left) = tx_queue
             .select_transactions(
                 self.max_block_size_chunks,
                 current_unprocessed_priority_op,
                 block_timestamp,
                 &self.mempool_state,
+                priority_op_deadline_approaching,
             )
             .await?;
+        // Transactions may be replaced or cancelled while the block is being proposed.
+        let txs = self.mempool_state.mark_txs_proposed(txs).await?;
 
         if !priority_ops.is_empty() || !txs.is_empty() {
             vlog::debug!(
@@ -104,7 +310,23 @@ impl MempoolBlocksHandler {
                 metrics::histogram!("process_tx", tx.elapsed(), &labels);
             }
         }
-        Ok(ProposedBlock { priority_ops, txs })
+        Ok(ProposedBlock {
+            priority_ops,
+            txs,
+            priority_op_deadline_approaching,
+        })
+    }
+
+    fn snapshot_builder(&self) -> MempoolSnapshotBuilder {
+        MempoolSnapshotBuilder {
+            mempool_state: self.mempool_state.clone(),
+            max_block_size_chunks: self.max_block_size_chunks,
+            transactions_ordering: self.transactions_ordering,
+            last_priority_op_number: self.last_priority_op_number,
+            executed_txs: self.executed_txs.clone(),
+            priority_op_deadline_margin: self.priority_op_deadline_margin,
+            last_eth_block: self.last_eth_block,
+        }
     }
 
     pub async fn run(mut self) {
@@ -113,6 +335,7 @@ impl MempoolBlocksHandler {
         // Remove any possible duplicates of already executed transactions
         // from the database.
         self.mempool_state.collect_garbage().await;
+        self.mempool_state.reset_proposed_txs().await;
         while let Some(request) = self.requests.next().await {
             match request {
                 MempoolBlocksRequest::GetBlock(block) => {
@@ -131,6 +354,21 @@ impl MempoolBlocksHandler {
                         .response_sender
                         .send(proposed_block)
                         .expect("Mempool request receiver dropped");
+
+                    // Remember the state of the pending block for the snapshots.
+                    self.last_priority_op_number = Some(block.last_priority_op_number);
+                    self.executed_txs = block.executed_txs;
+                }
+                MempoolBlocksRequest::GetSnapshot(response_sender) => {
+                    let snapshot_builder = self.snapshot_builder();
+                    tokio::spawn(async move {
+                        let snapshot = snapshot_builder.build().await;
+                        // Snapshot is requested by the API, which may have already timed out.
+                        response_sender.send(snapshot).unwrap_or_default();
+                    });
+                }
+                MempoolBlocksRequest::UpdateEthBlock(block_number) => {
+                    self.last_eth_block = Some(block_number);
                 }
             }
         }
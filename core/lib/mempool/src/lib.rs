@@ -16,11 +16,14 @@ use futures::channel::mpsc;
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::chain::{Mempool as MempoolConfig, TransactionsOrdering};
 use zksync_storage::ConnectionPool;
 
 // Local uses
 use crate::block_handler::MempoolBlocksHandler;
-pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
+pub use crate::block_handler::{
+    AccountPendingTxs, GetBlockRequest, MempoolBlocksRequest, MempoolSnapshot, ProposedBlock,
+};
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
 use crate::state::MempoolState;
 pub use crate::transactions_handler::MempoolTransactionRequest;
@@ -38,6 +41,7 @@ pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    config: MempoolConfig,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool.clone());
     let max_block_size_chunks = *block_chunk_sizes
@@ -49,6 +53,7 @@ pub fn run_mempool_tx_handler(
         mempool_state,
         requests: tx_requests,
         max_block_size_chunks,
+        config,
     };
     tokio::spawn(handler.run())
 }
@@ -58,6 +63,8 @@ pub fn run_mempool_block_handler(
     db_pool: ConnectionPool,
     block_requests: mpsc::Receiver<MempoolBlocksRequest>,
     block_chunk_sizes: Vec<usize>,
+    transactions_ordering: TransactionsOrdering,
+    priority_op_deadline_margin: u64,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool);
     let max_block_size_chunks = *block_chunk_sizes
@@ -69,6 +76,12 @@ pub fn run_mempool_block_handler(
         mempool_state,
         requests: block_requests,
         max_block_size_chunks,
+        transactions_ordering,
+        last_priority_op_number: None,
+        executed_txs: Vec::new(),
+        priority_op_deadline_margin,
+        last_eth_block: None,
+        priority_op_deadline_approaching: false,
     };
 
     tokio::spawn(blocks_handler.run())
@@ -1,14 +1,17 @@
 use crate::MempoolState;
+use num::{rational::Ratio, BigUint, Zero};
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use zksync_config::configs::chain::TransactionsOrdering;
 use zksync_types::mempool::SignedTxVariant;
 use zksync_types::tx::error::TxAddError;
-use zksync_types::PriorityOp;
+use zksync_types::{AccountId, PriorityOp, SignedZkSyncTx};
 
 #[derive(Debug, Clone)]
 struct MempoolPendingTransaction {
     valid_from: u64,
     tx: SignedTxVariant,
+    fee_per_chunk: Ratio<BigUint>,
 }
 
 impl Eq for MempoolPendingTransaction {}
@@ -35,10 +38,149 @@ impl PartialOrd for MempoolPendingTransaction {
     }
 }
 
+/// Ready transaction along with the fee it pays per chunk, used for the fee-based ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PrioritizedTransaction {
+    fee_per_chunk: Ratio<BigUint>,
+    /// Position of the transaction in the order the transactions became ready.
+    position: usize,
+}
+
+impl Ord for PrioritizedTransaction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The most paying transaction goes first, and in case of equal fees
+        // we prefer the one which became ready earlier.
+        self.fee_per_chunk
+            .cmp(&other.fee_per_chunk)
+            .then_with(|| other.position.cmp(&self.position))
+    }
+}
+
+impl PartialOrd for PrioritizedTransaction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ready transaction along with the accounts whose nonces it uses.
+#[derive(Debug, Clone)]
+struct ReadyTransaction {
+    tx: SignedTxVariant,
+    fee_per_chunk: Ratio<BigUint>,
+    accounts: Vec<AccountId>,
+}
+
+/// Transactions ready for execution, kept in the order they are proposed for the block.
+///
+/// With the fee-based ordering the transactions paying the highest fee per chunk go first,
+/// otherwise they go in the order they became ready. Transactions of the same account keep
+/// their relative (nonce) order: a transaction can be taken only after all the preceding ready
+/// transactions of the accounts it belongs to.
+#[derive(Debug, Clone)]
+struct ReadyTransactions {
+    ordering: TransactionsOrdering,
+    /// Not yet taken transactions by their positions in the order they became ready.
+    transactions: HashMap<usize, ReadyTransaction>,
+    /// Positions of the not yet taken transactions of every account, in their original order.
+    account_queues: HashMap<AccountId, VecDeque<usize>>,
+    /// Transactions which are the first ones for all of their accounts, i.e. can be taken.
+    candidates: BinaryHeap<PrioritizedTransaction>,
+    next_position: usize,
+}
+
+impl ReadyTransactions {
+    fn new(ordering: TransactionsOrdering) -> Self {
+        Self {
+            ordering,
+            transactions: HashMap::new(),
+            account_queues: HashMap::new(),
+            candidates: BinaryHeap::new(),
+            next_position: 0,
+        }
+    }
+
+    /// Returns the ready transactions in an arbitrary order.
+    fn iter(&self) -> impl Iterator<Item = &SignedTxVariant> {
+        self.transactions.values().map(|ready| &ready.tx)
+    }
+
+    fn push(&mut self, tx: SignedTxVariant, fee_per_chunk: Ratio<BigUint>) {
+        let position = self.next_position;
+        self.next_position += 1;
+
+        let mut accounts: Vec<_> = tx
+            .get_transactions()
+            .into_iter()
+            .filter_map(|tx| tx.tx.account_id().ok())
+            .collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        for account in &accounts {
+            self.account_queues
+                .entry(*account)
+                .or_default()
+                .push_back(position);
+        }
+
+        // Without the fee-based ordering the transactions are taken by their positions only.
+        let fee_per_chunk = match self.ordering {
+            TransactionsOrdering::FeePerChunk => fee_per_chunk,
+            TransactionsOrdering::Fifo => Ratio::zero(),
+        };
+        self.transactions.insert(
+            position,
+            ReadyTransaction {
+                tx,
+                fee_per_chunk,
+                accounts,
+            },
+        );
+        self.push_candidate(position);
+    }
+
+    fn pop(&mut self) -> Option<SignedTxVariant> {
+        let PrioritizedTransaction { position, .. } = self.candidates.pop()?;
+        let ready = self
+            .transactions
+            .remove(&position)
+            .expect("candidate must be in the queue");
+
+        for account in &ready.accounts {
+            let account_queue = self
+                .account_queues
+                .get_mut(account)
+                .expect("account queue must exist");
+            account_queue.pop_front();
+            match account_queue.front().copied() {
+                Some(next_position) => self.push_candidate(next_position),
+                None => {
+                    self.account_queues.remove(account);
+                }
+            }
+        }
+        Some(ready.tx)
+    }
+
+    /// Makes the transaction a candidate if it's the first one for all of its accounts.
+    fn push_candidate(&mut self, position: usize) {
+        let ready = &self.transactions[&position];
+        let can_be_taken = ready
+            .accounts
+            .iter()
+            .all(|account| self.account_queues[account].front() == Some(&position));
+        if can_be_taken {
+            self.candidates.push(PrioritizedTransaction {
+                fee_per_chunk: ready.fee_per_chunk.clone(),
+                position,
+            });
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolTransactionsQueue {
     /// Transactions ready for execution.
-    ready_l2_transactions: VecDeque<SignedTxVariant>,
+    ready_l2_transactions: ReadyTransactions,
     /// Transactions that are not ready yet because of the `valid_from` field.
     pending_l2_transactions: BinaryHeap<MempoolPendingTransaction>,
 
@@ -48,23 +190,24 @@ pub(crate) struct MempoolTransactionsQueue {
 impl MempoolTransactionsQueue {
     pub(crate) fn new(
         l1_transactions: VecDeque<PriorityOp>,
-        l2_transactions: VecDeque<SignedTxVariant>,
+        l2_transactions: VecDeque<(SignedTxVariant, Ratio<BigUint>)>,
+        ordering: TransactionsOrdering,
     ) -> Self {
         let mut res = Self {
-            ready_l2_transactions: Default::default(),
+            ready_l2_transactions: ReadyTransactions::new(ordering),
             pending_l2_transactions: Default::default(),
             l1_transactions,
         };
         // Due to complexity of json structure in database for transactions it's easier and safer
         // to add even not ready txs to mempool and prepare them before when it's needed.
-        for tx in l2_transactions {
-            res.add_l2_transaction(tx)
+        for (tx, fee_per_chunk) in l2_transactions {
+            res.add_l2_transaction(tx, fee_per_chunk)
         }
         res
     }
 
     fn pop_l2_transactions_front(&mut self) -> Option<SignedTxVariant> {
-        self.ready_l2_transactions.pop_front()
+        self.ready_l2_transactions.pop()
     }
 
     fn pop_front_l1_transactions(&mut self) -> Option<PriorityOp> {
@@ -79,7 +222,7 @@ impl MempoolTransactionsQueue {
         }
     }
 
-    fn add_l2_transaction(&mut self, tx: SignedTxVariant) {
+    fn add_l2_transaction(&mut self, tx: SignedTxVariant, fee_per_chunk: Ratio<BigUint>) {
         self.pending_l2_transactions
             .push(MempoolPendingTransaction {
                 valid_from: tx
@@ -89,53 +232,98 @@ impl MempoolTransactionsQueue {
                     .max()
                     .unwrap_or(0),
                 tx,
+                fee_per_chunk,
             });
     }
 
     fn prepare_new_ready_l2_transactions(&mut self, block_timestamp: u64) {
         // Move some pending transactions to the ready_txs queue
-        let mut ready_pending_l2_operations = {
-            let mut ready_pending_l2_operations = Vec::new();
-
-            while let Some(pending_tx) = self.pending_l2_transactions.peek() {
-                if pending_tx.valid_from <= block_timestamp {
-                    ready_pending_l2_operations.push(pending_tx.tx.clone());
-                    self.pending_l2_transactions.pop();
-                } else {
-                    break;
-                }
+        let mut ready_pending_l2_operations = Vec::new();
+
+        while let Some(pending_tx) = self.pending_l2_transactions.peek() {
+            if pending_tx.valid_from <= block_timestamp {
+                ready_pending_l2_operations.push(
+                    self.pending_l2_transactions
+                        .pop()
+                        .expect("pending transaction must exist"),
+                );
+            } else {
+                break;
             }
+        }
 
-            // Now transactions should be sorted by the nonce (transaction natural order)
-            // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
-            ready_pending_l2_operations.sort_by_key(|tx| match tx {
-                SignedTxVariant::Tx(tx) => tx.tx.nonce(),
-                SignedTxVariant::Batch(batch) => batch
-                    .txs
-                    .last()
-                    .expect("batch must contain at least one transaction")
-                    .tx
-                    .nonce(),
-            });
+        // Now transactions should be sorted by the nonce (transaction natural order)
+        // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
+        ready_pending_l2_operations.sort_by_key(|pending_tx| match &pending_tx.tx {
+            SignedTxVariant::Tx(tx) => tx.tx.nonce(),
+            SignedTxVariant::Batch(batch) => batch
+                .txs
+                .last()
+                .expect("batch must contain at least one transaction")
+                .tx
+                .nonce(),
+        });
+
+        for pending_tx in ready_pending_l2_operations {
+            self.ready_l2_transactions
+                .push(pending_tx.tx, pending_tx.fee_per_chunk);
+        }
+    }
 
-            VecDeque::<SignedTxVariant>::from(ready_pending_l2_operations)
-        };
+    /// Returns the serial ID of the first priority operation awaiting in the queue.
+    pub(crate) fn first_priority_op_serial_id(&self) -> Option<u64> {
+        self.l1_transactions.front().map(|op| op.serial_id)
+    }
+
+    /// Returns the deadline block of the first priority operation which is not processed yet.
+    pub(crate) fn priority_op_deadline(&self, current_unprocessed_priority_op: u64) -> Option<u64> {
+        self.l1_transactions
+            .iter()
+            .find(|op| op.serial_id >= current_unprocessed_priority_op)
+            .map(|op| op.deadline_block)
+    }
+
+    /// Returns the amount of the priority operations awaiting in the queue.
+    pub(crate) fn priority_ops_count(&self) -> usize {
+        self.l1_transactions.len()
+    }
 
-        self.ready_l2_transactions
-            .append(&mut ready_pending_l2_operations);
+    /// Returns all the L2 transactions (including the ones from batches) in the queue,
+    /// along with the flag whether the transaction is ready for execution at the provided timestamp.
+    pub(crate) fn l2_transactions(&self, block_timestamp: u64) -> Vec<(SignedZkSyncTx, bool)> {
+        let ready = self
+            .ready_l2_transactions
+            .iter()
+            .flat_map(|element| element.get_transactions())
+            .map(|tx| (tx, true));
+        let pending = self.pending_l2_transactions.iter().flat_map(|pending_tx| {
+            let is_ready = pending_tx.valid_from <= block_timestamp;
+            pending_tx
+                .tx
+                .get_transactions()
+                .into_iter()
+                .map(move |tx| (tx, is_ready))
+        });
+        ready.chain(pending).collect()
     }
 
-    /// Collect txs depending on desired chunks and execution time
+    /// Collect txs depending on desired chunks and execution time.
+    /// If `priority_ops_only` is set, L2 transactions are selected only if there are no priority operations.
     pub(crate) async fn select_transactions(
         &mut self,
         chunks: usize,
         current_unprocessed_priority_op: u64,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        priority_ops_only: bool,
     ) -> Result<(Vec<SignedTxVariant>, Vec<PriorityOp>, usize), TxAddError> {
         let (chunks_left, priority_ops) =
             self.select_l1_transactions(chunks, current_unprocessed_priority_op);
 
+        if priority_ops_only && !priority_ops.is_empty() {
+            return Ok((Vec::new(), priority_ops, chunks_left));
+        }
+
         let (chunks_left, executed_txs) = self
             .select_l2_transactions(chunks_left, block_timestamp, mempool_state)
             .await?;
@@ -203,13 +391,21 @@ impl MempoolTransactionsQueue {
 mod tests {
     use chrono::Utc;
 
-    use zksync_types::tx::{TimeRange, Transfer, Withdraw};
+    use zksync_types::tx::{TimeRange, Transfer, TxHash, Withdraw};
     use zksync_types::{
         AccountId, Address, Deposit, Nonce, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx,
     };
 
     use super::*;
 
+    /// Returns the hashes of the ready transactions in the order they would be taken.
+    fn ready_hashes(transactions_queue: &MempoolTransactionsQueue) -> Vec<Vec<TxHash>> {
+        let mut ready_l2_transactions = transactions_queue.ready_l2_transactions.clone();
+        std::iter::from_fn(|| ready_l2_transactions.pop())
+            .map(|tx| tx.hashes())
+            .collect()
+    }
+
     fn get_transfer_with_timestamps(valid_from: u64, valid_until: u64) -> SignedTxVariant {
         let transfer = Transfer::new(
             AccountId(4242),
@@ -230,6 +426,26 @@ mod tests {
         })
     }
 
+    fn get_transfer_from_account(account_id: u32, nonce: u32) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        })
+    }
+
     fn get_withdraw() -> SignedTxVariant {
         let withdraw = Withdraw::new(
             AccountId(3),
@@ -252,11 +468,11 @@ mod tests {
 
     #[test]
     fn test_priority_queue() {
-        let mut transactions_queue = MempoolTransactionsQueue {
-            ready_l2_transactions: VecDeque::new(),
-            pending_l2_transactions: BinaryHeap::new(),
-            l1_transactions: Default::default(),
-        };
+        let mut transactions_queue = MempoolTransactionsQueue::new(
+            Default::default(),
+            Default::default(),
+            TransactionsOrdering::Fifo,
+        );
 
         transactions_queue.add_l1_transactions(vec![
             PriorityOp {
@@ -356,11 +572,11 @@ mod tests {
 
     #[test]
     fn test_mempool_transactions_queue() {
-        let mut transactions_queue = MempoolTransactionsQueue {
-            ready_l2_transactions: VecDeque::new(),
-            pending_l2_transactions: BinaryHeap::new(),
-            l1_transactions: Default::default(),
-        };
+        let mut transactions_queue = MempoolTransactionsQueue::new(
+            Default::default(),
+            Default::default(),
+            TransactionsOrdering::Fifo,
+        );
 
         let withdraw0 = get_withdraw();
         let transfer1 = get_transfer_with_timestamps(5, 13);
@@ -368,7 +584,7 @@ mod tests {
 
         // Insert transactions to the mempool transcations queue
         {
-            transactions_queue.add_l2_transaction(withdraw0.clone());
+            transactions_queue.add_l2_transaction(withdraw0.clone(), Ratio::zero());
             assert_eq!(
                 transactions_queue
                     .pending_l2_transactions
@@ -379,29 +595,24 @@ mod tests {
             );
 
             // Some "random" order for trancsactions
-            transactions_queue.add_l2_transaction(transfer2.clone());
-            transactions_queue.add_l2_transaction(transfer1.clone());
+            transactions_queue.add_l2_transaction(transfer2.clone(), Ratio::zero());
+            transactions_queue.add_l2_transaction(transfer1.clone(), Ratio::zero());
         }
 
         // At first we should have only one transaction ready
         {
             transactions_queue.prepare_new_ready_l2_transactions(3);
 
-            assert_eq!(transactions_queue.ready_l2_transactions.len(), 1);
-            assert_eq!(
-                transactions_queue.ready_l2_transactions[0].hashes(),
-                withdraw0.hashes()
-            );
+            assert_eq!(ready_hashes(&transactions_queue), vec![withdraw0.hashes()]);
         }
 
         // One more transaction is ready
         {
             transactions_queue.prepare_new_ready_l2_transactions(9);
 
-            assert_eq!(transactions_queue.ready_l2_transactions.len(), 2);
             assert_eq!(
-                transactions_queue.ready_l2_transactions[1].hashes(),
-                transfer1.hashes()
+                ready_hashes(&transactions_queue),
+                vec![withdraw0.hashes(), transfer1.hashes()]
             );
         }
 
@@ -409,11 +620,134 @@ mod tests {
         {
             transactions_queue.prepare_new_ready_l2_transactions(10);
 
-            assert_eq!(transactions_queue.ready_l2_transactions.len(), 3);
             assert_eq!(
-                transactions_queue.ready_l2_transactions[2].hashes(),
-                transfer2.hashes()
+                ready_hashes(&transactions_queue),
+                vec![withdraw0.hashes(), transfer1.hashes(), transfer2.hashes()]
             );
         }
     }
+
+    /// Checks that the transactions waiting for their `valid_from` are reported as not ready.
+    #[test]
+    fn test_l2_transactions_readiness() {
+        let mut transactions_queue = MempoolTransactionsQueue::new(
+            Default::default(),
+            Default::default(),
+            TransactionsOrdering::Fifo,
+        );
+
+        let withdraw = get_withdraw();
+        let transfer = get_transfer_with_timestamps(10, 15);
+        transactions_queue.add_l2_transaction(withdraw.clone(), Ratio::zero());
+        transactions_queue.add_l2_transaction(transfer.clone(), Ratio::zero());
+
+        let readiness = |block_timestamp| {
+            let mut readiness: Vec<_> = transactions_queue
+                .l2_transactions(block_timestamp)
+                .into_iter()
+                .map(|(tx, is_ready)| (tx.hash(), is_ready))
+                .collect();
+            readiness.sort_by_key(|(_, is_ready)| !is_ready);
+            readiness
+        };
+
+        assert_eq!(
+            readiness(5),
+            vec![(withdraw.hashes()[0], true), (transfer.hashes()[0], false)]
+        );
+        assert!(readiness(10).iter().all(|(_, is_ready)| *is_ready));
+    }
+
+    /// Checks that the ready transactions are ordered by the fee per chunk,
+    /// while the nonce order of the transactions of the same account is preserved.
+    #[test]
+    fn test_order_by_fee_per_chunk() {
+        let fee = |value: u32| Ratio::from(BigUint::from(value));
+        let mut transactions_queue = MempoolTransactionsQueue::new(
+            Default::default(),
+            Default::default(),
+            TransactionsOrdering::FeePerChunk,
+        );
+
+        // Account 1 sends a cheap transaction followed by an expensive one,
+        // while account 2 sends a transaction with an average fee.
+        let cheap_tx = get_transfer_from_account(1, 0);
+        let expensive_tx = get_transfer_from_account(1, 1);
+        let average_tx = get_transfer_from_account(2, 0);
+        let premium_tx = get_transfer_from_account(3, 0);
+        transactions_queue.add_l2_transaction(cheap_tx.clone(), fee(1));
+        transactions_queue.add_l2_transaction(expensive_tx.clone(), fee(100));
+        transactions_queue.add_l2_transaction(average_tx.clone(), fee(10));
+        transactions_queue.add_l2_transaction(premium_tx.clone(), fee(50));
+        transactions_queue.prepare_new_ready_l2_transactions(0);
+
+        assert_eq!(
+            ready_hashes(&transactions_queue),
+            vec![
+                premium_tx.hashes(),
+                average_tx.hashes(),
+                cheap_tx.hashes(),
+                expensive_tx.hashes(),
+            ]
+        );
+
+        // Transactions which become ready later are put in place according to their fees.
+        let next_tx = get_transfer_from_account(4, 0);
+        transactions_queue.add_l2_transaction(next_tx.clone(), fee(20));
+        transactions_queue.prepare_new_ready_l2_transactions(0);
+        assert_eq!(
+            transactions_queue
+                .pop_l2_transactions_front()
+                .unwrap()
+                .hashes(),
+            premium_tx.hashes()
+        );
+        assert_eq!(
+            transactions_queue
+                .pop_l2_transactions_front()
+                .unwrap()
+                .hashes(),
+            next_tx.hashes()
+        );
+        assert_eq!(
+            ready_hashes(&transactions_queue),
+            vec![
+                average_tx.hashes(),
+                cheap_tx.hashes(),
+                expensive_tx.hashes()
+            ]
+        );
+    }
+
+    /// Checks that the deadline of the first unprocessed priority operation is reported.
+    #[test]
+    fn test_priority_op_deadline() {
+        let mut transactions_queue = MempoolTransactionsQueue::new(
+            Default::default(),
+            Default::default(),
+            TransactionsOrdering::Fifo,
+        );
+        assert_eq!(transactions_queue.priority_op_deadline(0), None);
+
+        let priority_op = |serial_id, deadline_block| PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::Deposit(Deposit {
+                from: Default::default(),
+                token: Default::default(),
+                amount: Default::default(),
+                to: Default::default(),
+            }),
+            deadline_block,
+            eth_hash: Default::default(),
+            eth_block: 0,
+            eth_block_index: None,
+        };
+        transactions_queue.add_l1_transactions(vec![priority_op(1, 100), priority_op(2, 110)]);
+
+        assert_eq!(transactions_queue.priority_op_deadline(0), Some(100));
+        assert_eq!(transactions_queue.priority_op_deadline(1), Some(100));
+        // The first operation is already processed.
+        assert_eq!(transactions_queue.priority_op_deadline(2), Some(110));
+        assert_eq!(transactions_queue.priority_op_deadline(3), None);
+    }
 }
@@ -1,11 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+use num::{rational::Ratio, BigUint, Zero};
+
+use zksync_config::configs::chain::TransactionsOrdering;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    Address, TokenLike, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
 use crate::MempoolTransactionsQueue;
@@ -98,6 +101,73 @@ impl MempoolState {
         }
     }
 
+    /// Calculates the fee paid by the transaction (or by all the transactions of the batch)
+    /// in USD per chunk it occupies in the block.
+    ///
+    /// Prices are taken from the tokens schema, fees paid in tokens without a known price are
+    /// considered to be zero. The fee is calculated once, when the transaction is added to the mempool,
+    /// and is used to order the ready transactions in the queue.
+    pub async fn fee_per_chunk(
+        &self,
+        element: &SignedTxVariant,
+    ) -> Result<Ratio<BigUint>, TxAddError> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let mut existing_accounts = HashSet::new();
+        // Price of the smallest token unit, by the fee token.
+        let mut unit_prices: HashMap<TokenLike, Option<Ratio<BigUint>>> = HashMap::new();
+
+        let mut chunks = 0;
+        let mut fee_in_usd = Ratio::zero();
+        for tx in element.get_transactions() {
+            chunks += self
+                .chunks_for_tx_with_cache(&tx.tx, &mut storage, &mut existing_accounts)
+                .await?;
+
+            let (token, fee) = match tx.tx.get_fee_info() {
+                Some((_, token, _, fee)) => (token, fee),
+                None => continue,
+            };
+            if !unit_prices.contains_key(&token) {
+                let unit_price = Self::token_unit_price(&mut storage, token.clone()).await?;
+                unit_prices.insert(token.clone(), unit_price);
+            }
+            if let Some(unit_price) = &unit_prices[&token] {
+                fee_in_usd += unit_price * &Ratio::from(fee);
+            }
+        }
+
+        metrics::histogram!("mempool_state.fee_per_chunk", start.elapsed());
+        Ok(fee_in_usd / BigUint::from(chunks.max(1)))
+    }
+
+    /// Returns the price of the smallest unit of the token in USD, if it's known.
+    async fn token_unit_price(
+        storage: &mut StorageProcessor<'_>,
+        token: TokenLike,
+    ) -> Result<Option<Ratio<BigUint>>, TxAddError> {
+        let mut tokens_schema = storage.tokens_schema();
+        let token = match tokens_schema
+            .get_token(token)
+            .await
+            .map_err(|_| TxAddError::DbError)?
+        {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        let price = tokens_schema
+            .get_historical_ticker_price(token.id)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let unit_multiplier = BigUint::from(10u32).pow(u32::from(token.decimals));
+        Ok(price.map(|price| price.usd_price / unit_multiplier))
+    }
+
     pub async fn collect_garbage(&self) {
         let mut storage = self.db_pool.access_storage().await.expect("Db error");
         // Remove any possible duplicates of already executed transactions
@@ -110,6 +180,47 @@ impl MempoolState {
             .expect("Db error");
     }
 
+    /// Makes the transactions proposed before the restart available for replacement and cancellation again.
+    pub async fn reset_proposed_txs(&self) {
+        let mut storage = self.db_pool.access_storage().await.expect("Db error");
+        storage
+            .chain()
+            .mempool_schema()
+            .reset_proposed_txs()
+            .await
+            .expect("Db error");
+    }
+
+    /// Marks the transactions as proposed for the pending block, so they can't be replaced
+    /// or cancelled anymore. Returns only the transactions (or the whole batches) which are
+    /// still in the mempool, i.e. weren't replaced or cancelled before being proposed.
+    pub async fn mark_txs_proposed(
+        &self,
+        txs: Vec<SignedTxVariant>,
+    ) -> Result<Vec<SignedTxVariant>, TxAddError> {
+        if txs.is_empty() {
+            return Ok(txs);
+        }
+        let hashes: Vec<_> = txs.iter().flat_map(|tx| tx.hashes()).collect();
+        let marked: HashSet<_> = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .chain()
+            .mempool_schema()
+            .mark_txs_proposed(&hashes)
+            .await
+            .map_err(|_| TxAddError::DbError)?
+            .into_iter()
+            .collect();
+
+        Ok(txs
+            .into_iter()
+            .filter(|tx| tx.hashes().iter().all(|hash| marked.contains(hash)))
+            .collect())
+    }
+
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
@@ -117,6 +228,7 @@ impl MempoolState {
     pub async fn get_transaction_queue(
         &self,
         executed_txs: &[TxHash],
+        ordering: TransactionsOrdering,
     ) -> Result<MempoolTransactionsQueue, TxAddError> {
         let mut storage = self
             .db_pool
@@ -144,7 +256,7 @@ impl MempoolState {
             .await
             .map_err(|_| TxAddError::DbError)?;
 
-        let transactions_queue = MempoolTransactionsQueue::new(priority_ops, mempool_txs);
+        let transactions_queue = MempoolTransactionsQueue::new(priority_ops, mempool_txs, ordering);
 
         Ok(transactions_queue)
     }
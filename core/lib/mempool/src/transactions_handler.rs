@@ -1,11 +1,14 @@
+use std::collections::HashMap;
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 
-use zksync_storage::ConnectionPool;
+use zksync_config::configs::chain::Mempool as MempoolConfig;
+use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
-    mempool::SignedTxsBatch,
-    tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    mempool::{SignedTxVariant, SignedTxsBatch},
+    tx::{error::TxAddError, TxEthSignature, TxHash},
+    AccountId, PriorityOp, SerialId, SignedZkSyncTx,
 };
 
 use crate::state::MempoolState;
@@ -14,8 +17,13 @@ use crate::state::MempoolState;
 pub enum MempoolTransactionRequest {
     /// Add new transaction to mempool, transaction should be previously checked
     /// for correctness (including its Ethereum and ZKSync signatures).
+    /// The second field is the IP address of the submitter, if known.
     /// oneshot is used to receive tx add result.
-    NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
+    NewTx(
+        Box<SignedZkSyncTx>,
+        Option<String>,
+        oneshot::Sender<Result<(), TxAddError>>,
+    ),
 
     /// Add new priority ops, confirmed or not
     NewPriorityOps(
@@ -23,6 +31,13 @@ pub enum MempoolTransactionRequest {
         bool,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Remove the priority ops which were dropped from L1 by the chain reorganization.
+    /// oneshot is used to receive the serial IDs of the operations that were already
+    /// executed and thus couldn't be removed.
+    RemovePriorityOps(
+        Vec<SerialId>,
+        oneshot::Sender<Result<Vec<SerialId>, TxAddError>>,
+    ),
     /// Add a new batch of transactions to the mempool. All transactions in batch must
     /// be either executed successfully, or otherwise fail all together.
     /// Invariants for each individual transaction in the batch are the same as in
@@ -30,8 +45,17 @@ pub enum MempoolTransactionRequest {
     NewTxsBatch(
         Vec<SignedZkSyncTx>,
         Vec<TxEthSignature>,
+        Option<String>,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Remove the transaction (or the whole batch it belongs to) from the mempool.
+    /// The request should be previously authorized by the transaction account.
+    /// oneshot is used to receive the hashes of the cancelled transactions,
+    /// or `None` if the transaction is not in the mempool.
+    CancelTx(
+        TxHash,
+        oneshot::Sender<Result<Option<Vec<TxHash>>, TxAddError>>,
+    ),
 }
 
 pub(crate) struct MempoolTransactionsHandler {
@@ -39,10 +63,187 @@ pub(crate) struct MempoolTransactionsHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolTransactionRequest>,
     pub max_block_size_chunks: usize,
+    pub config: MempoolConfig,
 }
 
 impl MempoolTransactionsHandler {
-    async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
+    /// Starts the database transaction holding the locks of the pending transactions of the
+    /// transactions accounts and of the submitter IP address, so that the limits are checked and
+    /// the new transactions are inserted atomically even if there are several mempool handlers running.
+    async fn lock_pending_txs<'a>(
+        storage: &'a mut StorageProcessor<'_>,
+        txs: &[SignedZkSyncTx],
+        submitter_ip: Option<&str>,
+    ) -> Result<StorageProcessor<'a>, TxAddError> {
+        let account_ids = txs
+            .iter()
+            // Close operation does not exist so we will never met this error
+            .map(|tx| tx.account_id().map_err(|_| TxAddError::Other))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .lock_pending_txs(&account_ids, submitter_ip)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        Ok(transaction)
+    }
+
+    /// Checks that adding the transactions to the mempool won't exceed the limits
+    /// of pending transactions per account and per submitter IP address.
+    async fn check_pending_txs_limits(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        txs: &[SignedZkSyncTx],
+        submitter_ip: Option<&str>,
+    ) -> Result<(), TxAddError> {
+        let mut new_txs_per_account: HashMap<AccountId, u32> = HashMap::new();
+        for tx in txs {
+            // Close operation does not exist so we will never met this error
+            let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+            *new_txs_per_account.entry(account_id).or_default() += 1;
+        }
+
+        for (account_id, new_txs) in new_txs_per_account {
+            let pending_txs = storage
+                .chain()
+                .mempool_schema()
+                .get_account_pending_txs_count(account_id)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            if pending_txs + new_txs > self.config.max_pending_txs_per_account {
+                return Err(TxAddError::PendingTxsLimitExceeded);
+            }
+        }
+
+        if let Some(ip) = submitter_ip {
+            let pending_txs = storage
+                .chain()
+                .mempool_schema()
+                .get_ip_pending_txs_count(ip)
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            if pending_txs + txs.len() as u32 > self.config.max_pending_txs_per_ip {
+                return Err(TxAddError::PendingTxsLimitExceeded);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the new transaction pays enough to replace the queued one with the same nonce:
+    /// the fee must be paid in the same token and exceed the old one by the configured percentage.
+    fn check_replacement_fee(
+        &self,
+        queued_tx: &SignedZkSyncTx,
+        new_tx: &SignedZkSyncTx,
+    ) -> Result<(), TxAddError> {
+        let (queued_fee, new_fee) = match (queued_tx.get_fee_info(), new_tx.get_fee_info()) {
+            (Some((_, queued_token, _, queued_fee)), Some((_, new_token, _, new_fee)))
+                if queued_token == new_token =>
+            {
+                (queued_fee, new_fee)
+            }
+            _ => return Err(TxAddError::ReplacementFeeTooLow),
+        };
+
+        let min_fee = &queued_fee * (100 + self.config.replacement_fee_bump_percent) / 100u32;
+        if new_fee <= queued_fee || new_fee < min_fee {
+            return Err(TxAddError::ReplacementFeeTooLow);
+        }
+        Ok(())
+    }
+
+    /// Replaces the queued transaction with the same account and nonce, if there is one.
+    /// Returns `false` if there is nothing to replace, so the transaction should be inserted as usual.
+    /// Transactions which are already proposed for the pending block or belong to some batch
+    /// can't be replaced.
+    async fn try_replace_queued_tx(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        tx: &SignedZkSyncTx,
+        submitter_ip: Option<&str>,
+    ) -> Result<bool, TxAddError> {
+        // Close operation does not exist so we will never met this error
+        let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+        let queued_tx = storage
+            .chain()
+            .mempool_schema()
+            .get_queued_tx_by_nonce(account_id, tx.nonce())
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let queued_tx = match queued_tx {
+            Some(queued_tx) => queued_tx,
+            None => return Ok(false),
+        };
+        // Batches must be executed as a whole, so their transactions are never replaced.
+        if queued_tx.in_batch {
+            return Err(TxAddError::BatchTxReplacement);
+        }
+        if queued_tx.proposed {
+            return Err(TxAddError::ReplacedTxAlreadyProposed);
+        }
+        let queued_tx = queued_tx.tx;
+
+        self.check_replacement_fee(&queued_tx, tx)?;
+
+        let replaced = storage
+            .chain()
+            .mempool_schema()
+            .replace_tx(queued_tx.hash(), tx, submitter_ip)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        if !replaced {
+            // Queued transaction is executed in the meantime.
+            return Err(TxAddError::NonceMismatch);
+        }
+        vlog::debug!(
+            "Tx {} is replaced by {}",
+            queued_tx.hash().to_string(),
+            tx.hash().to_string()
+        );
+        Ok(true)
+    }
+
+    /// Checks that the batch transactions don't reuse the nonces of the queued transactions,
+    /// since batches can't replace the transactions.
+    async fn check_batch_nonces_not_queued(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        txs: &[SignedZkSyncTx],
+    ) -> Result<(), TxAddError> {
+        for tx in txs {
+            // Close operation does not exist so we will never met this error
+            let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+            let queued_tx = storage
+                .chain()
+                .mempool_schema()
+                .get_queued_tx_by_nonce(account_id, tx.nonce())
+                .await
+                .map_err(|_| TxAddError::DbError)?;
+            if queued_tx.is_some() {
+                return Err(TxAddError::BatchTxReplacement);
+            }
+        }
+        Ok(())
+    }
+
+    async fn add_tx(
+        &mut self,
+        tx: SignedZkSyncTx,
+        submitter_ip: Option<String>,
+    ) -> Result<(), TxAddError> {
         // Correctness should be checked by `signature_checker`, thus
         // `tx.check_correctness()` is not invoked here.
         let mut storage = self.db_pool.access_storage().await.map_err(|err| {
@@ -63,15 +264,51 @@ impl MempoolTransactionsHandler {
             return Err(TxAddError::NonceMismatch);
         }
 
-        storage
+        let fee_per_chunk = self
+            .mempool_state
+            .fee_per_chunk(&SignedTxVariant::from(tx.clone()))
+            .await?;
+
+        let mut transaction = Self::lock_pending_txs(
+            &mut storage,
+            std::slice::from_ref(&tx),
+            submitter_ip.as_deref(),
+        )
+        .await?;
+        let replaced = self
+            .try_replace_queued_tx(&mut transaction, &tx, submitter_ip.as_deref())
+            .await?;
+        if !replaced {
+            self.check_pending_txs_limits(
+                &mut transaction,
+                std::slice::from_ref(&tx),
+                submitter_ip.as_deref(),
+            )
+            .await?;
+
+            transaction
+                .chain()
+                .mempool_schema()
+                .insert_tx(&tx, submitter_ip.as_deref())
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        transaction
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .set_fee_per_chunk(&[tx.hash()], &fee_per_chunk)
             .await
             .map_err(|err| {
                 vlog::error!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        transaction.commit().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         let labels = vec![
             ("stage", "mempool".to_string()),
@@ -131,10 +368,43 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    async fn remove_priority_ops(
+        &mut self,
+        serial_ids: Vec<SerialId>,
+    ) -> Result<Vec<SerialId>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let last_processed_priority_op = storage
+            .chain()
+            .operations_schema()
+            .get_max_priority_op_serial_id()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let (executed, pending): (Vec<_>, Vec<_>) = serial_ids.into_iter().partition(|serial_id| {
+            last_processed_priority_op.map_or(false, |last| *serial_id <= last)
+        });
+        if !pending.is_empty() {
+            storage
+                .chain()
+                .mempool_schema()
+                .remove_priority_ops_from_mempool(&pending)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        Ok(executed)
+    }
+
     async fn add_batch(
         &mut self,
         txs: Vec<SignedZkSyncTx>,
         eth_signatures: Vec<TxEthSignature>,
+        submitter_ip: Option<String>,
     ) -> Result<(), TxAddError> {
         let batch = SignedTxsBatch {
             txs: txs.clone(),
@@ -167,6 +437,17 @@ impl MempoolTransactionsHandler {
         if self.mempool_state.chunks_for_batch(&batch).await? > self.max_block_size_chunks {
             return Err(TxAddError::BatchTooBig);
         }
+        let fee_per_chunk = self
+            .mempool_state
+            .fee_per_chunk(&SignedTxVariant::Batch(batch.clone()))
+            .await?;
+
+        let mut transaction =
+            Self::lock_pending_txs(&mut storage, &batch.txs, submitter_ip.as_deref()).await?;
+        self.check_pending_txs_limits(&mut transaction, &batch.txs, submitter_ip.as_deref())
+            .await?;
+        self.check_batch_nonces_not_queued(&mut transaction, &batch.txs)
+            .await?;
 
         for tx in &batch.txs {
             let labels = vec![
@@ -178,35 +459,79 @@ impl MempoolTransactionsHandler {
             metrics::histogram!("process_tx", tx.elapsed(), &labels);
         }
 
-        storage
+        transaction
+            .chain()
+            .mempool_schema()
+            .insert_batch(&batch.txs, eth_signatures, submitter_ip.as_deref())
+            .await
+            .map_err(|err| {
+                vlog::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        let tx_hashes: Vec<_> = batch.txs.iter().map(|tx| tx.hash()).collect();
+        transaction
             .chain()
             .mempool_schema()
-            .insert_batch(&batch.txs, eth_signatures)
+            .set_fee_per_chunk(&tx_hashes, &fee_per_chunk)
             .await
             .map_err(|err| {
                 vlog::warn!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        transaction.commit().await.map_err(|err| {
+            vlog::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         Ok(())
     }
 
+    async fn cancel_tx(&mut self, tx_hash: TxHash) -> Result<Option<Vec<TxHash>>, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+
+        let cancelled_txs = storage
+            .chain()
+            .mempool_schema()
+            .cancel_tx(tx_hash)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+
+        if let Some(cancelled_txs) = &cancelled_txs {
+            metrics::counter!("mempool.cancelled_txs", cancelled_txs.len() as u64);
+        }
+        Ok(cancelled_txs)
+    }
+
     pub async fn run(mut self) {
         vlog::info!("Transaction mempool handler is running");
         while let Some(request) = self.requests.next().await {
             match request {
-                MempoolTransactionRequest::NewTx(tx, resp) => {
-                    let tx_add_result = self.add_tx(*tx).await;
+                MempoolTransactionRequest::NewTx(tx, submitter_ip, resp) => {
+                    let tx_add_result = self.add_tx(*tx, submitter_ip).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
-                MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
-                    let tx_add_result = self.add_batch(txs, eth_signatures).await;
+                MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, submitter_ip, resp) => {
+                    let tx_add_result = self.add_batch(txs, eth_signatures, submitter_ip).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewPriorityOps(ops, confirmed, resp) => {
                     let tx_add_result = self.add_priority_ops(ops, confirmed).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
+                MempoolTransactionRequest::RemovePriorityOps(serial_ids, resp) => {
+                    let remove_result = self.remove_priority_ops(serial_ids).await;
+                    resp.send(remove_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::CancelTx(tx_hash, resp) => {
+                    let cancel_result = self.cancel_tx(tx_hash).await;
+                    resp.send(cancel_result).unwrap_or_default();
+                }
             }
         }
     }
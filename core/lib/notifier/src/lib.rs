@@ -3,6 +3,7 @@ use reqwest::Url;
 use zksync_types::tokens::Token;
 
 mod matter_most_notifier;
+pub mod webhooks;
 
 pub struct Notifier {
     matter_most_notifier: MatterMostNotifier,
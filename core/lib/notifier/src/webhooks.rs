@@ -0,0 +1,496 @@
+//! Delivery of the webhook notifications about the account activity.
+//!
+//! The notifier follows the `events` table, creates notifications for the addresses
+//! with registered webhooks and stores them in the delivery log. Pending notifications
+//! are then POSTed to the webhooks and retried with exponential backoff until they're
+//! either delivered or run out of attempts.
+
+// Built-in uses
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+// External uses
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use hmac::{Hmac, Mac, NewMac};
+use reqwest::{header::CONTENT_TYPE, redirect, Client, Url};
+use sha2::Sha256;
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_config::WebhooksConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    event::{EventId, ZkSyncEvent},
+    webhooks::{WebhookDelivery, WebhookPayload},
+    Address,
+};
+// Local uses
+
+/// Header with the hex-encoded HMAC-SHA256 of the request body, keyed with the webhook secret.
+pub const SIGNATURE_HEADER: &str = "X-Zksync-Signature";
+/// Header with the unique id of the notification. Notifications may be delivered
+/// more than once, so the receivers should deduplicate them by this id.
+pub const DELIVERY_HEADER: &str = "X-Zksync-Delivery";
+
+/// Name under which the id of the last processed event is stored.
+const CURSOR_NAME: &str = "webhooks";
+
+/// Signs the request body with the webhook secret.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Checks whether the address is reachable from the internet, rather than being the address
+/// of the server itself or of a service in its private network.
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    // 0.0.0.0/8, the "this network" addresses.
+    let is_this_network = octets[0] == 0;
+    // 100.64.0.0/10, the carrier-grade NAT addresses.
+    let is_shared = octets[0] == 100 && octets[1] & 0xc0 == 64;
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || is_this_network
+        || is_shared)
+}
+
+/// Same as [`is_public_ipv4`], but for IPv6. IPv4 addresses embedded
+/// into IPv6 ones are checked as IPv4 addresses.
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first_segment = ip.segments()[0];
+    let is_unique_local = first_segment & 0xfe00 == 0xfc00;
+    let is_link_local = first_segment & 0xffc0 == 0xfe80;
+    if ip.is_loopback() || ip.is_unspecified() || is_unique_local || is_link_local {
+        return false;
+    }
+    match ip.to_ipv4() {
+        Some(ip) => is_public_ipv4(ip),
+        None => true,
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+/// Host of the webhook url together with the addresses it was resolved to.
+#[derive(Debug, Clone)]
+pub struct ResolvedHost {
+    pub host: String,
+    pub addresses: Vec<SocketAddr>,
+}
+
+/// Checks that the webhook url is an http or https url and its host resolves only to the public
+/// addresses, so that the webhooks can't be used to make requests into the server's network.
+///
+/// The host may be re-pointed after the registration, so the url is checked before every delivery,
+/// and the notification is sent to the returned addresses rather than to the ones resolved anew.
+pub async fn validate_webhook_url(url: &str) -> anyhow::Result<ResolvedHost> {
+    let url = Url::parse(url).map_err(|_| anyhow::anyhow!("Invalid webhook url"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        anyhow::bail!("Webhook url must be an http or https url");
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("Webhook url must be an http or https url"))?;
+    // IPv6 addresses are enclosed in brackets.
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| anyhow::anyhow!("Webhook host can't be resolved"))?
+        .collect();
+    if addresses.is_empty() {
+        anyhow::bail!("Webhook host can't be resolved");
+    }
+    if !addresses.iter().all(|address| is_public_ip(address.ip())) {
+        anyhow::bail!("Webhook url must point to a public address");
+    }
+    Ok(ResolvedHost {
+        host: host.to_string(),
+        addresses,
+    })
+}
+
+/// Creates the client for delivering notifications to the resolved host. The host is pinned
+/// to the validated address, so that it can't be re-pointed to the private one between
+/// the validation and the request. Redirects aren't followed for the same reason.
+fn delivery_client(config: &WebhooksConfig, resolved: &ResolvedHost) -> anyhow::Result<Client> {
+    let address = resolved
+        .addresses
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Webhook host can't be resolved"))?;
+    let client = Client::builder()
+        .timeout(config.delivery_timeout())
+        .redirect(redirect::Policy::none())
+        .resolve(&resolved.host, *address)
+        .build()?;
+    Ok(client)
+}
+
+/// POSTs the signed notification to the webhook.
+async fn send_notification(client: &Client, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+    let body = serde_json::to_vec(&delivery.payload)?;
+    let response = client
+        .post(&delivery.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign_payload(&delivery.secret, &body))
+        .header(DELIVERY_HEADER, delivery.id)
+        .body(body)
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Webhook responded with {}", status);
+    }
+    Ok(())
+}
+
+/// Returns the time of the next delivery attempt after the given amount of failed ones,
+/// or `None` if the notification ran out of attempts.
+fn next_attempt_at(
+    config: &WebhooksConfig,
+    failed_attempts: u32,
+) -> anyhow::Result<Option<DateTime<Utc>>> {
+    if failed_attempts >= config.max_attempts {
+        return Ok(None);
+    }
+    let backoff = chrono::Duration::from_std(config.retry_backoff(failed_attempts))?;
+    Ok(Some(Utc::now() + backoff))
+}
+
+pub struct WebhookNotifier {
+    pool: ConnectionPool,
+    config: WebhooksConfig,
+}
+
+impl WebhookNotifier {
+    pub fn new(pool: ConnectionPool, config: WebhooksConfig) -> Self {
+        Self { pool, config }
+    }
+
+    pub async fn run(self) {
+        let mut timer = tokio::time::interval(self.config.poll_interval());
+        loop {
+            timer.tick().await;
+            loop {
+                match self.enqueue_notifications().await {
+                    // The page was full, there may be more events to process.
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(err) => {
+                        vlog::error!("Failed to create webhook notifications: {}", err);
+                        break;
+                    }
+                }
+            }
+            if let Err(err) = self.deliver_notifications().await {
+                vlog::error!("Failed to deliver webhook notifications: {}", err);
+            }
+        }
+    }
+
+    /// Creates notifications for the next batch of events and adds them to the delivery log.
+    /// Returns `true` if the batch was full.
+    async fn enqueue_notifications(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage().await?;
+        let cursor = match storage.event_schema().get_sink_cursor(CURSOR_NAME).await? {
+            Some(cursor) => cursor,
+            // Don't notify about the events which happened before the first start.
+            None => storage
+                .event_schema()
+                .get_last_event_id()
+                .await?
+                .unwrap_or(EventId(0)),
+        };
+
+        let events = storage
+            .event_schema()
+            .fetch_new_events_page(cursor, self.config.batch_size)
+            .await?
+            .into_iter()
+            .map(ZkSyncEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let last_event_id = match events.last() {
+            Some(event) => event.id,
+            None => return Ok(false),
+        };
+
+        let payloads: Vec<WebhookPayload> =
+            events.iter().flat_map(WebhookPayload::from_event).collect();
+        let addresses: Vec<Address> = payloads
+            .iter()
+            .map(|payload| payload.address)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let webhooks = storage
+            .webhooks_schema()
+            .load_webhooks_for_addresses(&addresses)
+            .await?;
+        let deliveries: Vec<_> = payloads
+            .into_iter()
+            .flat_map(|payload| {
+                webhooks
+                    .iter()
+                    .filter(|webhook| webhook.address == payload.address)
+                    .map(|webhook| (webhook.id, payload.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .webhooks_schema()
+            .enqueue_deliveries(&deliveries)
+            .await?;
+        transaction
+            .event_schema()
+            .update_sink_cursor(CURSOR_NAME, last_event_id)
+            .await?;
+        transaction.commit().await?;
+
+        metrics::counter!("webhooks.enqueued", deliveries.len() as u64);
+        Ok(events.len() == self.config.batch_size as usize)
+    }
+
+    /// Sends the notifications which are due to be delivered and records the outcome.
+    async fn deliver_notifications(&self) -> anyhow::Result<()> {
+        let deliveries = self
+            .pool
+            .access_storage()
+            .await?
+            .webhooks_schema()
+            .load_due_deliveries(self.config.batch_size)
+            .await?;
+        let results = join_all(deliveries.iter().map(|delivery| self.deliver(delivery))).await;
+
+        let mut storage = self.pool.access_storage().await?;
+        for (delivery, result) in deliveries.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    storage
+                        .webhooks_schema()
+                        .mark_delivered(delivery.id)
+                        .await?;
+                    metrics::increment_counter!("webhooks.delivered");
+                }
+                Err(err) => {
+                    let next_attempt_at = next_attempt_at(&self.config, delivery.attempts + 1)?;
+                    if next_attempt_at.is_none() {
+                        vlog::warn!(
+                            "Giving up on delivering notification {} to webhook {}: {}",
+                            delivery.id,
+                            delivery.webhook_id,
+                            err
+                        );
+                        metrics::increment_counter!("webhooks.failed");
+                    }
+                    storage
+                        .webhooks_schema()
+                        .record_failed_attempt(delivery.id, &err.to_string(), next_attempt_at)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver(&self, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+        let resolved = validate_webhook_url(&delivery.url).await?;
+        let client = delivery_client(&self.config, &resolved)?;
+        send_notification(&client, delivery).await
+    }
+}
+
+#[must_use]
+pub fn run_webhook_notifier(pool: ConnectionPool, config: WebhooksConfig) -> JoinHandle<()> {
+    vlog::info!("Starting the webhook notifier");
+    tokio::spawn(WebhookNotifier::new(pool, config).run())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn config() -> WebhooksConfig {
+        WebhooksConfig {
+            enabled: true,
+            max_webhooks_per_address: 10,
+            request_lifetime: 300,
+            poll_interval: 1000,
+            batch_size: 100,
+            delivery_timeout: 10,
+            max_attempts: 3,
+            retry_backoff: 5,
+            max_retry_backoff: 600,
+        }
+    }
+
+    /// Checks whether the whole HTTP request with the body was received.
+    fn is_request_complete(request: &[u8]) -> bool {
+        let request = String::from_utf8_lossy(request).to_lowercase();
+        let headers_end = match request.find("\r\n\r\n") {
+            Some(position) => position + 4,
+            None => return false,
+        };
+        let content_length = request[..headers_end]
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length: "))
+            .and_then(|length| length.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        request.len() >= headers_end + content_length
+    }
+
+    /// Accepts a single request, answers it with the given status and returns the received request.
+    async fn respond_once(listener: &TcpListener, status: &str) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !is_request_complete(&request) {
+            let read = socket.read(&mut buffer).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+        let response = format!(
+            "HTTP/1.1 {}\r\nLocation: http://example.com/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8(request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validate_webhook_url() {
+        const VALID: &[&str] = &[
+            "https://93.184.216.34/hooks/zksync",
+            "http://100.128.0.1",
+            "http://[2606:2800:220:1::]:8080",
+        ];
+        for url in VALID {
+            assert!(validate_webhook_url(url).await.is_ok(), "{}", url);
+        }
+
+        const INVALID: &[&str] = &[
+            "ftp://93.184.216.34",
+            "not a url",
+            "unix:/run/hook.sock",
+            "http://127.0.0.1:8080",
+            "http://localhost:3000",
+            "http://10.0.0.1",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://0.0.0.0",
+            "http://0.1.2.3",
+            "http://100.64.0.1",
+            "http://100.127.255.254",
+            "http://224.0.0.1",
+            "http://239.255.255.250",
+            "http://[::1]",
+            "http://[fd00::1]",
+            "http://[fe80::1]",
+            "http://[::ffff:172.16.0.1]",
+        ];
+        for url in INVALID {
+            assert!(validate_webhook_url(url).await.is_err(), "{}", url);
+        }
+    }
+
+    /// Checks that the notifications are signed, and the failed deliveries
+    /// are retried with the growing delay until they run out of attempts.
+    #[tokio::test]
+    async fn test_delivery_and_retries() -> anyhow::Result<()> {
+        let config = config();
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let delivery = WebhookDelivery {
+            id: 42,
+            webhook_id: 1,
+            url: format!("http://{}/hook", listener.local_addr()?),
+            secret: "secret".to_string(),
+            payload: json!({ "eventType": "deposit" }),
+            attempts: 0,
+        };
+        // The notifier itself never delivers to the local addresses.
+        assert!(validate_webhook_url(&delivery.url).await.is_err());
+        // The host is pinned to the listener, so the request doesn't depend on the DNS.
+        let resolved = ResolvedHost {
+            host: "webhook.test".to_string(),
+            addresses: vec![listener.local_addr()?],
+        };
+        let client = delivery_client(&config, &resolved)?;
+        let delivery = WebhookDelivery {
+            url: format!("http://webhook.test:{}/hook", listener.local_addr()?.port()),
+            ..delivery
+        };
+
+        let (result, request) = tokio::join!(
+            send_notification(&client, &delivery),
+            respond_once(&listener, "200 OK")
+        );
+        result?;
+        let body = serde_json::to_vec(&delivery.payload)?;
+        assert!(request.ends_with(std::str::from_utf8(&body)?));
+        // Header names are case-insensitive.
+        let request = request.to_lowercase();
+        assert!(request.starts_with("post /hook "));
+        assert!(request.contains("host: webhook.test:"));
+        assert!(request.contains(&format!(
+            "{}: {}",
+            SIGNATURE_HEADER.to_lowercase(),
+            sign_payload(&delivery.secret, &body)
+        )));
+        assert!(request.contains(&format!("{}: 42", DELIVERY_HEADER.to_lowercase())));
+
+        // Error responses and redirects are failed attempts.
+        let (result, _) = tokio::join!(
+            send_notification(&client, &delivery),
+            respond_once(&listener, "500 Internal Server Error")
+        );
+        assert!(result.is_err());
+        let (result, _) = tokio::join!(
+            send_notification(&client, &delivery),
+            respond_once(&listener, "302 Found")
+        );
+        assert!(result.is_err());
+
+        // The delay is doubled with every failed attempt.
+        let now = Utc::now();
+        let first_retry = next_attempt_at(&config, 1)?.unwrap();
+        let second_retry = next_attempt_at(&config, 2)?.unwrap();
+        assert!(first_retry >= now + chrono::Duration::seconds(5));
+        assert!(first_retry < now + chrono::Duration::seconds(10));
+        assert!(second_retry >= now + chrono::Duration::seconds(10));
+        assert!(second_retry < now + chrono::Duration::seconds(15));
+        // The notification is given up on after the last attempt.
+        assert_eq!(next_attempt_at(&config, 3)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("key", b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            signature,
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+}
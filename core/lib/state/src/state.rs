@@ -347,7 +347,7 @@ impl ZkSyncState {
         }
     }
 
-    pub(crate) fn get_free_account_id(&self) -> AccountId {
+    pub fn get_free_account_id(&self) -> AccountId {
         self.next_free_id
     }
 
@@ -0,0 +1 @@
+ALTER TABLE eth_operations ADD last_used_priority_fee_per_gas NUMERIC;
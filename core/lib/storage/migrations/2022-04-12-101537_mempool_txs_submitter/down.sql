@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS mempool_txs_submitter_ip_idx;
+DROP INDEX IF EXISTS mempool_txs_account_id_idx;
+
+ALTER TABLE mempool_txs DROP COLUMN IF EXISTS submitter_ip;
+ALTER TABLE mempool_txs DROP COLUMN IF EXISTS account_id;
@@ -0,0 +1,5 @@
+ALTER TABLE mempool_txs ADD COLUMN account_id BIGINT;
+ALTER TABLE mempool_txs ADD COLUMN submitter_ip TEXT;
+
+CREATE INDEX IF NOT EXISTS mempool_txs_account_id_idx ON mempool_txs (account_id);
+CREATE INDEX IF NOT EXISTS mempool_txs_submitter_ip_idx ON mempool_txs (submitter_ip);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS mempool_replaced_txs;
+
+DROP INDEX IF EXISTS mempool_txs_account_id_nonce_idx;
+
+ALTER TABLE mempool_txs DROP COLUMN IF EXISTS nonce;
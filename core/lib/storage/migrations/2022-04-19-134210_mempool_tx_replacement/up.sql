@@ -0,0 +1,13 @@
+ALTER TABLE mempool_txs ADD COLUMN nonce BIGINT;
+
+CREATE INDEX IF NOT EXISTS mempool_txs_account_id_nonce_idx ON mempool_txs (account_id, nonce);
+
+-- Transactions replaced in the mempool by the transactions of the same account with the same nonce.
+CREATE TABLE IF NOT EXISTS mempool_replaced_txs (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    replaced_by bytea NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS mempool_replaced_txs_tx_hash_idx ON mempool_replaced_txs (tx_hash);
@@ -0,0 +1,5 @@
+-- Transactions removed from the mempool on request of the account owner.
+CREATE TABLE IF NOT EXISTS mempool_cancelled_txs (
+    tx_hash bytea PRIMARY KEY,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
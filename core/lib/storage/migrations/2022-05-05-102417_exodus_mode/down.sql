@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS exodus_mode;
+
+-- There is no easy way to remove a value from enum, so the type is re-created without it.
+DELETE FROM events WHERE event_type = 'Exodus';
+
+CREATE TYPE event_type_old AS ENUM ('Account', 'Block', 'Transaction');
+
+ALTER TABLE events
+    ALTER COLUMN event_type TYPE event_type_old
+        USING (event_type::text::event_type_old);
+
+DROP TYPE event_type;
+
+ALTER TYPE event_type_old RENAME TO event_type;
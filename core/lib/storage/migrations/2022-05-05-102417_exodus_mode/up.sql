@@ -0,0 +1,10 @@
+-- Set once the zkSync contract is found to be in the exodus mode, the row is never removed.
+CREATE TABLE IF NOT EXISTS exodus_mode (
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    eth_block BIGINT NOT NULL,
+    first_priority_request_id BIGINT NOT NULL,
+    entered_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT single_exodus_mode CHECK (id)
+);
+
+ALTER TYPE event_type ADD VALUE 'Exodus';
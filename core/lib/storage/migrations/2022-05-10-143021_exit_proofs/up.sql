@@ -0,0 +1,10 @@
+-- Exit proofs generated for the exodus mode. Proofs are bound to the verified block
+-- whose state they were generated for, so that the same proof is never generated twice.
+CREATE TABLE IF NOT EXISTS exit_proofs (
+    account_id BIGINT NOT NULL,
+    token_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    proof_data jsonb NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (account_id, token_id, block_number)
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS events_block_number_idx;
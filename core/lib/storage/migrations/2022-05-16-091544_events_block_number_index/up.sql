@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS events_block_number_idx ON events (block_number);
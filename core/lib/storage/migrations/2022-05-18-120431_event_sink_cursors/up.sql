@@ -0,0 +1,7 @@
+-- Ids of the last events exported by the event listener sinks, used
+-- to resume the export after restart.
+CREATE TABLE IF NOT EXISTS event_sink_cursors (
+    sink_name TEXT PRIMARY KEY,
+    last_event_id BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
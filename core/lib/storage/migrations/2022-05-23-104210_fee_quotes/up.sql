@@ -0,0 +1,12 @@
+-- Fees the server has promised to accept once until the quote expires.
+CREATE TABLE IF NOT EXISTS fee_quotes (
+    id BIGSERIAL PRIMARY KEY,
+    sender bytea NOT NULL,
+    token_id INTEGER NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Set once the quote is used by a transaction.
+    consumed_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX IF NOT EXISTS fee_quotes_expires_at_idx ON fee_quotes (expires_at);
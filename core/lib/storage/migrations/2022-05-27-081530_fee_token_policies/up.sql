@@ -0,0 +1,9 @@
+-- Admin-managed rules for paying fees in the tokens.
+CREATE TABLE IF NOT EXISTS fee_token_policies (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- Explicit 'allow' or 'deny' decision, NULL if the market volume check decides.
+    listing TEXT CHECK (listing IN ('allow', 'deny')),
+    discount_percent SMALLINT NOT NULL DEFAULT 0 CHECK (discount_percent BETWEEN 0 AND 99),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (token_id)
+);
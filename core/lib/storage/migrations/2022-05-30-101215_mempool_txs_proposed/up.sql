@@ -0,0 +1,2 @@
+-- Transactions proposed for the pending block can't be replaced or cancelled anymore.
+ALTER TABLE mempool_txs ADD COLUMN proposed BOOLEAN NOT NULL DEFAULT false;
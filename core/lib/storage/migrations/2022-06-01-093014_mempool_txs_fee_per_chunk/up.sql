@@ -0,0 +1,2 @@
+-- Fee paid by the transaction (or by the whole batch) in USD per chunk, calculated once on insertion.
+ALTER TABLE mempool_txs ADD COLUMN fee_per_chunk NUMERIC NOT NULL DEFAULT 0;
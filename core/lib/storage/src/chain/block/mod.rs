@@ -736,6 +736,42 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(maybe_block_chunks.map(|val| val.chunks_left as usize))
     }
 
+    /// Returns the number of chunks used by the executed operations of the last `count` saved blocks,
+    /// starting from the latest one. Unlike the block size, it doesn't include the padding chunks.
+    pub async fn load_last_blocks_chunks_used(&mut self, count: u32) -> QueryResult<Vec<usize>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let block_numbers = sqlx::query!(
+            "SELECT number FROM blocks
+            ORDER BY number DESC
+            LIMIT $1",
+            i64::from(count)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut chunks_used = Vec::with_capacity(block_numbers.len());
+        for row in block_numbers {
+            let executed_ops = BlockSchema(&mut transaction)
+                .get_block_executed_ops(BlockNumber(row.number as u32))
+                .await?;
+            chunks_used.push(
+                executed_ops
+                    .iter()
+                    .filter_map(ExecutedOperations::get_executed_op)
+                    .map(ZkSyncOp::chunks)
+                    .sum(),
+            );
+        }
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.block.load_last_blocks_chunks_used",
+            start.elapsed()
+        );
+
+        Ok(chunks_used)
+    }
+
     /// Helper method for retrieving pending blocks from the database.
     async fn load_storage_pending_block(&mut self) -> QueryResult<Option<StoragePendingBlock>> {
         let start = Instant::now();
@@ -2,6 +2,7 @@
 use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
 // External imports
 use itertools::Itertools;
+use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_api_types::v02::pagination::PaginationDirection;
 use zksync_api_types::v02::transaction::{
@@ -11,11 +12,14 @@ use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
     PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
 };
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
-use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
+use self::records::{
+    MempoolPriorityOp, MempoolSummary, MempoolTx, QueuedBatchTx, QueuedTx, RevertedBlock,
+};
 use crate::{QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
@@ -24,6 +28,14 @@ use crate::chain::operations::records::{
 
 pub mod records;
 
+/// Precision of the fee per chunk in USD.
+const FEE_PER_CHUNK_PRECISION: usize = 18;
+
+/// Advisory lock namespace of the pending transactions of an account.
+const PENDING_TXS_ACCOUNT_LOCK: i32 = 1;
+/// Advisory lock namespace of the pending transactions from an IP address.
+const PENDING_TXS_IP_LOCK: i32 = 2;
+
 /// Schema for persisting transactions awaiting for the execution.
 ///
 /// This schema holds the transactions that are received by the `mempool` module, but not yet have
@@ -34,14 +46,14 @@ pub mod records;
 pub struct MempoolSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> MempoolSchema<'a, 'c> {
-    /// Loads all transactions stored in the mempool schema.
+    /// Loads all transactions stored in the mempool schema along with the fees they pay per chunk.
     /// We want to exclude txs that have already been processed in memory,
     /// due to asynchronous execution,
     /// these txs may be executed in memory and not yet saved to the database
     pub async fn load_txs(
         &mut self,
         executed_txs: &[TxHash],
-    ) -> QueryResult<VecDeque<SignedTxVariant>> {
+    ) -> QueryResult<VecDeque<(SignedTxVariant, Ratio<BigUint>)>> {
         let start = Instant::now();
         // Load the transactions from mempool along with corresponding batch IDs.
         let excluded_txs: Vec<String> = executed_txs.iter().map(|tx| tx.to_string()).collect();
@@ -77,23 +89,28 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         for (batch_id, group) in grouped_txs.into_iter() {
             if let Some(batch_id) = batch_id {
+                let group: Vec<_> = group.collect();
+                // All the transactions of the batch share the fee per chunk.
+                let fee_per_chunk = big_decimal_to_ratio(&group[0].fee_per_chunk)?;
                 let deserialized_txs = group
+                    .into_iter()
                     .map(SignedZkSyncTx::try_from)
                     .collect::<Result<Vec<SignedZkSyncTx>, serde_json::Error>>()?;
                 let variant = SignedTxVariant::batch(deserialized_txs, batch_id, vec![]);
 
-                txs.push(variant);
+                txs.push((variant, fee_per_chunk));
             } else {
                 for mempool_tx in group {
+                    let fee_per_chunk = big_decimal_to_ratio(&mempool_tx.fee_per_chunk)?;
                     let signed_tx = SignedZkSyncTx::try_from(mempool_tx)?;
                     let variant = SignedTxVariant::Tx(signed_tx);
-                    txs.push(variant);
+                    txs.push((variant, fee_per_chunk));
                 }
             }
         }
 
         // Load signatures for batches.
-        for tx in txs.iter_mut() {
+        for (tx, _) in txs.iter_mut() {
             if let SignedTxVariant::Batch(batch) = tx {
                 let eth_signatures: Vec<TxEthSignature> = sqlx::query!(
                     "SELECT eth_signature FROM txs_batches_signatures
@@ -138,11 +155,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     }
 
     /// Adds a new transactions batch to the mempool schema.
+    /// `submitter_ip` is the IP address the batch was received from, if known.
     /// Returns id of the inserted batch
     pub async fn insert_batch(
         &mut self,
         txs: &[SignedZkSyncTx],
         eth_signatures: Vec<TxEthSignature>,
+        submitter_ip: Option<&str>,
     ) -> QueryResult<i64> {
         let start = Instant::now();
         if txs.is_empty() {
@@ -165,14 +184,19 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .eth_sign_data
                 .as_ref()
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let account_id = first_tx_data.account_id().ok().map(|id| *id as i64);
+            let nonce = i64::from(*first_tx_data.nonce());
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data)
-                VALUES ($1, $2, $3, $4)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, account_id, submitter_ip, nonce)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)",
                 tx_hash,
                 tx,
                 first_tx_data.created_at,
                 eth_sign_data,
+                account_id,
+                submitter_ip,
+                nonce,
             )
             .execute(transaction.conn())
             .await?;
@@ -199,15 +223,20 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .eth_sign_data
                 .as_ref()
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let account_id = tx_data.account_id().ok().map(|id| *id as i64);
+            let nonce = i64::from(*tx_data.nonce());
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, account_id, submitter_ip, nonce)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
                 tx_hash,
                 tx,
                 tx_data.created_at,
                 eth_sign_data,
-                batch_id
+                batch_id,
+                account_id,
+                submitter_ip,
+                nonce,
             )
             .execute(transaction.conn())
             .await?;
@@ -241,7 +270,12 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     }
 
     /// Adds a new transaction to the mempool schema.
-    pub async fn insert_tx(&mut self, tx_data: &SignedZkSyncTx) -> QueryResult<()> {
+    /// `submitter_ip` is the IP address the transaction was received from, if known.
+    pub async fn insert_tx(
+        &mut self,
+        tx_data: &SignedZkSyncTx,
+        submitter_ip: Option<&str>,
+    ) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx_data.tx.hash().as_ref());
         let tx = serde_json::to_value(&tx_data.tx)?;
@@ -251,15 +285,20 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .eth_sign_data
             .as_ref()
             .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+        let account_id = tx_data.account_id().ok().map(|id| *id as i64);
+        let nonce = i64::from(*tx_data.nonce());
 
         sqlx::query!(
-            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-            VALUES ($1, $2, $3, $4, $5)",
+            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, account_id, submitter_ip, nonce)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
             tx_hash,
             tx,
             tx_data.created_at,
             eth_sign_data,
             batch_id,
+            account_id,
+            submitter_ip,
+            nonce,
         )
         .execute(self.0.conn())
         .await?;
@@ -268,6 +307,300 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Sets the fee paid by the queued transactions in USD per chunk, used to order them in the queue.
+    pub async fn set_fee_per_chunk(
+        &mut self,
+        txs: &[TxHash],
+        fee_per_chunk: &Ratio<BigUint>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = txs.iter().map(hex::encode).collect();
+
+        sqlx::query!(
+            "UPDATE mempool_txs SET fee_per_chunk = $2
+            WHERE tx_hash = ANY($1)",
+            &tx_hashes,
+            ratio_to_big_decimal(fee_per_chunk, FEE_PER_CHUNK_PRECISION)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "set_fee_per_chunk");
+        Ok(())
+    }
+
+    /// Returns the queued transaction of the account with the given nonce, if any.
+    pub async fn get_queued_tx_by_nonce(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<Option<QueuedTx>> {
+        let start = Instant::now();
+
+        let mempool_tx = sqlx::query_as!(
+            MempoolTx,
+            "SELECT * FROM mempool_txs
+            WHERE account_id = $1 AND nonce = $2 AND reverted = false
+            ORDER BY id
+            LIMIT 1",
+            i64::from(*account_id),
+            i64::from(*nonce)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_tx_by_nonce");
+        mempool_tx
+            .map(QueuedTx::try_from)
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Replaces the queued transaction with the new one and records the replacement.
+    ///
+    /// Returns `false` if the old transaction is no longer in the mempool (e.g. it was executed
+    /// in the meantime) or it's already proposed for the pending block, in which case the new
+    /// transaction is not inserted.
+    pub async fn replace_tx(
+        &mut self,
+        old_tx_hash: TxHash,
+        new_tx: &SignedZkSyncTx,
+        submitter_ip: Option<&str>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let removed = sqlx::query!(
+            "DELETE FROM mempool_txs
+            WHERE tx_hash = $1 AND batch_id = 0 AND reverted = false AND proposed = false",
+            hex::encode(old_tx_hash.as_ref())
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        if removed == 0 {
+            return Ok(false);
+        }
+
+        MempoolSchema(&mut transaction)
+            .insert_tx(new_tx, submitter_ip)
+            .await?;
+        sqlx::query!(
+            "INSERT INTO mempool_replaced_txs (tx_hash, replaced_by, created_at)
+            VALUES ($1, $2, $3)",
+            old_tx_hash.as_ref(),
+            new_tx.tx.hash().as_ref(),
+            new_tx.created_at,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "replace_tx");
+        Ok(true)
+    }
+
+    /// Marks the transactions as proposed for the pending block, after which they can't be
+    /// replaced or cancelled anymore.
+    ///
+    /// Returns the hashes of the marked transactions. Transactions which were removed from the mempool
+    /// in the meantime (e.g. replaced or cancelled) are not returned and must not be executed.
+    pub async fn mark_txs_proposed(&mut self, txs: &[TxHash]) -> QueryResult<Vec<TxHash>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = txs.iter().map(hex::encode).collect();
+
+        let marked = sqlx::query!(
+            "UPDATE mempool_txs SET proposed = true
+            WHERE tx_hash = ANY($1)
+            RETURNING tx_hash",
+            &tx_hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            let hash = hex::decode(record.tx_hash)?;
+            TxHash::from_slice(&hash)
+                .ok_or_else(|| anyhow::format_err!("Incorrect tx hash stored in the mempool"))
+        })
+        .collect::<QueryResult<_>>()?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "mark_txs_proposed");
+        Ok(marked)
+    }
+
+    /// Resets the proposed flag of all the transactions.
+    ///
+    /// The pending block is restored from the database on the server restart, so the transactions
+    /// which were proposed but not saved as a part of the pending block have to be proposed again.
+    pub async fn reset_proposed_txs(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "UPDATE mempool_txs SET proposed = false
+            WHERE proposed = true"
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "reset_proposed_txs");
+        Ok(())
+    }
+
+    /// Returns the hash of the transaction which replaced the given one, if any.
+    pub async fn get_replacement(&mut self, tx_hash: TxHash) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+
+        let replaced_by = sqlx::query!(
+            "SELECT replaced_by FROM mempool_replaced_txs
+            WHERE tx_hash = $1
+            ORDER BY id DESC
+            LIMIT 1",
+            tx_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|record| TxHash::from_slice(&record.replaced_by));
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_replacement");
+        Ok(replaced_by)
+    }
+
+    /// Loads the replacements recorded after the one with the given ID, in the order they were made.
+    /// Returns the replacement ID along with the hashes of the replaced transaction and its replacement.
+    pub async fn load_replacements(
+        &mut self,
+        after_id: i64,
+    ) -> QueryResult<Vec<(i64, TxHash, TxHash)>> {
+        let start = Instant::now();
+
+        let replacements = sqlx::query!(
+            "SELECT id, tx_hash, replaced_by FROM mempool_replaced_txs
+            WHERE id > $1
+            ORDER BY id",
+            after_id
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            let tx_hash = TxHash::from_slice(&record.tx_hash)?;
+            let replaced_by = TxHash::from_slice(&record.replaced_by)?;
+            Some((record.id, tx_hash, replaced_by))
+        })
+        .collect();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_replacements");
+        Ok(replacements)
+    }
+
+    /// Removes the transaction from the mempool and marks it as cancelled.
+    /// If the transaction is a part of a batch, the whole batch is cancelled.
+    ///
+    /// Transactions proposed for the pending block can't be cancelled. Returns `None` if there is
+    /// no such transaction in the mempool or it's already proposed, otherwise returns the hashes
+    /// of all the cancelled transactions.
+    pub async fn cancel_tx(&mut self, tx_hash: TxHash) -> QueryResult<Option<Vec<TxHash>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let batch_id = sqlx::query!(
+            "SELECT batch_id FROM mempool_txs
+            WHERE tx_hash = $1 AND reverted = false AND proposed = false
+            FOR UPDATE",
+            hex::encode(tx_hash.as_ref())
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|record| record.batch_id);
+        let batch_id = match batch_id {
+            Some(batch_id) => batch_id,
+            None => return Ok(None),
+        };
+
+        // Special case: batch_id == 0 <==> transaction is not a part of some batch
+        let removed_hashes: Vec<String> = if batch_id == 0 {
+            sqlx::query!(
+                "DELETE FROM mempool_txs
+                WHERE tx_hash = $1 AND proposed = false
+                RETURNING tx_hash",
+                hex::encode(tx_hash.as_ref())
+            )
+            .fetch_all(transaction.conn())
+            .await?
+            .into_iter()
+            .map(|record| record.tx_hash)
+            .collect()
+        } else {
+            sqlx::query!(
+                "DELETE FROM mempool_txs
+                WHERE batch_id = $1 AND proposed = false
+                RETURNING tx_hash",
+                batch_id
+            )
+            .fetch_all(transaction.conn())
+            .await?
+            .into_iter()
+            .map(|record| record.tx_hash)
+            .collect()
+        };
+
+        let mut cancelled_txs = Vec::with_capacity(removed_hashes.len());
+        for hash in removed_hashes {
+            let hash = hex::decode(hash)?;
+            sqlx::query!(
+                "INSERT INTO mempool_cancelled_txs (tx_hash, created_at)
+                VALUES ($1, now())
+                ON CONFLICT (tx_hash) DO UPDATE SET created_at = now()",
+                &hash
+            )
+            .execute(transaction.conn())
+            .await?;
+
+            let hash = TxHash::from_slice(&hash)
+                .ok_or_else(|| anyhow::format_err!("Incorrect tx hash stored in the mempool"))?;
+            cancelled_txs.push(hash);
+        }
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "cancel_tx");
+        Ok(Some(cancelled_txs))
+    }
+
+    /// Checks whether the transaction was cancelled by the account owner.
+    pub async fn is_tx_cancelled(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let row = sqlx::query!(
+            "SELECT COUNT(*) from mempool_cancelled_txs
+            WHERE tx_hash = $1",
+            tx_hash.as_ref()
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        let cancelled = row.filter(|&counter| counter > 0).is_some();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "is_tx_cancelled");
+        Ok(cancelled)
+    }
+
+    /// Returns the ID of the last recorded replacement, or `0` if there were none.
+    pub async fn get_last_replacement_id(&mut self) -> QueryResult<i64> {
+        let start = Instant::now();
+
+        let last_id = sqlx::query!("SELECT MAX(id) FROM mempool_replaced_txs")
+            .fetch_one(self.0.conn())
+            .await?
+            .max;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_last_replacement_id");
+        Ok(last_id.unwrap_or(0))
+    }
+
     pub async fn remove_tx(&mut self, tx: &[u8]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx);
@@ -367,7 +700,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let all_txs = self.load_txs(&[]).await?;
         let mut tx_hashes_to_remove = Vec::new();
 
-        for tx in all_txs {
+        for (tx, _) in all_txs {
             let should_remove = match &tx {
                 SignedTxVariant::Tx(tx) => {
                     let tx_hash = tx.hash();
@@ -588,6 +921,144 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(size.unwrap_or(0) as u32)
     }
 
+    /// Locks the pending transactions of the accounts and of the submitter IP address
+    /// until the end of the current database transaction.
+    ///
+    /// Used to check the limits of the pending transactions and to insert the new ones atomically
+    /// across all the mempool handlers. Only the submissions for the same accounts or from the same
+    /// IP address wait for each other, other writes to the mempool are not blocked.
+    pub async fn lock_pending_txs(
+        &mut self,
+        account_ids: &[AccountId],
+        submitter_ip: Option<&str>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        assert!(
+            self.0.in_transaction(),
+            "Pending transactions can only be locked within a database transaction"
+        );
+
+        // Locks are always acquired in the same order, so the concurrent handlers can't deadlock.
+        let mut account_ids = account_ids.to_vec();
+        account_ids.sort_unstable();
+        account_ids.dedup();
+        for account_id in account_ids {
+            sqlx::query!(
+                "SELECT 1 FROM pg_advisory_xact_lock($1, $2)",
+                PENDING_TXS_ACCOUNT_LOCK,
+                *account_id as i32
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+        if let Some(ip) = submitter_ip {
+            sqlx::query!(
+                "SELECT 1 FROM pg_advisory_xact_lock($1, hashtext($2))",
+                PENDING_TXS_IP_LOCK,
+                ip
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "lock_pending_txs");
+        Ok(())
+    }
+
+    /// Returns the amount of transactions of the account awaiting in the mempool.
+    pub async fn get_account_pending_txs_count(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            "SELECT COUNT(*) from mempool_txs
+            WHERE account_id = $1 AND reverted = false",
+            i64::from(*account_id)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_account_pending_txs_count");
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Returns the amount of transactions received from the IP address and awaiting in the mempool.
+    pub async fn get_ip_pending_txs_count(&mut self, ip: &str) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            "SELECT COUNT(*) from mempool_txs
+            WHERE submitter_ip = $1 AND reverted = false",
+            ip
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_ip_pending_txs_count");
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Returns the summary of the mempool contents without loading the operations themselves.
+    /// Readiness of the transactions is checked against the provided timestamp.
+    pub async fn get_summary(&mut self, timestamp: u64) -> QueryResult<MempoolSummary> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let txs = sqlx::query!(
+            r#"SELECT
+                COUNT(*) FILTER (WHERE COALESCE((tx->>'validFrom')::numeric, 0) <= $1::bigint) AS "ready_txs!",
+                COUNT(*) FILTER (WHERE COALESCE((tx->>'validFrom')::numeric, 0) > $1::bigint) AS "pending_txs!",
+                MIN(created_at) AS oldest_tx_created_at
+            FROM mempool_txs
+            WHERE proposed = false"#,
+            timestamp as i64
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        let priority_ops = sqlx::query!(
+            "SELECT COUNT(*) FROM mempool_priority_operations
+            WHERE confirmed AND reverted = false"
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_summary");
+        Ok(MempoolSummary {
+            ready_txs: txs.ready_txs as u64,
+            pending_txs: txs.pending_txs as u64,
+            priority_ops: priority_ops.unwrap_or(0) as u64,
+            oldest_tx_created_at: txs.oldest_tx_created_at,
+        })
+    }
+
+    /// Returns the amounts of transactions awaiting in the mempool for every account,
+    /// the largest ones go first. Transactions proposed for the pending block are not counted.
+    pub async fn get_pending_txs_per_account(&mut self) -> QueryResult<Vec<(AccountId, u64)>> {
+        let start = Instant::now();
+
+        let accounts = sqlx::query!(
+            r#"SELECT account_id AS "account_id!", COUNT(*) AS "txs!" FROM mempool_txs
+            WHERE account_id IS NOT NULL AND proposed = false AND reverted = false
+            GROUP BY account_id
+            ORDER BY COUNT(*) DESC, account_id"#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| (AccountId(record.account_id as u32), record.txs as u64))
+        .collect();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_pending_txs_per_account");
+        Ok(accounts)
+    }
+
     /// Get info about batch in mempool.
     pub async fn get_queued_batch_info(
         &mut self,
@@ -872,6 +1343,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             let from_account = signed_tx.from_account().as_bytes().to_vec();
             let to_account = signed_tx.to_account().map(|a| a.as_bytes().to_vec());
             let primary_account_address = signed_tx.account().as_bytes().to_vec();
+            let account_id = signed_tx.account_id().ok().map(|id| *id as i64);
 
             let SignedZkSyncTx {
                 tx, eth_sign_data, ..
@@ -909,14 +1381,16 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?;
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted)
-                VALUES ($1, $2, $3, $4, $5, $6, true)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, next_priority_op_serial_id, reverted, account_id, nonce)
+                VALUES ($1, $2, $3, $4, $5, $6, true, $7, $8)",
                 tx_hash,
                 tx_value,
                 created_at,
                 eth_sign_data,
                 batch_id.unwrap_or(0i64),
                 next_priority_op_serial_id as i64,
+                account_id,
+                *nonce as i64,
             )
             .execute(transaction.conn())
             .await?;
@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 
 // External imports
 use chrono::{DateTime, Utc};
-use sqlx::FromRow;
+use sqlx::{types::BigDecimal, FromRow};
 
 // Workspace imports
 use zksync_types::{PriorityOp, SignedZkSyncTx, H256};
@@ -19,6 +19,44 @@ pub(crate) struct RevertedBlock {
     pub timestamp: i64,
 }
 
+/// Summary of the operations awaiting in the mempool.
+/// Transactions proposed for the pending block are not taken into account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolSummary {
+    /// Transactions which can be executed at the requested timestamp.
+    pub ready_txs: u64,
+    /// Transactions which can't be executed yet because of their `valid_from` field.
+    pub pending_txs: u64,
+    /// Confirmed priority operations.
+    pub priority_ops: u64,
+    /// Creation time of the oldest transaction.
+    pub oldest_tx_created_at: Option<DateTime<Utc>>,
+}
+
+/// Transaction awaiting in the mempool along with its state.
+#[derive(Debug, Clone)]
+pub struct QueuedTx {
+    pub tx: SignedZkSyncTx,
+    /// Whether the transaction is a part of some batch.
+    pub in_batch: bool,
+    /// Whether the transaction is proposed for the pending block.
+    pub proposed: bool,
+}
+
+impl TryFrom<MempoolTx> for QueuedTx {
+    type Error = serde_json::Error;
+
+    fn try_from(value: MempoolTx) -> Result<Self, Self::Error> {
+        let in_batch = value.batch_id != 0;
+        let proposed = value.proposed;
+        Ok(Self {
+            tx: SignedZkSyncTx::try_from(value)?,
+            in_batch,
+            proposed,
+        })
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub(crate) struct MempoolTx {
     #[allow(dead_code)]
@@ -33,6 +71,14 @@ pub(crate) struct MempoolTx {
     pub next_priority_op_serial_id: Option<i64>,
     #[allow(dead_code)]
     pub reverted: bool,
+    #[allow(dead_code)]
+    pub account_id: Option<i64>,
+    #[allow(dead_code)]
+    pub submitter_ip: Option<String>,
+    #[allow(dead_code)]
+    pub nonce: Option<i64>,
+    pub proposed: bool,
+    pub fee_per_chunk: BigDecimal,
 }
 
 impl TryFrom<MempoolTx> for SignedZkSyncTx {
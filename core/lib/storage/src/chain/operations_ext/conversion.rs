@@ -2,8 +2,8 @@
 // External imports
 // Workspace imports
 use zksync_api_types::v02::transaction::{
-    ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
-    TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
+    L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction, TransactionData,
+    TxData, TxInBlockStatus,
 };
 use zksync_types::{
     tx::{EthSignData, TxHash},
@@ -62,26 +62,10 @@ impl StorageTxData {
         tx: ZkSyncTx,
         complete_withdrawals_tx_hash: Option<H256>,
     ) -> TransactionData {
-        let tx = match tx {
-            ZkSyncTx::ChangePubKey(tx) => L2Transaction::ChangePubKey(tx),
-            ZkSyncTx::Close(tx) => L2Transaction::Close(tx),
-            ZkSyncTx::ForcedExit(tx) => L2Transaction::ForcedExit(Box::new(ForcedExitData {
-                tx: *tx,
-                eth_tx_hash: complete_withdrawals_tx_hash,
-            })),
-            ZkSyncTx::Transfer(tx) => L2Transaction::Transfer(tx),
-            ZkSyncTx::Withdraw(tx) => L2Transaction::Withdraw(Box::new(WithdrawData {
-                tx: *tx,
-                eth_tx_hash: complete_withdrawals_tx_hash,
-            })),
-            ZkSyncTx::MintNFT(tx) => L2Transaction::MintNFT(tx),
-            ZkSyncTx::WithdrawNFT(tx) => L2Transaction::WithdrawNFT(Box::new(WithdrawNFTData {
-                tx: *tx,
-                eth_tx_hash: complete_withdrawals_tx_hash,
-            })),
-            ZkSyncTx::Swap(tx) => L2Transaction::Swap(tx),
-        };
-        TransactionData::L2(tx)
+        TransactionData::L2(L2Transaction::from_zksync_tx(
+            tx,
+            complete_withdrawals_tx_hash,
+        ))
     }
 
     pub(super) fn data_from_storage_data(
@@ -14,7 +14,9 @@ use zksync_types::{
     BlockNumber, H256, U256,
 };
 // Local imports
-use self::records::{ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
+use self::records::{
+    ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation, StorageExodusMode,
+};
 use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
 use chrono::{DateTime, Utc};
 
@@ -93,6 +95,9 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                 .expect("Stored operation type must have a valid value");
             let last_used_gas_price =
                 U256::from_str(&eth_op.last_used_gas_price.to_string()).unwrap();
+            let last_used_priority_fee_per_gas = eth_op
+                .last_used_priority_fee_per_gas
+                .map(|fee| U256::from_str(&fee.to_string()).unwrap());
             let used_tx_hashes = eth_tx_hashes
                 .iter()
                 .map(|entry| H256::from_slice(&entry.tx_hash))
@@ -106,6 +111,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
                 nonce: eth_op.nonce.into(),
                 last_deadline_block: eth_op.last_deadline_block as u64,
                 last_used_gas_price,
+                last_used_priority_fee_per_gas,
                 used_tx_hashes,
                 encoded_tx_data: eth_op.raw_tx,
                 confirmed: eth_op.confirmed,
@@ -215,12 +221,16 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
     /// Stores the sent (but not confirmed yet) Ethereum transaction in the database.
     /// Returns the `ETHOperation` object containing the assigned nonce and operation ID.
+    ///
+    /// `last_used_priority_fee_per_gas` is expected to be set only for EIP-1559 transactions,
+    /// in which case `last_used_gas_price` holds the `max_fee_per_gas` value.
     pub async fn save_new_eth_tx(
         &mut self,
         op_type: AggregatedActionType,
         operation: Option<(i64, AggregatedOperation)>,
         last_deadline_block: i64,
         last_used_gas_price: BigUint,
+        last_used_priority_fee_per_gas: Option<BigUint>,
         raw_tx: Vec<u8>,
     ) -> QueryResult<InsertedOperationResponse> {
         let start = Instant::now();
@@ -234,13 +244,15 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         // Obtain the operation ID for the follow-up queried.
         let last_used_gas_price = BigDecimal::from(BigInt::from(last_used_gas_price));
+        let last_used_priority_fee_per_gas =
+            last_used_priority_fee_per_gas.map(|fee| BigDecimal::from(BigInt::from(fee)));
         let eth_op_id = sqlx::query!(
             "
-                INSERT INTO eth_operations (op_type, nonce, last_deadline_block, last_used_gas_price, raw_tx)
-                VALUES ($1, $2, $3, $4, $5)
+                INSERT INTO eth_operations (op_type, nonce, last_deadline_block, last_used_gas_price, last_used_priority_fee_per_gas, raw_tx)
+                VALUES ($1, $2, $3, $4, $5, $6)
                 RETURNING id
             ",
-            op_type.to_string(), nonce, last_deadline_block, last_used_gas_price, raw_tx,
+            op_type.to_string(), nonce, last_deadline_block, last_used_gas_price, last_used_priority_fee_per_gas, raw_tx,
         )
         .fetch_one(transaction.conn())
         .await?
@@ -323,21 +335,25 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
     }
 
     /// Updates the Ethereum operation by adding a new tx data.
-    /// The new deadline block / gas value are placed instead of old values to the main entry.
+    /// The new deadline block / gas value / priority fee are placed instead of old values to the main entry.
     pub async fn update_eth_tx(
         &mut self,
         eth_op_id: i64,
         new_deadline_block: i64,
         new_gas_value: BigUint,
+        new_priority_fee_per_gas: Option<BigUint>,
     ) -> QueryResult<()> {
         let start = Instant::now();
         // Update the stored tx.
         let new_gas_price = BigDecimal::from(BigInt::from(new_gas_value));
+        let new_priority_fee_per_gas =
+            new_priority_fee_per_gas.map(|fee| BigDecimal::from(BigInt::from(fee)));
         sqlx::query!(
             "UPDATE eth_operations 
-            SET last_used_gas_price = $1, last_deadline_block = $2
-            WHERE id = $3",
+            SET last_used_gas_price = $1, last_used_priority_fee_per_gas = $2, last_deadline_block = $3
+            WHERE id = $4",
             new_gas_price,
+            new_priority_fee_per_gas,
             new_deadline_block,
             eth_op_id
         )
@@ -721,4 +737,41 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         metrics::histogram!("sql.ethereum.update_eth_parameters", start.elapsed());
         Ok(())
     }
+
+    /// Records that the zkSync contract has entered the exodus mode.
+    /// Since the exodus mode is irreversible, only the first call has an effect.
+    pub async fn save_exodus_mode(
+        &mut self,
+        eth_block: u64,
+        first_priority_request_id: u64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO exodus_mode (id, eth_block, first_priority_request_id, entered_at)
+            VALUES (true, $1, $2, now())
+            ON CONFLICT (id) DO NOTHING",
+            eth_block as i64,
+            first_priority_request_id as i64,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.save_exodus_mode", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the details of the zkSync contract entering the exodus mode.
+    /// Returns `None` if the network operates normally.
+    pub async fn load_exodus_mode(&mut self) -> QueryResult<Option<StorageExodusMode>> {
+        let start = Instant::now();
+        let exodus_mode = sqlx::query_as!(
+            StorageExodusMode,
+            "SELECT eth_block, first_priority_request_id, entered_at FROM exodus_mode WHERE id = true"
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.load_exodus_mode", start.elapsed());
+        Ok(exodus_mode)
+    }
 }
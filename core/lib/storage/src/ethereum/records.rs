@@ -15,6 +15,7 @@ pub struct StorageETHOperation {
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
     pub created_at: Option<DateTime<Utc>>,
+    pub last_used_priority_fee_per_gas: Option<BigDecimal>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -30,6 +31,7 @@ pub struct ETHOperationData {
     pub agg_op_id: Option<i64>,
     pub arguments: Option<serde_json::Value>,
     pub created_at: Option<DateTime<Utc>>,
+    pub last_used_priority_fee_per_gas: Option<BigDecimal>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -67,3 +69,11 @@ impl From<ETHParams> for ETHStats {
         }
     }
 }
+
+/// Details of the zkSync contract entering the exodus mode.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageExodusMode {
+    pub eth_block: i64,
+    pub first_priority_request_id: i64,
+    pub entered_at: DateTime<Utc>,
+}
@@ -10,6 +10,7 @@ use zksync_types::{
             AccountEvent, AccountStateChangeStatus, AccountStateChangeType, AccountUpdateDetails,
         },
         block::{BlockEvent, BlockStatus},
+        exodus::ExodusEvent,
         transaction::{TransactionEvent, TransactionStatus},
         EventId,
     },
@@ -94,6 +95,56 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         Ok(events)
     }
 
+    /// Fetches at most `limit` events with ids greater than `from`, ordered by id.
+    /// Used to replay the past events in pages.
+    pub async fn fetch_new_events_page(
+        &mut self,
+        from: EventId,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredEvent>> {
+        let start = Instant::now();
+        let events = sqlx::query_as!(
+            StoredEvent,
+            r#"
+            SELECT
+                id,
+                block_number,
+                event_type as "event_type!: EventType",
+                event_data
+            FROM events WHERE id > $1
+            ORDER BY id ASC
+            LIMIT $2
+            "#,
+            *from as i64,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.event.fetch_new_events_page", start.elapsed());
+        Ok(events)
+    }
+
+    /// Load the id of the first event emitted for the given or any later block.
+    /// Returns `None` if there're no such events.
+    pub async fn get_first_event_id_from_block(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT MIN(id) as min FROM events WHERE block_number >= $1",
+            i64::from(*block_number)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .min
+        .map(|id| EventId(id as u64));
+
+        metrics::histogram!("sql.event.get_first_event_id_from_block", start.elapsed());
+        Ok(id)
+    }
+
     /// Load the id of the latest event in the database.
     /// Returns `None` if the `events` table is empty.
     pub async fn get_last_event_id(&mut self) -> QueryResult<Option<EventId>> {
@@ -108,6 +159,44 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         Ok(id)
     }
 
+    /// Load the id of the last event exported by the sink with the given name.
+    /// Returns `None` if the sink didn't export anything yet.
+    pub async fn get_sink_cursor(&mut self, sink_name: &str) -> QueryResult<Option<EventId>> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "SELECT last_event_id FROM event_sink_cursors WHERE sink_name = $1",
+            sink_name
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| EventId(row.last_event_id as u64));
+
+        metrics::histogram!("sql.event.get_sink_cursor", start.elapsed());
+        Ok(id)
+    }
+
+    /// Persist the id of the last event exported by the sink with the given name.
+    pub async fn update_sink_cursor(
+        &mut self,
+        sink_name: &str,
+        last_event_id: EventId,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO event_sink_cursors (sink_name, last_event_id)
+            VALUES ($1, $2)
+            ON CONFLICT (sink_name)
+            DO UPDATE SET last_event_id = $2, updated_at = now()",
+            sink_name,
+            *last_event_id as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.event.update_sink_cursor", start.elapsed());
+        Ok(())
+    }
+
     /// Create new block event and store it in the database.
     /// This method relies on the `load_block_range` which may return `None`
     /// if there're no Ethereum transactions featuring this block (`Committed` or `Executed`).
@@ -287,4 +376,26 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         metrics::histogram!("sql.event.store_queued_transaction_event", start.elapsed());
         Ok(())
     }
+
+    /// Stores the event about the zkSync contract entering the exodus mode.
+    /// `block_number` is the number of the last block executed on L1.
+    pub async fn store_exodus_event(
+        &mut self,
+        block_number: BlockNumber,
+        exodus_event: ExodusEvent,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let event_data =
+            serde_json::to_value(exodus_event).expect("couldn't serialize exodus event");
+        self.store_event_data(
+            block_number,
+            EventType::Exodus,
+            slice::from_ref(&event_data),
+        )
+        .await?;
+
+        metrics::histogram!("sql.event.store_exodus_event", start.elapsed());
+        Ok(())
+    }
 }
@@ -0,0 +1,73 @@
+// Built-in uses
+use std::time::Instant;
+// External uses
+use chrono::{DateTime, Utc};
+use num::{BigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace uses
+use zksync_types::{Address, TokenId};
+// Local uses
+use crate::{QueryResult, StorageProcessor};
+
+/// Schema for the fee quotes issued by the API.
+/// Every quote is honored only once, and only until it expires.
+#[derive(Debug)]
+pub struct FeeQuotesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeQuotesSchema<'a, 'c> {
+    /// Stores the new fee quote. Returns the id of the quote.
+    pub async fn store_quote(
+        &mut self,
+        sender: Address,
+        token_id: TokenId,
+        total_fee: &BigUint,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let total_fee = BigDecimal::from(BigInt::from(total_fee.clone()));
+        let id = sqlx::query!(
+            "INSERT INTO fee_quotes (sender, token_id, total_fee, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id",
+            sender.as_bytes(),
+            *token_id as i32,
+            total_fee,
+            expires_at
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.fee_quotes.store_quote", start.elapsed());
+        Ok(id)
+    }
+
+    /// Marks the quote as consumed. Returns `false` if the quote doesn't exist,
+    /// has expired or has already been consumed.
+    pub async fn consume_quote(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let consumed = sqlx::query!(
+            "UPDATE fee_quotes SET consumed_at = now()
+            WHERE id = $1 AND consumed_at IS NULL AND expires_at > now()
+            RETURNING id",
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .is_some();
+
+        metrics::histogram!("sql.fee_quotes.consume_quote", start.elapsed());
+        Ok(consumed)
+    }
+
+    /// Removes the expired quotes. Returns the number of removed quotes.
+    pub async fn remove_expired_quotes(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let result = sqlx::query!("DELETE FROM fee_quotes WHERE expires_at <= now()")
+            .execute(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.fee_quotes.remove_expired_quotes", start.elapsed());
+        Ok(result.rows_affected())
+    }
+}
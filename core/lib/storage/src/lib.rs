@@ -94,6 +94,7 @@ pub mod data_restore;
 pub mod diff;
 pub mod ethereum;
 pub mod event;
+pub mod fee_quotes;
 pub mod forced_exit_requests;
 pub mod listener;
 pub mod misc;
@@ -101,6 +102,7 @@ pub mod prover;
 pub mod test_data;
 pub mod tokens;
 pub mod utils;
+pub mod webhooks;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -243,6 +245,16 @@ impl<'a> StorageProcessor<'a> {
         misc::MiscSchema(self)
     }
 
+    /// Gains access to the `Webhooks` schema.
+    pub fn webhooks_schema(&mut self) -> webhooks::WebhooksSchema<'_, 'a> {
+        webhooks::WebhooksSchema(self)
+    }
+
+    /// Gains access to the `FeeQuotes` schema.
+    pub fn fee_quotes_schema(&mut self) -> fee_quotes::FeeQuotesSchema<'_, 'a> {
+        fee_quotes::FeeQuotesSchema(self)
+    }
+
     fn conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
@@ -3,9 +3,9 @@ use std::time::Instant;
 // External imports
 use anyhow::format_err;
 // Workspace imports
-use zksync_types::BlockNumber;
+use zksync_types::{AccountId, BlockNumber, TokenId};
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredExitProof, StoredProof};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
@@ -324,6 +324,56 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(proof)
     }
 
+    /// Stores the exit proof data generated for the account and token
+    /// from the state of the given verified block.
+    pub async fn store_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+        proof_data: serde_json::Value,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO exit_proofs (account_id, token_id, block_number, proof_data, created_at)
+            VALUES ($1, $2, $3, $4, now())
+            ON CONFLICT (account_id, token_id, block_number) DO NOTHING",
+            i64::from(*account_id),
+            i64::from(*token_id),
+            i64::from(*block_number),
+            proof_data
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_exit_proof");
+        Ok(())
+    }
+
+    /// Gets the exit proof data generated for the account and token
+    /// from the state of the given verified block.
+    pub async fn load_exit_proof(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StoredExitProof>> {
+        let start = Instant::now();
+        let proof = sqlx::query_as!(
+            StoredExitProof,
+            "SELECT * FROM exit_proofs
+            WHERE account_id = $1 AND token_id = $2 AND block_number = $3",
+            i64::from(*account_id),
+            i64::from(*token_id),
+            i64::from(*block_number)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_exit_proof");
+        Ok(proof)
+    }
+
     /// Stores witness for a block
     pub async fn store_witness(
         &mut self,
@@ -69,3 +69,12 @@ pub struct StorageProverJobQueue {
     pub last_block: i64,
     pub job_data: serde_json::Value,
 }
+
+#[derive(Debug, FromRow)]
+pub struct StoredExitProof {
+    pub account_id: i64,
+    pub token_id: i64,
+    pub block_number: i64,
+    pub proof_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -275,6 +275,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -318,6 +319,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -483,6 +485,7 @@ async fn test_block_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -516,6 +519,7 @@ async fn test_block_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -618,6 +622,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -655,6 +660,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -1292,6 +1298,73 @@ async fn test_get_block_number_by_hash(mut storage: StorageProcessor<'_>) -> Que
     Ok(())
 }
 
+/// Check that `load_last_blocks_chunks_used` returns the number of chunks used by the operations
+/// of the latest blocks first, without the padding chunks.
+#[db_test]
+async fn test_load_last_blocks_chunks_used(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{operations::ChangePubKeyOp, ExecutedOperations, ExecutedTx, ZkSyncOp};
+
+    let change_pubkey = |account_id: u32| {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(AccountId(account_id)));
+        let tx = account.sign_change_pubkey_tx(
+            None,
+            false,
+            TokenId(0),
+            Default::default(),
+            ChangePubKeyType::ECDSA,
+            Default::default(),
+        );
+        let op = ZkSyncOp::ChangePubKeyOffchain(Box::new(ChangePubKeyOp {
+            tx,
+            account_id: AccountId(account_id),
+        }));
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: op.try_get_tx().unwrap().into(),
+            success: true,
+            op: Some(op),
+            fail_reason: None,
+            block_index: Some(account_id),
+            created_at: chrono::Utc::now(),
+            batch_id: None,
+        }))
+    };
+
+    let chunks_used = storage
+        .chain()
+        .block_schema()
+        .load_last_blocks_chunks_used(2)
+        .await?;
+    assert!(chunks_used.is_empty());
+
+    let blocks_txs = vec![
+        vec![change_pubkey(1)],
+        vec![],
+        vec![change_pubkey(2), change_pubkey(3)],
+    ];
+    for (block_number, txs) in (1..).zip(blocks_txs) {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                BLOCK_SIZE_CHUNKS,
+                txs,
+            ))
+            .await?;
+    }
+
+    let chunks_used = storage
+        .chain()
+        .block_schema()
+        .load_last_blocks_chunks_used(2)
+        .await?;
+    assert_eq!(chunks_used, vec![2 * ChangePubKeyOp::CHUNKS, 0]);
+
+    Ok(())
+}
+
 /// Check that `get_block_transactions_hashes` works correctly
 #[db_test]
 async fn test_get_block_transactions_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
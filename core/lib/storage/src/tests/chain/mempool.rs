@@ -1,12 +1,13 @@
 // External imports
 use chrono::Utc;
+use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
     block::{Block, ExecutedOperations},
     mempool::SignedTxVariant,
     priority_ops::FullExit,
-    tx::{ChangePubKey, Transfer, TxHash, Withdraw},
+    tx::{ChangePubKey, TimeRange, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
@@ -141,7 +142,7 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let txs = zksync_txs();
     for tx in &txs {
         MempoolSchema(&mut storage)
-            .insert_tx(&tx.clone())
+            .insert_tx(&tx.clone(), None)
             .await
             .expect("Can't insert txs");
     }
@@ -154,7 +155,7 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(txs_from_db.len(), txs.len());
 
     for (tx, tx_from_db) in txs.iter().zip(txs_from_db) {
-        let tx_from_db = unwrap_tx(tx_from_db);
+        let tx_from_db = unwrap_tx(tx_from_db.0);
         assert_eq!(tx_from_db.hash(), tx.hash(), "transaction changed");
         assert_eq!(
             tx_from_db.eth_sign_data, tx.eth_sign_data,
@@ -183,44 +184,74 @@ async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     let elements_count = alone_txs_1.len() + alone_txs_2.len() + 3; // Amount of alone txs + amount of batches.
 
     for tx in alone_txs_1 {
-        MempoolSchema(&mut storage).insert_tx(tx).await?;
+        MempoolSchema(&mut storage).insert_tx(tx, None).await?;
     }
 
     // Store the first batch with a signature.
     MempoolSchema(&mut storage)
-        .insert_batch(batch_1, batch_1_signature.clone())
+        .insert_batch(batch_1, batch_1_signature.clone(), None)
         .await?;
     // Store the second one with multiple signatures.
     MempoolSchema(&mut storage)
-        .insert_batch(batch_2, batch_2_signatures.clone())
+        .insert_batch(batch_2, batch_2_signatures.clone(), None)
         .await?;
 
     for tx in alone_txs_2 {
-        MempoolSchema(&mut storage).insert_tx(tx).await?;
+        MempoolSchema(&mut storage).insert_tx(tx, None).await?;
     }
 
     MempoolSchema(&mut storage)
-        .insert_batch(batch_3, vec![])
+        .insert_batch(batch_3, vec![], None)
         .await?;
 
     // Load the txs and check that they match the expected list.
     let txs_from_db = MempoolSchema(&mut storage).load_txs(&[]).await?;
     assert_eq!(txs_from_db.len(), elements_count);
 
-    assert!(matches!(txs_from_db[0], SignedTxVariant::Tx(_)));
-    assert!(matches!(txs_from_db[1], SignedTxVariant::Tx(_)));
+    assert!(matches!(txs_from_db[0].0, SignedTxVariant::Tx(_)));
+    assert!(matches!(txs_from_db[1].0, SignedTxVariant::Tx(_)));
     // Try to load the batches with the signature.
-    match &txs_from_db[2] {
+    match &txs_from_db[2].0 {
         SignedTxVariant::Batch(batch) => assert_eq!(batch.eth_signatures, batch_1_signature),
         SignedTxVariant::Tx(_) => panic!("expected to load batch of transactions 1"),
     };
-    match &txs_from_db[3] {
+    match &txs_from_db[3].0 {
         SignedTxVariant::Batch(batch) => assert_eq!(batch.eth_signatures, batch_2_signatures),
         SignedTxVariant::Tx(_) => panic!("expected to load batch of transactions 2"),
     };
-    assert!(matches!(txs_from_db[4], SignedTxVariant::Tx(_)));
-    assert!(matches!(txs_from_db[5], SignedTxVariant::Tx(_)));
-    assert!(matches!(txs_from_db[6], SignedTxVariant::Batch(_)));
+    assert!(matches!(txs_from_db[4].0, SignedTxVariant::Tx(_)));
+    assert!(matches!(txs_from_db[5].0, SignedTxVariant::Tx(_)));
+    assert!(matches!(txs_from_db[6].0, SignedTxVariant::Batch(_)));
+
+    Ok(())
+}
+
+/// Checks that the fees per chunk are loaded along with the transactions and batches.
+#[db_test]
+async fn store_load_fee_per_chunk(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(&txs[0], None).await?;
+    mempool.insert_batch(&txs[1..], Vec::new(), None).await?;
+
+    let batch_hashes: Vec<_> = txs[1..].iter().map(|tx| tx.hash()).collect();
+    let batch_fee = Ratio::new(BigUint::from(1u32), BigUint::from(4u32));
+    mempool.set_fee_per_chunk(&batch_hashes, &batch_fee).await?;
+
+    // Fee of the transaction is not set, so it's considered to be zero.
+    let fees: Vec<_> = mempool
+        .load_txs(&[])
+        .await?
+        .into_iter()
+        .map(|(tx, fee_per_chunk)| (tx.hashes(), fee_per_chunk))
+        .collect();
+    assert_eq!(
+        fees,
+        vec![
+            (vec![txs[0].hash()], Ratio::from(BigUint::from(0u32))),
+            (batch_hashes, batch_fee)
+        ]
+    );
 
     Ok(())
 }
@@ -234,7 +265,9 @@ async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     // Insert several txs into the mempool schema.
     let txs = zksync_txs();
     for tx in &txs {
-        MempoolSchema(&mut storage).insert_tx(&tx.clone()).await?;
+        MempoolSchema(&mut storage)
+            .insert_tx(&tx.clone(), None)
+            .await?;
     }
 
     // Remove several txs from the schema.
@@ -252,7 +285,7 @@ async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(txs_from_db.len(), retained_hashes.len());
 
     for (expected_hash, tx_from_db) in retained_hashes.iter().zip(txs_from_db) {
-        assert_eq!(*expected_hash, unwrap_tx(tx_from_db).hash());
+        assert_eq!(*expected_hash, unwrap_tx(tx_from_db.0).hash());
     }
 
     Ok(())
@@ -265,7 +298,7 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let txs = zksync_txs();
     for tx in &txs {
         MempoolSchema(&mut storage)
-            .insert_tx(&tx.clone())
+            .insert_tx(&tx.clone(), None)
             .await
             .expect("Can't insert txs");
     }
@@ -303,7 +336,7 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(txs_from_db.len(), retained_hashes.len());
 
     for (expected_hash, tx_from_db) in retained_hashes.iter().zip(txs_from_db) {
-        assert_eq!(*expected_hash, unwrap_tx(tx_from_db).hash());
+        assert_eq!(*expected_hash, unwrap_tx(tx_from_db.0).hash());
     }
 
     Ok(())
@@ -333,8 +366,8 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
             vec![gen_eth_sign_data("test message".to_owned()).signature; txs.len() - 1];
 
         let mut mempool = MempoolSchema(&mut storage);
-        mempool.insert_tx(single_tx).await?;
-        mempool.insert_batch(batch, batch_signature).await?;
+        mempool.insert_tx(single_tx, None).await?;
+        mempool.insert_batch(batch, batch_signature, None).await?;
     }
 
     // Make sure that the memory pool now responds that these transactions exist.
@@ -361,7 +394,7 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
 async fn test_get_batch_info_from_mempool(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let txs = gen_transfers(5);
     MempoolSchema(&mut storage)
-        .insert_batch(&txs, Vec::new())
+        .insert_batch(&txs, Vec::new(), None)
         .await?;
 
     let tx_hashes: Vec<TxHash> = txs.into_iter().map(|tx| tx.hash()).collect();
@@ -512,3 +545,247 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the pending transactions are counted per account and per submitter IP.
+#[db_test]
+async fn test_pending_txs_count(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let mut mempool = MempoolSchema(&mut storage);
+    // Locks may be acquired for the repeated accounts and several times within the database transaction.
+    let account_ids: Vec<_> = txs
+        .iter()
+        .chain(&txs)
+        .map(|tx| tx.account_id().unwrap())
+        .collect();
+    mempool
+        .lock_pending_txs(&account_ids, Some("127.0.0.1"))
+        .await?;
+    mempool
+        .lock_pending_txs(&account_ids[..1], Some("127.0.0.1"))
+        .await?;
+
+    mempool.insert_tx(&txs[0], Some("127.0.0.1")).await?;
+    mempool
+        .insert_batch(&txs[1..], Vec::new(), Some("127.0.0.2"))
+        .await?;
+
+    for tx in &txs {
+        let account_id = tx.account_id().unwrap();
+        assert_eq!(mempool.get_account_pending_txs_count(account_id).await?, 1);
+    }
+    assert_eq!(
+        mempool.get_account_pending_txs_count(AccountId(42)).await?,
+        0
+    );
+
+    assert_eq!(mempool.get_ip_pending_txs_count("127.0.0.1").await?, 1);
+    assert_eq!(mempool.get_ip_pending_txs_count("127.0.0.2").await?, 2);
+    assert_eq!(mempool.get_ip_pending_txs_count("127.0.0.3").await?, 0);
+
+    Ok(())
+}
+
+/// Checks that the summary of the mempool is calculated correctly.
+#[db_test]
+async fn test_mempool_summary(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let future_transfer = Transfer::new(
+        AccountId(0),
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(11),
+        TimeRange::new(1000, u64::max_value()),
+        None,
+    );
+    let future_tx = SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(future_transfer)),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    };
+
+    let mut mempool = MempoolSchema(&mut storage);
+    let summary = mempool.get_summary(500).await?;
+    assert_eq!((summary.ready_txs, summary.pending_txs), (0, 0));
+    assert_eq!(summary.priority_ops, 0);
+    assert!(summary.oldest_tx_created_at.is_none());
+
+    for tx in txs.iter().chain(std::iter::once(&future_tx)) {
+        mempool.insert_tx(tx, None).await?;
+    }
+    // Transactions proposed for the pending block are not taken into account.
+    mempool.mark_txs_proposed(&[txs[2].hash()]).await?;
+
+    let summary = mempool.get_summary(500).await?;
+    assert_eq!((summary.ready_txs, summary.pending_txs), (2, 1));
+    assert!(summary.oldest_tx_created_at.is_some());
+    let summary = mempool.get_summary(1000).await?;
+    assert_eq!((summary.ready_txs, summary.pending_txs), (3, 0));
+
+    assert_eq!(
+        mempool.get_pending_txs_per_account().await?,
+        vec![(AccountId(0), 2), (AccountId(1), 1)]
+    );
+
+    Ok(())
+}
+
+/// Checks that the queued transaction can be replaced with the one having the same nonce.
+#[db_test]
+async fn test_replace_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let old_tx = zksync_txs()[0].clone();
+    let account_id = old_tx.account_id().unwrap();
+    let nonce = old_tx.nonce();
+
+    let transfer = Transfer::new(
+        account_id,
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        20u32.into(),
+        nonce,
+        Default::default(),
+        None,
+    );
+    let new_tx = SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    };
+
+    let mut mempool = MempoolSchema(&mut storage);
+    assert_eq!(mempool.get_last_replacement_id().await?, 0);
+    mempool.insert_tx(&old_tx, None).await?;
+
+    let queued_tx = mempool.get_queued_tx_by_nonce(account_id, nonce).await?;
+    assert_eq!(
+        queued_tx.map(|queued| (queued.tx.hash(), queued.in_batch, queued.proposed)),
+        Some((old_tx.hash(), false, false))
+    );
+    assert!(mempool
+        .get_queued_tx_by_nonce(account_id, nonce + 1)
+        .await?
+        .is_none());
+
+    assert!(mempool.replace_tx(old_tx.hash(), &new_tx, None).await?);
+
+    let queued_tx = mempool.get_queued_tx_by_nonce(account_id, nonce).await?;
+    assert_eq!(
+        queued_tx.map(|queued| queued.tx.hash()),
+        Some(new_tx.hash())
+    );
+    assert!(!mempool.contains_tx(old_tx.hash()).await?);
+    assert_eq!(
+        mempool.get_replacement(old_tx.hash()).await?,
+        Some(new_tx.hash())
+    );
+    assert_eq!(mempool.get_replacement(new_tx.hash()).await?, None);
+
+    let replacements = mempool.load_replacements(0).await?;
+    assert_eq!(replacements.len(), 1);
+    let (id, replaced, replaced_by) = replacements[0];
+    assert_eq!((replaced, replaced_by), (old_tx.hash(), new_tx.hash()));
+    assert_eq!(mempool.get_last_replacement_id().await?, id);
+    assert!(mempool.load_replacements(id).await?.is_empty());
+
+    // The old transaction is not in the mempool anymore, so it can't be replaced again.
+    assert!(!mempool.replace_tx(old_tx.hash(), &new_tx, None).await?);
+
+    Ok(())
+}
+
+/// Checks that the transaction proposed for the pending block can't be replaced.
+#[db_test]
+async fn test_replace_proposed_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(2);
+    let (old_tx, new_tx) = (&txs[0], &txs[1]);
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(old_tx, None).await?;
+
+    // Unknown transactions are not marked.
+    let marked = mempool
+        .mark_txs_proposed(&[old_tx.hash(), new_tx.hash()])
+        .await?;
+    assert_eq!(marked, vec![old_tx.hash()]);
+    let queued_tx = mempool
+        .get_queued_tx_by_nonce(old_tx.account_id().unwrap(), old_tx.nonce())
+        .await?
+        .unwrap();
+    assert!(queued_tx.proposed);
+
+    assert!(!mempool.replace_tx(old_tx.hash(), new_tx, None).await?);
+    assert!(mempool.contains_tx(old_tx.hash()).await?);
+    assert!(!mempool.contains_tx(new_tx.hash()).await?);
+    assert_eq!(mempool.get_replacement(old_tx.hash()).await?, None);
+
+    // After the restart the transaction is not considered proposed anymore.
+    mempool.reset_proposed_txs().await?;
+    assert!(mempool.replace_tx(old_tx.hash(), new_tx, None).await?);
+    assert!(!mempool.contains_tx(old_tx.hash()).await?);
+
+    Ok(())
+}
+
+/// Checks that the queued transactions of the batches are reported as such.
+#[db_test]
+async fn test_queued_batch_tx_by_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(2);
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_batch(&txs, Vec::new(), None).await?;
+
+    for tx in &txs {
+        let queued_tx = mempool
+            .get_queued_tx_by_nonce(tx.account_id().unwrap(), tx.nonce())
+            .await?
+            .unwrap();
+        assert_eq!(queued_tx.tx.hash(), tx.hash());
+        assert!(queued_tx.in_batch);
+        assert!(!queued_tx.proposed);
+    }
+
+    Ok(())
+}
+
+/// Checks that the transactions can be cancelled, and the batches are cancelled as a whole.
+#[db_test]
+async fn test_cancel_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(&txs[0], None).await?;
+    mempool.insert_batch(&txs[1..], Vec::new(), None).await?;
+
+    // Unknown transaction can't be cancelled.
+    assert!(mempool.cancel_tx(TxHash::default()).await?.is_none());
+    assert!(!mempool.is_tx_cancelled(txs[0].hash()).await?);
+
+    let cancelled = mempool.cancel_tx(txs[0].hash()).await?;
+    assert_eq!(cancelled, Some(vec![txs[0].hash()]));
+    assert!(!mempool.contains_tx(txs[0].hash()).await?);
+    assert!(mempool.is_tx_cancelled(txs[0].hash()).await?);
+    // Transaction is not in the mempool anymore.
+    assert!(mempool.cancel_tx(txs[0].hash()).await?.is_none());
+
+    // Cancelling one transaction of the batch removes the whole batch.
+    let mut cancelled = mempool.cancel_tx(txs[2].hash()).await?.unwrap();
+    cancelled.sort();
+    let mut expected = vec![txs[1].hash(), txs[2].hash()];
+    expected.sort();
+    assert_eq!(cancelled, expected);
+    for tx in &txs[1..] {
+        assert!(!mempool.contains_tx(tx.hash()).await?);
+        assert!(mempool.is_tx_cancelled(tx.hash()).await?);
+    }
+
+    // Transaction proposed for the pending block can't be cancelled.
+    let proposed_tx = &gen_transfers(4)[3];
+    mempool.insert_tx(proposed_tx, None).await?;
+    mempool.mark_txs_proposed(&[proposed_tx.hash()]).await?;
+    assert!(mempool.cancel_tx(proposed_tx.hash()).await?.is_none());
+    assert!(mempool.contains_tx(proposed_tx.hash()).await?);
+    assert!(!mempool.is_tx_cancelled(proposed_tx.hash()).await?);
+
+    Ok(())
+}
@@ -53,6 +53,7 @@ impl EthereumTxParams {
             nonce: nonce.into(),
             last_deadline_block: self.deadline_block,
             last_used_gas_price,
+            last_used_priority_fee_per_gas: None,
             used_tx_hashes,
             encoded_tx_data: self.raw_tx.clone(),
             confirmed: false,
@@ -116,6 +117,7 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -156,6 +158,7 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params_2.op.clone(),
             params_2.deadline_block as i64,
             params_2.gas_price.clone(),
+            None,
             params_2.raw_tx.clone(),
         )
         .await?;
@@ -270,6 +273,7 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -328,6 +332,7 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
             verify_params.op,
             verify_params.deadline_block as i64,
             verify_params.gas_price.clone(),
+            None,
             verify_params.raw_tx.clone(),
         )
         .await?;
@@ -399,3 +404,33 @@ async fn test_update_eth_parameters(mut storage: StorageProcessor<'_>) -> QueryR
 
     Ok(())
 }
+
+/// Checks that the exodus mode is stored once and can't be overwritten.
+#[db_test]
+async fn exodus_mode(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(storage
+        .ethereum_schema()
+        .load_exodus_mode()
+        .await?
+        .is_none());
+
+    storage.ethereum_schema().save_exodus_mode(100, 5).await?;
+    let exodus_mode = storage
+        .ethereum_schema()
+        .load_exodus_mode()
+        .await?
+        .expect("Exodus mode must be stored");
+    assert_eq!(exodus_mode.eth_block, 100);
+    assert_eq!(exodus_mode.first_priority_request_id, 5);
+
+    // The first detection of the exodus mode is kept.
+    storage.ethereum_schema().save_exodus_mode(200, 5).await?;
+    let exodus_mode = storage
+        .ethereum_schema()
+        .load_exodus_mode()
+        .await?
+        .expect("Exodus mode must be stored");
+    assert_eq!(exodus_mode.eth_block, 100);
+
+    Ok(())
+}
@@ -5,7 +5,8 @@ use std::convert::TryFrom;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     event::{
-        account::AccountStateChangeStatus, block::BlockStatus, EventData, EventId, ZkSyncEvent,
+        account::AccountStateChangeStatus, block::BlockStatus, exodus::ExodusEvent, EventData,
+        EventId, ZkSyncEvent,
     },
     AccountMap, BlockNumber,
 };
@@ -72,6 +73,7 @@ async fn store_operation(
             Some((id, op)),
             100,
             100u32.into(),
+            None,
             Default::default(),
         )
         .await?;
@@ -331,3 +333,78 @@ async fn test_account_events(mut storage: StorageProcessor<'_>) -> QueryResult<(
             && check_account_event(event, AccountStateChangeStatus::Finalized)));
     Ok(())
 }
+
+/// Checks that the events can be fetched in pages and looked up by the block number.
+#[db_test]
+async fn test_events_replay(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for block_number in 1..=3 {
+        let event = ExodusEvent {
+            eth_block: 100 + block_number,
+            first_priority_request_id: 0,
+            total_open_priority_requests: 0,
+        };
+        storage
+            .event_schema()
+            .store_exodus_event(BlockNumber(block_number as u32), event)
+            .await?;
+    }
+
+    let first_page = storage
+        .event_schema()
+        .fetch_new_events_page(EventId(0), 2)
+        .await?;
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page[0].block_number, 1);
+    assert_eq!(first_page[1].block_number, 2);
+
+    let last_id = EventId(first_page[1].id as u64);
+    let second_page = storage
+        .event_schema()
+        .fetch_new_events_page(last_id, 2)
+        .await?;
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].block_number, 3);
+
+    let first_id = storage
+        .event_schema()
+        .get_first_event_id_from_block(BlockNumber(2))
+        .await?;
+    assert_eq!(first_id, Some(last_id));
+    let first_id = storage
+        .event_schema()
+        .get_first_event_id_from_block(BlockNumber(4))
+        .await?;
+    assert_eq!(first_id, None);
+
+    Ok(())
+}
+
+/// Checks that the sink cursors are persisted independently for each sink.
+#[db_test]
+async fn test_sink_cursors(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(storage.event_schema().get_sink_cursor("file").await?, None);
+
+    storage
+        .event_schema()
+        .update_sink_cursor("file", EventId(10))
+        .await?;
+    storage
+        .event_schema()
+        .update_sink_cursor("bus", EventId(5))
+        .await?;
+    storage
+        .event_schema()
+        .update_sink_cursor("file", EventId(20))
+        .await?;
+
+    assert_eq!(
+        storage.event_schema().get_sink_cursor("file").await?,
+        Some(EventId(20))
+    );
+    assert_eq!(
+        storage.event_schema().get_sink_cursor("bus").await?,
+        Some(EventId(5))
+    );
+
+    Ok(())
+}
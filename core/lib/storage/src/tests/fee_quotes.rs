@@ -0,0 +1,43 @@
+// Built-in uses
+// External uses
+use chrono::{Duration, Utc};
+use num::BigUint;
+// Workspace uses
+use zksync_types::{Address, TokenId};
+// Local uses
+use super::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the quotes can be consumed only once, and the expired ones are removed.
+#[db_test]
+async fn test_fee_quotes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::repeat_byte(1);
+    let fee = BigUint::from(100u32);
+
+    let active = storage
+        .fee_quotes_schema()
+        .store_quote(sender, TokenId(0), &fee, Utc::now() + Duration::minutes(1))
+        .await?;
+    let expired = storage
+        .fee_quotes_schema()
+        .store_quote(sender, TokenId(0), &fee, Utc::now() - Duration::minutes(1))
+        .await?;
+    assert_ne!(active, expired);
+
+    assert!(storage.fee_quotes_schema().consume_quote(active).await?);
+    assert!(!storage.fee_quotes_schema().consume_quote(active).await?);
+    assert!(!storage.fee_quotes_schema().consume_quote(expired).await?);
+    assert!(
+        !storage
+            .fee_quotes_schema()
+            .consume_quote(expired + 1)
+            .await?
+    );
+
+    let removed = storage.fee_quotes_schema().remove_expired_quotes().await?;
+    assert_eq!(removed, 1);
+    // The active quote stays consumed.
+    assert!(!storage.fee_quotes_schema().consume_quote(active).await?);
+
+    Ok(())
+}
@@ -28,10 +28,12 @@ mod config;
 mod data_restore;
 mod ethereum;
 mod event;
+mod fee_quotes;
 mod forced_exit_requests;
 mod misc;
 mod prover;
 mod tokens;
+mod webhooks;
 
 pub use db_test_macro::test as db_test;
 
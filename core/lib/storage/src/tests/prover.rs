@@ -5,7 +5,7 @@ use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
     prover::{ProverJob, ProverJobType},
-    BlockNumber,
+    AccountId, BlockNumber, TokenId,
 };
 // Local imports
 use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
@@ -441,3 +441,30 @@ async fn test_remove_prover_jobs(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the exit proofs are cached per account, token and verified block.
+#[db_test]
+async fn test_exit_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let proof_data = serde_json::json!({ "amount": "100" });
+    ProverSchema(&mut storage)
+        .store_exit_proof(AccountId(1), TokenId(0), BlockNumber(5), proof_data.clone())
+        .await?;
+
+    let stored = ProverSchema(&mut storage)
+        .load_exit_proof(AccountId(1), TokenId(0), BlockNumber(5))
+        .await?
+        .expect("Exit proof must be stored");
+    assert_eq!(stored.proof_data, proof_data);
+
+    // Proofs for the other tokens and blocks are not affected.
+    assert!(ProverSchema(&mut storage)
+        .load_exit_proof(AccountId(1), TokenId(1), BlockNumber(5))
+        .await?
+        .is_none());
+    assert!(ProverSchema(&mut storage)
+        .load_exit_proof(AccountId(1), TokenId(0), BlockNumber(6))
+        .await?
+        .is_none());
+
+    Ok(())
+}
@@ -6,8 +6,9 @@ use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
-    tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
-    Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
+    tokens::{FeeTokenListing, TokenMarketVolume},
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx, Token, TokenId, TokenKind,
+    TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
@@ -179,6 +180,40 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that only the prices updated after the given timestamp are loaded.
+#[db_test]
+async fn test_ticker_prices_updated_after(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let last_updated = Utc::now();
+    let price = TokenPrice {
+        usd_price: Ratio::from_integer(BigUint::from(2u32)),
+        last_updated,
+    };
+    storage
+        .tokens_schema()
+        .update_historical_ticker_price(TOKEN_ID, price)
+        .await?;
+
+    let prices = storage
+        .tokens_schema()
+        .load_ticker_prices_updated_after(last_updated - chrono::Duration::seconds(1))
+        .await?;
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].token_id, *TOKEN_ID as i32);
+    assert_eq!(
+        big_decimal_to_ratio(&prices[0].usd_price).unwrap(),
+        Ratio::from_integer(BigUint::from(2u32))
+    );
+
+    let prices = storage
+        .tokens_schema()
+        .load_ticker_prices_updated_after(last_updated + chrono::Duration::seconds(1))
+        .await?;
+    assert!(prices.is_empty());
+
+    Ok(())
+}
+
 /// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
 #[db_test]
 async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -222,6 +257,55 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks the fee token policies are stored, replaced and removed correctly.
+#[db_test]
+async fn test_fee_token_policies(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    assert!(storage
+        .tokens_schema()
+        .load_fee_token_policies()
+        .await?
+        .is_empty());
+
+    let policy = storage
+        .tokens_schema()
+        .store_fee_token_policy(TOKEN_ID, Some(FeeTokenListing::Deny), 0)
+        .await?;
+    assert_eq!(policy.token_id, TOKEN_ID);
+    assert_eq!(policy.listing, Some(FeeTokenListing::Deny));
+
+    // The second policy for the same token replaces the first one.
+    let policy = storage
+        .tokens_schema()
+        .store_fee_token_policy(TOKEN_ID, None, 25)
+        .await?;
+    let policies = storage.tokens_schema().load_fee_token_policies().await?;
+    assert_eq!(policies, vec![policy]);
+    assert_eq!(policies[0].listing, None);
+    assert_eq!(policies[0].discount_percent, 25);
+
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_fee_token_policy(TOKEN_ID)
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_fee_token_policy(TOKEN_ID)
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_fee_token_policies()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 /// Checks the store/load factories for nft
 #[db_test]
 async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
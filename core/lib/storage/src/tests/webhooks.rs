@@ -0,0 +1,149 @@
+// Built-in uses
+// External uses
+use chrono::{Duration, Utc};
+// Workspace uses
+use zksync_types::{
+    event::EventId,
+    webhooks::{WebhookEventType, WebhookPayload},
+    Address, BlockNumber, TokenId,
+};
+// Local uses
+use super::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn get_payload(event_id: u64, address: Address) -> WebhookPayload {
+    WebhookPayload {
+        event_id: EventId(event_id),
+        event_type: WebhookEventType::IncomingTransfer,
+        address,
+        tx_hash: String::new(),
+        block_number: BlockNumber(1),
+        token_id: TokenId(0),
+        amount: None,
+        tx: Default::default(),
+        created_at: Utc::now(),
+    }
+}
+
+/// Checks the registration and removal of webhooks.
+#[db_test]
+async fn test_webhooks_registration(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_address = Address::repeat_byte(1);
+    let second_address = Address::repeat_byte(2);
+
+    let first = storage
+        .webhooks_schema()
+        .register_webhook(first_address, "https://example.com/1", "secret", 1)
+        .await?
+        .unwrap();
+    let second = storage
+        .webhooks_schema()
+        .register_webhook(second_address, "https://example.com/2", "secret", 1)
+        .await?
+        .unwrap();
+    assert_eq!(first.address, first_address);
+    assert_eq!(first.url, "https://example.com/1");
+    // The limit of webhooks per address is reached.
+    assert!(storage
+        .webhooks_schema()
+        .register_webhook(first_address, "https://example.com/3", "secret", 1)
+        .await?
+        .is_none());
+
+    let webhooks = storage
+        .webhooks_schema()
+        .load_webhooks(first_address)
+        .await?;
+    assert_eq!(webhooks, vec![first.clone()]);
+    let webhooks = storage
+        .webhooks_schema()
+        .load_webhooks_for_addresses(&[first_address, second_address, Address::zero()])
+        .await?;
+    assert_eq!(webhooks.len(), 2);
+
+    // Only the owner can remove the webhook.
+    assert!(
+        !storage
+            .webhooks_schema()
+            .remove_webhook(second.id, first_address)
+            .await?
+    );
+    assert!(
+        storage
+            .webhooks_schema()
+            .remove_webhook(second.id, second_address)
+            .await?
+    );
+    let webhooks = storage
+        .webhooks_schema()
+        .load_webhooks(second_address)
+        .await?;
+    assert!(webhooks.is_empty());
+
+    Ok(())
+}
+
+/// Checks the lifecycle of the webhook notifications in the delivery log.
+#[db_test]
+async fn test_webhook_deliveries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(1);
+    let webhook = storage
+        .webhooks_schema()
+        .register_webhook(address, "https://example.com", "secret", 1)
+        .await?
+        .unwrap();
+
+    let deliveries = vec![
+        (webhook.id, get_payload(1, address)),
+        (webhook.id, get_payload(2, address)),
+        (webhook.id, get_payload(3, address)),
+    ];
+    storage
+        .webhooks_schema()
+        .enqueue_deliveries(&deliveries)
+        .await?;
+    // Duplicates are ignored.
+    storage
+        .webhooks_schema()
+        .enqueue_deliveries(&deliveries[..1])
+        .await?;
+
+    let due = storage.webhooks_schema().load_due_deliveries(10).await?;
+    assert_eq!(due.len(), 3);
+    assert_eq!(due[0].url, webhook.url);
+    assert_eq!(due[0].attempts, 0);
+    assert_eq!(
+        serde_json::from_value::<WebhookPayload>(due[0].payload.clone())?.event_id,
+        EventId(1)
+    );
+
+    // The first one is delivered, the second one is retried later
+    // and the third one failed completely.
+    storage.webhooks_schema().mark_delivered(due[0].id).await?;
+    storage
+        .webhooks_schema()
+        .record_failed_attempt(due[1].id, "timeout", Some(Utc::now() + Duration::hours(1)))
+        .await?;
+    storage
+        .webhooks_schema()
+        .record_failed_attempt(due[2].id, "timeout", None)
+        .await?;
+    let due = storage.webhooks_schema().load_due_deliveries(10).await?;
+    assert!(due.is_empty());
+
+    // Retry is due now.
+    storage
+        .webhooks_schema()
+        .enqueue_deliveries(&[(webhook.id, get_payload(4, address))])
+        .await?;
+    let id = storage.webhooks_schema().load_due_deliveries(10).await?[0].id;
+    storage
+        .webhooks_schema()
+        .record_failed_attempt(id, "500 Internal Server Error", Some(Utc::now()))
+        .await?;
+    let due = storage.webhooks_schema().load_due_deliveries(10).await?;
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].attempts, 1);
+
+    Ok(())
+}
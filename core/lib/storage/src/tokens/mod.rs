@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -13,11 +14,13 @@ use zksync_api_types::v02::{
 use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbFeeTokenPolicy, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{FeeTokenListing, FeeTokenPolicy, TokenMarketVolume};
 
 pub mod records;
 
@@ -505,6 +508,67 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Loads all the fee token policies.
+    pub async fn load_fee_token_policies(&mut self) -> QueryResult<Vec<FeeTokenPolicy>> {
+        let start = Instant::now();
+        let policies = sqlx::query_as!(
+            DbFeeTokenPolicy,
+            "SELECT * FROM fee_token_policies ORDER BY token_id ASC"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(FeeTokenPolicy::from)
+        .collect();
+
+        metrics::histogram!("sql.token.load_fee_token_policies", start.elapsed());
+        Ok(policies)
+    }
+
+    /// Creates or replaces the fee token policy for the given token.
+    pub async fn store_fee_token_policy(
+        &mut self,
+        token_id: TokenId,
+        listing: Option<FeeTokenListing>,
+        discount_percent: u8,
+    ) -> QueryResult<FeeTokenPolicy> {
+        let start = Instant::now();
+        let policy = sqlx::query_as!(
+            DbFeeTokenPolicy,
+            r#"
+            INSERT INTO fee_token_policies ( token_id, listing, discount_percent, updated_at )
+            VALUES ( $1, $2, $3, now() )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET listing = $2, discount_percent = $3, updated_at = now()
+            RETURNING *
+            "#,
+            *token_id as i32,
+            listing.map(FeeTokenListing::as_str),
+            i16::from(discount_percent)
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_fee_token_policy", start.elapsed());
+        Ok(policy.into())
+    }
+
+    /// Removes the fee token policy for the given token.
+    /// Returns `false` if there was no policy for the token.
+    pub async fn remove_fee_token_policy(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM fee_token_policies WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.remove_fee_token_policy", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Given token id, returns its price in USD and a timestamp of the last update.
     pub async fn get_historical_ticker_price(
         &mut self,
@@ -527,6 +591,31 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_price.map(|p| p.into()))
     }
 
+    /// Loads the prices in USD updated after the given timestamp, ordered by the update time.
+    pub async fn load_ticker_prices_updated_after(
+        &mut self,
+        updated_after: DateTime<Utc>,
+    ) -> QueryResult<Vec<DbTickerPrice>> {
+        let start = Instant::now();
+        let prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT * FROM ticker_price
+            WHERE last_updated > $1
+            ORDER BY last_updated ASC
+            "#,
+            updated_after
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.token.load_ticker_prices_updated_after",
+            start.elapsed()
+        );
+        Ok(prices)
+    }
+
     /// Updates price in USD for the given token.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
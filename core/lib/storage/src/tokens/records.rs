@@ -9,7 +9,7 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use zksync_api_types::v02::token::ApiNFT;
 use zksync_types::{
-    tokens::{TokenMarketVolume, TokenPrice},
+    tokens::{FeeTokenPolicy, TokenMarketVolume, TokenPrice},
     AccountId, Address, Token, TokenId, H256, NFT,
 };
 use zksync_utils::big_decimal_to_ratio;
@@ -159,6 +159,27 @@ pub struct DBMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeTokenPolicy {
+    pub token_id: i32,
+    pub listing: Option<String>,
+    pub discount_percent: i16,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DbFeeTokenPolicy> for FeeTokenPolicy {
+    fn from(val: DbFeeTokenPolicy) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            listing: val
+                .listing
+                .map(|listing| listing.parse().expect("Invalid fee token listing")),
+            discount_percent: val.discount_percent as u8,
+            updated_at: val.updated_at,
+        }
+    }
+}
+
 impl From<DBMarketVolume> for TokenMarketVolume {
     fn from(val: DBMarketVolume) -> Self {
         Self {
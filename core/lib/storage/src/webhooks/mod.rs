@@ -0,0 +1,240 @@
+// Built-in uses
+use std::time::Instant;
+// External uses
+use chrono::{DateTime, Utc};
+// Workspace uses
+use zksync_types::{
+    webhooks::{Webhook, WebhookDelivery, WebhookId, WebhookPayload},
+    Address,
+};
+// Local uses
+use crate::{QueryResult, StorageProcessor};
+use records::{StoredWebhook, StoredWebhookDelivery};
+
+pub mod records;
+
+/// Advisory lock namespace of the webhooks of an address. Namespaces `1` and `2`
+/// are taken by the pending transactions locks of the mempool schema.
+const WEBHOOKS_ADDRESS_LOCK: i32 = 3;
+
+/// Schema for the webhooks registered by the account owners and the log
+/// of the notifications delivered to them.
+#[derive(Debug)]
+pub struct WebhooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WebhooksSchema<'a, 'c> {
+    /// Stores the new webhook for the address. Returns `None` if the address
+    /// already has the maximum amount of webhooks registered.
+    pub async fn register_webhook(
+        &mut self,
+        address: Address,
+        url: &str,
+        secret: &str,
+        max_webhooks_per_address: u32,
+    ) -> QueryResult<Option<Webhook>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        // Concurrent registrations for the same address could exceed the limit otherwise.
+        sqlx::query!(
+            "SELECT 1 FROM pg_advisory_xact_lock($1, hashtext($2))",
+            WEBHOOKS_ADDRESS_LOCK,
+            format!("{:x}", address)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        let registered = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM webhooks WHERE address = $1"#,
+            address.as_bytes()
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count;
+        if registered >= i64::from(max_webhooks_per_address) {
+            return Ok(None);
+        }
+
+        let webhook = sqlx::query_as!(
+            StoredWebhook,
+            "INSERT INTO webhooks (address, url, secret)
+            VALUES ($1, $2, $3)
+            RETURNING id, address, url, secret, created_at",
+            address.as_bytes(),
+            url,
+            secret
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.webhooks.register_webhook", start.elapsed());
+        Ok(Some(webhook.into()))
+    }
+
+    /// Removes the webhook of the address. Returns `false` if there's no such webhook.
+    pub async fn remove_webhook(&mut self, id: WebhookId, address: Address) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM webhooks WHERE id = $1 AND address = $2",
+            id,
+            address.as_bytes()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.remove_webhook", start.elapsed());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all webhooks registered for the address.
+    pub async fn load_webhooks(&mut self, address: Address) -> QueryResult<Vec<Webhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT id, address, url, secret, created_at FROM webhooks
+            WHERE address = $1
+            ORDER BY id",
+            address.as_bytes()
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(Webhook::from)
+        .collect();
+
+        metrics::histogram!("sql.webhooks.load_webhooks", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Loads all webhooks registered for any of the addresses.
+    pub async fn load_webhooks_for_addresses(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<Vec<Webhook>> {
+        let start = Instant::now();
+        let addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT id, address, url, secret, created_at FROM webhooks
+            WHERE address = ANY($1)",
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(Webhook::from)
+        .collect();
+
+        metrics::histogram!("sql.webhooks.load_webhooks_for_addresses", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Adds notifications to the delivery log. Notifications that
+    /// were already added are ignored.
+    pub async fn enqueue_deliveries(
+        &mut self,
+        deliveries: &[(WebhookId, WebhookPayload)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        let mut webhook_ids = Vec::with_capacity(deliveries.len());
+        let mut event_ids = Vec::with_capacity(deliveries.len());
+        let mut event_types = Vec::with_capacity(deliveries.len());
+        let mut payloads = Vec::with_capacity(deliveries.len());
+        for (webhook_id, payload) in deliveries {
+            webhook_ids.push(*webhook_id);
+            event_ids.push(*payload.event_id as i64);
+            event_types.push(payload.event_type.as_str().to_string());
+            payloads.push(serde_json::to_value(payload)?);
+        }
+
+        sqlx::query!(
+            "INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload)
+            SELECT u.webhook_id, u.event_id, u.event_type, u.payload
+                FROM UNNEST ($1::bigint[], $2::bigint[], $3::text[], $4::jsonb[])
+                AS u(webhook_id, event_id, event_type, payload)
+            ON CONFLICT (webhook_id, event_id, event_type) DO NOTHING",
+            &webhook_ids,
+            &event_ids,
+            &event_types,
+            &payloads
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.enqueue_deliveries", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads pending notifications which are due to be delivered.
+    pub async fn load_due_deliveries(&mut self, limit: u32) -> QueryResult<Vec<WebhookDelivery>> {
+        let start = Instant::now();
+        let deliveries = sqlx::query_as!(
+            StoredWebhookDelivery,
+            "SELECT d.id, d.webhook_id, w.url, w.secret, d.payload, d.attempts
+            FROM webhook_deliveries d
+            INNER JOIN webhooks w ON w.id = d.webhook_id
+            WHERE d.status = 'pending' AND d.next_attempt_at <= now()
+            ORDER BY d.id
+            LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(WebhookDelivery::from)
+        .collect();
+
+        metrics::histogram!("sql.webhooks.load_due_deliveries", start.elapsed());
+        Ok(deliveries)
+    }
+
+    /// Marks the notification as delivered.
+    pub async fn mark_delivered(&mut self, id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET status = 'delivered', attempts = attempts + 1, delivered_at = now()
+            WHERE id = $1",
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.mark_delivered", start.elapsed());
+        Ok(())
+    }
+
+    /// Records the failed delivery attempt. If `next_attempt_at` is `None`,
+    /// the notification is marked as failed and won't be retried.
+    pub async fn record_failed_attempt(
+        &mut self,
+        id: i64,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET attempts = attempts + 1,
+                last_error = $2,
+                status = CASE WHEN $3::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                next_attempt_at = COALESCE($3, next_attempt_at)
+            WHERE id = $1",
+            id,
+            error,
+            next_attempt_at
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.record_failed_attempt", start.elapsed());
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+// Built-in uses
+// External uses
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace uses
+use zksync_types::{
+    webhooks::{Webhook, WebhookDelivery},
+    Address,
+};
+// Local uses
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhook {
+    pub id: i64,
+    pub address: Vec<u8>,
+    pub url: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredWebhook> for Webhook {
+    fn from(stored: StoredWebhook) -> Self {
+        Self {
+            id: stored.id,
+            address: Address::from_slice(&stored.address),
+            url: stored.url,
+            secret: stored.secret,
+            created_at: stored.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub url: String,
+    pub secret: String,
+    pub payload: Value,
+    pub attempts: i32,
+}
+
+impl From<StoredWebhookDelivery> for WebhookDelivery {
+    fn from(stored: StoredWebhookDelivery) -> Self {
+        Self {
+            id: stored.id,
+            webhook_id: stored.webhook_id,
+            url: stored.url,
+            secret: stored.secret,
+            payload: stored.payload,
+            attempts: stored.attempts as u32,
+        }
+    }
+}
@@ -23,7 +23,11 @@ pub struct ETHOperation {
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
     /// Gas price used in the last sent transaction.
+    /// For EIP-1559 transactions this is the `max_fee_per_gas` value.
     pub last_used_gas_price: U256,
+    /// Priority fee per gas used in the last sent transaction.
+    /// Set only if the last transaction was an EIP-1559 one.
+    pub last_used_priority_fee_per_gas: Option<U256>,
     /// Hashes of all the sent transactions.
     pub used_tx_hashes: Vec<H256>,
     /// Tx payload (not signed).
@@ -61,6 +65,7 @@ impl PartialEq for ETHOperation {
         (self.id == other.id)
             && (self.last_deadline_block == other.last_deadline_block)
             && (self.last_used_gas_price == other.last_used_gas_price)
+            && (self.last_used_priority_fee_per_gas == other.last_used_priority_fee_per_gas)
             && (self.used_tx_hashes == other.used_tx_hashes)
             && (self.confirmed == other.confirmed)
             && (self.final_hash == other.final_hash)
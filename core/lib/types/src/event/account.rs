@@ -5,6 +5,7 @@ use bigdecimal::BigDecimal;
 use num::BigInt;
 use serde::{Deserialize, Serialize};
 // Workspace uses
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 // Local uses
 use crate::{
     account::{AccountUpdate, PubKeyHash},
@@ -39,6 +40,19 @@ pub struct AccountEvent {
     pub update_details: AccountUpdateDetails,
 }
 
+impl AccountEvent {
+    /// Whether the update concerns an NFT balance.
+    pub fn is_nft(&self) -> bool {
+        match self.update_type {
+            AccountStateChangeType::MintNFT | AccountStateChangeType::RemoveNFT => true,
+            _ => matches!(
+                self.update_details.token_id,
+                Some(token_id) if token_id.0 >= MIN_NFT_TOKEN_ID
+            ),
+        }
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountUpdateDetails {
@@ -0,0 +1,18 @@
+// Built-in uses
+// External uses
+use serde::{Deserialize, Serialize};
+// Workspace uses
+// Local uses
+
+/// Event emitted once the zkSync contract is found to be in the exodus mode.
+/// After that, no blocks can be processed anymore and the users can only withdraw
+/// their funds on L1 with the exit proofs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExodusEvent {
+    /// Number of the Ethereum block at which the exodus mode was detected.
+    pub eth_block: u64,
+    /// Id of the oldest priority request which wasn't processed by the network.
+    pub first_priority_request_id: u64,
+    /// Amount of the priority requests which weren't processed by the network.
+    pub total_open_priority_requests: u64,
+}
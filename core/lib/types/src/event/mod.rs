@@ -4,12 +4,15 @@ use serde::Serialize;
 // Workspace uses
 use zksync_basic_types::BlockNumber;
 // Local uses
-use self::{account::AccountEvent, block::BlockEvent, transaction::TransactionEvent};
+use self::{
+    account::AccountEvent, block::BlockEvent, exodus::ExodusEvent, transaction::TransactionEvent,
+};
 
 pub use crate::EventId;
 
 pub mod account;
 pub mod block;
+pub mod exodus;
 pub mod transaction;
 
 pub mod test_data;
@@ -21,6 +24,7 @@ pub enum EventData {
     Account(AccountEvent),
     Block(BlockEvent),
     Transaction(TransactionEvent),
+    Exodus(ExodusEvent),
 }
 
 // An event that happened in the zkSync network.
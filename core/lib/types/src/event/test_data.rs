@@ -5,7 +5,9 @@ use chrono::Utc;
 use once_cell::sync::OnceCell;
 // Workspace uses
 // Local uses
-use super::{account::*, block::*, transaction::*, EventData, EventId, ZkSyncEvent};
+use super::{
+    account::*, block::*, exodus::ExodusEvent, transaction::*, EventData, EventId, ZkSyncEvent,
+};
 use crate::{AccountId, BlockNumber, Nonce, TokenId};
 
 /// Constructs default values for `BlockDetails` struct. Since block events
@@ -96,3 +98,38 @@ pub fn get_transaction_event(
         data: EventData::Transaction(tx_event),
     }
 }
+
+/// Construct transfer or withdrawal event with the given amount.
+pub fn get_transaction_event_with_amount(
+    tx_type: TransactionType,
+    token_id: TokenId,
+    amount: u64,
+) -> ZkSyncEvent {
+    let mut event = get_transaction_event(
+        tx_type,
+        AccountId(0),
+        token_id,
+        TransactionStatus::Committed,
+    );
+    if let EventData::Transaction(tx_event) = &mut event.data {
+        tx_event.tx = serde_json::json!({
+            "type": format!("{:?}", tx_type),
+            "amount": amount.to_string(),
+        });
+    }
+    event
+}
+
+/// Construct exodus mode event.
+pub fn get_exodus_event() -> ZkSyncEvent {
+    let exodus_event = ExodusEvent {
+        eth_block: 0,
+        first_priority_request_id: 0,
+        total_open_priority_requests: 1,
+    };
+    ZkSyncEvent {
+        id: EventId(0),
+        block_number: BlockNumber(0),
+        data: EventData::Exodus(exodus_event),
+    }
+}
@@ -1,9 +1,12 @@
 // Built-in uses
 // External uses
 use chrono::{DateTime, Utc};
+use num::BigUint;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 // Workspace uses
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_utils::BigUintSerdeWrapper;
 // Local uses
 use super::account::AccountStateChangeStatus;
 use crate::{block::ExecutedOperations, AccountId, BlockNumber, TokenId};
@@ -94,6 +97,26 @@ impl TransactionEvent {
             .tx_type
             .get_or_init(|| serde_json::from_value(self.tx["type"].clone()).unwrap())
     }
+
+    /// Returns the amount of the transfer or withdrawal, [`None`] for other transactions.
+    pub fn amount(&self) -> Option<BigUint> {
+        match self.tx_type() {
+            TransactionType::Transfer | TransactionType::Withdraw => {
+                serde_json::from_value::<BigUintSerdeWrapper>(self.tx["amount"].clone())
+                    .ok()
+                    .map(|amount| amount.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the transaction mints, transfers or withdraws an NFT.
+    pub fn is_nft(&self) -> bool {
+        match self.tx_type() {
+            TransactionType::MintNFT | TransactionType::WithdrawNFT => true,
+            _ => self.token_id.0 >= MIN_NFT_TOKEN_ID,
+        }
+    }
 }
 
 impl From<AccountStateChangeStatus> for TransactionStatus {
@@ -1,9 +1,11 @@
+use chrono::{DateTime, Utc};
 use num::rational::Ratio;
-use num::BigUint;
+use num::{BigUint, One};
 use serde::{Deserialize, Serialize};
 
 use crate::helpers::{pack_fee_amount, unpack_fee_amount};
-use crate::tokens::ChangePubKeyFeeTypeArg;
+use crate::tokens::{ChangePubKeyFeeTypeArg, TxFeeTypes};
+use crate::{Address, TokenId};
 use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str};
 
 /// Type of the fee calculation pattern.
@@ -35,6 +37,9 @@ pub struct Fee {
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub zkp_fee: BigUint,
+    /// Part of the total fee charged due to the network congestion.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub surge_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
 }
@@ -46,6 +51,9 @@ pub struct BatchFee {
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub zkp_fee: BigUint,
+    /// Part of the total fee charged due to the network congestion.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub surge_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
 }
@@ -63,9 +71,18 @@ impl BatchFee {
         Self {
             gas_fee,
             zkp_fee,
+            surge_fee: BigUint::default(),
             total_fee,
         }
     }
+
+    /// Scales the fee by the congestion surge multiplier, see `Fee::apply_surge`.
+    pub fn apply_surge(&mut self, multiplier: &Ratio<BigUint>) {
+        if let Some((surge_fee, total_fee)) = surge_fee(&self.zkp_fee, &self.gas_fee, multiplier) {
+            self.surge_fee = surge_fee;
+            self.total_fee = total_fee;
+        }
+    }
 }
 
 impl Fee {
@@ -83,9 +100,40 @@ impl Fee {
             gas_price_wei,
             gas_fee,
             zkp_fee,
+            surge_fee: BigUint::default(),
             total_fee,
         }
     }
+
+    /// Scales the fee by the congestion surge multiplier.
+    /// The difference with the base fee is reported as the `surge_fee`.
+    pub fn apply_surge(&mut self, multiplier: &Ratio<BigUint>) {
+        if let Some((surge_fee, total_fee)) = surge_fee(&self.zkp_fee, &self.gas_fee, multiplier) {
+            self.surge_fee = surge_fee;
+            self.total_fee = total_fee;
+        }
+    }
+}
+
+/// Returns the surge fee and the new total fee, or `None` if the multiplier doesn't increase the fee.
+fn surge_fee(
+    zkp_fee: &BigUint,
+    gas_fee: &BigUint,
+    multiplier: &Ratio<BigUint>,
+) -> Option<(BigUint, BigUint)> {
+    if multiplier <= &Ratio::one() {
+        return None;
+    }
+    let base_fee = zkp_fee + gas_fee;
+    let surge_fee = ((multiplier - Ratio::one()) * &base_fee)
+        .ceil()
+        .to_integer();
+
+    let total_fee = base_fee + &surge_fee;
+    Some((
+        surge_fee,
+        unpack_fee_amount(&pack_fee_amount(&total_fee)).expect("Failed to round surge fee amount."),
+    ))
 }
 
 fn total_fee(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> (BigUint, BigUint, BigUint) {
@@ -99,3 +147,43 @@ fn total_fee(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> (BigUint, Bi
         unpack_fee_amount(&pack_fee_amount(&total_fee)).expect("Failed to round gas fee amount."),
     )
 }
+
+/// Fee the server has promised to accept for the transactions of the given types paid by the sender
+/// in the given token until the quote expires, even if the token prices change in the meantime.
+///
+/// Quotes are signed when issued, and the signature is checked when the quote is submitted back
+/// along with the transactions. Issued quotes are also stored by the server, so that every quote
+/// is honored only once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeQuote {
+    /// Id of the stored quote.
+    pub id: i64,
+    /// Address of the account paying the fee.
+    pub sender: Address,
+    pub token_id: TokenId,
+    /// Types of the quoted transactions along with the addresses used for the fee calculation.
+    pub txs: Vec<(TxFeeTypes, Address)>,
+    pub total_fee: BigUint,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl FeeQuote {
+    /// Message signed by the server to prove the quote was issued by it.
+    pub fn get_sign_message(&self) -> String {
+        let txs = self
+            .txs
+            .iter()
+            .map(|(tx_type, address)| format!("{:?}/{:?}", tx_type, address))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{}:{:?}:{}:{}:{}:{}",
+            self.id,
+            self.sender,
+            self.token_id,
+            txs,
+            self.total_fee,
+            self.expires_at.timestamp()
+        )
+    }
+}
@@ -54,6 +54,7 @@ pub mod register_factory;
 pub mod tokens;
 pub mod tx;
 mod utils;
+pub mod webhooks;
 
 #[cfg(test)]
 mod tests;
@@ -241,6 +241,51 @@ pub struct TokenMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Explicit decision on whether the token can be used to pay fees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeeTokenListing {
+    Allow,
+    Deny,
+}
+
+impl FeeTokenListing {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+impl FromStr for FeeTokenListing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            _ => Err(format!("Unknown fee token listing: {}", s)),
+        }
+    }
+}
+
+/// Maximum discount of the fees paid in the token, in percent.
+/// Fees are never waived completely.
+pub const MAX_FEE_DISCOUNT_PERCENT: u8 = 99;
+
+/// Admin-managed rules for paying fees in the token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenPolicy {
+    pub token_id: TokenId,
+    /// Overrides the market volume check, `None` if the check decides.
+    pub listing: Option<FeeTokenListing>,
+    /// Discount of the fees paid in the token, in percent, up to `MAX_FEE_DISCOUNT_PERCENT`.
+    pub discount_percent: u8,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 #[serde(untagged)]
 pub enum ChangePubKeyFeeTypeArg {
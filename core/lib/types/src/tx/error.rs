@@ -57,6 +57,18 @@ pub enum TxAddError {
 
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Too many pending transactions from the same account or IP address")]
+    PendingTxsLimitExceeded,
+
+    #[error("Replacement transaction fee is too low")]
+    ReplacementFeeTooLow,
+
+    #[error("Transaction with the same nonce is already proposed for the pending block")]
+    ReplacedTxAlreadyProposed,
+
+    #[error("Transactions can't be replaced within batches")]
+    BatchTxReplacement,
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
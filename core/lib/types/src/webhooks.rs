@@ -0,0 +1,251 @@
+//! Webhook notifications about the account activity.
+
+// Built-in uses
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+// Workspace uses
+use zksync_basic_types::{Address, BlockNumber, TokenId};
+use zksync_utils::BigUintSerdeWrapper;
+// Local uses
+use crate::event::{
+    transaction::{TransactionStatus, TransactionType},
+    EventData, EventId, ZkSyncEvent,
+};
+
+pub type WebhookId = i64;
+
+/// Callback registered by the owner of the address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub address: Address,
+    pub url: String,
+    /// Secret used to sign the payloads. Only revealed to the owner on registration.
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Notification which is due to be delivered to the webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: WebhookId,
+    pub url: String,
+    pub secret: String,
+    /// Serialized [`WebhookPayload`].
+    pub payload: Value,
+    /// Amount of the failed delivery attempts.
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEventType {
+    IncomingTransfer,
+    OutgoingTransfer,
+    Deposit,
+    /// Withdrawal, forced exit or full exit executed on L1.
+    WithdrawalExecuted,
+    /// Mint, transfer or withdrawal of an NFT.
+    Nft,
+}
+
+impl WebhookEventType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::IncomingTransfer => "incomingTransfer",
+            Self::OutgoingTransfer => "outgoingTransfer",
+            Self::Deposit => "deposit",
+            Self::WithdrawalExecuted => "withdrawalExecuted",
+            Self::Nft => "nft",
+        }
+    }
+}
+
+/// JSON payload sent to the webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    /// Id of the event the notification was created for. Several notifications
+    /// may be created for a single event, e.g. for the self-transfer.
+    pub event_id: EventId,
+    pub event_type: WebhookEventType,
+    /// Address the webhook was registered for.
+    pub address: Address,
+    pub tx_hash: String,
+    pub block_number: BlockNumber,
+    pub token_id: TokenId,
+    pub amount: Option<BigUintSerdeWrapper>,
+    pub tx: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookPayload {
+    /// Creates notifications for all addresses affected by the event.
+    ///
+    /// Only successful transactions produce notifications: transfers, deposits and NFT
+    /// operations once they're committed, withdrawals once they're executed on L1.
+    pub fn from_event(event: &ZkSyncEvent) -> Vec<Self> {
+        let tx_event = match &event.data {
+            EventData::Transaction(tx_event) => tx_event,
+            _ => return Vec::new(),
+        };
+        let tx = &tx_event.tx;
+        let committed = tx_event.status == TransactionStatus::Committed;
+        let finalized = tx_event.status == TransactionStatus::Finalized;
+
+        let recipients: Vec<(WebhookEventType, &Value)> = match tx_event.tx_type() {
+            TransactionType::Transfer if committed && tx_event.is_nft() => vec![
+                (WebhookEventType::Nft, &tx["from"]),
+                (WebhookEventType::Nft, &tx["to"]),
+            ],
+            TransactionType::Transfer if committed => vec![
+                (WebhookEventType::OutgoingTransfer, &tx["from"]),
+                (WebhookEventType::IncomingTransfer, &tx["to"]),
+            ],
+            TransactionType::Deposit if committed => {
+                vec![(WebhookEventType::Deposit, &tx["priority_op"]["to"])]
+            }
+            TransactionType::MintNFT if committed => vec![
+                (WebhookEventType::Nft, &tx["creatorAddress"]),
+                (WebhookEventType::Nft, &tx["recipient"]),
+            ],
+            TransactionType::Withdraw if finalized => vec![
+                (WebhookEventType::WithdrawalExecuted, &tx["from"]),
+                (WebhookEventType::WithdrawalExecuted, &tx["to"]),
+            ],
+            TransactionType::ForcedExit if finalized => {
+                vec![(WebhookEventType::WithdrawalExecuted, &tx["target"])]
+            }
+            TransactionType::FullExit if finalized => vec![(
+                WebhookEventType::WithdrawalExecuted,
+                &tx["priority_op"]["eth_address"],
+            )],
+            TransactionType::WithdrawNFT if finalized => vec![
+                (WebhookEventType::Nft, &tx["from"]),
+                (WebhookEventType::Nft, &tx["to"]),
+            ],
+            _ => Vec::new(),
+        };
+
+        let amount = tx_event
+            .amount()
+            .map(BigUintSerdeWrapper)
+            .or_else(|| serde_json::from_value(tx["priority_op"]["amount"].clone()).ok())
+            .or_else(|| serde_json::from_value(tx["withdraw_amount"].clone()).ok());
+
+        let mut payloads: Vec<Self> = Vec::with_capacity(recipients.len());
+        for (event_type, address) in recipients {
+            let address: Address = match serde_json::from_value(address.clone()) {
+                Ok(address) => address,
+                Err(_) => continue,
+            };
+            // The sender and the recipient may be the same.
+            if payloads
+                .iter()
+                .any(|payload| payload.event_type == event_type && payload.address == address)
+            {
+                continue;
+            }
+            payloads.push(Self {
+                event_id: event.id,
+                event_type,
+                address,
+                tx_hash: tx_event.tx_hash.clone(),
+                block_number: event.block_number,
+                token_id: tx_event.token_id,
+                amount: amount.clone(),
+                tx: tx.clone(),
+                created_at: tx_event.created_at,
+            });
+        }
+        payloads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::test_data::get_transaction_event, AccountId};
+    use num::BigUint;
+    use serde_json::json;
+
+    fn get_event(tx_type: TransactionType, status: TransactionStatus, tx: Value) -> ZkSyncEvent {
+        let mut event = get_transaction_event(tx_type, AccountId(1), TokenId(0), status);
+        if let EventData::Transaction(tx_event) = &mut event.data {
+            tx_event.tx = tx;
+        }
+        event
+    }
+
+    #[test]
+    fn test_payloads_from_event() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+
+        let transfer = json!({ "type": "Transfer", "from": from, "to": to, "amount": "100" });
+        let event = get_event(
+            TransactionType::Transfer,
+            TransactionStatus::Committed,
+            transfer.clone(),
+        );
+        let payloads = WebhookPayload::from_event(&event);
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].event_type, WebhookEventType::OutgoingTransfer);
+        assert_eq!(payloads[0].address, from);
+        assert_eq!(payloads[1].event_type, WebhookEventType::IncomingTransfer);
+        assert_eq!(payloads[1].address, to);
+        assert_eq!(payloads[1].amount, Some(BigUint::from(100u32).into()));
+
+        // Transfers are only notified about once committed.
+        let event = get_event(
+            TransactionType::Transfer,
+            TransactionStatus::Finalized,
+            transfer,
+        );
+        assert!(WebhookPayload::from_event(&event).is_empty());
+
+        // Withdrawals are notified about once executed, the same address is notified once.
+        let withdraw = json!({ "type": "Withdraw", "from": from, "to": from, "amount": "5" });
+        let event = get_event(
+            TransactionType::Withdraw,
+            TransactionStatus::Committed,
+            withdraw.clone(),
+        );
+        assert!(WebhookPayload::from_event(&event).is_empty());
+        let event = get_event(
+            TransactionType::Withdraw,
+            TransactionStatus::Finalized,
+            withdraw,
+        );
+        let payloads = WebhookPayload::from_event(&event);
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].event_type, WebhookEventType::WithdrawalExecuted);
+
+        let deposit = json!({
+            "type": "Deposit",
+            "priority_op": { "from": from, "to": to, "token": 0, "amount": "7" },
+            "account_id": 1
+        });
+        let event = get_event(
+            TransactionType::Deposit,
+            TransactionStatus::Committed,
+            deposit,
+        );
+        let payloads = WebhookPayload::from_event(&event);
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].event_type, WebhookEventType::Deposit);
+        assert_eq!(payloads[0].address, to);
+        assert_eq!(payloads[0].amount, Some(BigUint::from(7u32).into()));
+
+        // Rejected transactions are ignored.
+        let event = get_event(
+            TransactionType::MintNFT,
+            TransactionStatus::Rejected,
+            json!({ "type": "MintNFT", "creatorAddress": from, "recipient": to }),
+        );
+        assert!(WebhookPayload::from_event(&event).is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! Authorization of the internal HTTP APIs with the JsonWebToken signed by the shared secret.
+
+// External uses
+use actix_web::dev::ServiceRequest;
+use actix_web_httpauth::extractors::{
+    bearer::{BearerAuth, Config},
+    AuthenticationError,
+};
+use jsonwebtoken::{decode, errors::Error as JwtError, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    pub sub: String,
+    /// Expiration time (as UTC timestamp).
+    pub exp: usize,
+}
+
+impl PayloadAuthToken {
+    pub fn new(exp: usize) -> Self {
+        Self {
+            sub: "Authorization".to_string(),
+            exp,
+        }
+    }
+}
+
+/// The structure that stores the secret key for checking JsonWebToken matching.
+pub struct AuthTokenValidator<'a> {
+    decoding_key: DecodingKey<'a>,
+}
+
+impl<'a> AuthTokenValidator<'a> {
+    pub fn new(secret: &'a str) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+        }
+    }
+
+    /// Checks whether the secret key and the authorization token match.
+    pub fn validate_auth_token(&self, token: &str) -> Result<(), JwtError> {
+        decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+
+        Ok(())
+    }
+
+    pub async fn validator(
+        &self,
+        req: ServiceRequest,
+        credentials: BearerAuth,
+    ) -> actix_web::Result<ServiceRequest> {
+        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+
+        self.validate_auth_token(credentials.token())
+            .map_err(|_| AuthenticationError::from(config))?;
+
+        Ok(req)
+    }
+}
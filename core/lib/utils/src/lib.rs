@@ -3,6 +3,8 @@
 mod convert;
 mod env_tools;
 mod format;
+#[cfg(feature = "http_auth")]
+pub mod http_auth;
 mod macros;
 pub mod panic_notify;
 mod serde_wrappers;
@@ -72,6 +72,7 @@ pub fn spawn_state_keeper(
         block_chunks_sizes,
         max_miniblock_iterations,
         max_miniblock_iterations,
+        Default::default(),
         processed_tx_events_sender,
     );
 
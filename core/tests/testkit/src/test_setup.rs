@@ -319,6 +319,7 @@ impl TestSetup {
         let block = ProposedBlock {
             priority_ops: Vec::new(),
             txs: vec![SignedTxVariant::from(SignedZkSyncTx::from(tx))],
+            priority_op_deadline_approaching: false,
         };
 
         // Request miniblock execution.
@@ -400,6 +401,7 @@ impl TestSetup {
         let block = ProposedBlock {
             priority_ops: vec![op],
             txs: Vec::new(),
+            priority_op_deadline_approaching: false,
         };
 
         // Request miniblock execution.
@@ -67,4 +67,4 @@ esac
 
 zk f ./target/release/zksync_data_restore $COMMAND --finite --config $CONFIG_FILE --web3 $WEB3_URL || exit 1
 
-zk f ./target/release/examples/generate_exit_proof --address $ADDRESS --token $TOKEN
+zk f ./target/release/zksync_exit_proof_service generate --address $ADDRESS --token $TOKEN
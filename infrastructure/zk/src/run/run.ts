@@ -99,7 +99,7 @@ export async function explorer() {
 }
 
 export async function exitProof(...args: string[]) {
-    await utils.spawn(`cargo run --example generate_exit_proof --release -- ${args.join(' ')}`);
+    await utils.spawn(`cargo run --bin zksync_exit_proof_service --release -- ${args.join(' ')}`);
 }
 
 export async function catLogs(exitCode?: number) {
@@ -225,15 +225,18 @@ command
 
 command
     .command('exit-proof')
-    .option('--account <id>')
-    .option('--token <id>')
+    .option('--address <address>')
+    .option('--token <token>')
+    .option('--server')
     .option('--help')
-    .description('generate exit proof')
+    .description('generate exit proof or run the exit proof server')
     .action(async (cmd: Command) => {
-        if (!cmd.account || !cmd.token) {
+        if (cmd.server) {
+            await exitProof('server');
+        } else if (!cmd.address || !cmd.token) {
             await exitProof('--help');
         } else {
-            await exitProof('--account_id', cmd.account, '--token', cmd.token);
+            await exitProof('generate', '--address', cmd.address, '--token', cmd.token);
         }
     });
 